@@ -19,4 +19,5 @@
  */
 
 pub use clap;
+pub use rand;
 pub use tokio;
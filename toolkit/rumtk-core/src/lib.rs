@@ -243,6 +243,19 @@ mod tests {
         println!("Passed!")
     }
 
+    #[test]
+    fn test_encode() {
+        let input = "Café";
+        let result = strings::try_encode_with(input, "iso-8859-1").unwrap();
+        println!("Input: {} Expected: {:?} Got: {:?}", input, b"Caf\xe9", result);
+        assert_eq!(result, b"Caf\xe9", "Incorrect string encoding!");
+        let decoded = strings::try_decode_with(&result, "iso-8859-1").unwrap();
+        assert_eq!(input, decoded, "Encoding did not round trip!");
+        assert!(strings::try_encode_with("I ❤ my wife!", "iso-8859-1").is_err(), "Heart is not in Latin-1!");
+        assert!(strings::try_encode_with(input, "not-an-encoding").is_err(), "Unknown encodings should fail!");
+        println!("Passed!")
+    }
+
     #[test]
     fn test_rumcache_insertion() {
         let mut cache: RUMCache<&str, RUMString> = RUMCache::with_capacity(5);
@@ -18,6 +18,324 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+///
+/// This module provides the TLS primitives used to secure the connections in [tcp]. Certificates,
+/// keys, and certificate authorities are loaded from PEM files on disk, which makes it easy to
+/// test locally with self-signed certificates.
+///
+/// Mutual TLS is enabled on the server side by providing a CA bundle to verify client
+/// certificates against. On top of that, you can pin the exact certificates you expect from the
+/// peer by listing their SHA-256 fingerprints.
+///
+pub mod tls {
+    use crate::base::RUMResult;
+    use crate::strings::{rumtk_format, RUMString};
+    use std::fs::File;
+    use std::io::BufReader;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::net::TcpStream;
+    use tokio_rustls::rustls::crypto::{ring as ring_provider, CryptoProvider};
+    use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+    use tokio_rustls::rustls::server::WebPkiClientVerifier;
+    use tokio_rustls::rustls::{ClientConfig, RootCertStore, ServerConfig};
+    pub use tokio_rustls::TlsStream;
+    use tokio_rustls::{TlsAcceptor, TlsConnector};
+
+    /// Seconds to wait for a peer to complete the TLS handshake before dropping the connection.
+    pub const TLS_HANDSHAKE_TIMEOUT: f32 = 10.0;
+
+    ///
+    /// Paths and policies needed to establish a TLS session.
+    ///
+    /// As a server, `cert` and `key` are required. Setting `ca` turns on mutual TLS by requiring
+    /// clients to present a certificate signed by one of the authorities in that bundle.
+    ///
+    /// As a client, `ca` is required and holds the authorities we trust to sign the server
+    /// certificate. Setting `cert` and `key` presents a client certificate for mutual TLS.
+    ///
+    /// In both modes, `pinned` lists the SHA-256 fingerprints (hex, colons optional) of the peer
+    /// certificates we accept. Leave it empty to accept any certificate that passes verification.
+    ///
+    #[derive(Debug, Default, Clone, PartialEq)]
+    pub struct RUMTLSConfig {
+        pub cert: Option<RUMString>,
+        pub key: Option<RUMString>,
+        pub ca: Option<RUMString>,
+        pub pinned: Vec<RUMString>,
+        pub server_name: Option<RUMString>,
+    }
+
+    impl RUMTLSConfig {
+        ///
+        /// Configuration presenting the certificate chain in `cert` signed with the private key
+        /// in `key`.
+        ///
+        pub fn new(cert: &str, key: &str) -> RUMTLSConfig {
+            RUMTLSConfig {
+                cert: Some(RUMString::from(cert)),
+                key: Some(RUMString::from(key)),
+                ..Default::default()
+            }
+        }
+
+        ///
+        /// Configuration for a client that only verifies the server against the authorities in
+        /// `ca`.
+        ///
+        pub fn trusting(ca: &str) -> RUMTLSConfig {
+            RUMTLSConfig::default().with_ca(ca)
+        }
+
+        pub fn with_ca(mut self, ca: &str) -> Self {
+            self.ca = Some(RUMString::from(ca));
+            self
+        }
+
+        pub fn with_pin(mut self, fingerprint: &str) -> Self {
+            self.pinned.push(RUMString::from(fingerprint));
+            self
+        }
+
+        ///
+        /// Name to verify the server certificate against. Defaults to the host we connect to.
+        ///
+        pub fn with_server_name(mut self, name: &str) -> Self {
+            self.server_name = Some(RUMString::from(name));
+            self
+        }
+
+        pub fn is_mutual(&self) -> bool {
+            self.ca.is_some() && self.cert.is_some()
+        }
+
+        ///
+        /// Build the server side [RUMTLSAcceptor] out of this configuration.
+        ///
+        pub fn acceptor(&self) -> RUMResult<RUMTLSAcceptor> {
+            RUMTLSAcceptor::new(self)
+        }
+
+        ///
+        /// Perform the client side of the TLS handshake over an already connected `socket`.
+        /// `host` is used as the server name unless [RUMTLSConfig::server_name] is set.
+        ///
+        pub async fn connect(&self, host: &str, socket: TcpStream) -> RUMResult<TlsStream<TcpStream>> {
+            let connector = TlsConnector::from(Arc::new(self.client_config()?));
+            let name = self.server_name.as_deref().unwrap_or(host);
+            let server_name = match ServerName::try_from(name.to_string()) {
+                Ok(server_name) => server_name,
+                Err(e) => return Err(rumtk_format!("Invalid TLS server name {} because {}", name, e)),
+            };
+
+            let handshake = connector.connect(server_name, socket);
+            let stream = match tokio::time::timeout(handshake_timeout(), handshake).await {
+                Ok(Ok(stream)) => stream,
+                Ok(Err(e)) => return Err(rumtk_format!("TLS handshake with {} failed because {}", name, e)),
+                Err(_) => return Err(rumtk_format!("TLS handshake with {} timed out!", name)),
+            };
+
+            self.verify_pin(stream.get_ref().1.peer_certificates())?;
+            Ok(TlsStream::from(stream))
+        }
+
+        ///
+        /// Check the peer's leaf certificate against the pinned fingerprints. Nothing to check if
+        /// no fingerprints were pinned.
+        ///
+        pub fn verify_pin(&self, peer_certs: Option<&[CertificateDer<'_>]>) -> RUMResult<()> {
+            if self.pinned.is_empty() {
+                return Ok(());
+            }
+
+            let leaf = match peer_certs.and_then(|certs| certs.first()) {
+                Some(leaf) => leaf,
+                None => return Err(RUMString::from("Peer presented no certificate to check against the pinned fingerprints!")),
+            };
+            let fingerprint = cert_fingerprint(leaf.as_ref());
+
+            match self
+                .pinned
+                .iter()
+                .any(|pin| normalize_fingerprint(pin) == fingerprint)
+            {
+                true => Ok(()),
+                false => Err(rumtk_format!("Peer certificate {} is not pinned!", fingerprint)),
+            }
+        }
+
+        fn server_config(&self) -> RUMResult<ServerConfig> {
+            let (cert, key) = match (&self.cert, &self.key) {
+                (Some(cert), Some(key)) => (load_certs(cert)?, load_key(key)?),
+                _ => return Err(RUMString::from("A TLS server requires both a certificate and a private key!")),
+            };
+            let provider = tls_provider();
+            let builder = match ServerConfig::builder_with_provider(provider.clone())
+                .with_safe_default_protocol_versions()
+            {
+                Ok(builder) => builder,
+                Err(e) => return Err(rumtk_format!("Unable to configure TLS because {}", e)),
+            };
+
+            let builder = match &self.ca {
+                Some(ca) => {
+                    let roots = Arc::new(load_roots(ca)?);
+                    match WebPkiClientVerifier::builder_with_provider(roots, provider).build() {
+                        Ok(verifier) => builder.with_client_cert_verifier(verifier),
+                        Err(e) => return Err(rumtk_format!("Unable to verify client certificates against {} because {}", ca, e)),
+                    }
+                }
+                None if !self.pinned.is_empty() => {
+                    return Err(RUMString::from("Pinning client certificates requires a CA to verify them against!"))
+                }
+                None => builder.with_no_client_auth(),
+            };
+
+            match builder.with_single_cert(cert, key) {
+                Ok(config) => Ok(config),
+                Err(e) => Err(rumtk_format!("Unable to use TLS certificate because {}", e)),
+            }
+        }
+
+        fn client_config(&self) -> RUMResult<ClientConfig> {
+            let roots = match &self.ca {
+                Some(ca) => load_roots(ca)?,
+                None => return Err(RUMString::from("A TLS client requires a CA to verify the server against!")),
+            };
+            let builder = match ClientConfig::builder_with_provider(tls_provider())
+                .with_safe_default_protocol_versions()
+            {
+                Ok(builder) => builder.with_root_certificates(roots),
+                Err(e) => return Err(rumtk_format!("Unable to configure TLS because {}", e)),
+            };
+
+            match (&self.cert, &self.key) {
+                (Some(cert), Some(key)) => {
+                    match builder.with_client_auth_cert(load_certs(cert)?, load_key(key)?) {
+                        Ok(config) => Ok(config),
+                        Err(e) => Err(rumtk_format!("Unable to use TLS client certificate because {}", e)),
+                    }
+                }
+                (None, None) => Ok(builder.with_no_client_auth()),
+                _ => Err(RUMString::from("A TLS client certificate requires both a certificate and a private key!")),
+            }
+        }
+    }
+
+    ///
+    /// Server side of the TLS handshake. Cheap to clone so it can be handed to the accept loop.
+    ///
+    #[derive(Clone)]
+    pub struct RUMTLSAcceptor {
+        acceptor: TlsAcceptor,
+        config: RUMTLSConfig,
+    }
+
+    impl RUMTLSAcceptor {
+        pub fn new(config: &RUMTLSConfig) -> RUMResult<RUMTLSAcceptor> {
+            Ok(RUMTLSAcceptor {
+                acceptor: TlsAcceptor::from(Arc::new(config.server_config()?)),
+                config: config.clone(),
+            })
+        }
+
+        ///
+        /// Perform the server side of the TLS handshake over a freshly accepted `socket`.
+        ///
+        pub async fn accept(&self, socket: TcpStream) -> RUMResult<TlsStream<TcpStream>> {
+            let peer = match socket.peer_addr() {
+                Ok(addr) => addr.to_string(),
+                Err(_) => RUMString::from("unknown peer"),
+            };
+
+            let handshake = self.acceptor.accept(socket);
+            let stream = match tokio::time::timeout(handshake_timeout(), handshake).await {
+                Ok(Ok(stream)) => stream,
+                Ok(Err(e)) => return Err(rumtk_format!("TLS handshake with {} failed because {}", peer, e)),
+                Err(_) => return Err(rumtk_format!("TLS handshake with {} timed out!", peer)),
+            };
+
+            self.config.verify_pin(stream.get_ref().1.peer_certificates())?;
+            Ok(TlsStream::from(stream))
+        }
+    }
+
+    ///
+    /// SHA-256 fingerprint of a DER encoded certificate as lowercase hex. This is the format
+    /// expected in [RUMTLSConfig::pinned].
+    ///
+    pub fn cert_fingerprint(der: &[u8]) -> RUMString {
+        ring::digest::digest(&ring::digest::SHA256, der)
+            .as_ref()
+            .iter()
+            .map(|byte| rumtk_format!("{:02x}", byte))
+            .collect()
+    }
+
+    ///
+    /// SHA-256 fingerprint of the leaf certificate found in the PEM file at `path`.
+    ///
+    pub fn cert_file_fingerprint(path: &str) -> RUMResult<RUMString> {
+        match load_certs(path)?.first() {
+            Some(cert) => Ok(cert_fingerprint(cert.as_ref())),
+            None => Err(rumtk_format!("No certificate found in {}!", path)),
+        }
+    }
+
+    fn normalize_fingerprint(fingerprint: &str) -> RUMString {
+        fingerprint
+            .chars()
+            .filter(|c| *c != ':')
+            .collect::<RUMString>()
+            .to_lowercase()
+    }
+
+    fn handshake_timeout() -> Duration {
+        Duration::from_secs_f32(TLS_HANDSHAKE_TIMEOUT)
+    }
+
+    fn tls_provider() -> Arc<CryptoProvider> {
+        Arc::new(ring_provider::default_provider())
+    }
+
+    fn open_pem(path: &str) -> RUMResult<BufReader<File>> {
+        match File::open(path) {
+            Ok(file) => Ok(BufReader::new(file)),
+            Err(e) => Err(rumtk_format!("Unable to open {} because {}", path, e)),
+        }
+    }
+
+    fn load_certs(path: &str) -> RUMResult<Vec<CertificateDer<'static>>> {
+        let certs = match rustls_pemfile::certs(&mut open_pem(path)?).collect::<Result<Vec<_>, _>>() {
+            Ok(certs) => certs,
+            Err(e) => return Err(rumtk_format!("Unable to read certificates in {} because {}", path, e)),
+        };
+
+        match certs.is_empty() {
+            true => Err(rumtk_format!("No certificate found in {}!", path)),
+            false => Ok(certs),
+        }
+    }
+
+    fn load_key(path: &str) -> RUMResult<PrivateKeyDer<'static>> {
+        match rustls_pemfile::private_key(&mut open_pem(path)?) {
+            Ok(Some(key)) => Ok(key),
+            Ok(None) => Err(rumtk_format!("No private key found in {}!", path)),
+            Err(e) => Err(rumtk_format!("Unable to read private key in {} because {}", path, e)),
+        }
+    }
+
+    fn load_roots(path: &str) -> RUMResult<RootCertStore> {
+        let mut roots = RootCertStore::empty();
+        for cert in load_certs(path)? {
+            if let Err(e) = roots.add(cert) {
+                return Err(rumtk_format!("Unable to trust certificate in {} because {}", path, e));
+            }
+        }
+        Ok(roots)
+    }
+}
+
 ///
 /// This module provides the basic types necessary to be able to handle connections and message
 /// transmission in both synchronous and asynchronous contexts.
@@ -26,6 +344,7 @@
 ///
 pub mod tcp {
     use crate::base::{RUMResult, RUMVec};
+    pub use crate::net::tls::{RUMTLSAcceptor, RUMTLSConfig, TlsStream};
     use crate::strings::{rumtk_format, RUMString};
     pub use crate::threading::thread_primitives::*;
     use crate::threading::threading_manager::SafeTaskArgs;
@@ -35,8 +354,11 @@ pub mod tcp {
         rumtk_new_lock, rumtk_resolve_task, rumtk_wait_on_task,
     };
     use ahash::HashMapExt;
+    use socket2::{SockRef, TcpKeepalive};
     use std::collections::VecDeque;
+    use std::net::SocketAddr;
     use std::sync::Arc;
+    use std::time::Duration;
     pub use tokio::net::{TcpListener, TcpStream};
 
     const MESSAGE_BUFFER_SIZE: usize = 1024;
@@ -55,12 +377,89 @@ pub mod tcp {
     pub type ConnectionInfo = (RUMString, u16);
 
     ///
-    /// This structs encapsulates the [tokio::net::TcpStream] instance that will be our adapter
+    /// Transport underneath a [RUMClient]. Either a plain [TcpStream] or a TLS session running on
+    /// top of one.
+    ///
+    #[derive(Debug)]
+    pub enum RUMNetStream {
+        PLAIN(TcpStream),
+        TLS(Box<TlsStream<TcpStream>>),
+    }
+
+    impl RUMNetStream {
+        fn tcp(&self) -> &TcpStream {
+            match self {
+                RUMNetStream::PLAIN(socket) => socket,
+                RUMNetStream::TLS(stream) => stream.get_ref().0,
+            }
+        }
+
+        pub fn local_addr(&self) -> io::Result<SocketAddr> {
+            self.tcp().local_addr()
+        }
+
+        pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+            self.tcp().peer_addr()
+        }
+
+        pub fn is_tls(&self) -> bool {
+            matches!(self, RUMNetStream::TLS(_))
+        }
+
+        ///
+        /// Turn on TCP keep-alive. The first probe goes out after `idle` seconds without traffic.
+        ///
+        pub fn set_keepalive(&self, idle: f32) -> RUMResult<()> {
+            let keepalive = TcpKeepalive::new().with_time(Duration::from_secs_f32(idle));
+            match SockRef::from(self.tcp()).set_tcp_keepalive(&keepalive) {
+                Ok(_) => Ok(()),
+                Err(e) => Err(rumtk_format!("Unable to enable keep-alive because {}", e)),
+            }
+        }
+
+        async fn shutdown(&mut self) -> io::Result<()> {
+            match self {
+                RUMNetStream::PLAIN(socket) => socket.shutdown().await,
+                RUMNetStream::TLS(stream) => stream.shutdown().await,
+            }
+        }
+
+        async fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+            match self {
+                RUMNetStream::PLAIN(socket) => socket.write_all(buf).await,
+                RUMNetStream::TLS(stream) => {
+                    stream.write_all(buf).await?;
+                    stream.flush().await
+                }
+            }
+        }
+
+        ///
+        /// Non-blocking read. TLS streams have no `try_read`, so we give the read the same tiny
+        /// window we use for sleeping between network checks and report
+        /// [io::ErrorKind::WouldBlock] if nothing showed up.
+        ///
+        async fn try_read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self {
+                RUMNetStream::PLAIN(socket) => socket.try_read(buf),
+                RUMNetStream::TLS(stream) => {
+                    let window = Duration::from_secs_f32(NET_SLEEP_TIMEOUT);
+                    match tokio::time::timeout(window, stream.read(buf)).await {
+                        Ok(result) => result,
+                        Err(_) => Err(io::Error::from(io::ErrorKind::WouldBlock)),
+                    }
+                }
+            }
+        }
+    }
+
+    ///
+    /// This structs encapsulates the [RUMNetStream] instance that will be our adapter
     /// for connecting and sending messages to a peer or server.
     ///
     #[derive(Debug)]
     pub struct RUMClient {
-        socket: TcpStream,
+        socket: RUMNetStream,
         disconnected: bool,
     }
 
@@ -69,12 +468,29 @@ pub mod tcp {
         /// Connect to peer and construct the client.
         ///
         pub async fn connect(ip: &str, port: u16) -> RUMResult<RUMClient> {
+            let socket = Self::connect_socket(ip, port).await?;
+            Ok(RUMClient {
+                socket: RUMNetStream::PLAIN(socket),
+                disconnected: false,
+            })
+        }
+
+        ///
+        /// Connect to peer, perform the TLS handshake described by `tls`, and construct the client.
+        ///
+        pub async fn connect_tls(ip: &str, port: u16, tls: &RUMTLSConfig) -> RUMResult<RUMClient> {
+            let socket = Self::connect_socket(ip, port).await?;
+            let stream = tls.connect(ip, socket).await?;
+            Ok(RUMClient {
+                socket: RUMNetStream::TLS(Box::new(stream)),
+                disconnected: false,
+            })
+        }
+
+        async fn connect_socket(ip: &str, port: u16) -> RUMResult<TcpStream> {
             let addr = rumtk_format!("{}:{}", ip, port);
             match TcpStream::connect(addr.as_str()).await {
-                Ok(socket) => Ok(RUMClient {
-                    socket,
-                    disconnected: false,
-                }),
+                Ok(socket) => Ok(socket),
                 Err(e) => Err(rumtk_format!(
                     "Unable to connect to {} because {}",
                     &addr.as_str(),
@@ -89,7 +505,17 @@ pub mod tcp {
         ///
         pub async fn accept(socket: TcpStream) -> RUMResult<RUMClient> {
             Ok(RUMClient {
-                socket,
+                socket: RUMNetStream::PLAIN(socket),
+                disconnected: false,
+            })
+        }
+
+        ///
+        /// Same as [RUMClient::accept], but for a connection whose TLS handshake already completed.
+        ///
+        pub async fn accept_tls(stream: TlsStream<TcpStream>) -> RUMResult<RUMClient> {
+            Ok(RUMClient {
+                socket: RUMNetStream::TLS(Box::new(stream)),
                 disconnected: false,
             })
         }
@@ -145,7 +571,7 @@ pub mod tcp {
 
         async fn recv_some(&mut self) -> RUMResult<RUMNetPartialMessage> {
             let mut buf: [u8; MESSAGE_BUFFER_SIZE] = [0; MESSAGE_BUFFER_SIZE];
-            match self.socket.try_read(&mut buf) {
+            match self.socket.try_read(&mut buf).await {
                 Ok(n) => match n {
                     0 => {
                         self.disconnect();
@@ -189,9 +615,30 @@ pub mod tcp {
             self.disconnected
         }
 
+        pub fn is_tls(&self) -> bool {
+            self.socket.is_tls()
+        }
+
         pub fn disconnect(&mut self) {
             self.disconnected = true;
         }
+
+        ///
+        /// See [RUMNetStream::set_keepalive].
+        ///
+        pub fn set_keepalive(&self, idle: f32) -> RUMResult<()> {
+            self.socket.set_keepalive(idle)
+        }
+
+        ///
+        /// Shut the connection down and mark the client as disconnected. Unlike
+        /// [RUMClient::disconnect], the peer is told we are done.
+        ///
+        pub async fn close(&mut self) {
+            // The peer might already be gone, in which case there is nothing left to shut down.
+            let _ = self.socket.shutdown().await;
+            self.disconnect();
+        }
     }
 
     /// List of clients that you can interact with.
@@ -255,6 +702,23 @@ pub mod tcp {
         /// management is not started until you invoke [Self::run].
         ///
         pub async fn new(ip: &str, port: u16) -> RUMResult<RUMServer> {
+            Self::bind(ip, port, None).await
+        }
+
+        ///
+        /// Same as [RUMServer::new], but every accepted client has to complete the TLS handshake
+        /// described by `tls` before it shows up in the client list.
+        ///
+        pub async fn new_tls(ip: &str, port: u16, tls: &RUMTLSConfig) -> RUMResult<RUMServer> {
+            let acceptor = tls.acceptor()?;
+            Self::bind(ip, port, Some(acceptor)).await
+        }
+
+        async fn bind(
+            ip: &str,
+            port: u16,
+            acceptor: Option<RUMTLSAcceptor>,
+        ) -> RUMResult<RUMServer> {
             let mut address = rumtk_format!("{}:{}", ip, port);
             let tcp_listener_handle = match TcpListener::bind(address.as_str()).await {
                 Ok(listener) => {
@@ -276,7 +740,7 @@ pub mod tcp {
 
             //TODO: In the future, see if it is necessary to pass a oneshot channel and gracefully handle closure.
             //for now, it is ok to leak the handle and let process termination kill any future connections.
-            tokio::spawn(Self::handle_accept(tcp_listener, clients.clone()));
+            tokio::spawn(Self::handle_accept(tcp_listener, clients.clone(), acceptor));
 
             Ok(RUMServer { address, clients })
         }
@@ -284,10 +748,14 @@ pub mod tcp {
         ///
         /// Contains basic logic for listening for incoming connections.
         ///
-        pub async fn handle_accept(listener: SafeListener, clients: RUMNetClients) {
+        pub async fn handle_accept(
+            listener: SafeListener,
+            clients: RUMNetClients,
+            acceptor: Option<RUMTLSAcceptor>,
+        ) {
             #[allow(clippy::never_loop)]
             loop {
-                match Self::_handle_accept(&listener, &clients).await {
+                match Self::_handle_accept(&listener, &clients, &acceptor).await {
                     Ok(_) => {}
                     Err(_) => {
                         //TODO: Log error accepting client...
@@ -299,20 +767,19 @@ pub mod tcp {
         pub async fn _handle_accept(
             listener: &SafeListener,
             clients: &RUMNetClients,
+            acceptor: &Option<RUMTLSAcceptor>,
         ) -> RUMResult<()> {
-            match listener.lock().await.accept().await {
-                Ok((socket, _)) => {
-                    let client = RUMClient::accept(socket).await?;
-                    let client_id = match client.get_address(false).await {
-                        Some(client_id) => client_id,
-                        None => return Err(rumtk_format!("Accepted client returned no peer address. This should not be happening!"))
-                    };
-                    clients
-                        .write()
-                        .await
-                        .insert(client_id, RUMNetClient::new(AsyncRwLock::new(client)));
-                    Ok(())
-                }
+            let accepted = listener.lock().await.accept().await;
+            match accepted {
+                Ok((socket, _)) => match acceptor {
+                    Some(acceptor) => {
+                        // The handshake runs in its own task so a slow or silent peer does not
+                        // hold up the connections queued behind it.
+                        tokio::spawn(Self::handle_tls_handshake(socket, acceptor.clone(), clients.clone()));
+                        Ok(())
+                    }
+                    None => Self::register_client(clients, RUMClient::accept(socket).await?).await,
+                },
                 Err(e) => Err(rumtk_format!(
                     "Error accepting incoming client! Error: {}",
                     e
@@ -320,6 +787,27 @@ pub mod tcp {
             }
         }
 
+        async fn handle_tls_handshake(
+            socket: TcpStream,
+            acceptor: RUMTLSAcceptor,
+            clients: RUMNetClients,
+        ) -> RUMResult<()> {
+            let client = RUMClient::accept_tls(acceptor.accept(socket).await?).await?;
+            Self::register_client(&clients, client).await
+        }
+
+        async fn register_client(clients: &RUMNetClients, client: RUMClient) -> RUMResult<()> {
+            let client_id = match client.get_address(false).await {
+                Some(client_id) => client_id,
+                None => return Err(RUMString::from("Accepted client returned no peer address. This should not be happening!"))
+            };
+            clients
+                .write()
+                .await
+                .insert(client_id, RUMNetClient::new(AsyncRwLock::new(client)));
+            Ok(())
+        }
+
         pub async fn receive(
             &self,
             client_id: &RUMString,
@@ -363,6 +851,18 @@ pub mod tcp {
             lock_client_ex(client).await.disconnect()
         }
 
+        ///
+        /// Close the connection to a client and drop it from the client list.
+        ///
+        pub async fn close_client(&self, client_id: &RUMString) -> RUMResult<()> {
+            let client = match self.clients.write().await.shift_remove(client_id) {
+                Some(client) => client,
+                None => return Err(rumtk_format!("Client {} not found!", client_id)),
+            };
+            lock_client_ex(&client).await.close().await;
+            Ok(())
+        }
+
         pub async fn get_client(&self, client: &RUMString) -> RUMResult<RUMNetClient> {
             match self.clients.read().await.get(client) {
                 Some(client) => Ok(client.clone()),
@@ -297,10 +297,21 @@ pub fn count_tokens_ignoring_pattern(vector: &Vec<&str>, string_token: &RUMStrin
 /// Note => Decoding is facilitated via the crates chardet-ng and encoding_rs.
 ///
 pub fn try_decode(src: &[u8]) -> RUMResult<RUMString> {
+    decode(src, detect_encoding(src))
+}
+
+///
+/// Name of the encoding [try_decode] would pick for `src` (e.g. `windows-1252`). The name is a
+/// valid label for [try_decode_with] and [try_encode_with].
+///
+pub fn guess_encoding(src: &[u8]) -> &'static str {
+    detect_encoding(src).name()
+}
+
+fn detect_encoding(src: &[u8]) -> &'static Encoding {
     let mut detector = EncodingDetector::new(Iso2022JpDetection::Allow);
     detector.feed(&src, true);
-    let encoding = detector.guess(None, Utf8Detection::Allow);
-    decode(src, encoding)
+    detector.guess(None, Utf8Detection::Allow)
 }
 
 ///
@@ -316,6 +327,27 @@ pub fn try_decode_with(src: &[u8], encoding_name: &str) -> RUMResult<RUMString>
     decode(src, encoding)
 }
 
+///
+/// Implements encoding this UTF-8 string into a specific encoding. Fails if the label is unknown or
+/// if the string has characters the encoding cannot represent.
+///
+/// Note => Encoding is facilitated via the crate encoding_rs.
+///
+pub fn try_encode_with(src: &str, encoding_name: &str) -> RUMResult<Vec<u8>> {
+    let encoding = match Encoding::for_label(encoding_name.as_bytes()) {
+        Some(v) => v,
+        None => return Err(rumtk_format!("Unknown encoding {}!", encoding_name)),
+    };
+    let (encoded, used, had_errors) = encoding.encode(src);
+    if had_errors {
+        return Err(rumtk_format!(
+            "String cannot be represented in {}!",
+            used.name()
+        ));
+    }
+    Ok(encoded.into_owned())
+}
+
 ///
 /// Implements decoding of input with encoder.
 ///
@@ -0,0 +1,632 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2026  Luis M. Santos, M.D. <lsantos@medicalmasses.com>
+ * Copyright (C) 2026  MedicalMasses L.L.C. <contact@medicalmasses.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+///
+/// HL7 acknowledgement generation (Section 2.9). Builds the ACK for an inbound [V2Message] with
+/// the sender and receiver swapped in MSH, the inbound control id echoed in MSA-2 and one ERR
+/// segment per warning or error found by [V2Message::validate].
+///
+/// ## Example
+///
+/// ```
+/// use rumtk_hl7_v2::hl7_v2_ack::v2_ack::{ack_code, V2AckCode};
+/// use rumtk_hl7_v2::hl7_v2_parser::v2_parser::V2Message;
+///
+/// let raw = "MSH|^~\\&|ADT1|HOSP|LAB|LABFAC|20250101120000||ADT^A01^ADT_A01|MSG0001|P|2.5.1\rEVN|A01|20250101120000\rPID|1||12345^^^HOSP^MR||DOE^JANE\rPV1|1|I\r";
+/// let message = V2Message::try_from(raw).unwrap();
+/// let ack = message.ack().unwrap();
+/// let msa = ack.get_named("MSA", 1).unwrap();
+///
+/// assert_eq!(ack_code(&ack), Some(V2AckCode::AA), "Expected the message to be accepted!");
+/// assert_eq!(msa.to_string(ack.parser_chars()), "AA|MSG0001", "MSA-2 does not echo MSH-10!");
+/// ```
+///
+pub mod v2_ack {
+    use crate::hl7_v2_base_types::v2_primitives::{V2DateTime, V2ParserCharacters, V2Result, V2String};
+    use crate::hl7_v2_constants::V2_MSHEADER_ID;
+    use crate::hl7_v2_parser::v2_parser::{V2Message, V2Segment};
+    use crate::hl7_v2_segment_descriptors::v2_segment_descriptor::segment_field_index;
+    use crate::hl7_v2_tables::v2_tables::get_table;
+    use crate::hl7_v2_validation::v2_validation::{
+        V2Severity, V2ValidationIssue, V2ValidationReport, V2ValidationRule,
+    };
+    use chrono::Utc;
+    use rumtk_core::rumtk_generate_id;
+    use rumtk_core::strings::{rumtk_format, AsStr, AsString, RUMString};
+    use std::str::FromStr;
+
+    pub const V2_ACK_MESSAGE_TYPE: &str = "ACK";
+    /// HL7 table 0357 - Message error condition codes.
+    pub const V2_ERROR_CODE_TABLE: u16 = 357;
+    pub const V2_ERROR_CODE_SYSTEM: &str = "HL70357";
+
+    ///
+    /// Acknowledgment codes from HL7 table 0008.
+    ///
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum V2AckCode {
+        /// Original mode: Application Accept.
+        AA,
+        /// Original mode: Application Error.
+        AE,
+        /// Original mode: Application Reject.
+        AR,
+        /// Enhanced mode: Commit Accept.
+        CA,
+        /// Enhanced mode: Commit Error.
+        CE,
+        /// Enhanced mode: Commit Reject.
+        CR,
+    }
+
+    impl V2AckCode {
+        pub fn as_str(&self) -> &'static str {
+            match self {
+                V2AckCode::AA => "AA",
+                V2AckCode::AE => "AE",
+                V2AckCode::AR => "AR",
+                V2AckCode::CA => "CA",
+                V2AckCode::CE => "CE",
+                V2AckCode::CR => "CR",
+            }
+        }
+
+        ///
+        /// True if the peer accepted the message (AA or CA).
+        ///
+        pub fn is_accept(&self) -> bool {
+            matches!(self, V2AckCode::AA | V2AckCode::CA)
+        }
+    }
+
+    impl FromStr for V2AckCode {
+        type Err = RUMString;
+
+        fn from_str(code: &str) -> Result<Self, Self::Err> {
+            match code {
+                "AA" => Ok(V2AckCode::AA),
+                "AE" => Ok(V2AckCode::AE),
+                "AR" => Ok(V2AckCode::AR),
+                "CA" => Ok(V2AckCode::CA),
+                "CE" => Ok(V2AckCode::CE),
+                "CR" => Ok(V2AckCode::CR),
+                _ => Err(rumtk_format!("Unknown acknowledgement code {}!", code)),
+            }
+        }
+    }
+
+    ///
+    /// Acknowledgment mode requested by the sender. Per Section 2.9.3, valuing MSH-15 or MSH-16
+    /// requests the enhanced mode. Otherwise, the original mode applies.
+    ///
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum V2AckMode {
+        Original,
+        Enhanced,
+    }
+
+    impl V2AckMode {
+        pub fn from_message(message: &V2Message) -> V2AckMode {
+            let msh = match message.get(V2_MSHEADER_ID, 1) {
+                Ok(msh) => msh,
+                Err(_) => return V2AckMode::Original,
+            };
+            let parser_chars = message.parser_chars();
+            match msh_field(msh, 15, parser_chars).is_empty() && msh_field(msh, 16, parser_chars).is_empty() {
+                true => V2AckMode::Original,
+                false => V2AckMode::Enhanced,
+            }
+        }
+
+        pub fn accept(&self) -> V2AckCode {
+            match self {
+                V2AckMode::Original => V2AckCode::AA,
+                V2AckMode::Enhanced => V2AckCode::CA,
+            }
+        }
+
+        pub fn error(&self) -> V2AckCode {
+            match self {
+                V2AckMode::Original => V2AckCode::AE,
+                V2AckMode::Enhanced => V2AckCode::CE,
+            }
+        }
+
+        pub fn reject(&self) -> V2AckCode {
+            match self {
+                V2AckMode::Original => V2AckCode::AR,
+                V2AckMode::Enhanced => V2AckCode::CR,
+            }
+        }
+    }
+
+    ///
+    /// Validate `message` and acknowledge it in the mode it requested. The message is accepted if
+    /// the report has no errors. See [generate_ack].
+    ///
+    pub fn acknowledge(message: &V2Message) -> V2Result<V2Message> {
+        let report = message.validate();
+        let mode = V2AckMode::from_message(message);
+        let code = match report.is_valid() {
+            true => mode.accept(),
+            false => mode.error(),
+        };
+        generate_ack(message, code, Some(&report))
+    }
+
+    ///
+    /// Build the ACK for `message` with the given acknowledgment code.
+    ///
+    /// * MSH reuses the inbound separators and encoding characters. The sending and receiving
+    ///   application/facility pairs (MSH-3/4 and MSH-5/6) are swapped. MSH-9 is `ACK^<event>^ACK`,
+    ///   MSH-10 is a newly generated control id and MSH-11/MSH-12 are echoed.
+    /// * MSA-2 echoes the inbound MSH-10.
+    /// * Each warning or error in `report` becomes an ERR segment. Versions before 2.5 report it in ERR-1
+    ///   (ELD). Later versions use ERR-2 (ERL), ERR-3, ERR-4 and ERR-7.
+    ///
+    pub fn generate_ack(
+        message: &V2Message,
+        code: V2AckCode,
+        report: Option<&V2ValidationReport>,
+    ) -> V2Result<V2Message> {
+        let parser_chars = message.parser_chars();
+        let msh = message.get(V2_MSHEADER_ID, 1)?;
+        let field_separator = parser_chars.field_separator.as_string();
+        let component_separator = parser_chars.component_separator.as_string();
+        let event = msh_component(msh, 9, 2, parser_chars);
+        let message_type = match event.is_empty() {
+            true => V2String::from(V2_ACK_MESSAGE_TYPE),
+            false => [V2_ACK_MESSAGE_TYPE, event.as_str(), V2_ACK_MESSAGE_TYPE].join(&component_separator),
+        };
+        let version = msh_field(msh, 12, parser_chars);
+        let header = [
+            msh_field(msh, 2, parser_chars),
+            msh_field(msh, 5, parser_chars),
+            msh_field(msh, 6, parser_chars),
+            msh_field(msh, 3, parser_chars),
+            msh_field(msh, 4, parser_chars),
+            V2DateTime::from_utc_datetime(&Utc::now()).as_v2_date_time(),
+            V2String::new(),
+            message_type,
+            rumtk_generate_id!(),
+            msh_field(msh, 11, parser_chars),
+            version.clone(),
+        ];
+
+        let mut segments = Vec::with_capacity(2);
+        segments.push(rumtk_format!("MSH{}{}", field_separator, header.join(&field_separator)));
+        segments.push(
+            [
+                "MSA",
+                code.as_str(),
+                msh_field(msh, 10, parser_chars).as_str(),
+            ]
+            .join(&field_separator),
+        );
+        if let Some(report) = report {
+            let legacy = uses_error_code_and_location(&version, parser_chars);
+            for issue in report.issues().iter().filter(|i| i.severity >= V2Severity::Warning) {
+                segments.push(err_segment(issue, legacy, parser_chars));
+            }
+        }
+
+        let terminator = parser_chars.segment_terminator.as_string();
+        let mut raw = segments.join(&terminator);
+        raw.push_str(&terminator);
+        V2Message::try_from(raw.as_str())
+    }
+
+    ///
+    /// Acknowledgment code in MSA-1 if `message` is an acknowledgement.
+    ///
+    pub fn ack_code(message: &V2Message) -> Option<V2AckCode> {
+        let msa = message.get_named("MSA", 1).ok()?;
+        let field = msa.get(1).ok()?.first()?;
+        field.get(1).ok()?.as_str().parse().ok()
+    }
+
+    ///
+    /// True if MSH-9.1 is `ACK`.
+    ///
+    pub fn is_ack_message(message: &V2Message) -> bool {
+        match message.get(V2_MSHEADER_ID, 1) {
+            Ok(msh) => msh_component(msh, 9, 1, message.parser_chars()) == V2_ACK_MESSAGE_TYPE,
+            Err(_) => false,
+        }
+    }
+
+    ///
+    /// One error reported by the peer in an ERR segment of its acknowledgement.
+    ///
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct V2AckError {
+        /// Location in the same notation as [V2ValidationIssue::location], e.g. `PID-5[2].1`.
+        pub location: V2String,
+        /// HL7 table 0357 error condition code.
+        pub code: V2String,
+        pub description: V2String,
+        /// ERR-4. Errors reported in the deprecated ERR-1 carry no severity and count as errors.
+        pub severity: V2Severity,
+        /// ERR-7 diagnostic information, or ERR-8 user message if the former is not valued.
+        pub diagnostic: V2String,
+    }
+
+    ///
+    /// Decoded MSA and ERR segments of an acknowledgement message.
+    ///
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct V2AckResponse {
+        pub code: V2AckCode,
+        /// MSA-2. Control id (MSH-10) of the message being acknowledged.
+        pub control_id: V2String,
+        /// MSA-3. Free text message from the peer.
+        pub text: V2String,
+        pub errors: Vec<V2AckError>,
+    }
+
+    impl V2AckResponse {
+        pub fn from_message(message: &V2Message) -> V2Result<V2AckResponse> {
+            if !is_ack_message(message) {
+                return Err(RUMString::from("Message is not an acknowledgement!"));
+            }
+            let code = match ack_code(message) {
+                Some(code) => code,
+                None => return Err(RUMString::from("Acknowledgement has no valid code in MSA-1!")),
+            };
+            let parser_chars = message.parser_chars();
+            let msa = message.get_named("MSA", 1)?;
+            let errors = (1..=message.named_segment_count("ERR"))
+                .filter_map(|i| message.get_named("ERR", i).ok())
+                .flat_map(|err| ack_errors(err, parser_chars))
+                .collect();
+
+            Ok(V2AckResponse {
+                code,
+                control_id: component_text(msa, 2, 1, parser_chars),
+                text: component_text(msa, 3, 1, parser_chars),
+                errors,
+            })
+        }
+
+        ///
+        /// True if MSA-2 matches `control_id`, the MSH-10 of the message we sent.
+        ///
+        pub fn acknowledges(&self, control_id: &str) -> bool {
+            self.control_id == control_id
+        }
+
+        pub fn outcome(self) -> V2AckOutcome {
+            match self.code {
+                V2AckCode::AA | V2AckCode::CA => V2AckOutcome::Accepted(self),
+                V2AckCode::AE | V2AckCode::CE => V2AckOutcome::Error(self),
+                V2AckCode::AR | V2AckCode::CR => V2AckOutcome::Rejected(self),
+            }
+        }
+    }
+
+    ///
+    /// What the peer did with our message, regardless of acknowledgment mode.
+    ///
+    /// * [V2AckOutcome::Accepted] - AA or CA.
+    /// * [V2AckOutcome::Error] - AE or CE. The peer had trouble processing the message. Sending it
+    ///   again may succeed.
+    /// * [V2AckOutcome::Rejected] - AR or CR. The peer will not take this message. Do not resend it
+    ///   as is.
+    ///
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum V2AckOutcome {
+        Accepted(V2AckResponse),
+        Error(V2AckResponse),
+        Rejected(V2AckResponse),
+    }
+
+    impl V2AckOutcome {
+        pub fn response(&self) -> &V2AckResponse {
+            match self {
+                V2AckOutcome::Accepted(response) => response,
+                V2AckOutcome::Error(response) => response,
+                V2AckOutcome::Rejected(response) => response,
+            }
+        }
+
+        pub fn is_accepted(&self) -> bool {
+            matches!(self, V2AckOutcome::Accepted(_))
+        }
+    }
+
+    ///
+    /// Map a validation rule to its HL7 table 0357 error condition code.
+    ///
+    pub fn error_code(rule: V2ValidationRule) -> &'static str {
+        match rule {
+            V2ValidationRule::SegmentCardinality | V2ValidationRule::MessageStructure => "100",
+            V2ValidationRule::RequiredField
+            | V2ValidationRule::RequiredComponent
+            | V2ValidationRule::ConditionalComponent => "101",
+            V2ValidationRule::DataType
+            | V2ValidationRule::ExtraComponents
+            | V2ValidationRule::Repetition
+            | V2ValidationRule::NotSupported => "102",
+            V2ValidationRule::TableValue => "103",
+            V2ValidationRule::MaxLength => "104",
+        }
+    }
+
+    fn severity_code(severity: V2Severity) -> &'static str {
+        match severity {
+            V2Severity::Error => "E",
+            V2Severity::Warning => "W",
+            V2Severity::Info => "I",
+        }
+    }
+
+    fn err_segment(issue: &V2ValidationIssue, legacy: bool, parser_chars: &V2ParserCharacters) -> V2String {
+        let field_separator = parser_chars.field_separator.as_string();
+        let component_separator = parser_chars.component_separator.as_string();
+        let code = error_code(issue.rule);
+        let description = match get_table(V2_ERROR_CODE_TABLE) {
+            Some(table) => parser_chars.escape(table.description(code).unwrap_or_default()),
+            None => V2String::new(),
+        };
+        let location = V2ErrorLocation::parse(&issue.location);
+
+        match legacy {
+            true => {
+                let subcomponent_separator = parser_chars.subcomponent_separator.as_string();
+                let error = [code, description.as_str(), V2_ERROR_CODE_SYSTEM].join(&subcomponent_separator);
+                let (segment, sequence, field) = match &location {
+                    Some(location) => (location.segment, location.sequence, location.field),
+                    None => ("", "", ""),
+                };
+                let eld = [segment, sequence, field, error.as_str()].join(&component_separator);
+                ["ERR", eld.as_str()].join(&field_separator)
+            }
+            false => {
+                let erl = match location {
+                    Some(location) => location.to_erl(&component_separator),
+                    None => V2String::new(),
+                };
+                let error = [code, description.as_str(), V2_ERROR_CODE_SYSTEM].join(&component_separator);
+                let diagnostic = parser_chars.escape(&issue.message);
+                [
+                    "ERR",
+                    "",
+                    erl.as_str(),
+                    error.as_str(),
+                    severity_code(issue.severity),
+                    "",
+                    "",
+                    diagnostic.as_str(),
+                ]
+                .join(&field_separator)
+            }
+        }
+    }
+
+    fn parse_severity(code: &str) -> V2Severity {
+        match code {
+            "W" => V2Severity::Warning,
+            "I" => V2Severity::Info,
+            _ => V2Severity::Error,
+        }
+    }
+
+    ///
+    /// Errors listed in one ERR segment. ERR-3 (2.5+) describes a single error. Otherwise, each
+    /// repetition of ERR-1 (ELD) describes one.
+    ///
+    fn ack_errors(err: &V2Segment, parser_chars: &V2ParserCharacters) -> Vec<V2AckError> {
+        if !component_text(err, 3, 1, parser_chars).is_empty() {
+            let erl = (1..=6)
+                .map(|c| component_text(err, 2, c, parser_chars))
+                .collect::<Vec<V2String>>();
+            let diagnostic = match component_text(err, 7, 1, parser_chars) {
+                diagnostic if diagnostic.is_empty() => component_text(err, 8, 1, parser_chars),
+                diagnostic => diagnostic,
+            };
+            return vec![V2AckError {
+                location: format_error_location(&erl),
+                code: component_text(err, 3, 1, parser_chars),
+                description: component_text(err, 3, 2, parser_chars),
+                severity: parse_severity(&component_text(err, 4, 1, parser_chars)),
+                diagnostic,
+            }];
+        }
+
+        let eld = match err.get(1) {
+            Ok(eld) => eld,
+            Err(_) => return Vec::new(),
+        };
+        eld.iter()
+            .filter(|field| !field.is_empty())
+            .map(|field| {
+                let text = |c: isize| match field.get(c) {
+                    Ok(component) => parser_chars.unescape(component.as_str()),
+                    Err(_) => V2String::new(),
+                };
+                let error = match field.get(4) {
+                    Ok(component) => component
+                        .as_str()
+                        .split(parser_chars.subcomponent_separator as char)
+                        .map(|sub| parser_chars.unescape(sub))
+                        .collect::<Vec<V2String>>(),
+                    Err(_) => Vec::new(),
+                };
+                V2AckError {
+                    location: format_error_location(&[text(1), text(2), text(3)]),
+                    code: error.first().cloned().unwrap_or_default(),
+                    description: error.get(1).cloned().unwrap_or_default(),
+                    severity: V2Severity::Error,
+                    diagnostic: V2String::new(),
+                }
+            })
+            .collect()
+    }
+
+    ///
+    /// Inverse of [V2ErrorLocation::to_erl]. Turns ERL components (segment, sequence, field,
+    /// repetition, component, subcomponent) back into a location like `OBX(3)-5[2].1`.
+    ///
+    fn format_error_location(erl: &[V2String]) -> V2String {
+        let part = |i: usize| erl.get(i).map(|p| p.as_str()).unwrap_or_default();
+        let mut location = V2String::from(part(0));
+        if location.is_empty() {
+            return location;
+        }
+        if !part(1).is_empty() && part(1) != "1" {
+            location.push_str(&rumtk_format!("({})", part(1)));
+        }
+        if part(2).is_empty() {
+            return location;
+        }
+        location.push_str(&rumtk_format!("-{}", part(2)));
+        if !part(3).is_empty() {
+            location.push_str(&rumtk_format!("[{}]", part(3)));
+        }
+        for i in 4..6 {
+            if part(i).is_empty() {
+                break;
+            }
+            location.push_str(&rumtk_format!(".{}", part(i)));
+        }
+        location
+    }
+
+    ///
+    /// Plain text of component `component` in the first repetition of field `seq`. Empty if not
+    /// valued.
+    ///
+    fn component_text(segment: &V2Segment, seq: isize, component: isize, parser_chars: &V2ParserCharacters) -> V2String {
+        let field = match segment.get(seq) {
+            Ok(field_group) => match field_group.first() {
+                Some(field) => field,
+                None => return V2String::new(),
+            },
+            Err(_) => return V2String::new(),
+        };
+        match field.get(component) {
+            Ok(component) => parser_chars.unescape(component.as_str()),
+            Err(_) => V2String::new(),
+        }
+    }
+
+    ///
+    /// Error location decoded from a validation location such as `PID-5[2].1.3` or `OBX(3)-5`.
+    ///
+    struct V2ErrorLocation<'a> {
+        segment: &'a str,
+        sequence: &'a str,
+        field: &'a str,
+        repetition: &'a str,
+        component: &'a str,
+        subcomponent: &'a str,
+    }
+
+    impl<'a> V2ErrorLocation<'a> {
+        fn parse(location: &'a str) -> Option<V2ErrorLocation<'a>> {
+            let (segment, field_path) = match location.split_once('-') {
+                Some((segment, field_path)) => (segment, field_path),
+                None => (location, ""),
+            };
+            let (segment, sequence) = match segment.split_once('(') {
+                Some((segment, sequence)) => (segment, sequence.trim_end_matches(')')),
+                None => (segment, "1"),
+            };
+            if segment.len() != 3 || !segment.bytes().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()) {
+                return None;
+            }
+
+            let mut path = field_path.split('.');
+            let field = path.next().unwrap_or_default();
+            let (field, repetition) = match field.split_once('[') {
+                Some((field, repetition)) => (field, repetition.trim_end_matches(']')),
+                None => (field, ""),
+            };
+            Some(V2ErrorLocation {
+                segment,
+                sequence,
+                field,
+                repetition,
+                component: path.next().unwrap_or_default(),
+                subcomponent: path.next().unwrap_or_default(),
+            })
+        }
+
+        fn to_erl(&self, component_separator: &str) -> V2String {
+            let components = [
+                self.segment,
+                self.sequence,
+                self.field,
+                self.repetition,
+                self.component,
+                self.subcomponent,
+            ];
+            let valued = components.iter().rposition(|c| !c.is_empty()).unwrap_or(0) + 1;
+            components[..valued].join(component_separator)
+        }
+    }
+
+    ///
+    /// ERR-1 (ELD) was deprecated in 2.5 in favour of ERR-2 and ERR-3.
+    ///
+    fn uses_error_code_and_location(version: &str, parser_chars: &V2ParserCharacters) -> bool {
+        let version_id = version
+            .split(parser_chars.component_separator as char)
+            .next()
+            .unwrap_or_default();
+        let mut parts = version_id.split('.').map(|p| p.parse::<u16>().unwrap_or(0));
+        match (parts.next(), parts.next()) {
+            (Some(2), Some(minor)) => minor < 5,
+            _ => false,
+        }
+    }
+
+    ///
+    /// Full repeating field `seq` of the MSH as sent.
+    ///
+    fn msh_field(msh: &V2Segment, seq: u16, parser_chars: &V2ParserCharacters) -> V2String {
+        let indx = segment_field_index("MSH", seq);
+        if indx < 1 || indx as usize > msh.len() {
+            return V2String::new();
+        }
+        match msh.get(indx) {
+            Ok(field_group) => field_group
+                .iter()
+                .map(|field| field.to_string(parser_chars))
+                .collect::<Vec<V2String>>()
+                .join(&parser_chars.repetition_separator.as_string()),
+            Err(_) => V2String::new(),
+        }
+    }
+
+    fn msh_component(msh: &V2Segment, seq: u16, component: usize, parser_chars: &V2ParserCharacters) -> V2String {
+        let field = msh_field(msh, seq, parser_chars);
+        let first_repetition = field
+            .split(parser_chars.repetition_separator as char)
+            .next()
+            .unwrap_or_default();
+        V2String::from(
+            first_repetition
+                .split(parser_chars.component_separator as char)
+                .nth(component.saturating_sub(1))
+                .unwrap_or_default(),
+        )
+    }
+}
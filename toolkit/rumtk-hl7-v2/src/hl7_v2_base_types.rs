@@ -20,15 +20,14 @@
 
 pub mod v2_base_types {
     use crate::hl7_v2_constants::{V2_COMPONENT_TERMINATOR, V2_DATETIME_MICRO_LENGTH, V2_DATETIME_THOUSAND_TICK, V2_ESCAPE_TERMINATOR, V2_FIELD_TERMINATOR, V2_MSHEADER_PATTERN, V2_REPETITION_TERMINATOR, V2_SEARCH_EXPR_TYPE, V2_SEGMENT_IDS, V2_SEGMENT_TERMINATOR, V2_SUBCOMPONENT_TERMINATOR, V2_TRUNCATION_CHARACTER};
-    use crate::hl7_v2_search::REGEX_V2_SEARCH_DEFAULT;
+    use crate::hl7_v2_escape::v2_escape::v2_unescape;
+    use crate::hl7_v2_search::V2SearchCursor;
     use chrono::prelude::*;
     use rumtk_core::base::RUMResult;
     use rumtk_core::buffers::*;
     use rumtk_core::buffers::{buffer_find, buffer_to_str, is_unique_bytes};
     use rumtk_core::maths::generate_tenth_factor;
-    use rumtk_core::search::rumtk_search::{
-        string_search, string_search_named_captures, SearchGroups,
-    };
+    use rumtk_core::search::rumtk_search::string_search;
     use rumtk_core::serde::json::{RUMDeJson, RUMSerJson};
     use rumtk_core::strings::RUMString;
     use rumtk_core::strings::{rumtk_format, AsStr};
@@ -214,6 +213,39 @@ pub mod v2_base_types {
                 self.truncation_character,
             ]
         }
+
+        ///
+        /// Escape the delimiters found in `text` using the escape sequences in Section 2.7.1 so it
+        /// can be placed inside a field, component or subcomponent.
+        ///
+        pub fn escape(&self, text: &str) -> V2String {
+            let escape = self.escape_character as char;
+            let mut escaped = V2String::with_capacity(text.len());
+            for c in text.chars() {
+                let sequence = match c as u32 {
+                    c if c == self.field_separator as u32 => 'F',
+                    c if c == self.component_separator as u32 => 'S',
+                    c if c == self.subcomponent_separator as u32 => 'T',
+                    c if c == self.repetition_separator as u32 => 'R',
+                    c if c == self.escape_character as u32 => 'E',
+                    _ => {
+                        escaped.push(c);
+                        continue;
+                    }
+                };
+                escaped.push(escape);
+                escaped.push(sequence);
+                escaped.push(escape);
+            }
+            escaped
+        }
+
+        ///
+        /// Plain text of escaped `text`. See [v2_unescape](crate::hl7_v2_escape::v2_escape::v2_unescape).
+        ///
+        pub fn unescape(&self, text: &str) -> V2String {
+            v2_unescape(text, self)
+        }
     }
     ///
     /// Object representing the exact indices needed to search for a field or component.
@@ -221,10 +253,14 @@ pub mod v2_base_types {
     #[derive(Debug, PartialEq, Eq, Default, Clone)]
     pub struct V2SearchIndex {
         pub segment: u8,
+        /// Needed to tell custom segments apart since they all share the same id.
+        pub segment_name: V2String,
         pub segment_group: u8,
         pub field_group: u8,
         pub field: i16,
         pub component: i16,
+        /// 0 when the whole component is addressed.
+        pub subcomponent: u8,
     }
 
     impl V2SearchIndex {
@@ -237,38 +273,99 @@ pub mod v2_base_types {
         ) -> V2SearchIndex {
             V2SearchIndex {
                 segment: V2_SEGMENT_IDS(_segment.as_bytes()),
+                segment_name: V2String::from(_segment),
                 segment_group: _segment_group,
                 field_group: _sub_field,
                 field: _field,
                 component: _component,
+                subcomponent: 0,
             }
         }
 
-        pub fn from(expr: &str) -> V2SearchIndex {
-            match Self::expr_type(expr) {
-                V2_SEARCH_EXPR_TYPE::V2_DEFAULT => Self::from_v2_default(expr),
+        ///
+        /// Same as [V2SearchIndex::try_from]. Malformed expressions are reported as an error
+        /// instead of panicking.
+        ///
+        pub fn from(expr: &str) -> V2Result<V2SearchIndex> {
+            Self::try_from(expr)
+        }
+
+        ///
+        /// Grammar is `SEG[(group)][-]field[[repetition]][.component[.subcomponent]]`, with every
+        /// omitted index defaulting to 1 except the subcomponent, which is only set when given. A
+        /// single dash after the segment name or group is the usual HL7 notation, so `ZPD-3.1` and
+        /// `ZPD3.1` are both field 3. The field and component may be negative to count from the
+        /// end, which for the field takes a second dash (e.g. `ZPD--1` is the last field).
+        ///
+        fn from_v2_default(expr: &str) -> V2Result<V2SearchIndex> {
+            let mut cursor = V2SearchCursor::new(expr);
+            let _segment = cursor.take_segment_name()?;
+            let mut _segment_group: u8 = 1;
+            if cursor.eat("(") {
+                _segment_group = Self::take_index(&mut cursor, "segment group")?;
+                cursor.expect(")")?;
             }
+            let _field: i16 = match cursor.eat("-") || !cursor.at_end() {
+                true => Self::take_index(&mut cursor, "field")?,
+                false => 1,
+            };
+            let mut _sub_field: u8 = 1;
+            if cursor.eat("[") {
+                _sub_field = Self::take_index(&mut cursor, "field repetition")?;
+                cursor.expect("]")?;
+            }
+            let mut _component: i16 = 1;
+            let mut _subcomponent: u8 = 0;
+            if cursor.eat(".") {
+                _component = Self::take_index(&mut cursor, "component")?;
+                if cursor.eat(".") {
+                    _subcomponent = Self::take_index(&mut cursor, "subcomponent")?;
+                    if _subcomponent == 0 {
+                        return Err(cursor.error("Subcomponents are 1-indexed"));
+                    }
+                }
+            }
+            if !cursor.at_end() {
+                return Err(cursor.error("Unexpected trailing characters"));
+            }
+            let mut index = V2SearchIndex::new(&_segment, _segment_group, _field, _sub_field, _component);
+            index.subcomponent = _subcomponent;
+            Ok(index)
         }
 
-        fn from_v2_default(expr: &str) -> V2SearchIndex {
-            let expr_groups: SearchGroups =
-                string_search_named_captures(expr, REGEX_V2_SEARCH_DEFAULT, "1").unwrap();
-            let _segment = expr_groups.get("segment").unwrap();
-            let _segment_group: u8 = expr_groups
-                .get("segment_group")
-                .unwrap()
-                .parse()
-                .unwrap_or(1);
-            let _field: i16 = expr_groups.get("field").unwrap().parse().unwrap_or(1);
-            let _sub_field: u8 = expr_groups.get("sub_field").unwrap().parse().unwrap_or(1);
-            let _component: i16 = expr_groups.get("component").unwrap().parse().unwrap_or(1);
-            V2SearchIndex::new(_segment, _segment_group, _field, _sub_field, _component)
+        fn take_index<T: TryFrom<i64>>(cursor: &mut V2SearchCursor, what: &str) -> V2Result<T> {
+            let start = cursor.position();
+            let number = cursor.take_integer()?;
+            match T::try_from(number) {
+                Ok(index) => Ok(index),
+                Err(_) => Err(rumtk_format!(
+                    "The {} index {} at position {} of search expression {} is out of range!",
+                    what,
+                    number,
+                    start,
+                    cursor.expr()
+                )),
+            }
         }
 
         fn expr_type(expr: &str) -> V2_SEARCH_EXPR_TYPE {
             V2_SEARCH_EXPR_TYPE::V2_DEFAULT
         }
     }
+
+    impl TryFrom<&str> for V2SearchIndex {
+        type Error = RUMString;
+
+        fn try_from(expr: &str) -> V2Result<V2SearchIndex> {
+            match Self::expr_type(expr) {
+                V2_SEARCH_EXPR_TYPE::V2_DEFAULT => Self::from_v2_default(expr),
+                V2_SEARCH_EXPR_TYPE::V2_EXTENDED => Err(rumtk_format!(
+                    "Search expression {} needs the extended grammar! Use V2SearchExpression instead.",
+                    expr
+                )),
+            }
+        }
+    }
     ///
     /// Raw component list or vector of German strings. This list is structured as a  vector of
     /// subcomponents. Most V2 Components should be composed of a vector of one string.
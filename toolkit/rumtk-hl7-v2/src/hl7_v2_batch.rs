@@ -0,0 +1,569 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2026  Luis M. Santos, M.D. <lsantos@medicalmasses.com>
+ * Copyright (C) 2026  MedicalMasses L.L.C. <contact@medicalmasses.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+///
+/// HL7 batch files (Section 2.10.3). A file is an optional FHS, any number of batches and an
+/// optional FTS. Each batch is a BHS, its messages and a BTS. Messages outside of a BHS/BTS pair
+/// are accepted as a single unnamed batch.
+///
+/// [V2BatchReader] pulls one segment at a time from any [BufRead] so files of any size can be
+/// processed one message at a time. [V2BatchWriter] streams batches back out and fills the
+/// trailer counts in.
+///
+/// ## Example
+///
+/// ```
+/// use rumtk_hl7_v2::hl7_v2_batch::v2_batch::{V2BatchReader, V2BatchSegment, V2BatchWriter};
+/// use rumtk_hl7_v2::hl7_v2_parser::v2_parser::V2Message;
+///
+/// let message = V2Message::try_from("MSH|^~\\&|LAB|FAC|||20250101120000||ORU^R01^ORU_R01|MSG0001|P|2.5.1\rPID|1||12345\r").unwrap();
+/// let mut writer = V2BatchWriter::new(Vec::new());
+/// let header = V2BatchSegment::new_header("BHS", writer.parser_chars());
+/// writer.begin_batch(&header).unwrap();
+/// writer.write_message(&message).unwrap();
+/// writer.write_message(&message).unwrap();
+/// writer.end_batch().unwrap();
+/// let raw = writer.into_inner();
+///
+/// let mut reader = V2BatchReader::new(raw.as_slice());
+/// let messages: Vec<_> = reader.by_ref().map(|entry| entry.unwrap()).collect();
+/// assert_eq!(messages.len(), 2, "Expected both messages!");
+/// assert_eq!(messages[1].batch, 1, "Messages belong to the first batch!");
+/// assert_eq!(reader.batch_trailer().unwrap().message_count(), Some(2), "BTS-1 should count the messages!");
+/// ```
+///
+pub mod v2_batch {
+    use crate::hl7_v2_base_types::v2_primitives::{V2ParserCharacters, V2Result, V2String};
+    use crate::hl7_v2_constants::V2_MSHEADER_PATTERN;
+    use crate::hl7_v2_parser::v2_parser::V2Message;
+    use rumtk_core::buffers::RUMBuffer;
+    use rumtk_core::strings::{rumtk_format, RUMString};
+    use std::io::{BufRead, Cursor, Write};
+
+    pub const V2_FILE_HEADER: &[u8; 3] = b"FHS";
+    pub const V2_FILE_TRAILER: &[u8; 3] = b"FTS";
+    pub const V2_BATCH_HEADER: &[u8; 3] = b"BHS";
+    pub const V2_BATCH_TRAILER: &[u8; 3] = b"BTS";
+
+    /**************************** Types *****************************************/
+
+    ///
+    /// FHS, BHS, BTS or FTS segment. Fields are kept as raw (escaped) text and numbered like in
+    /// the standard, so for headers field 1 is the field separator and field 2 the encoding
+    /// characters.
+    ///
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct V2BatchSegment {
+        name: V2String,
+        fields: Vec<V2String>,
+    }
+
+    impl V2BatchSegment {
+        ///
+        /// Empty FHS or BHS carrying the separators in `sep`.
+        ///
+        pub fn new_header(name: &str, sep: &V2ParserCharacters) -> Self {
+            let encoding_characters: V2String = [
+                sep.component_separator,
+                sep.repetition_separator,
+                sep.escape_character,
+                sep.subcomponent_separator,
+            ]
+            .iter()
+            .map(|c| *c as char)
+            .collect();
+            V2BatchSegment {
+                name: V2String::from(name),
+                fields: vec![(sep.field_separator as char).to_string(), encoding_characters],
+            }
+        }
+
+        ///
+        /// BTS or FTS with its count in field 1.
+        ///
+        pub fn new_trailer(name: &str, count: usize) -> Self {
+            V2BatchSegment {
+                name: V2String::from(name),
+                fields: vec![count.to_string()],
+            }
+        }
+
+        fn parse(segment: &[u8], sep: &V2ParserCharacters) -> V2Result<Self> {
+            let text = V2String::from_utf8_lossy(segment);
+            let name = match text.get(..3) {
+                Some(name) => V2String::from(name),
+                None => return Err(rumtk_format!("Batch segment {} is too short!", text)),
+            };
+            let is_header = segment.starts_with(V2_FILE_HEADER) || segment.starts_with(V2_BATCH_HEADER);
+            let field_separator = match is_header {
+                true => match text[3..].chars().next() {
+                    Some(field_separator) => field_separator,
+                    None => return Err(rumtk_format!("{} is missing its field separator!", name)),
+                },
+                false => sep.field_separator as char,
+            };
+            let mut fields: Vec<V2String> = match text.get(4..) {
+                Some(rest) => rest.split(field_separator).map(V2String::from).collect(),
+                None => Vec::new(),
+            };
+            if is_header {
+                fields.insert(0, field_separator.to_string());
+            }
+            Ok(V2BatchSegment { name, fields })
+        }
+
+        pub fn name(&self) -> &str {
+            &self.name
+        }
+
+        ///
+        /// Raw contents of field `indx` (1-indexed). Unvalued fields are empty.
+        ///
+        pub fn field(&self, indx: usize) -> &str {
+            match indx.checked_sub(1).and_then(|indx| self.fields.get(indx)) {
+                Some(field) => field,
+                None => "",
+            }
+        }
+
+        ///
+        /// Set field `indx` (1-indexed) to raw `value`, padding with empty fields as needed.
+        ///
+        pub fn set_field(&mut self, indx: usize, value: &str) -> V2Result<()> {
+            if indx == 0 {
+                return Err(RUMString::from("Batch segment fields are 1-indexed!"));
+            }
+            if self.fields.len() < indx {
+                self.fields.resize(indx, V2String::new());
+            }
+            self.fields[indx - 1] = V2String::from(value);
+            Ok(())
+        }
+
+        pub fn sending_application(&self) -> &str {
+            self.field(3)
+        }
+
+        pub fn sending_facility(&self) -> &str {
+            self.field(4)
+        }
+
+        pub fn receiving_application(&self) -> &str {
+            self.field(5)
+        }
+
+        pub fn receiving_facility(&self) -> &str {
+            self.field(6)
+        }
+
+        pub fn creation_date_time(&self) -> &str {
+            self.field(7)
+        }
+
+        pub fn name_id_type(&self) -> &str {
+            self.field(9)
+        }
+
+        pub fn comment(&self) -> &str {
+            match self.is_trailer() {
+                true => self.field(2),
+                false => self.field(10),
+            }
+        }
+
+        pub fn control_id(&self) -> &str {
+            self.field(11)
+        }
+
+        ///
+        /// BTS-1 (messages in the batch) or FTS-1 (batches in the file), if valued.
+        ///
+        pub fn message_count(&self) -> Option<usize> {
+            match self.is_trailer() {
+                true => self.field(1).trim().parse::<usize>().ok(),
+                false => None,
+            }
+        }
+
+        pub fn is_trailer(&self) -> bool {
+            self.name.as_bytes() == V2_BATCH_TRAILER || self.name.as_bytes() == V2_FILE_TRAILER
+        }
+
+        pub fn to_string(&self, sep: &V2ParserCharacters) -> V2String {
+            let field_separator = (sep.field_separator as char).to_string();
+            match (self.is_trailer(), self.fields.split_first()) {
+                (_, None) => self.name.clone(),
+                (true, Some(_)) => rumtk_format!("{}{}{}", self.name, field_separator, self.fields.join(&field_separator)),
+                (false, Some((first, rest))) => {
+                    rumtk_format!("{}{}{}", self.name, first, rest.join(&field_separator))
+                }
+            }
+        }
+    }
+
+    ///
+    /// Message read from a batch file along with the batch it belongs to.
+    ///
+    #[derive(Debug, Clone)]
+    pub struct V2BatchMessage {
+        pub message: V2Message,
+        /// 1-indexed batch number, 0 for messages outside of a BHS/BTS pair.
+        pub batch: usize,
+        pub batch_header: Option<V2BatchSegment>,
+    }
+
+    ///
+    /// Lazy reader over a batch file. Each item is a message, or an error for segments that do
+    /// not belong anywhere, messages that fail to parse and trailers whose counts do not match
+    /// what was read. Reading can continue after an error.
+    ///
+    pub struct V2BatchReader<R: BufRead> {
+        reader: R,
+        sep: V2ParserCharacters,
+        pending: Option<Vec<u8>>,
+        file_header: Option<V2BatchSegment>,
+        file_trailer: Option<V2BatchSegment>,
+        batch_header: Option<V2BatchSegment>,
+        batch_trailer: Option<V2BatchSegment>,
+        /// Set by BHS and cleared by BTS.
+        in_batch: bool,
+        batches: usize,
+        batch_messages: usize,
+        messages: usize,
+        done: bool,
+    }
+
+    impl<R: BufRead> V2BatchReader<R> {
+        pub fn new(reader: R) -> Self {
+            V2BatchReader {
+                reader,
+                sep: V2ParserCharacters::new(),
+                pending: None,
+                file_header: None,
+                file_trailer: None,
+                batch_header: None,
+                batch_trailer: None,
+                in_batch: false,
+                batches: 0,
+                batch_messages: 0,
+                messages: 0,
+                done: false,
+            }
+        }
+
+        pub fn file_header(&self) -> Option<&V2BatchSegment> {
+            self.file_header.as_ref()
+        }
+
+        pub fn file_trailer(&self) -> Option<&V2BatchSegment> {
+            self.file_trailer.as_ref()
+        }
+
+        ///
+        /// Header of the batch being read.
+        ///
+        pub fn batch_header(&self) -> Option<&V2BatchSegment> {
+            self.batch_header.as_ref()
+        }
+
+        ///
+        /// Trailer of the last batch that was closed.
+        ///
+        pub fn batch_trailer(&self) -> Option<&V2BatchSegment> {
+            self.batch_trailer.as_ref()
+        }
+
+        pub fn batch_count(&self) -> usize {
+            self.batches
+        }
+
+        pub fn message_count(&self) -> usize {
+            self.messages
+        }
+
+        ///
+        /// Next non empty segment. Segments may be terminated by `\r`, `\n` or both.
+        ///
+        fn next_segment(&mut self) -> V2Result<Option<Vec<u8>>> {
+            if let Some(segment) = self.pending.take() {
+                return Ok(Some(segment));
+            }
+            let mut segment = Vec::new();
+            loop {
+                let available = match self.reader.fill_buf() {
+                    Ok(available) => available,
+                    Err(e) => return Err(rumtk_format!("Failed to read batch file: {}", e)),
+                };
+                if available.is_empty() {
+                    return Ok(match segment.is_empty() {
+                        true => None,
+                        false => Some(segment),
+                    });
+                }
+                match available.iter().position(|c| *c == b'\r' || *c == b'\n') {
+                    Some(end) => {
+                        segment.extend_from_slice(&available[..end]);
+                        self.reader.consume(end + 1);
+                        if !segment.is_empty() {
+                            return Ok(Some(segment));
+                        }
+                    }
+                    None => {
+                        let len = available.len();
+                        segment.extend_from_slice(available);
+                        self.reader.consume(len);
+                    }
+                }
+            }
+        }
+
+        fn read_message(&mut self, msh: Vec<u8>) -> V2Result<V2Message> {
+            let mut raw = msh;
+            raw.push(self.sep.segment_terminator);
+            while let Some(segment) = self.next_segment()? {
+                if is_boundary(&segment) {
+                    self.pending = Some(segment);
+                    break;
+                }
+                raw.extend_from_slice(&segment);
+                raw.push(self.sep.segment_terminator);
+            }
+            V2Message::try_from_buffer(RUMBuffer::from(raw))
+        }
+
+        fn read_next(&mut self) -> V2Result<Option<V2BatchMessage>> {
+            while let Some(segment) = self.next_segment()? {
+                match segment.get(..3).unwrap_or_default() {
+                    name if name == V2_FILE_HEADER => {
+                        if self.file_header.is_some() || self.batches > 0 || self.messages > 0 {
+                            return Err(RUMString::from("FHS must be the first segment of a batch file!"));
+                        }
+                        self.sep = header_parser_chars(&segment)?;
+                        self.file_header = Some(V2BatchSegment::parse(&segment, &self.sep)?);
+                    }
+                    name if name == V2_BATCH_HEADER => {
+                        self.sep = header_parser_chars(&segment)?;
+                        self.batch_header = Some(V2BatchSegment::parse(&segment, &self.sep)?);
+                        self.batch_trailer = None;
+                        self.in_batch = true;
+                        self.batches += 1;
+                        self.batch_messages = 0;
+                    }
+                    name if name == V2_BATCH_TRAILER => {
+                        let trailer = V2BatchSegment::parse(&segment, &self.sep)?;
+                        let expected = trailer.message_count();
+                        let batch_messages = self.batch_messages;
+                        self.batch_header = None;
+                        self.batch_trailer = Some(trailer);
+                        self.in_batch = false;
+                        self.batch_messages = 0;
+                        check_count("BTS", expected, batch_messages)?;
+                    }
+                    name if name == V2_FILE_TRAILER => {
+                        let trailer = V2BatchSegment::parse(&segment, &self.sep)?;
+                        let expected = trailer.message_count();
+                        self.file_trailer = Some(trailer);
+                        let batches = match (self.batches, self.messages) {
+                            (0, 0) => 0,
+                            (0, _) => 1,
+                            (batches, _) => batches,
+                        };
+                        check_count("FTS", expected, batches)?;
+                    }
+                    name if name == V2_MSHEADER_PATTERN => {
+                        let message = self.read_message(segment)?;
+                        self.messages += 1;
+                        self.batch_messages += 1;
+                        return Ok(Some(V2BatchMessage {
+                            message,
+                            batch: match self.in_batch {
+                                true => self.batches,
+                                false => 0,
+                            },
+                            batch_header: self.batch_header.clone(),
+                        }));
+                    }
+                    _ => {
+                        return Err(rumtk_format!(
+                            "Segment {} is outside of any message!",
+                            V2String::from_utf8_lossy(&segment[..segment.len().min(3)])
+                        ))
+                    }
+                }
+            }
+            Ok(None)
+        }
+    }
+
+    impl V2BatchReader<Cursor<RUMBuffer>> {
+        ///
+        /// Reader over a batch file that is already in memory.
+        ///
+        pub fn from_buffer(buffer: RUMBuffer) -> Self {
+            Self::new(Cursor::new(buffer))
+        }
+    }
+
+    impl<R: BufRead> Iterator for V2BatchReader<R> {
+        type Item = V2Result<V2BatchMessage>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.done {
+                return None;
+            }
+            match self.read_next() {
+                Ok(Some(message)) => Some(Ok(message)),
+                Ok(None) => {
+                    self.done = true;
+                    None
+                }
+                Err(e) => Some(Err(e)),
+            }
+        }
+    }
+
+    ///
+    /// Streams a batch file out. Trailer counts are filled in from what was written.
+    ///
+    pub struct V2BatchWriter<W: Write> {
+        writer: W,
+        sep: V2ParserCharacters,
+        batches: usize,
+        batch_messages: usize,
+        messages: usize,
+    }
+
+    impl<W: Write> V2BatchWriter<W> {
+        pub fn new(writer: W) -> Self {
+            Self::with_parser_chars(writer, V2ParserCharacters::new())
+        }
+
+        pub fn with_parser_chars(writer: W, sep: V2ParserCharacters) -> Self {
+            V2BatchWriter {
+                writer,
+                sep,
+                batches: 0,
+                batch_messages: 0,
+                messages: 0,
+            }
+        }
+
+        pub fn parser_chars(&self) -> &V2ParserCharacters {
+            &self.sep
+        }
+
+        pub fn begin_file(&mut self, header: &V2BatchSegment) -> V2Result<()> {
+            self.write_segment(header)
+        }
+
+        pub fn begin_batch(&mut self, header: &V2BatchSegment) -> V2Result<()> {
+            self.batches += 1;
+            self.batch_messages = 0;
+            self.write_segment(header)
+        }
+
+        ///
+        /// Write `message` encoded with its character set. See [V2Message::to_bytes].
+        ///
+        pub fn write_message(&mut self, message: &V2Message) -> V2Result<()> {
+            let mut raw = message.to_bytes()?;
+            if raw.last() != Some(&self.sep.segment_terminator) {
+                raw.push(self.sep.segment_terminator);
+            }
+            self.write_raw(&raw)?;
+            self.batch_messages += 1;
+            self.messages += 1;
+            Ok(())
+        }
+
+        ///
+        /// Write the BTS with the number of messages written since the last BHS.
+        ///
+        pub fn end_batch(&mut self) -> V2Result<()> {
+            let trailer = V2BatchSegment::new_trailer("BTS", self.batch_messages);
+            self.write_segment(&trailer)
+        }
+
+        ///
+        /// Write the FTS with the number of batches written.
+        ///
+        pub fn end_file(&mut self) -> V2Result<()> {
+            let trailer = V2BatchSegment::new_trailer("FTS", self.batches);
+            self.write_segment(&trailer)?;
+            match self.writer.flush() {
+                Ok(_) => Ok(()),
+                Err(e) => Err(rumtk_format!("Failed to flush batch file: {}", e)),
+            }
+        }
+
+        pub fn message_count(&self) -> usize {
+            self.messages
+        }
+
+        pub fn into_inner(self) -> W {
+            self.writer
+        }
+
+        fn write_segment(&mut self, segment: &V2BatchSegment) -> V2Result<()> {
+            let mut raw = segment.to_string(&self.sep).into_bytes();
+            raw.push(self.sep.segment_terminator);
+            self.write_raw(&raw)
+        }
+
+        fn write_raw(&mut self, raw: &[u8]) -> V2Result<()> {
+            match self.writer.write_all(raw) {
+                Ok(_) => Ok(()),
+                Err(e) => Err(rumtk_format!("Failed to write batch file: {}", e)),
+            }
+        }
+    }
+
+    /**************************** Helpers ***************************************/
+
+    fn is_boundary(segment: &[u8]) -> bool {
+        [V2_MSHEADER_PATTERN, V2_FILE_HEADER, V2_FILE_TRAILER, V2_BATCH_HEADER, V2_BATCH_TRAILER]
+            .iter()
+            .any(|name| segment.starts_with(*name))
+    }
+
+    ///
+    /// FHS and BHS carry the separators the same way MSH does.
+    ///
+    fn header_parser_chars(segment: &[u8]) -> V2Result<V2ParserCharacters> {
+        let fragment = &segment[V2_MSHEADER_PATTERN.len()..];
+        let fragment_len = match fragment.get(1..).and_then(|rest| rest.iter().position(|c| *c == fragment[0])) {
+            Some(len) => len + 1,
+            None => fragment.len(),
+        };
+        V2ParserCharacters::from_fragment(&fragment[..fragment_len])
+    }
+
+    fn check_count(trailer: &str, expected: Option<usize>, found: usize) -> V2Result<()> {
+        match expected {
+            Some(expected) if expected != found => Err(rumtk_format!(
+                "{}-1 says {} but {} were read!",
+                trailer,
+                expected,
+                found
+            )),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,345 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2026  Luis M. Santos, M.D. <lsantos@medicalmasses.com>
+ * Copyright (C) 2026  MedicalMasses L.L.C. <contact@medicalmasses.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+///
+/// Programmatic construction of [V2Message]. The builder starts with an MSH carrying the encoding
+/// characters of its [V2ParserCharacters], a timestamp in MSH-7 and a generated control id in
+/// MSH-10. Values are placed by path using the same notation as validation locations, i.e.
+/// `SEG(instance)-field[repetition].component.subcomponent` where everything after the field is
+/// optional. Values are escaped on the way in.
+///
+/// ## Example
+///
+/// ```
+/// use rumtk_hl7_v2::hl7_v2_builder::v2_builder::V2MessageBuilder;
+///
+/// let mut builder = V2MessageBuilder::new();
+/// builder
+///     .set("MSH-3", "ADT1").unwrap()
+///     .set("MSH-7", "20250101120000").unwrap()
+///     .set("MSH-9.1", "ADT").unwrap()
+///     .set("MSH-9.2", "A01").unwrap()
+///     .set("MSH-10", "MSG0001").unwrap()
+///     .set("MSH-12", "2.5.1").unwrap()
+///     .set("PID-3.1", "12345").unwrap()
+///     .set("PID-5[1].1", "O'Brien & Sons").unwrap()
+///     .set("PID-5[1].2", "John").unwrap();
+/// let message = builder.build().unwrap();
+///
+/// assert_eq!(
+///     message.to_string(),
+///     "MSH|^~\\&|ADT1||||20250101120000||ADT^A01|MSG0001||2.5.1\rPID|||12345||O'Brien \\T\\ Sons^John\r",
+///     "Built message is wrong!"
+/// );
+/// ```
+///
+pub mod v2_builder {
+    use crate::hl7_v2_base_types::v2_primitives::{V2DateTime, V2ParserCharacters, V2Result, V2String};
+    use crate::hl7_v2_constants::V2_MSHEADER_PATTERN_STR;
+    use crate::hl7_v2_parser::v2_parser::V2Message;
+    use chrono::Utc;
+    use rumtk_core::rumtk_generate_id;
+    use rumtk_core::strings::{rumtk_format, AsString, RUMString};
+    use std::fmt::{Display, Formatter};
+
+    /// Repetitions -> components -> subcomponents.
+    type V2BuilderField = Vec<Vec<Vec<V2String>>>;
+
+    #[derive(Debug, Clone)]
+    struct V2BuilderSegment {
+        name: V2String,
+        /// Fields in sequence order. The first entry is field 1.
+        fields: Vec<V2BuilderField>,
+    }
+
+    ///
+    /// Decoded builder path. Zero means the whole level is being set.
+    ///
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct V2BuilderPath {
+        pub segment: V2String,
+        pub instance: usize,
+        pub field: usize,
+        pub repetition: usize,
+        pub component: usize,
+        pub subcomponent: usize,
+    }
+
+    impl V2BuilderPath {
+        pub fn parse(path: &str) -> V2Result<V2BuilderPath> {
+            let (segment, field_path) = match path.split_once('-') {
+                Some((segment, field_path)) => (segment, field_path),
+                None => return Err(rumtk_format!("Path {} does not name a field!", path)),
+            };
+            let (segment, instance) = match segment.split_once('(') {
+                Some((segment, instance)) => (segment, parse_index(instance.trim_end_matches(')'), path)?),
+                None => (segment, 1),
+            };
+            validate_segment_name(segment)?;
+
+            let mut levels = field_path.split('.');
+            let field = levels.next().unwrap_or_default();
+            let (field, repetition) = match field.split_once('[') {
+                Some((field, repetition)) => (field, parse_index(repetition.trim_end_matches(']'), path)?),
+                None => (field, 1),
+            };
+            let component = match levels.next() {
+                Some(component) => parse_index(component, path)?,
+                None => 0,
+            };
+            let subcomponent = match levels.next() {
+                Some(subcomponent) => parse_index(subcomponent, path)?,
+                None => 0,
+            };
+            if levels.next().is_some() {
+                return Err(rumtk_format!("Path {} goes deeper than subcomponents!", path));
+            }
+
+            Ok(V2BuilderPath {
+                segment: V2String::from(segment),
+                instance,
+                field: parse_index(field, path)?,
+                repetition,
+                component,
+                subcomponent,
+            })
+        }
+    }
+
+    fn parse_index(index: &str, path: &str) -> V2Result<usize> {
+        match index.parse::<usize>() {
+            Ok(index) if index > 0 => Ok(index),
+            _ => Err(rumtk_format!("Invalid index {} in path {}! Indices start at 1.", index, path)),
+        }
+    }
+
+    fn validate_segment_name(name: &str) -> V2Result<()> {
+        match name.len() == 3 && name.bytes().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()) {
+            true => Ok(()),
+            false => Err(rumtk_format!(
+                "Segment name {} is invalid! Expected 3 uppercase letters or digits.",
+                name
+            )),
+        }
+    }
+
+    fn slot<T: Default>(list: &mut Vec<T>, index: usize) -> &mut T {
+        if list.len() < index {
+            list.resize_with(index, T::default);
+        }
+        &mut list[index - 1]
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct V2MessageBuilder {
+        parser_chars: V2ParserCharacters,
+        segments: Vec<V2BuilderSegment>,
+    }
+
+    impl Default for V2MessageBuilder {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl V2MessageBuilder {
+        ///
+        /// Builder using the default separators (`|^~\&`).
+        ///
+        pub fn new() -> V2MessageBuilder {
+            Self::from_parser_chars(V2ParserCharacters::new())
+        }
+
+        pub fn from_parser_chars(parser_chars: V2ParserCharacters) -> V2MessageBuilder {
+            let mut builder = V2MessageBuilder {
+                parser_chars,
+                segments: vec![V2BuilderSegment {
+                    name: V2String::from(V2_MSHEADER_PATTERN_STR),
+                    fields: Vec::new(),
+                }],
+            };
+            let now = V2DateTime::from_utc_datetime(&Utc::now()).as_v2_date_time();
+            builder.set_value("MSH-7", now, false).unwrap();
+            builder.set_value("MSH-10", rumtk_generate_id!(), false).unwrap();
+            builder
+        }
+
+        pub fn parser_chars(&self) -> &V2ParserCharacters {
+            &self.parser_chars
+        }
+
+        ///
+        /// Append an empty segment. Segments named in a path passed to [V2MessageBuilder::set] are
+        /// appended automatically if the path refers to the next instance, so this is only needed
+        /// for segments that carry no fields or to force a specific order.
+        ///
+        pub fn add_segment(&mut self, name: &str) -> V2Result<&mut Self> {
+            validate_segment_name(name)?;
+            self.segments.push(V2BuilderSegment {
+                name: V2String::from(name),
+                fields: Vec::new(),
+            });
+            Ok(self)
+        }
+
+        ///
+        /// Set the value at `path`. The value is escaped so separators in it are kept as data.
+        ///
+        pub fn set(&mut self, path: &str, value: &str) -> V2Result<&mut Self> {
+            let escaped = self.parser_chars.escape(value);
+            self.set_value(path, escaped, false)?;
+            Ok(self)
+        }
+
+        ///
+        /// Set the value at `path` as is. Use this to place already encoded content such as
+        /// `ADT^A01^ADT_A01` in one go.
+        ///
+        pub fn set_raw(&mut self, path: &str, value: &str) -> V2Result<&mut Self> {
+            self.set_value(path, V2String::from(value), false)?;
+            Ok(self)
+        }
+
+        ///
+        /// Add a repetition to the field at `path` and place `value` in it. The repetition index in
+        /// the path, if any, is ignored.
+        ///
+        pub fn push_repetition(&mut self, path: &str, value: &str) -> V2Result<&mut Self> {
+            let escaped = self.parser_chars.escape(value);
+            self.set_value(path, escaped, true)?;
+            Ok(self)
+        }
+
+        ///
+        /// Parse the generated text into a [V2Message].
+        ///
+        pub fn build(&self) -> V2Result<V2Message> {
+            V2Message::try_from(self.to_string().as_str())
+        }
+
+        fn set_value(&mut self, path: &str, value: V2String, new_repetition: bool) -> V2Result<()> {
+            let path = V2BuilderPath::parse(path)?;
+            let is_msh = path.segment == V2_MSHEADER_PATTERN_STR;
+            if is_msh && path.field < 3 {
+                return Err(RUMString::from(
+                    "MSH-1 and MSH-2 are derived from the parser characters and cannot be set!",
+                ));
+            }
+            if is_msh && path.instance > 1 {
+                return Err(RUMString::from("A message has a single MSH!"));
+            }
+
+            let segment = self.segment_mut(&path.segment, path.instance)?;
+            let field = slot(&mut segment.fields, path.field);
+            let repetition = match new_repetition {
+                true => {
+                    field.push(Vec::new());
+                    field.len()
+                }
+                false => path.repetition,
+            };
+            let components = slot(field, repetition);
+            match (path.component, path.subcomponent) {
+                (0, _) => *components = vec![vec![value]],
+                (component, 0) => *slot(components, component) = vec![value],
+                (component, subcomponent) => {
+                    *slot(slot(components, component), subcomponent) = value
+                }
+            }
+            Ok(())
+        }
+
+        fn segment_mut(&mut self, name: &str, instance: usize) -> V2Result<&mut V2BuilderSegment> {
+            let count = self.segments.iter().filter(|s| s.name == name).count();
+            if instance == count + 1 {
+                self.add_segment(name)?;
+            } else if instance > count {
+                return Err(rumtk_format!(
+                    "Cannot set {}({}) because the message has only {} {} segments!",
+                    name,
+                    instance,
+                    count,
+                    name
+                ));
+            }
+            match self.segments.iter_mut().filter(|s| s.name == name).nth(instance - 1) {
+                Some(segment) => Ok(segment),
+                None => Err(rumtk_format!("Segment {}({}) not found!", name, instance)),
+            }
+        }
+
+        fn segment_to_string(&self, segment: &V2BuilderSegment) -> V2String {
+            let field_separator = self.parser_chars.field_separator.as_string();
+            let component_separator = self.parser_chars.component_separator.as_string();
+            let repetition_separator = self.parser_chars.repetition_separator.as_string();
+            let subcomponent_separator = self.parser_chars.subcomponent_separator.as_string();
+
+            let mut fields: Vec<V2String> = segment
+                .fields
+                .iter()
+                .map(|field| {
+                    field
+                        .iter()
+                        .map(|components| {
+                            components
+                                .iter()
+                                .map(|subcomponents| subcomponents.join(&subcomponent_separator))
+                                .collect::<Vec<V2String>>()
+                                .join(&component_separator)
+                        })
+                        .collect::<Vec<V2String>>()
+                        .join(&repetition_separator)
+                })
+                .collect();
+
+            match segment.name == V2_MSHEADER_PATTERN_STR {
+                true => {
+                    // MSH-1 is the field separator itself, so MSH-2 directly follows the name.
+                    let encoding_characters = rumtk_format!(
+                        "{}{}{}{}",
+                        self.parser_chars.component_separator as char,
+                        self.parser_chars.repetition_separator as char,
+                        self.parser_chars.escape_character as char,
+                        self.parser_chars.subcomponent_separator as char
+                    );
+                    if fields.len() < 2 {
+                        fields.resize_with(2, V2String::new);
+                    }
+                    fields[1] = encoding_characters;
+                    rumtk_format!("{}{}{}", segment.name, field_separator, fields[1..].join(&field_separator))
+                }
+                false => match fields.is_empty() {
+                    true => segment.name.clone(),
+                    false => rumtk_format!("{}{}{}", segment.name, field_separator, fields.join(&field_separator)),
+                },
+            }
+        }
+    }
+
+    impl Display for V2MessageBuilder {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            let terminator = self.parser_chars.segment_terminator.as_string();
+            for segment in self.segments.iter() {
+                write!(f, "{}{}", self.segment_to_string(segment), terminator)?;
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,189 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2026  Luis M. Santos, M.D. <lsantos@medicalmasses.com>
+ * Copyright (C) 2026  MedicalMasses L.L.C. <contact@medicalmasses.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+///
+/// Character set handling (Section 2.14.9.18). MSH-18 names the character set of the message; when
+/// it is valued and we know the encoding, the message is decoded and encoded with it. Guessing the
+/// encoding is only a fallback for messages that do not say or say something we cannot decode.
+///
+/// ## Example
+///
+/// ```
+/// use rumtk_hl7_v2::hl7_v2_charset::v2_charset::{v2_decode_message, v2_encode_message, V2EncodingSource};
+///
+/// let raw = b"MSH|^~\\&|APP|FAC|||20250101120000||ADT^A01^ADT_A01|MSG0001|P|2.5.1||||||8859/1\rPID|1||12345||Mu\xf1oz^Jos\xe9\r";
+/// let (text, encoding) = v2_decode_message(raw).unwrap();
+///
+/// assert!(text.ends_with("PID|1||12345||Muñoz^José\r"), "Latin-1 was not decoded!");
+/// assert_eq!(encoding.source, V2EncodingSource::Declared, "MSH-18 should have been honored!");
+/// assert_eq!(v2_encode_message(&text).unwrap(), raw.to_vec(), "Message should encode back to Latin-1!");
+/// ```
+///
+pub mod v2_charset {
+    use crate::hl7_v2_base_types::v2_primitives::{V2ParserCharacters, V2Result, V2String};
+    use crate::hl7_v2_constants::{V2_CHARSET_ENCODINGS, V2_MSHEADER_PATTERN};
+    use rumtk_core::serde::json::{RUMDeJson, RUMSerJson};
+    use rumtk_core::strings::{guess_encoding, try_decode_with, try_encode_with};
+    use std::borrow::Cow;
+
+    /// Encoding used when MSH-18 is not valued. HL7 defaults to ASCII, which UTF-8 is a superset of.
+    pub const V2_DEFAULT_ENCODING: &str = "utf-8";
+    /// Index of MSH-18 when the header is split on the field separator, the segment name included.
+    const MSH_CHARSET_INDEX: usize = 17;
+
+    ///
+    /// How the encoding of a message was picked.
+    ///
+    #[derive(Debug, Default, RUMSerJson, RUMDeJson, PartialEq, Eq, Clone, Copy)]
+    pub enum V2EncodingSource {
+        /// MSH-18 named a character set we could decode with.
+        Declared,
+        /// MSH-18 was not valued or not usable and the message was valid UTF-8.
+        #[default]
+        Default,
+        /// MSH-18 was missing, unknown, or did not match the data, so the encoding was detected.
+        Guessed,
+    }
+
+    ///
+    /// Character set of a message and how it was determined.
+    ///
+    #[derive(Debug, RUMSerJson, RUMDeJson, PartialEq, Eq, Clone)]
+    pub struct V2MessageEncoding {
+        /// First repetition of MSH-18 as found in the message.
+        pub charset: Option<V2String>,
+        /// Encoding label the message was decoded with and will be encoded with.
+        pub encoding: V2String,
+        pub source: V2EncodingSource,
+    }
+
+    impl Default for V2MessageEncoding {
+        fn default() -> Self {
+            V2MessageEncoding {
+                charset: None,
+                encoding: V2String::from(V2_DEFAULT_ENCODING),
+                source: V2EncodingSource::Default,
+            }
+        }
+    }
+
+    impl V2MessageEncoding {
+        ///
+        /// Encoding of text that is already decoded (e.g. a Rust string). Only MSH-18 is looked at
+        /// since there are no bytes left to guess from.
+        ///
+        pub fn from_text(text: &str) -> Self {
+            let charset = v2_declared_charset(text.as_bytes());
+            match charset.as_deref().and_then(V2_CHARSET_ENCODINGS) {
+                Some(encoding) => V2MessageEncoding {
+                    charset,
+                    encoding: V2String::from(encoding),
+                    source: V2EncodingSource::Declared,
+                },
+                None => V2MessageEncoding {
+                    charset,
+                    ..Default::default()
+                },
+            }
+        }
+    }
+
+    ///
+    /// First repetition and component of MSH-18 in a raw message, if valued. The header is
+    /// assumed to be ASCII compatible, as HL7 requires.
+    ///
+    pub fn v2_declared_charset(raw: &[u8]) -> Option<V2String> {
+        let msh_start = V2ParserCharacters::find_msh(raw).ok()?;
+        let header = &raw[msh_start..];
+        let sep = V2ParserCharacters::from(header).ok()?;
+        let header_len = header
+            .iter()
+            .position(|c| *c == b'\r' || *c == b'\n')
+            .unwrap_or(header.len());
+        if header_len < V2_MSHEADER_PATTERN.len() {
+            return None;
+        }
+        let field = header[..header_len]
+            .split(|c| *c == sep.field_separator)
+            .nth(MSH_CHARSET_INDEX)?;
+        let charset = field
+            .split(|c| *c == sep.repetition_separator)
+            .next()?
+            .split(|c| *c == sep.component_separator)
+            .next()?;
+        match std::str::from_utf8(charset).map(|charset| charset.trim()) {
+            Ok(charset) if !charset.is_empty() => Some(V2String::from(charset)),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Decode a raw message to UTF-8. MSH-18 wins when we know its encoding and the bytes decode
+    /// with it. Otherwise, valid UTF-8 is taken as is and anything else is decoded with the
+    /// detected encoding. UTF-8 input is borrowed rather than copied.
+    ///
+    pub fn v2_decode_message(raw: &[u8]) -> V2Result<(Cow<'_, str>, V2MessageEncoding)> {
+        let charset = v2_declared_charset(raw);
+        let utf8 = std::str::from_utf8(raw);
+        if let Some(encoding) = charset.as_deref().and_then(V2_CHARSET_ENCODINGS) {
+            let decoded = match (encoding, utf8) {
+                (V2_DEFAULT_ENCODING, Ok(text)) => Ok(Cow::Borrowed(text)),
+                _ => try_decode_with(raw, encoding).map(Cow::Owned),
+            };
+            if let Ok(text) = decoded {
+                let encoding = V2MessageEncoding {
+                    charset,
+                    encoding: V2String::from(encoding),
+                    source: V2EncodingSource::Declared,
+                };
+                return Ok((text, encoding));
+            }
+        }
+
+        if let Ok(text) = utf8 {
+            let encoding = V2MessageEncoding {
+                charset,
+                ..Default::default()
+            };
+            return Ok((Cow::Borrowed(text), encoding));
+        }
+
+        let guessed = guess_encoding(raw);
+        let text = try_decode_with(raw, guessed)?;
+        let encoding = V2MessageEncoding {
+            charset,
+            encoding: V2String::from(guessed),
+            source: V2EncodingSource::Guessed,
+        };
+        Ok((Cow::Owned(text), encoding))
+    }
+
+    ///
+    /// Encode message text with the encoding its MSH-18 names, or UTF-8 when MSH-18 is not
+    /// valued or unknown.
+    ///
+    pub fn v2_encode_message(text: &str) -> V2Result<Vec<u8>> {
+        v2_encode_message_with(text, &V2MessageEncoding::from_text(text))
+    }
+
+    pub fn v2_encode_message_with(text: &str, encoding: &V2MessageEncoding) -> V2Result<Vec<u8>> {
+        try_encode_with(text, &encoding.encoding)
+    }
+}
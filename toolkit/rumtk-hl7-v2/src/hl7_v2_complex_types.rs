@@ -21,7 +21,7 @@
 pub mod hl7_v2_complex_types {
     use crate::hl7_v2_base_types::v2_primitives::*;
     use crate::hl7_v2_field_descriptors::v2_field_descriptor::*;
-    use rumtk_core::strings::rumtk_format;
+    use rumtk_core::strings::{rumtk_format, StringUtils};
 
     type V2StrField<'a> = Vec<&'a str>;
 
@@ -47,13 +47,158 @@ pub mod hl7_v2_complex_types {
         V2ST(V2Result<V2ST>),
         V2Text(V2Result<V2TX>),
         V2SI(V2Result<V2SI>),
+        V2Complex(V2ComplexValue),
+        /// Component was not valued and is not required.
+        Empty,
         Err(V2String),
     }
 
+    impl V2Type {
+        ///
+        /// Returns true if this value or any of its nested components failed to cast.
+        ///
+        pub fn is_err(&self) -> bool {
+            match self {
+                V2Type::V2String(r) => r.is_err(),
+                V2Type::V2DateTime(r) => r.is_err(),
+                V2Type::V2Date(r) => r.is_err(),
+                V2Type::V2Time(r) => r.is_err(),
+                V2Type::V2FT(r) => r.is_err(),
+                V2Type::V2SNM(r) => r.is_err(),
+                V2Type::V2NM(r) => r.is_err(),
+                V2Type::V2ID(r) => r.is_err(),
+                V2Type::V2IS(r) => r.is_err(),
+                V2Type::V2ST(r) => r.is_err(),
+                V2Type::V2Text(r) => r.is_err(),
+                V2Type::V2SI(r) => r.is_err(),
+                V2Type::V2Complex(c) => !c.errors().is_empty(),
+                V2Type::Empty => false,
+                V2Type::Err(_) => true,
+            }
+        }
+
+        pub fn is_empty(&self) -> bool {
+            matches!(self, V2Type::Empty)
+        }
+
+        ///
+        /// Returns the nested complex value if this is a complex type.
+        ///
+        pub fn as_complex(&self) -> Option<&V2ComplexValue> {
+            match self {
+                V2Type::V2Complex(c) => Some(c),
+                _ => None,
+            }
+        }
+
+        ///
+        /// Returns the string form of a successfully cast string like primitive (ST, ID, IS, FT, TX
+        /// and String). Everything else yields None.
+        ///
+        pub fn as_str(&self) -> Option<&str> {
+            match self {
+                V2Type::V2String(Ok(s)) => Some(s.as_str()),
+                V2Type::V2FT(Ok(s)) => Some(s.as_str()),
+                V2Type::V2ID(Ok(s)) => Some(s.as_str()),
+                V2Type::V2IS(Ok(s)) => Some(s.as_str()),
+                V2Type::V2ST(Ok(s)) => Some(s.as_str()),
+                V2Type::V2Text(Ok(s)) => Some(s.as_str()),
+                V2Type::V2SNM(Ok(s)) => Some(s.as_str()),
+                _ => None,
+            }
+        }
+    }
+
+    pub type V2NamedComponent = (&'static str, V2Type);
+
+    ///
+    /// Result of casting a complex data type (CWE, XPN, CX, etc). Components are kept in sequence
+    /// order and can be retrieved by descriptor name or by their 1-indexed sequence number.
+    ///
+    #[derive(Debug)]
+    pub struct V2ComplexValue {
+        type_name: &'static str,
+        components: Vec<V2NamedComponent>,
+    }
+
+    impl V2ComplexValue {
+        pub fn type_name(&self) -> &str {
+            self.type_name
+        }
+
+        ///
+        /// Get component by descriptor name (e.g. `family_name`).
+        ///
+        pub fn get(&self, name: &str) -> Option<&V2Type> {
+            self.components
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, v)| v)
+        }
+
+        ///
+        /// Get component by sequence. Sequences are 1-indexed like in the standard.
+        ///
+        pub fn get_seq(&self, seq: usize) -> Option<&V2Type> {
+            match seq {
+                0 => None,
+                _ => self.components.get(seq - 1).map(|(_, v)| v),
+            }
+        }
+
+        pub fn len(&self) -> usize {
+            self.components.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.components.iter().all(|(_, v)| v.is_empty())
+        }
+
+        pub fn iter(&self) -> std::slice::Iter<'_, V2NamedComponent> {
+            self.components.iter()
+        }
+
+        ///
+        /// Collect all casting errors in this value, including the ones in nested components.
+        /// Each message is prefixed with the path of the component that failed.
+        ///
+        pub fn errors(&self) -> Vec<V2String> {
+            let mut errors = Vec::new();
+            for (name, component) in self.components.iter() {
+                match component {
+                    V2Type::V2Complex(nested) => {
+                        for e in nested.errors() {
+                            errors.push(rumtk_format!("{}.{}", self.type_name, e));
+                        }
+                    }
+                    V2Type::Err(e) => errors.push(rumtk_format!("{}.{}: {}", self.type_name, name, e)),
+                    c if c.is_err() => {
+                        errors.push(rumtk_format!("{}.{}: {:?}", self.type_name, name, c))
+                    }
+                    _ => continue,
+                }
+            }
+            errors
+        }
+    }
+
     pub fn cast_component(
         component: Vec<&str>,
         component_type: &V2ComponentTypeDescriptor,
         characters: &V2ParserCharacters,
+    ) -> V2Type {
+        cast_component_as(component, component_type, characters, V2Version::default())
+    }
+
+    ///
+    /// Same as [cast_component] but complex types are resolved with the descriptors of `version`
+    /// instead of the latest release. See [get_field_descriptor].
+    ///
+    pub fn cast_component_as(
+        component: Vec<&str>,
+        component_type: &V2ComponentTypeDescriptor,
+        characters: &V2ParserCharacters,
+        version: V2Version,
     ) -> V2Type {
         if component_type.optionality.is_required() && component.len() == 0 {
             return V2Type::Err(rumtk_format!(
@@ -70,8 +215,17 @@ pub mod hl7_v2_complex_types {
                             expect only one string. Got [{:?}]", &component
                         )
                     )
+                } else if component.is_empty() {
+                    V2Type::Empty
                 } else {
-                    let c = component[0];
+                    let truncated;
+                    let c = match component_type.truncate && component_type.max_input_len > 0 {
+                        true => {
+                            truncated = component[0].truncate(component_type.max_input_len as usize);
+                            truncated.as_str()
+                        }
+                        false => component[0],
+                    };
                     match primitive {
                         V2PrimitiveType::DateTime => V2Type::V2DateTime(c.to_v2datetime()),
                         V2PrimitiveType::Date => V2Type::V2Date(c.to_v2date()),
@@ -92,9 +246,140 @@ pub mod hl7_v2_complex_types {
                     }
                 }
             }
-            V2ComponentType::Complex(complex) => match complex {
-                _ => V2Type::Err(rumtk_format!("Unknown requested type!")),
-            },
+            V2ComponentType::Complex(complex) => {
+                cast_complex_type_as(component, complex, characters, version)
+            }
+        }
+    }
+
+    ///
+    /// Cast a list of component strings into the requested complex type by walking its entry in
+    /// [V2_FIELD_DESCRIPTORS] as of the latest release.
+    ///
+    /// Complex components nested inside the requested type (e.g. the HD in CX.4 or the FN in XPN.1)
+    /// are split on the subcomponent separator. The standard does not allow another level of
+    /// nesting below subcomponents, so complex types found at that depth receive the raw
+    /// subcomponent as their first component.
+    ///
+    pub fn cast_complex_type(
+        component: Vec<&str>,
+        complex_type: &V2ComplexType,
+        characters: &V2ParserCharacters,
+    ) -> V2Type {
+        cast_complex_type_as(component, complex_type, characters, V2Version::default())
+    }
+
+    ///
+    /// Same as [cast_complex_type] but using the definition of the type in `version`. Nested types
+    /// are resolved with the same version.
+    ///
+    pub fn cast_complex_type_as(
+        component: Vec<&str>,
+        complex_type: &V2ComplexType,
+        characters: &V2ParserCharacters,
+        version: V2Version,
+    ) -> V2Type {
+        cast_complex_type_at_depth(&component, complex_type, characters, version, 0)
+    }
+
+    fn cast_complex_type_at_depth(
+        component: &Vec<&str>,
+        complex_type: &V2ComplexType,
+        characters: &V2ParserCharacters,
+        version: V2Version,
+        depth: u8,
+    ) -> V2Type {
+        let type_name = complex_type_to_str(complex_type);
+        let descriptors = match get_field_descriptor(type_name, version) {
+            Some(descriptors) if !descriptors.is_empty() => descriptors,
+            _ => {
+                return V2Type::Err(rumtk_format!(
+                    "No descriptor available for complex type {} in v{}!",
+                    type_name,
+                    version.as_str()
+                ))
+            }
+        };
+        let conditions = build_condition_list(component, condition_list_size(type_name, descriptors.len()), characters);
+        let mut components = Vec::with_capacity(descriptors.len());
+
+        for (i, descriptor) in descriptors.iter().enumerate() {
+            let raw = match component.get(i) {
+                Some(c) => *c,
+                None => "",
+            };
+            let required = descriptor.optionality.is_required()
+                || descriptor.optionality.is_conditionally_required(&conditions);
+
+            if raw.is_empty() {
+                let value = match required {
+                    true => V2Type::Err(rumtk_format!(
+                        "Required component {}.{} ({}) is missing!",
+                        type_name,
+                        descriptor.seq,
+                        descriptor.description
+                    )),
+                    false => V2Type::Empty,
+                };
+                components.push((descriptor.name, value));
+                continue;
+            }
+
+            let value = match &descriptor.data_type {
+                V2ComponentType::Primitive(_) => {
+                    cast_component_as(vec![raw], descriptor, characters, version)
+                }
+                V2ComponentType::Complex(nested) => {
+                    let subcomponents = match depth {
+                        0 => raw
+                            .split(characters.subcomponent_separator as char)
+                            .collect(),
+                        _ => vec![raw],
+                    };
+                    cast_complex_type_at_depth(&subcomponents, nested, characters, version, depth + 1)
+                }
+            };
+            components.push((descriptor.name, value));
+        }
+
+        V2Type::V2Complex(V2ComplexValue {
+            type_name,
+            components,
+        })
+    }
+
+    ///
+    /// Builds the list handed to conditional optionality rules. Rules index components directly, so
+    /// the list is padded to the number of descriptors. Empty components yield an empty vector so
+    /// the `len() > 0` checks in the rules read as "is valued".
+    ///
+    ///
+    /// Length of the list passed to the conditions of `type_name`. Conditions index components
+    /// by their position in the latest release, so lists of older, shorter layouts are padded to
+    /// that length.
+    ///
+    pub fn condition_list_size(type_name: &str, size: usize) -> usize {
+        match get_field_descriptor(type_name, V2Version::default()) {
+            Some(latest) => size.max(latest.len()),
+            None => size,
+        }
+    }
+
+    pub fn build_condition_list<'a>(
+        component: &Vec<&'a str>,
+        size: usize,
+        characters: &V2ParserCharacters,
+    ) -> V2ComponentList<'a> {
+        let mut list: V2ComponentList = component
+            .iter()
+            .map(|&c| match c.is_empty() {
+                true => Vec::new(),
+                false => c.split(characters.subcomponent_separator as char).collect(),
+            })
+            .collect();
+        while list.len() < size {
+            list.push(Vec::new());
         }
+        list
     }
 }
@@ -23,6 +23,8 @@ pub const V2_MSHEADER_PATTERN_STR: &str = "MSH";
 pub const V2_MSHEADER_PATTERN: &[u8;3] = b"MSH";
 pub const V2_MSHEADER_ID: u8 = 88u8;
 pub const V2_TOTAL_VALID_SEGMENTS: u8 = 192u8;
+/// Id shared by every segment outside the standard (Z segments and anything we do not recognize).
+pub const V2_CUSTOM_SEGMENT_ID: u8 = V2_TOTAL_VALID_SEGMENTS;
 pub const V2_DELETE_FIELD: &str = "\"\"";
 pub const V2_SEGMENT_TERMINATOR: u8 = '\r' as u8;
 pub const V2_SEGMENT_TERMINATORS: &str = "\r\n";
@@ -41,6 +43,7 @@ pub type ElementDesc = fn(u8) -> &'static str;
 pub type ElementID = fn(&[u8]) -> u8;
 pub type ElementIDUSize = fn(&[u8]) -> usize;
 pub type ElementName = fn(u8) -> &'static [u8];
+pub type CharsetEncoding = fn(&str) -> Option<&'static str>;
 pub const V2_SEGMENT_DESC: ElementDesc = |k | -> &'static str {
     match k {
         1u8 => "Abstract",
@@ -837,6 +840,48 @@ pub static V2_MESSAGE_DESC: ElementDesc = |k| -> &'static str {
     }
 };
 
+///
+/// Maps the character sets of HL7 table 0211 (MSH-18) to encoding labels understood by
+/// [try_decode_with](rumtk_core::strings::try_decode_with). Character sets we cannot decode
+/// map to `None`, which makes the message fall back to UTF-8 or a guessed encoding:
+///
+/// * UTF-16 and UTF-32 (`UNICODE UTF-16`, `UNICODE UTF-32`). The header is no longer ASCII
+///   compatible, and the encoder can only write UTF-8.
+/// * JIS X 0201 Roman and katakana (`ISO IR14`, `ISO IR13`). Shift_JIS reads `\` and `~` where
+///   IR14 has `¥` and `‾`, and has no label for the katakana set alone.
+/// * CNS 11643 and JIS X 0212.
+///
+/// Note => encoding_rs follows the WHATWG labels, so `us-ascii` and `iso-8859-1` (ASCII,
+/// 8859/1) are decoded as windows-1252. It only differs from them in 0x80-0x9F, where the C1
+/// controls become printable characters such as `€`. Neither charset should carry those bytes.
+///
+pub static V2_CHARSET_ENCODINGS: CharsetEncoding = |k| -> Option<&'static str> {
+    match k.trim() {
+        "UNICODE UTF-16" | "UNICODE UTF-32" => None,
+        "ISO IR14" | "ISO IR13" => None,
+        "ASCII" | "ISO IR6" => Some("us-ascii"),
+        "8859/1" | "ISO IR100" => Some("iso-8859-1"),
+        "8859/2" | "ISO IR101" => Some("iso-8859-2"),
+        "8859/3" | "ISO IR109" => Some("iso-8859-3"),
+        "8859/4" | "ISO IR110" => Some("iso-8859-4"),
+        "8859/5" | "ISO IR144" => Some("iso-8859-5"),
+        "8859/6" | "ISO IR127" => Some("iso-8859-6"),
+        "8859/7" | "ISO IR126" => Some("iso-8859-7"),
+        "8859/8" | "ISO IR138" => Some("iso-8859-8"),
+        "8859/9" | "ISO IR148" => Some("iso-8859-9"),
+        "8859/15" => Some("iso-8859-15"),
+        "ISO IR87" => Some("iso-2022-jp"),
+        "GB 18030-2000" => Some("gb18030"),
+        "KS X 1001" => Some("euc-kr"),
+        "BIG-5" => Some("big5"),
+        "UNICODE" | "UNICODE UTF-8" => Some("utf-8"),
+        _ => None,
+    }
+};
+
 pub enum V2_SEARCH_EXPR_TYPE {
+    /// Single component lookup. See [REGEX_V2_SEARCH_DEFAULT](crate::hl7_v2_search::REGEX_V2_SEARCH_DEFAULT).
     V2_DEFAULT,
+    /// Wildcards, predicates and subcomponents. See [V2SearchExpression](crate::hl7_v2_search::V2SearchExpression).
+    V2_EXTENDED,
 }
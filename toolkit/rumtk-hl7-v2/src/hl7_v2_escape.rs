@@ -0,0 +1,512 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2026  Luis M. Santos, M.D. <lsantos@medicalmasses.com>
+ * Copyright (C) 2026  MedicalMasses L.L.C. <contact@medicalmasses.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+///
+/// HL7 escape sequences (Section 2.7). Component text is kept escaped in the message, so this
+/// codec is how applications read it as plain text and write plain text back.
+///
+/// [V2EscapedText::parse] splits raw component text into [V2EscapeSequence] tokens and
+/// [V2EscapedText::encode] writes them back exactly as they were read, including sequences that
+/// are malformed or that the application chose not to decode. [V2EscapedText::render] produces
+/// plain text, handling each [V2EscapeClass] as configured in [V2EscapeOptions].
+///
+/// ## Example
+///
+/// ```
+/// use rumtk_hl7_v2::hl7_v2_base_types::v2_primitives::V2ParserCharacters;
+/// use rumtk_hl7_v2::hl7_v2_escape::v2_escape::{V2EscapeHandling, V2EscapeOptions, V2EscapedText};
+///
+/// let sep = V2ParserCharacters::new();
+/// let raw = "Na\\T\\K\\.br\\\\H\\high\\N\\ \\X4F4B\\ \\Zlocal\\";
+/// let text = V2EscapedText::parse(raw, &sep);
+///
+/// assert_eq!(text.encode(), raw, "Escaped text should round trip!");
+/// assert_eq!(text.render(&V2EscapeOptions::default()), "Na&K\nhigh OK \\Zlocal\\", "Wrong plain text!");
+///
+/// let mut options = V2EscapeOptions::default();
+/// options.local = V2EscapeHandling::Strip;
+/// options.formatting = V2EscapeHandling::Preserve;
+/// assert_eq!(text.render(&options), "Na&K\\.br\\\\H\\high\\N\\ OK ", "Wrong plain text!");
+/// ```
+///
+pub mod v2_escape {
+    use crate::hl7_v2_base_types::v2_primitives::{V2ParserCharacters, V2Result, V2String};
+    use rumtk_core::strings::{rumtk_format, try_decode, try_decode_with, RUMString};
+
+    /**************************** Types *****************************************/
+
+    ///
+    /// Families of escape sequences that can be handled differently.
+    ///
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum V2EscapeClass {
+        /// `\F\ \S\ \T\ \R\ \E\` plus `\P\` for the truncation character (2.7.1).
+        Delimiter,
+        /// `\H\ \N\` highlighting (2.7.2) and the FT commands `\.br\ \.sp\ \.in\ ...` (2.7.6).
+        Formatting,
+        /// `\Xhhhh\` (2.7.3).
+        Hex,
+        /// `\Cxxyy\` and `\Mxxyyzz\` ISO 2022 character set shifts (2.7.4 and 2.7.5).
+        CharacterSet,
+        /// `\Zdddd\` locally defined escape sequences (2.7.8).
+        Local,
+    }
+
+    ///
+    /// What [V2EscapedText::render] does with a sequence of a given [V2EscapeClass].
+    ///
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum V2EscapeHandling {
+        /// Replace the sequence with what it stands for.
+        Decode,
+        /// Leave the sequence as is, escape characters included.
+        Preserve,
+        /// Drop the sequence.
+        Strip,
+    }
+
+    ///
+    /// Per class handling used when rendering. The defaults decode delimiters, hex data and
+    /// formatting, and preserve character set shifts and local escapes since their meaning is up to
+    /// the receiving application.
+    ///
+    #[derive(Debug, Clone)]
+    pub struct V2EscapeOptions {
+        pub delimiters: V2EscapeHandling,
+        pub formatting: V2EscapeHandling,
+        pub hex: V2EscapeHandling,
+        pub character_set: V2EscapeHandling,
+        pub local: V2EscapeHandling,
+        /// Encoding label (e.g. `ISO-8859-1`) of the bytes in hex sequences. When unset, the
+        /// encoding is guessed.
+        pub hex_encoding: Option<V2String>,
+        /// Called to decode local escapes when [V2EscapeOptions::local] is
+        /// [V2EscapeHandling::Decode]. Receives the payload after `Z`. Without a decoder, local
+        /// escapes are preserved.
+        pub local_decoder: Option<fn(&str) -> V2String>,
+    }
+
+    impl Default for V2EscapeOptions {
+        fn default() -> Self {
+            V2EscapeOptions {
+                delimiters: V2EscapeHandling::Decode,
+                formatting: V2EscapeHandling::Decode,
+                hex: V2EscapeHandling::Decode,
+                character_set: V2EscapeHandling::Preserve,
+                local: V2EscapeHandling::Preserve,
+                hex_encoding: None,
+                local_decoder: None,
+            }
+        }
+    }
+
+    ///
+    /// FT formatting commands from Section 2.7.6 plus the highlighting toggles of 2.7.2.
+    ///
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum V2FormattingCommand {
+        /// `\H\` start highlighting.
+        Highlight,
+        /// `\N\` normal text, ends highlighting.
+        Normal,
+        /// `\.sp<n>\` end the line and skip `n` vertical spaces.
+        Space(Option<u16>),
+        /// `\.br\` line break.
+        Break,
+        /// `\.fi\` word wrap on.
+        Fill,
+        /// `\.nf\` word wrap off.
+        NoFill,
+        /// `\.in<n>\` indent by `n` spaces.
+        Indent(i16),
+        /// `\.ti<n>\` temporarily indent by `n` spaces.
+        TemporaryIndent(i16),
+        /// `\.sk<n>\` skip `n` spaces to the right.
+        Skip(Option<u16>),
+        /// `\.ce\` end the line and center the next one.
+        Center,
+    }
+
+    ///
+    /// One token of escaped component text.
+    ///
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum V2EscapeSequence {
+        /// Plain text. Never contains delimiters or the escape character.
+        Text(V2String),
+        /// A delimiter or the escape character, stored as the character it stands for.
+        Delimiter(char),
+        Formatting(V2FormattingCommand),
+        /// Raw bytes from `\X...\`.
+        Hex(Vec<u8>),
+        /// ISO 2022 escape bytes from `\Cxxyy\` (2 bytes) or `\Mxxyy[zz]\` (2 or 3 bytes).
+        CharacterSet(Vec<u8>),
+        /// Payload of `\Z...\`.
+        Local(V2String),
+        /// Anything between escape characters that is not a valid sequence, or a dangling escape
+        /// character. Kept verbatim.
+        Raw(V2String),
+    }
+
+    impl V2EscapeSequence {
+        pub fn class(&self) -> Option<V2EscapeClass> {
+            match self {
+                V2EscapeSequence::Text(_) | V2EscapeSequence::Raw(_) => None,
+                V2EscapeSequence::Delimiter(_) => Some(V2EscapeClass::Delimiter),
+                V2EscapeSequence::Formatting(_) => Some(V2EscapeClass::Formatting),
+                V2EscapeSequence::Hex(_) => Some(V2EscapeClass::Hex),
+                V2EscapeSequence::CharacterSet(_) => Some(V2EscapeClass::CharacterSet),
+                V2EscapeSequence::Local(_) => Some(V2EscapeClass::Local),
+            }
+        }
+
+        ///
+        /// Parse the body of a sequence, i.e. the text between the two escape characters.
+        ///
+        fn parse(body: &str, sep: &V2ParserCharacters) -> V2Result<V2EscapeSequence> {
+            let mut chars = body.chars();
+            let code = match chars.next() {
+                Some(code) => code,
+                None => return Err(RUMString::from("Empty escape sequence!")),
+            };
+            let args = chars.as_str();
+            let delimiter = match code {
+                'F' => Some(sep.field_separator),
+                'S' => Some(sep.component_separator),
+                'T' => Some(sep.subcomponent_separator),
+                'R' => Some(sep.repetition_separator),
+                'E' => Some(sep.escape_character),
+                'P' => Some(sep.truncation_character),
+                _ => None,
+            };
+            if let (Some(delimiter), "") = (delimiter, args) {
+                return Ok(V2EscapeSequence::Delimiter(delimiter as char));
+            }
+            match (code, args) {
+                ('H', "") => Ok(V2EscapeSequence::Formatting(V2FormattingCommand::Highlight)),
+                ('N', "") => Ok(V2EscapeSequence::Formatting(V2FormattingCommand::Normal)),
+                ('.', _) => Ok(V2EscapeSequence::Formatting(parse_formatting(args)?)),
+                ('X', _) => Ok(V2EscapeSequence::Hex(parse_hex(args)?)),
+                ('C', _) if args.len() == 4 => Ok(V2EscapeSequence::CharacterSet(parse_hex(args)?)),
+                ('M', _) if args.len() == 4 || args.len() == 6 => {
+                    Ok(V2EscapeSequence::CharacterSet(parse_hex(args)?))
+                }
+                ('Z', _) => Ok(V2EscapeSequence::Local(V2String::from(args))),
+                _ => Err(rumtk_format!("Unknown escape sequence {}!", body)),
+            }
+        }
+
+        ///
+        /// Escaped form of this token. Sequences are written in their canonical form (uppercase
+        /// hex, signed indents), which is not necessarily how they were read.
+        ///
+        pub fn encode(&self, sep: &V2ParserCharacters) -> V2String {
+            let escape = sep.escape_character as char;
+            let body = match self {
+                V2EscapeSequence::Text(text) | V2EscapeSequence::Raw(text) => return text.clone(),
+                V2EscapeSequence::Delimiter(c) => match *c as u32 {
+                    c if c == sep.field_separator as u32 => V2String::from("F"),
+                    c if c == sep.component_separator as u32 => V2String::from("S"),
+                    c if c == sep.subcomponent_separator as u32 => V2String::from("T"),
+                    c if c == sep.repetition_separator as u32 => V2String::from("R"),
+                    c if c == sep.truncation_character as u32 => V2String::from("P"),
+                    _ => V2String::from("E"),
+                },
+                V2EscapeSequence::Formatting(command) => encode_formatting(command),
+                V2EscapeSequence::Hex(bytes) => rumtk_format!("X{}", encode_hex(bytes)),
+                V2EscapeSequence::CharacterSet(bytes) => match bytes.len() {
+                    2 => rumtk_format!("C{}", encode_hex(bytes)),
+                    _ => rumtk_format!("M{}", encode_hex(bytes)),
+                },
+                V2EscapeSequence::Local(payload) => rumtk_format!("Z{}", payload),
+            };
+            rumtk_format!("{}{}{}", escape, body, escape)
+        }
+
+        ///
+        /// Plain text of this token. `source` is the escaped form it was read from and is what
+        /// gets written for preserved sequences.
+        ///
+        fn render(&self, source: &str, options: &V2EscapeOptions) -> V2String {
+            let handling = match self.class() {
+                Some(V2EscapeClass::Delimiter) => options.delimiters,
+                Some(V2EscapeClass::Formatting) => options.formatting,
+                Some(V2EscapeClass::Hex) => options.hex,
+                Some(V2EscapeClass::CharacterSet) => options.character_set,
+                Some(V2EscapeClass::Local) => options.local,
+                None => V2EscapeHandling::Preserve,
+            };
+            match handling {
+                V2EscapeHandling::Preserve => V2String::from(source),
+                V2EscapeHandling::Strip => V2String::new(),
+                V2EscapeHandling::Decode => match self {
+                    V2EscapeSequence::Delimiter(c) => c.to_string(),
+                    V2EscapeSequence::Formatting(command) => render_formatting(command),
+                    V2EscapeSequence::Hex(bytes) => {
+                        let decoded = match &options.hex_encoding {
+                            Some(encoding) => try_decode_with(bytes, encoding),
+                            None => try_decode(bytes),
+                        };
+                        decoded.unwrap_or_else(|_| V2String::from(source))
+                    }
+                    V2EscapeSequence::CharacterSet(bytes) => {
+                        let mut shift = V2String::from('\u{1b}');
+                        shift.extend(bytes.iter().map(|b| *b as char));
+                        shift
+                    }
+                    V2EscapeSequence::Local(payload) => match options.local_decoder {
+                        Some(decoder) => decoder(payload),
+                        None => V2String::from(source),
+                    },
+                    _ => V2String::from(source),
+                },
+            }
+        }
+    }
+
+    ///
+    /// Component text split into plain text and escape sequences. The escaped form of every
+    /// token is kept next to it so text read from a message is written back byte for byte.
+    ///
+    #[derive(Debug, Clone, PartialEq, Eq, Default)]
+    pub struct V2EscapedText {
+        tokens: Vec<V2EscapeSequence>,
+        sources: Vec<V2String>,
+    }
+
+    impl V2EscapedText {
+        pub fn new() -> Self {
+            V2EscapedText {
+                tokens: Vec::new(),
+                sources: Vec::new(),
+            }
+        }
+
+        ///
+        /// Tokenize raw (escaped) component text. Never fails: anything that is not a valid
+        /// sequence is kept as [V2EscapeSequence::Raw] so the text still round trips.
+        ///
+        pub fn parse(raw: &str, sep: &V2ParserCharacters) -> Self {
+            let escape = sep.escape_character as char;
+            let mut text = Self::new();
+            let mut rest = raw;
+            while let Some(start) = rest.find(escape) {
+                text.push_source(V2EscapeSequence::Text(V2String::from(&rest[..start])), &rest[..start]);
+                let after = &rest[start + 1..];
+                match after.find(escape) {
+                    Some(end) => {
+                        let source = &rest[start..start + end + 2];
+                        let token = match V2EscapeSequence::parse(&after[..end], sep) {
+                            Ok(token) => token,
+                            Err(_) => V2EscapeSequence::Raw(V2String::from(source)),
+                        };
+                        text.push_source(token, source);
+                        rest = &after[end + 1..];
+                    }
+                    None => {
+                        text.push_source(V2EscapeSequence::Raw(V2String::from(&rest[start..])), &rest[start..]);
+                        rest = "";
+                    }
+                }
+            }
+            text.push_source(V2EscapeSequence::Text(V2String::from(rest)), rest);
+            text
+        }
+
+        ///
+        /// Build escaped text from plain text. Delimiters and escape characters in `text` become
+        /// delimiter escapes.
+        ///
+        pub fn from_text(text: &str, sep: &V2ParserCharacters) -> Self {
+            let mut escaped = Self::new();
+            escaped.push_text(text, sep);
+            escaped
+        }
+
+        pub fn push_text(&mut self, text: &str, sep: &V2ParserCharacters) {
+            let delimiters = [
+                sep.field_separator,
+                sep.component_separator,
+                sep.subcomponent_separator,
+                sep.repetition_separator,
+                sep.escape_character,
+            ];
+            let mut plain = V2String::new();
+            for c in text.chars() {
+                match delimiters.iter().any(|d| *d as char == c) {
+                    true => {
+                        self.push_token(V2EscapeSequence::Text(plain), sep);
+                        self.push_token(V2EscapeSequence::Delimiter(c), sep);
+                        plain = V2String::new();
+                    }
+                    false => plain.push(c),
+                }
+            }
+            self.push_token(V2EscapeSequence::Text(plain), sep);
+        }
+
+        ///
+        /// Append a token, escaped with `sep`. Plain text must not contain delimiters; use
+        /// [V2EscapedText::push_text] for that.
+        ///
+        pub fn push_token(&mut self, token: V2EscapeSequence, sep: &V2ParserCharacters) {
+            let source = token.encode(sep);
+            self.push_source(token, &source);
+        }
+
+        ///
+        /// Empty text is skipped and adjacent text is merged.
+        ///
+        fn push_source(&mut self, token: V2EscapeSequence, source: &str) {
+            match (self.tokens.last_mut(), token) {
+                (_, V2EscapeSequence::Text(text)) if text.is_empty() => {}
+                (Some(V2EscapeSequence::Text(last)), V2EscapeSequence::Text(text)) => {
+                    last.push_str(&text);
+                    self.sources.last_mut().unwrap().push_str(source);
+                }
+                (_, token) => {
+                    self.tokens.push(token);
+                    self.sources.push(V2String::from(source));
+                }
+            }
+        }
+
+        pub fn tokens(&self) -> &[V2EscapeSequence] {
+            &self.tokens
+        }
+
+        ///
+        /// Escaped text, ready to be stored in a component.
+        ///
+        pub fn encode(&self) -> V2String {
+            self.sources.concat()
+        }
+
+        ///
+        /// Plain text with each sequence handled as `options` says.
+        ///
+        pub fn render(&self, options: &V2EscapeOptions) -> V2String {
+            self.tokens
+                .iter()
+                .zip(self.sources.iter())
+                .map(|(token, source)| token.render(source, options))
+                .collect()
+        }
+    }
+
+    /**************************** Helpers ***************************************/
+
+    ///
+    /// Plain text of raw component text using the default [V2EscapeOptions].
+    ///
+    pub fn v2_unescape(raw: &str, sep: &V2ParserCharacters) -> V2String {
+        V2EscapedText::parse(raw, sep).render(&V2EscapeOptions::default())
+    }
+
+    ///
+    /// Escaped form of plain text. See [V2EscapedText::from_text].
+    ///
+    pub fn v2_escape(text: &str, sep: &V2ParserCharacters) -> V2String {
+        sep.escape(text)
+    }
+
+    fn parse_hex(digits: &str) -> V2Result<Vec<u8>> {
+        if digits.is_empty() || digits.len() % 2 != 0 {
+            return Err(rumtk_format!("Hex data {} must have an even number of digits!", digits));
+        }
+        (0..digits.len())
+            .step_by(2)
+            .map(|i| match digits.get(i..i + 2).map(|byte| u8::from_str_radix(byte, 16)) {
+                Some(Ok(byte)) => Ok(byte),
+                _ => Err(rumtk_format!("Invalid hex data {}!", digits)),
+            })
+            .collect()
+    }
+
+    fn encode_hex(bytes: &[u8]) -> V2String {
+        bytes.iter().map(|byte| rumtk_format!("{:02X}", byte)).collect()
+    }
+
+    fn parse_formatting(args: &str) -> V2Result<V2FormattingCommand> {
+        let (command, number) = match args.get(..2) {
+            Some(command) => (command, &args[2..]),
+            None => return Err(rumtk_format!("Unknown formatting command .{}!", args)),
+        };
+        let count = || -> V2Result<Option<u16>> {
+            match number {
+                "" => Ok(None),
+                _ => match number.parse::<u16>() {
+                    Ok(count) => Ok(Some(count)),
+                    Err(_) => Err(rumtk_format!("Invalid count {} in formatting command!", number)),
+                },
+            }
+        };
+        let indent = || -> V2Result<i16> {
+            match number.strip_prefix('+').unwrap_or(number).parse::<i16>() {
+                Ok(indent) => Ok(indent),
+                Err(_) => Err(rumtk_format!("Invalid indent {} in formatting command!", number)),
+            }
+        };
+        match (command, number) {
+            ("br", "") => Ok(V2FormattingCommand::Break),
+            ("fi", "") => Ok(V2FormattingCommand::Fill),
+            ("nf", "") => Ok(V2FormattingCommand::NoFill),
+            ("ce", "") => Ok(V2FormattingCommand::Center),
+            ("sp", _) => Ok(V2FormattingCommand::Space(count()?)),
+            ("sk", _) => Ok(V2FormattingCommand::Skip(count()?)),
+            ("in", _) => Ok(V2FormattingCommand::Indent(indent()?)),
+            ("ti", _) => Ok(V2FormattingCommand::TemporaryIndent(indent()?)),
+            _ => Err(rumtk_format!("Unknown formatting command .{}!", args)),
+        }
+    }
+
+    fn encode_formatting(command: &V2FormattingCommand) -> V2String {
+        let count = |count: &Option<u16>| match count {
+            Some(count) => count.to_string(),
+            None => V2String::new(),
+        };
+        match command {
+            V2FormattingCommand::Highlight => V2String::from("H"),
+            V2FormattingCommand::Normal => V2String::from("N"),
+            V2FormattingCommand::Space(n) => rumtk_format!(".sp{}", count(n)),
+            V2FormattingCommand::Break => V2String::from(".br"),
+            V2FormattingCommand::Fill => V2String::from(".fi"),
+            V2FormattingCommand::NoFill => V2String::from(".nf"),
+            V2FormattingCommand::Indent(n) => rumtk_format!(".in{:+}", n),
+            V2FormattingCommand::TemporaryIndent(n) => rumtk_format!(".ti{:+}", n),
+            V2FormattingCommand::Skip(n) => rumtk_format!(".sk{}", count(n)),
+            V2FormattingCommand::Center => V2String::from(".ce"),
+        }
+    }
+
+    ///
+    /// Plain text approximation of a formatting command. Line oriented commands become line
+    /// breaks and skips become spaces. Highlighting, wrapping and indentation have no plain text
+    /// equivalent and are dropped.
+    ///
+    fn render_formatting(command: &V2FormattingCommand) -> V2String {
+        match command {
+            V2FormattingCommand::Break | V2FormattingCommand::Center => V2String::from("\n"),
+            V2FormattingCommand::Space(n) => "\n".repeat(n.unwrap_or(1).max(1) as usize),
+            V2FormattingCommand::Skip(n) => " ".repeat(n.unwrap_or(1) as usize),
+            _ => V2String::new(),
+        }
+    }
+}
@@ -880,11 +880,327 @@ pub mod v2_field_descriptor {
         ///
         ///
         CSU,
+        ///
+        /// # CWE - coded with exceptions
+        ///
+        /// Definition: Specifies a coded element and its associated detail. The CWE data type is used when
+        /// more than one table may be applicable or when the specified HL7 or externally defined table may
+        /// be extended with local values or when text is in place, the code may be omitted.
+        ///
+        /// The components follow the same layout as [V2ComplexType::CNE]. The difference is that the
+        /// identifier is not required and local codes are allowed.
+        /// ```text
+        ///     Example:
+        ///         |2054-5^Black or African American^HL70005|
+        ///         |67471-3^Pregnancy status^LN^1903^Pregnancy status^99USL^2.44^^Pregnancy status|
+        /// ```
+        ///
         CWE,
+        ///
+        /// # CX - extended composite ID with check digit
+        ///
+        /// Definition: Specifies an identifier (such as a medical record number) along with the assigning
+        /// authority and facility that issued it.
+        /// ```text
+        ///     Example:
+        ///         |PATID1234^5^M11^ADT1^MR^GOOD HEALTH HOSPITAL|
+        /// ```
+        /// ## CX.1 ID Number (ST)
+        /// ## CX.2 Identifier Check Digit (ST)
+        /// ## CX.3 Check Digit Scheme (ID)
+        /// ## CX.4 Assigning Authority (HD)
+        /// ## CX.5 Identifier Type Code (ID)
+        /// ## CX.6 Assigning Facility (HD)
+        /// ## CX.7 Effective Date (DT)
+        /// ## CX.8 Expiration Date (DT)
+        /// ## CX.9 Assigning Jurisdiction (CWE)
+        /// ## CX.10 Assigning Agency or Department (CWE)
+        /// ## CX.11 Security Check (ST)
+        /// ## CX.12 Security Check Scheme (ID)
+        ///
+        CX,
+        ///
+        /// # DR - date/time range
+        ///
+        /// Definition: Specifies an interval between two points in time.
+        /// ## DR.1 Range Start Date/Time (DTM)
+        /// ## DR.2 Range End Date/Time (DTM)
+        ///
+        DR,
+        ///
+        /// # EI - entity identifier
+        ///
+        /// Definition: The entity identifier defines a given entity within a specified series of
+        /// identifiers.
+        /// ## EI.1 Entity Identifier (ST)
+        /// ## EI.2 Namespace ID (IS)
+        /// ## EI.3 Universal ID (ST)
+        /// Required if EI.4 is valued.
+        /// ## EI.4 Universal ID Type (ID)
+        /// Required if EI.3 is valued.
+        ///
+        EI,
+        ///
+        /// # FN - family name
+        ///
+        /// Definition: This data type allows full specification of the surname of a person. Used as the
+        /// first component of [V2ComplexType::XPN] and the second component of [V2ComplexType::XCN],
+        /// which means its parts arrive as subcomponents.
+        /// ## FN.1 Surname (ST)
+        /// ## FN.2 Own Surname Prefix (ST)
+        /// ## FN.3 Own Surname (ST)
+        /// ## FN.4 Surname Prefix from Partner/Spouse (ST)
+        /// ## FN.5 Surname from Partner/Spouse (ST)
+        ///
+        FN,
+        ///
+        /// # HD - hierarchic designator
+        ///
+        /// Definition: The basic definition of the HD is that it identifies an (HL7) application. It is
+        /// also used to identify assigning authorities and facilities.
+        /// ```text
+        ///     Example:
+        ///         |NPI&2.16.840.1.113883.4.6&ISO|
+        /// ```
+        /// ## HD.1 Namespace ID (IS)
+        /// Must be valued if HD.2 and HD.3 are not valued.
+        /// ## HD.2 Universal ID (ST)
+        /// Must be valued if HD.1 is not valued or if HD.3 is valued.
+        /// ## HD.3 Universal ID Type (ID)
+        /// Must be valued if HD.2 is valued.
+        ///
+        HD,
+        ///
+        /// # MO - money
+        ///
+        /// ## MO.1 Quantity (NM)
+        /// ## MO.2 Denomination (ID)
+        ///
         MO,
+        ///
+        /// # NR - numeric range
+        ///
+        /// ## NR.1 Low Value (NM)
+        /// ## NR.2 High Value (NM)
+        ///
         NR,
+        ///
+        /// # SAD - street address
+        ///
+        /// Definition: Specifies an entity's street address and associated detail. Used as the first
+        /// component of [V2ComplexType::XAD], which means its parts arrive as subcomponents.
+        /// ## SAD.1 Street or Mailing Address (ST)
+        /// ## SAD.2 Street Name (ST)
+        /// ## SAD.3 Dwelling Number (ST)
+        ///
+        SAD,
+        ///
+        /// # WVI - channel identifier
+        ///
+        /// ## WVI.1 Channel Number (NM)
+        /// ## WVI.2 Channel Name (ST)
+        ///
         WVI,
+        ///
+        /// # WVS - waveform source
+        ///
+        /// ## WVS.1 Source One Name (ST)
+        /// ## WVS.2 Source Two Name (ST)
+        ///
         WVS,
+        ///
+        /// # XAD - extended address
+        ///
+        /// ```text
+        ///     Example:
+        ///         |1000 Hospital Lane^Ste. 123^Ann Arbor ^MI^99999^USA^M^^&W^^^20000110&20000120|
+        /// ```
+        ///
+        XAD,
+        ///
+        /// # XCN - extended composite ID number and name for persons
+        ///
+        /// ```text
+        ///     Example:
+        ///         |134569827^Feller^Hans^^^^^^NPI&2.16.840.1.113883.4.6&ISO^L^^^NPI|
+        /// ```
+        /// ## XCN.8 Source Table (CWE)
+        /// If XCN.1 is valued and XCN.9 is not, this component must be valued.
+        /// ## XCN.9 Assigning Authority (HD)
+        /// If XCN.1 is valued and XCN.8 is not, this component must be valued.
+        /// ## XCN.12 Check Digit Scheme (ID)
+        /// Required if XCN.11 is valued.
+        ///
+        XCN,
+        ///
+        /// # XPN - extended person name
+        ///
+        /// ```text
+        ///     Example:
+        ///         |EVERYMAN^ADAM^A^III|
+        ///         |Smith&St^John^J^III^DR^PHD^L|
+        /// ```
+        ///
+        XPN,
+        ///
+        /// # XTN - extended telecommunication number
+        ///
+        /// ```text
+        ///     Example:
+        ///         |(920)386-5555^PRN^PH^^^920^3865555|
+        ///         |^NET^Internet^smith@hospital.org|
+        /// ```
+        /// ## XTN.4 Communication Address (ST)
+        /// Required if XTN.3 is Internet or X.400.
+        /// ## XTN.7 Local Number (SNM)
+        /// Required if XTN.3 is a phone type and XTN.12 is not valued.
+        ///
+        XTN,
+        ///
+        /// # DLD - discharge to location and date
+        ///
+        /// ## DLD.1 Discharge to Location (IS)
+        /// ## DLD.2 Effective Date (DTM)
+        ///
+        DLD,
+        ///
+        /// # DLN - driver's license number
+        ///
+        /// ## DLN.1 License Number (ST)
+        /// ## DLN.2 Issuing State, Province, Country (IS)
+        /// ## DLN.3 Expiration Date (DT)
+        ///
+        DLN,
+        ///
+        /// # EIP - entity identifier pair
+        ///
+        /// ## EIP.1 Placer Assigned Identifier (EI)
+        /// ## EIP.2 Filler Assigned Identifier (EI)
+        ///
+        EIP,
+        ///
+        /// # FC - financial class
+        ///
+        /// ## FC.1 Financial Class Code (IS)
+        /// ## FC.2 Effective Date (DTM)
+        ///
+        FC,
+        ///
+        /// # JCC - job code/class
+        ///
+        /// ## JCC.1 Job Code (IS)
+        /// ## JCC.2 Job Class (IS)
+        /// ## JCC.3 Job Description Text (TX)
+        ///
+        JCC,
+        ///
+        /// # MOC - money and code
+        ///
+        /// ## MOC.1 Monetary Amount (MO)
+        /// ## MOC.2 Charge Code (CE)
+        ///
+        MOC,
+        ///
+        /// # MSG - message type
+        ///
+        /// ```text
+        ///     Example:
+        ///         |VXU^V04^VXU_V04|
+        /// ```
+        /// ## MSG.1 Message Code (ID)
+        /// ## MSG.2 Trigger Event (ID)
+        /// ## MSG.3 Message Structure (ID)
+        ///
+        MSG,
+        ///
+        /// # NDL - name with date and location
+        ///
+        /// ## NDL.1 Name (CNN)
+        /// ## NDL.2 Start Date/time (DTM)
+        /// ## NDL.3 End Date/time (DTM)
+        /// ## NDL.4 - NDL.11 Point of Care, Room, Bed, Facility, Location Status, Patient Location Type,
+        /// Building, Floor
+        ///
+        NDL,
+        ///
+        /// # PL - person location
+        ///
+        /// ## PL.1 Point of Care (IS)
+        /// ## PL.2 Room (IS)
+        /// ## PL.3 Bed (IS)
+        /// ## PL.4 Facility (HD)
+        /// ## PL.5 Location Status (IS)
+        /// ## PL.6 Person Location Type (IS)
+        /// ## PL.7 Building (IS)
+        /// ## PL.8 Floor (IS)
+        /// ## PL.9 Location Description (ST)
+        /// ## PL.10 Comprehensive Location Identifier (EI)
+        /// ## PL.11 Assigning Authority for Location (HD)
+        ///
+        PL,
+        ///
+        /// # PRL - parent result link
+        ///
+        /// ## PRL.1 Parent Observation Identifier (CE)
+        /// ## PRL.2 Parent Observation Sub-identifier (ST)
+        /// ## PRL.3 Parent Observation Value Descriptor (TX)
+        ///
+        PRL,
+        ///
+        /// # PT - processing type
+        ///
+        /// ## PT.1 Processing ID (ID)
+        /// ## PT.2 Processing Mode (ID)
+        ///
+        PT,
+        ///
+        /// # VID - version identifier
+        ///
+        /// ## VID.1 Version ID (ID)
+        /// ## VID.2 Internationalization Code (CE)
+        /// ## VID.3 International Version ID (CE)
+        ///
+        VID,
+        ///
+        /// # XON - extended composite name and identification number for organizations
+        ///
+        /// ```text
+        ///     Example:
+        ///         |Good Health Hospital^L^^^^GHH&2.16.840.1.113883.19.4.6&ISO^XX^^^1234|
+        /// ```
+        ///
+        XON,
+        ///
+        /// # ELD - error location and description
+        ///
+        /// Retained for backwards compatibility. Superseded by [V2ComplexType::ERL] and ERR-3 as of
+        /// v2.5.
+        /// ## ELD.1 Segment ID (ST)
+        /// ## ELD.2 Segment Sequence (NM)
+        /// ## ELD.3 Field Position (NM)
+        /// ## ELD.4 Code Identifying Error (CE)
+        ///
+        ELD,
+        ///
+        /// # ERL - error location
+        ///
+        /// Definition: This data type identifies the segment and its constituent where an error has
+        /// occurred.
+        /// ```text
+        ///     Example:
+        ///         |PID^1^5^1^1^1|
+        /// ```
+        ///
+        ERL,
+        ///
+        /// # TS - time stamp
+        ///
+        /// Withdrawn as of v2.7 in favor of the DTM primitive. Only defined for the versions that
+        /// still carry it. See [get_field_descriptor].
+        /// ## TS.1 Time (DTM)
+        /// ## TS.2 Degree of Precision (ID)
+        ///
+        TS,
     }
 
     #[derive(Debug)]
@@ -1006,7 +1322,14 @@ pub mod v2_field_descriptor {
             v2_component_descriptor!("channel_sampling_frequency", "Channel Sampling Frequency", V2ComponentType::Primitive(V2PrimitiveType::NM), 6, 5, 0, Optionality::O, true),
             v2_component_descriptor!("min_max_values", "Minimum and Maximum Data Values", V2ComponentType::Complex(V2ComplexType::NR), 0, 6, 0, Optionality::O, false)
         ],
-        "CE" => &[  ],
+        "CE" => &[
+            v2_component_descriptor!("id", "Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("text", "Text", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 2, 0, Optionality::O, true),
+            v2_component_descriptor!("coding_system", "Name of Coding System", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 3, 396, Optionality::O, false),
+            v2_component_descriptor!("alt_id", "Alternate Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 4, 0, Optionality::O, false),
+            v2_component_descriptor!("alt_text", "Alternate Text", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 5, 0, Optionality::O, true),
+            v2_component_descriptor!("alt_coding_system", "Name of Alternate Coding System", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 6, 396, Optionality::O, false)
+        ],
         "CF" => &[
             v2_component_descriptor!("id", "Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 1, 0, Optionality::O, false),
             v2_component_descriptor!("formatted_text", "Formatted Text", V2ComponentType::Primitive(V2PrimitiveType::FT), 0, 2, 0, Optionality::O, false),
@@ -1083,13 +1406,433 @@ pub mod v2_field_descriptor {
         "CSU" => &[
             v2_component_descriptor!("quantity", "Quantity", V2ComponentType::Primitive(V2PrimitiveType::NM), 0, 1, 0, Optionality::O, false),
             v2_component_descriptor!("units", "Units", V2ComponentType::Complex(V2ComplexType::CWE), 0, 2, 794, Optionality::O, false)
+        ],
+        "CWE" => &[
+            v2_component_descriptor!("id", "Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("text", "Text", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 2, 0, Optionality::O, true),
+            v2_component_descriptor!("coding_system", "Name of Coding System", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 3, 396, Optionality::C(CONDITION_CWE1), false),
+            v2_component_descriptor!("alt_id", "Alternate Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 4, 0, Optionality::O, false),
+            v2_component_descriptor!("alt_text", "Alternate Text", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 5, 0, Optionality::O, true),
+            v2_component_descriptor!("alt_coding_system", "Name of Alternate Coding System", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 6, 396, Optionality::C(CONDITION_CWE2), false),
+            v2_component_descriptor!("version_id", "Coding System Version ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 10, 7, 0, Optionality::C(CONDITION_CWE3), false),
+            v2_component_descriptor!("alt_version_id", "Alternate Coding System Version ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 10, 8, 0, Optionality::O, false),
+            v2_component_descriptor!("original_text", "Original Text", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 9, 0, Optionality::O, true),
+            v2_component_descriptor!("second_alt_id", "Second Alternate Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 10, 0, Optionality::O, false),
+            v2_component_descriptor!("second_alt_text", "Second Alternate Text", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 11, 0, Optionality::O, true),
+            v2_component_descriptor!("second_alt_coding_system", "Name of Second Alternate Coding System", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 12, 396, Optionality::O, false),
+            v2_component_descriptor!("second_alt_version_id", "Second Alternate Coding System Version ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 10, 13, 0, Optionality::C(CONDITION_CWE3), false),
+            v2_component_descriptor!("coding_system_oid", "Coding System OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 14, 0, Optionality::C(CONDITION_CWE4), false),
+            v2_component_descriptor!("valueset_oid", "Value Set OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 15, 0, Optionality::O, false),
+            v2_component_descriptor!("valueset_version_id", "Value Set Version ID", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 8, 16, 0, Optionality::C(CONDITION_CWE5), false),
+            v2_component_descriptor!("alt_coding_system_oid", "Alternate Coding System OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 17, 0, Optionality::C(CONDITION_CWE6), false),
+            v2_component_descriptor!("alt_valueset_oid", "Alternate Value Set OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 18, 0, Optionality::O, false),
+            v2_component_descriptor!("alt_valueset_version_id", "Alternate Value Set Version ID", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 8, 19, 0, Optionality::C(CONDITION_CWE7), false),
+            v2_component_descriptor!("second_alt_coding_system_oid", "Second Alternate Coding System OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 20, 0, Optionality::C(CONDITION_CWE8), false),
+            v2_component_descriptor!("second_alt_valueset_oid", "Second Alternate Value Set OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 21, 0, Optionality::O, false),
+            v2_component_descriptor!("second_alt_valueset_version_id", "Second Alternate Value Set Version ID", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 8, 22, 0, Optionality::C(CONDITION_CWE9), false)
+        ],
+        "CX" => &[
+            v2_component_descriptor!("id", "ID Number", V2ComponentType::Primitive(V2PrimitiveType::ST), 15, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("check_digit", "Identifier Check Digit", V2ComponentType::Primitive(V2PrimitiveType::ST), 4, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("check_digit_scheme", "Check Digit Scheme", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 3, 61, Optionality::O, false),
+            v2_component_descriptor!("assigning_authority", "Assigning Authority", V2ComponentType::Complex(V2ComplexType::HD), 0, 4, 363, Optionality::O, false),
+            v2_component_descriptor!("id_type", "Identifier Type Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 5, 203, Optionality::R, false),
+            v2_component_descriptor!("assigning_facility", "Assigning Facility", V2ComponentType::Complex(V2ComplexType::HD), 0, 6, 0, Optionality::O, false),
+            v2_component_descriptor!("effective_date", "Effective Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 0, 7, 0, Optionality::O, false),
+            v2_component_descriptor!("expiration_date", "Expiration Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 0, 8, 0, Optionality::O, false),
+            v2_component_descriptor!("assigning_jurisdiction", "Assigning Jurisdiction", V2ComponentType::Complex(V2ComplexType::CWE), 0, 9, 0, Optionality::O, false),
+            v2_component_descriptor!("assigning_agency", "Assigning Agency or Department", V2ComponentType::Complex(V2ComplexType::CWE), 0, 10, 0, Optionality::O, false),
+            v2_component_descriptor!("security_check", "Security Check", V2ComponentType::Primitive(V2PrimitiveType::ST), 4, 11, 0, Optionality::O, false),
+            v2_component_descriptor!("security_check_scheme", "Security Check Scheme", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 12, 904, Optionality::O, false)
+        ],
+        "DR" => &[
+            v2_component_descriptor!("range_start", "Range Start Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("range_end", "Range End Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 2, 0, Optionality::O, false)
+        ],
+        "EI" => &[
+            v2_component_descriptor!("entity_id", "Entity Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("namespace_id", "Namespace ID", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 2, 363, Optionality::O, false),
+            v2_component_descriptor!("universal_id", "Universal ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 3, 0, Optionality::C(CONDITION_EI1), false),
+            v2_component_descriptor!("universal_id_type", "Universal ID Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 4, 301, Optionality::C(CONDITION_EI2), false)
+        ],
+        "FN" => &[
+            v2_component_descriptor!("surname", "Surname", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 1, 0, Optionality::R, true),
+            v2_component_descriptor!("own_surname_prefix", "Own Surname Prefix", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 2, 0, Optionality::O, true),
+            v2_component_descriptor!("own_surname", "Own Surname", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 3, 0, Optionality::O, true),
+            v2_component_descriptor!("partner_surname_prefix", "Surname Prefix from Partner/Spouse", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 4, 0, Optionality::O, true),
+            v2_component_descriptor!("partner_surname", "Surname from Partner/Spouse", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 5, 0, Optionality::O, true)
+        ],
+        "HD" => &[
+            v2_component_descriptor!("namespace_id", "Namespace ID", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 1, 300, Optionality::C(CONDITION_HD1), false),
+            v2_component_descriptor!("universal_id", "Universal ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 2, 0, Optionality::C(CONDITION_HD2), false),
+            v2_component_descriptor!("universal_id_type", "Universal ID Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 3, 301, Optionality::C(CONDITION_HD3), false)
+        ],
+        "MO" => &[
+            v2_component_descriptor!("quantity", "Quantity", V2ComponentType::Primitive(V2PrimitiveType::NM), 16, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("denomination", "Denomination", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 2, 913, Optionality::O, false)
+        ],
+        "NR" => &[
+            v2_component_descriptor!("low_value", "Low Value", V2ComponentType::Primitive(V2PrimitiveType::NM), 0, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("high_value", "High Value", V2ComponentType::Primitive(V2PrimitiveType::NM), 0, 2, 0, Optionality::O, false)
+        ],
+        "SAD" => &[
+            v2_component_descriptor!("street_address", "Street or Mailing Address", V2ComponentType::Primitive(V2PrimitiveType::ST), 120, 1, 0, Optionality::O, true),
+            v2_component_descriptor!("street_name", "Street Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 2, 0, Optionality::O, true),
+            v2_component_descriptor!("dwelling_number", "Dwelling Number", V2ComponentType::Primitive(V2PrimitiveType::ST), 12, 3, 0, Optionality::O, true)
+        ],
+        "WVI" => &[
+            v2_component_descriptor!("channel_number", "Channel Number", V2ComponentType::Primitive(V2PrimitiveType::NM), 4, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("channel_name", "Channel Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 17, 2, 0, Optionality::O, true)
+        ],
+        "WVS" => &[
+            v2_component_descriptor!("source_one_name", "Source One Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 8, 1, 0, Optionality::R, true),
+            v2_component_descriptor!("source_two_name", "Source Two Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 8, 2, 0, Optionality::O, true)
+        ],
+        "DLD" => &[
+            v2_component_descriptor!("discharge_location", "Discharge to Location", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 1, 113, Optionality::R, false),
+            v2_component_descriptor!("effective_date", "Effective Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 2, 0, Optionality::O, false)
+        ],
+        "DLN" => &[
+            v2_component_descriptor!("license_number", "License Number", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("issuing_state", "Issuing State, Province, Country", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 2, 333, Optionality::O, false),
+            v2_component_descriptor!("expiration_date", "Expiration Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 0, 3, 0, Optionality::O, false)
+        ],
+        "EIP" => &[
+            v2_component_descriptor!("placer_assigned_id", "Placer Assigned Identifier", V2ComponentType::Complex(V2ComplexType::EI), 0, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("filler_assigned_id", "Filler Assigned Identifier", V2ComponentType::Complex(V2ComplexType::EI), 0, 2, 0, Optionality::O, false)
+        ],
+        "FC" => &[
+            v2_component_descriptor!("financial_class_code", "Financial Class Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 1, 64, Optionality::R, false),
+            v2_component_descriptor!("effective_date", "Effective Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 2, 0, Optionality::O, false)
+        ],
+        "JCC" => &[
+            v2_component_descriptor!("job_code", "Job Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 1, 327, Optionality::O, false),
+            v2_component_descriptor!("job_class", "Job Class", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 2, 328, Optionality::O, false),
+            v2_component_descriptor!("job_description", "Job Description Text", V2ComponentType::Primitive(V2PrimitiveType::Text), 0, 3, 0, Optionality::O, false)
+        ],
+        "MOC" => &[
+            v2_component_descriptor!("monetary_amount", "Monetary Amount", V2ComponentType::Complex(V2ComplexType::MO), 0, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("charge_code", "Charge Code", V2ComponentType::Complex(V2ComplexType::CE), 0, 2, 0, Optionality::O, false)
+        ],
+        "MSG" => &[
+            v2_component_descriptor!("message_code", "Message Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 1, 76, Optionality::R, false),
+            v2_component_descriptor!("trigger_event", "Trigger Event", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 2, 3, Optionality::R, false),
+            v2_component_descriptor!("message_structure", "Message Structure", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 3, 354, Optionality::O, false)
+        ],
+        "NDL" => &[
+            v2_component_descriptor!("name", "Name", V2ComponentType::Complex(V2ComplexType::CNN), 0, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("start_datetime", "Start Date/time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("end_datetime", "End Date/time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 3, 0, Optionality::O, false),
+            v2_component_descriptor!("point_of_care", "Point of Care", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 4, 302, Optionality::O, false),
+            v2_component_descriptor!("room", "Room", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 5, 303, Optionality::O, false),
+            v2_component_descriptor!("bed", "Bed", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 6, 304, Optionality::O, false),
+            v2_component_descriptor!("facility", "Facility", V2ComponentType::Complex(V2ComplexType::HD), 0, 7, 0, Optionality::O, false),
+            v2_component_descriptor!("location_status", "Location Status", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 8, 306, Optionality::O, false),
+            v2_component_descriptor!("patient_location_type", "Patient Location Type", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 9, 305, Optionality::O, false),
+            v2_component_descriptor!("building", "Building", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 10, 307, Optionality::O, false),
+            v2_component_descriptor!("floor", "Floor", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 11, 308, Optionality::O, false)
+        ],
+        "PL" => &[
+            v2_component_descriptor!("point_of_care", "Point of Care", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 1, 302, Optionality::O, false),
+            v2_component_descriptor!("room", "Room", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 2, 303, Optionality::O, false),
+            v2_component_descriptor!("bed", "Bed", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 3, 304, Optionality::O, false),
+            v2_component_descriptor!("facility", "Facility", V2ComponentType::Complex(V2ComplexType::HD), 0, 4, 0, Optionality::O, false),
+            v2_component_descriptor!("location_status", "Location Status", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 5, 306, Optionality::O, false),
+            v2_component_descriptor!("person_location_type", "Person Location Type", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 6, 305, Optionality::O, false),
+            v2_component_descriptor!("building", "Building", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 7, 307, Optionality::O, false),
+            v2_component_descriptor!("floor", "Floor", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 8, 308, Optionality::O, false),
+            v2_component_descriptor!("location_description", "Location Description", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 9, 0, Optionality::O, true),
+            v2_component_descriptor!("comprehensive_location_id", "Comprehensive Location Identifier", V2ComponentType::Complex(V2ComplexType::EI), 0, 10, 0, Optionality::O, false),
+            v2_component_descriptor!("location_assigning_authority", "Assigning Authority for Location", V2ComponentType::Complex(V2ComplexType::HD), 0, 11, 0, Optionality::O, false)
+        ],
+        "PRL" => &[
+            v2_component_descriptor!("parent_observation_id", "Parent Observation Identifier", V2ComponentType::Complex(V2ComplexType::CE), 0, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("parent_observation_sub_id", "Parent Observation Sub-identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("parent_observation_value", "Parent Observation Value Descriptor", V2ComponentType::Primitive(V2PrimitiveType::Text), 0, 3, 0, Optionality::O, false)
+        ],
+        "PT" => &[
+            v2_component_descriptor!("processing_id", "Processing ID", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 1, 103, Optionality::O, false),
+            v2_component_descriptor!("processing_mode", "Processing Mode", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 2, 207, Optionality::O, false)
+        ],
+        "VID" => &[
+            v2_component_descriptor!("version_id", "Version ID", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 1, 104, Optionality::O, false),
+            v2_component_descriptor!("internationalization_code", "Internationalization Code", V2ComponentType::Complex(V2ComplexType::CE), 0, 2, 399, Optionality::O, false),
+            v2_component_descriptor!("international_version_id", "International Version ID", V2ComponentType::Complex(V2ComplexType::CE), 0, 3, 0, Optionality::O, false)
+        ],
+        "XON" => &[
+            v2_component_descriptor!("organization_name", "Organization Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 1, 0, Optionality::O, true),
+            v2_component_descriptor!("organization_name_type", "Organization Name Type Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 2, 204, Optionality::O, false),
+            v2_component_descriptor!("id_number", "ID Number", V2ComponentType::Primitive(V2PrimitiveType::NM), 0, 3, 0, Optionality::B, false),
+            v2_component_descriptor!("check_digit", "Check Digit", V2ComponentType::Primitive(V2PrimitiveType::NM), 0, 4, 0, Optionality::O, false),
+            v2_component_descriptor!("check_digit_scheme", "Check Digit Scheme", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 5, 61, Optionality::O, false),
+            v2_component_descriptor!("assigning_authority", "Assigning Authority", V2ComponentType::Complex(V2ComplexType::HD), 0, 6, 363, Optionality::O, false),
+            v2_component_descriptor!("id_type", "Identifier Type Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 7, 203, Optionality::O, false),
+            v2_component_descriptor!("assigning_facility", "Assigning Facility", V2ComponentType::Complex(V2ComplexType::HD), 0, 8, 0, Optionality::O, false),
+            v2_component_descriptor!("name_representation_code", "Name Representation Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 9, 465, Optionality::O, false),
+            v2_component_descriptor!("organization_id", "Organization Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 10, 0, Optionality::O, false)
+        ],
+        "ELD" => &[
+            v2_component_descriptor!("segment_id", "Segment ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 3, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("segment_sequence", "Segment Sequence", V2ComponentType::Primitive(V2PrimitiveType::NM), 2, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("field_position", "Field Position", V2ComponentType::Primitive(V2PrimitiveType::NM), 2, 3, 0, Optionality::O, false),
+            v2_component_descriptor!("error_code", "Code Identifying Error", V2ComponentType::Complex(V2ComplexType::CE), 0, 4, 357, Optionality::O, false)
+        ],
+        "ERL" => &[
+            v2_component_descriptor!("segment_id", "Segment ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 3, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("segment_sequence", "Segment Sequence", V2ComponentType::Primitive(V2PrimitiveType::NM), 2, 2, 0, Optionality::R, false),
+            v2_component_descriptor!("field_position", "Field Position", V2ComponentType::Primitive(V2PrimitiveType::NM), 2, 3, 0, Optionality::O, false),
+            v2_component_descriptor!("field_repetition", "Field Repetition", V2ComponentType::Primitive(V2PrimitiveType::NM), 2, 4, 0, Optionality::O, false),
+            v2_component_descriptor!("component_number", "Component Number", V2ComponentType::Primitive(V2PrimitiveType::NM), 2, 5, 0, Optionality::O, false),
+            v2_component_descriptor!("sub_component_number", "Sub-Component Number", V2ComponentType::Primitive(V2PrimitiveType::NM), 2, 6, 0, Optionality::O, false)
+        ],
+        "XAD" => &[
+            v2_component_descriptor!("street_address", "Street Address", V2ComponentType::Complex(V2ComplexType::SAD), 0, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("other_designation", "Other Designation", V2ComponentType::Primitive(V2PrimitiveType::ST), 120, 2, 0, Optionality::O, true),
+            v2_component_descriptor!("city", "City", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 3, 0, Optionality::O, true),
+            v2_component_descriptor!("state", "State or Province", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 4, 0, Optionality::O, true),
+            v2_component_descriptor!("zip", "Zip or Postal Code", V2ComponentType::Primitive(V2PrimitiveType::ST), 12, 5, 0, Optionality::O, false),
+            v2_component_descriptor!("country", "Country", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 6, 399, Optionality::O, false),
+            v2_component_descriptor!("address_type", "Address Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 7, 190, Optionality::O, false),
+            v2_component_descriptor!("other_geographic_designation", "Other Geographic Designation", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 8, 0, Optionality::O, true),
+            v2_component_descriptor!("county_code", "County/Parish Code", V2ComponentType::Complex(V2ComplexType::CWE), 0, 9, 289, Optionality::O, false),
+            v2_component_descriptor!("census_tract", "Census Tract", V2ComponentType::Complex(V2ComplexType::CWE), 0, 10, 288, Optionality::O, false),
+            v2_component_descriptor!("address_representation_code", "Address Representation Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 11, 465, Optionality::O, false),
+            v2_component_descriptor!("address_validity_range", "Address Validity Range", V2ComponentType::Complex(V2ComplexType::DR), 0, 12, 0, Optionality::B, false),
+            v2_component_descriptor!("effective_date", "Effective Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 13, 0, Optionality::O, false),
+            v2_component_descriptor!("expiration_date", "Expiration Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 14, 0, Optionality::O, false),
+            v2_component_descriptor!("expiration_reason", "Expiration Reason", V2ComponentType::Complex(V2ComplexType::CWE), 0, 15, 616, Optionality::O, false),
+            v2_component_descriptor!("temporary_indicator", "Temporary Indicator", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 16, 136, Optionality::O, false),
+            v2_component_descriptor!("bad_address_indicator", "Bad Address Indicator", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 17, 136, Optionality::O, false),
+            v2_component_descriptor!("address_usage", "Address Usage", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 18, 617, Optionality::O, false),
+            v2_component_descriptor!("addressee", "Addressee", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 19, 0, Optionality::O, true),
+            v2_component_descriptor!("comment", "Comment", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 20, 0, Optionality::O, true),
+            v2_component_descriptor!("preference_order", "Preference Order", V2ComponentType::Primitive(V2PrimitiveType::NM), 0, 21, 0, Optionality::O, false),
+            v2_component_descriptor!("protection_code", "Protection Code", V2ComponentType::Complex(V2ComplexType::CWE), 0, 22, 618, Optionality::O, false),
+            v2_component_descriptor!("address_identifier", "Address Identifier", V2ComponentType::Complex(V2ComplexType::EI), 0, 23, 0, Optionality::O, false)
+        ],
+        "XCN" => &[
+            v2_component_descriptor!("person_id", "Person Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 15, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("family_name", "Family Name", V2ComponentType::Complex(V2ComplexType::FN), 0, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("given_name", "Given Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 30, 3, 0, Optionality::O, true),
+            v2_component_descriptor!("second_given_name", "Second and Further Given Names or Initials Thereof", V2ComponentType::Primitive(V2PrimitiveType::ST), 30, 4, 0, Optionality::O, true),
+            v2_component_descriptor!("suffix", "Suffix (e.g. JR or III)", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 5, 0, Optionality::O, true),
+            v2_component_descriptor!("prefix", "Prefix (e.g. DR)", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 6, 0, Optionality::O, true),
+            v2_component_descriptor!("degree", "Degree (e.g. MD)", V2ComponentType::Primitive(V2PrimitiveType::IS), 6, 7, 360, Optionality::B, false),
+            v2_component_descriptor!("source_table", "Source Table", V2ComponentType::Complex(V2ComplexType::CWE), 0, 8, 297, Optionality::C(CONDITION_XCN1), false),
+            v2_component_descriptor!("assigning_authority", "Assigning Authority", V2ComponentType::Complex(V2ComplexType::HD), 0, 9, 363, Optionality::C(CONDITION_XCN2), false),
+            v2_component_descriptor!("name_type", "Name Type Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 10, 200, Optionality::O, false),
+            v2_component_descriptor!("check_digit", "Identifier Check Digit", V2ComponentType::Primitive(V2PrimitiveType::ST), 4, 11, 0, Optionality::O, false),
+            v2_component_descriptor!("check_digit_scheme", "Check Digit Scheme", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 12, 61, Optionality::C(CONDITION_XCN3), false),
+            v2_component_descriptor!("id_type", "Identifier Type Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 13, 203, Optionality::O, false),
+            v2_component_descriptor!("assigning_facility", "Assigning Facility", V2ComponentType::Complex(V2ComplexType::HD), 0, 14, 0, Optionality::O, false),
+            v2_component_descriptor!("name_representation_code", "Name Representation Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 15, 465, Optionality::O, false),
+            v2_component_descriptor!("name_context", "Name Context", V2ComponentType::Complex(V2ComplexType::CWE), 0, 16, 448, Optionality::O, false),
+            v2_component_descriptor!("name_validity_range", "Name Validity Range", V2ComponentType::Complex(V2ComplexType::DR), 0, 17, 0, Optionality::B, false),
+            v2_component_descriptor!("name_assembly_order", "Name Assembly Order", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 18, 444, Optionality::O, false),
+            v2_component_descriptor!("effective_date", "Effective Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 19, 0, Optionality::O, false),
+            v2_component_descriptor!("expiration_date", "Expiration Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 20, 0, Optionality::O, false),
+            v2_component_descriptor!("professional_suffix", "Professional Suffix", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 21, 0, Optionality::O, true),
+            v2_component_descriptor!("assigning_jurisdiction", "Assigning Jurisdiction", V2ComponentType::Complex(V2ComplexType::CWE), 0, 22, 0, Optionality::O, false),
+            v2_component_descriptor!("assigning_agency", "Assigning Agency or Department", V2ComponentType::Complex(V2ComplexType::CWE), 0, 23, 0, Optionality::O, false),
+            v2_component_descriptor!("security_check", "Security Check", V2ComponentType::Primitive(V2PrimitiveType::ST), 4, 24, 0, Optionality::O, false),
+            v2_component_descriptor!("security_check_scheme", "Security Check Scheme", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 25, 904, Optionality::O, false)
+        ],
+        "XPN" => &[
+            v2_component_descriptor!("family_name", "Family Name", V2ComponentType::Complex(V2ComplexType::FN), 0, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("given_name", "Given Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 30, 2, 0, Optionality::O, true),
+            v2_component_descriptor!("second_given_name", "Second and Further Given Names or Initials Thereof", V2ComponentType::Primitive(V2PrimitiveType::ST), 30, 3, 0, Optionality::O, true),
+            v2_component_descriptor!("suffix", "Suffix (e.g. JR or III)", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 4, 0, Optionality::O, true),
+            v2_component_descriptor!("prefix", "Prefix (e.g. DR)", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 5, 0, Optionality::O, true),
+            v2_component_descriptor!("degree", "Degree (e.g. MD)", V2ComponentType::Primitive(V2PrimitiveType::IS), 6, 6, 360, Optionality::B, false),
+            v2_component_descriptor!("name_type", "Name Type Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 7, 200, Optionality::O, false),
+            v2_component_descriptor!("name_representation_code", "Name Representation Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 8, 465, Optionality::O, false),
+            v2_component_descriptor!("name_context", "Name Context", V2ComponentType::Complex(V2ComplexType::CWE), 0, 9, 448, Optionality::O, false),
+            v2_component_descriptor!("name_validity_range", "Name Validity Range", V2ComponentType::Complex(V2ComplexType::DR), 0, 10, 0, Optionality::B, false),
+            v2_component_descriptor!("name_assembly_order", "Name Assembly Order", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 11, 444, Optionality::O, false),
+            v2_component_descriptor!("effective_date", "Effective Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 12, 0, Optionality::O, false),
+            v2_component_descriptor!("expiration_date", "Expiration Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 13, 0, Optionality::O, false),
+            v2_component_descriptor!("professional_suffix", "Professional Suffix", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 14, 0, Optionality::O, true),
+            v2_component_descriptor!("called_by", "Called By", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 15, 0, Optionality::O, true)
+        ],
+        "XTN" => &[
+            v2_component_descriptor!("telephone_number", "Telephone Number", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 1, 0, Optionality::B, false),
+            v2_component_descriptor!("use_code", "Telecommunication Use Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 2, 201, Optionality::O, false),
+            v2_component_descriptor!("equipment_type", "Telecommunication Equipment Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 3, 202, Optionality::O, false),
+            v2_component_descriptor!("communication_address", "Communication Address", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 4, 0, Optionality::C(CONDITION_XTN1), false),
+            v2_component_descriptor!("country_code", "Country Code", V2ComponentType::Primitive(V2PrimitiveType::SNM), 3, 5, 0, Optionality::O, false),
+            v2_component_descriptor!("area_code", "Area/City Code", V2ComponentType::Primitive(V2PrimitiveType::SNM), 5, 6, 0, Optionality::O, false),
+            v2_component_descriptor!("local_number", "Local Number", V2ComponentType::Primitive(V2PrimitiveType::SNM), 9, 7, 0, Optionality::C(CONDITION_XTN2), false),
+            v2_component_descriptor!("extension", "Extension", V2ComponentType::Primitive(V2PrimitiveType::SNM), 5, 8, 0, Optionality::O, false),
+            v2_component_descriptor!("any_text", "Any Text", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 9, 0, Optionality::O, true),
+            v2_component_descriptor!("extension_prefix", "Extension Prefix", V2ComponentType::Primitive(V2PrimitiveType::ST), 4, 10, 0, Optionality::O, false),
+            v2_component_descriptor!("speed_dial_code", "Speed Dial Code", V2ComponentType::Primitive(V2PrimitiveType::ST), 6, 11, 0, Optionality::O, false),
+            v2_component_descriptor!("unformatted_telephone_number", "Unformatted Telephone Number", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 12, 0, Optionality::O, false),
+            v2_component_descriptor!("effective_start_date", "Effective Start Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 13, 0, Optionality::O, false),
+            v2_component_descriptor!("expiration_date", "Expiration Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 14, 0, Optionality::O, false),
+            v2_component_descriptor!("expiration_reason", "Expiration Reason", V2ComponentType::Complex(V2ComplexType::CWE), 0, 15, 868, Optionality::O, false),
+            v2_component_descriptor!("protection_code", "Protection Code", V2ComponentType::Complex(V2ComplexType::CWE), 0, 16, 618, Optionality::O, false),
+            v2_component_descriptor!("shared_telecommunication_id", "Shared Telecommunication Identifier", V2ComponentType::Complex(V2ComplexType::EI), 0, 17, 0, Optionality::O, false),
+            v2_component_descriptor!("preference_order", "Preference Order", V2ComponentType::Primitive(V2PrimitiveType::NM), 0, 18, 0, Optionality::O, false)
         ]
     };
 
+    ///
+    /// HL7 v2 releases we carry descriptor sets for, as sent in MSH-12. Releases are ordered so
+    /// ranges of versions can be compared directly.
+    ///
+    /// [V2_FIELD_DESCRIPTORS] follows v2.7 onward. Older releases are resolved by overlaying
+    /// [V2_FIELD_DESCRIPTORS_PRE_V2_7] and trimming the types that gained components over time. See
+    /// [get_field_descriptor].
+    ///
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub enum V2Version {
+        V2_3,
+        V2_3_1,
+        V2_4,
+        V2_5,
+        V2_5_1,
+        V2_6,
+        V2_7,
+        V2_7_1,
+        V2_8,
+        V2_8_1,
+        V2_8_2,
+        #[default]
+        V2_9,
+    }
+
+    impl V2Version {
+        ///
+        /// Map the version id in MSH-12.1 (table 0104) to a release. Releases older than v2.3 and
+        /// unknown ids yield [None].
+        ///
+        pub fn from_version_id(version_id: &str) -> Option<V2Version> {
+            match version_id.trim() {
+                "2.3" => Some(V2Version::V2_3),
+                "2.3.1" => Some(V2Version::V2_3_1),
+                "2.4" => Some(V2Version::V2_4),
+                "2.5" => Some(V2Version::V2_5),
+                "2.5.1" => Some(V2Version::V2_5_1),
+                "2.6" => Some(V2Version::V2_6),
+                "2.7" => Some(V2Version::V2_7),
+                "2.7.1" => Some(V2Version::V2_7_1),
+                "2.8" => Some(V2Version::V2_8),
+                "2.8.1" => Some(V2Version::V2_8_1),
+                "2.8.2" => Some(V2Version::V2_8_2),
+                "2.9" => Some(V2Version::V2_9),
+                _ => None,
+            }
+        }
+
+        pub fn as_str(&self) -> &'static str {
+            match self {
+                V2Version::V2_3 => "2.3",
+                V2Version::V2_3_1 => "2.3.1",
+                V2Version::V2_4 => "2.4",
+                V2Version::V2_5 => "2.5",
+                V2Version::V2_5_1 => "2.5.1",
+                V2Version::V2_6 => "2.6",
+                V2Version::V2_7 => "2.7",
+                V2Version::V2_7_1 => "2.7.1",
+                V2Version::V2_8 => "2.8",
+                V2Version::V2_8_1 => "2.8.1",
+                V2Version::V2_8_2 => "2.8.2",
+                V2Version::V2_9 => "2.9",
+            }
+        }
+    }
+
+    ///
+    /// Data types whose definition changed in v2.7. TS was withdrawn in favor of DTM, which also
+    /// turns the components that carried it into plain date/times, and XCN still used IS and CE
+    /// for its coded components.
+    ///
+    pub static V2_FIELD_DESCRIPTORS_PRE_V2_7: V2FieldDescriptors = phf_map! {
+        "DR" => &[
+            v2_component_descriptor!("range_start", "Range Start Date/Time", V2ComponentType::Complex(V2ComplexType::TS), 0, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("range_end", "Range End Date/Time", V2ComponentType::Complex(V2ComplexType::TS), 0, 2, 0, Optionality::O, false)
+        ],
+        "TS" => &[
+            v2_component_descriptor!("time", "Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("degree_of_precision", "Degree of Precision", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 2, 529, Optionality::B, false)
+        ],
+        "XCN" => &[
+            v2_component_descriptor!("person_id", "ID Number", V2ComponentType::Primitive(V2PrimitiveType::ST), 15, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("family_name", "Family Name", V2ComponentType::Complex(V2ComplexType::FN), 0, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("given_name", "Given Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 30, 3, 0, Optionality::O, true),
+            v2_component_descriptor!("second_given_name", "Second and Further Given Names or Initials Thereof", V2ComponentType::Primitive(V2PrimitiveType::ST), 30, 4, 0, Optionality::O, true),
+            v2_component_descriptor!("suffix", "Suffix (e.g. JR or III)", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 5, 0, Optionality::O, true),
+            v2_component_descriptor!("prefix", "Prefix (e.g. DR)", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 6, 0, Optionality::O, true),
+            v2_component_descriptor!("degree", "Degree (e.g. MD)", V2ComponentType::Primitive(V2PrimitiveType::IS), 6, 7, 360, Optionality::B, false),
+            v2_component_descriptor!("source_table", "Source Table", V2ComponentType::Primitive(V2PrimitiveType::IS), 4, 8, 297, Optionality::C(CONDITION_XCN1), false),
+            v2_component_descriptor!("assigning_authority", "Assigning Authority", V2ComponentType::Complex(V2ComplexType::HD), 0, 9, 363, Optionality::C(CONDITION_XCN2), false),
+            v2_component_descriptor!("name_type", "Name Type Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 10, 200, Optionality::O, false),
+            v2_component_descriptor!("check_digit", "Identifier Check Digit", V2ComponentType::Primitive(V2PrimitiveType::ST), 4, 11, 0, Optionality::O, false),
+            v2_component_descriptor!("check_digit_scheme", "Check Digit Scheme", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 12, 61, Optionality::C(CONDITION_XCN3), false),
+            v2_component_descriptor!("id_type", "Identifier Type Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 13, 203, Optionality::O, false),
+            v2_component_descriptor!("assigning_facility", "Assigning Facility", V2ComponentType::Complex(V2ComplexType::HD), 0, 14, 0, Optionality::O, false),
+            v2_component_descriptor!("name_representation_code", "Name Representation Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 15, 465, Optionality::O, false),
+            v2_component_descriptor!("name_context", "Name Context", V2ComponentType::Complex(V2ComplexType::CE), 0, 16, 448, Optionality::O, false),
+            v2_component_descriptor!("name_validity_range", "Name Validity Range", V2ComponentType::Complex(V2ComplexType::DR), 0, 17, 0, Optionality::B, false),
+            v2_component_descriptor!("name_assembly_order", "Name Assembly Order", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 18, 444, Optionality::O, false),
+            v2_component_descriptor!("effective_date", "Effective Date", V2ComponentType::Complex(V2ComplexType::TS), 0, 19, 0, Optionality::O, false),
+            v2_component_descriptor!("expiration_date", "Expiration Date", V2ComponentType::Complex(V2ComplexType::TS), 0, 20, 0, Optionality::O, false),
+            v2_component_descriptor!("professional_suffix", "Professional Suffix", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 21, 0, Optionality::O, true),
+            v2_component_descriptor!("assigning_jurisdiction", "Assigning Jurisdiction", V2ComponentType::Complex(V2ComplexType::CWE), 0, 22, 0, Optionality::O, false),
+            v2_component_descriptor!("assigning_agency", "Assigning Agency or Department", V2ComponentType::Complex(V2ComplexType::CWE), 0, 23, 0, Optionality::O, false)
+        ]
+    };
+
+    ///
+    /// Number of components `type_name` had in `version` when that is fewer than its descriptor
+    /// defines. Components were only ever appended so older layouts are a prefix of the newer ones.
+    ///
+    fn component_limit(type_name: &str, version: V2Version) -> Option<usize> {
+        match (type_name, version) {
+            ("CNE" | "CWE", v) if v < V2Version::V2_5 => Some(6),
+            ("CNE" | "CWE", v) if v < V2Version::V2_7 => Some(9),
+            ("XCN", V2Version::V2_3) => Some(14),
+            ("XCN", v) if v < V2Version::V2_5 => Some(18),
+            ("XTN", v) if v < V2Version::V2_5 => Some(9),
+            ("XTN", v) if v < V2Version::V2_7 => Some(12),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Look up the component descriptors of complex type `type_name` as defined in `version`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rumtk_hl7_v2::hl7_v2_field_descriptors::v2_field_descriptor::{get_field_descriptor, V2Version};
+    ///
+    /// assert_eq!(get_field_descriptor("CWE", V2Version::V2_9).unwrap().len(), 22);
+    /// assert_eq!(get_field_descriptor("CWE", V2Version::V2_5_1).unwrap().len(), 9);
+    /// assert_eq!(get_field_descriptor("CWE", V2Version::V2_4).unwrap().len(), 6);
+    /// assert!(get_field_descriptor("TS", V2Version::V2_5_1).is_some());
+    /// assert!(get_field_descriptor("TS", V2Version::V2_8).is_none(), "TS was withdrawn in v2.7!");
+    /// ```
+    ///
+    pub fn get_field_descriptor(type_name: &str, version: V2Version) -> Option<&'static V2ComponentDescriptor> {
+        let descriptors: &'static V2ComponentDescriptor = match version < V2Version::V2_7 {
+            true => V2_FIELD_DESCRIPTORS_PRE_V2_7
+                .get(type_name)
+                .or_else(|| V2_FIELD_DESCRIPTORS.get(type_name))
+                .copied()?,
+            false => V2_FIELD_DESCRIPTORS.get(type_name).copied()?,
+        };
+        match component_limit(type_name, version) {
+            Some(limit) if limit < descriptors.len() => Some(&descriptors[..limit]),
+            _ => Some(descriptors),
+        }
+    }
+
+
     ///
     /// Return string key corresponding to enumerator key.
     ///
-    pub fn complex_type_to_str(complex_type: &V2ComplexType) -> &str {
+    pub fn complex_type_to_str(complex_type: &V2ComplexType) -> &'static str {
         match complex_type {
             V2ComplexType::AD => "AD",
             V2ComplexType::AUI => "AUI",
@@ -1103,7 +1846,90 @@ pub mod v2_field_descriptor {
             V2ComplexType::CP => "CP",
             V2ComplexType::CQ => "CQ",
             V2ComplexType::CSU => "CSU",
-            _ => "Error",
+            V2ComplexType::CWE => "CWE",
+            V2ComplexType::CX => "CX",
+            V2ComplexType::DR => "DR",
+            V2ComplexType::EI => "EI",
+            V2ComplexType::FN => "FN",
+            V2ComplexType::HD => "HD",
+            V2ComplexType::MO => "MO",
+            V2ComplexType::NR => "NR",
+            V2ComplexType::SAD => "SAD",
+            V2ComplexType::WVI => "WVI",
+            V2ComplexType::WVS => "WVS",
+            V2ComplexType::XAD => "XAD",
+            V2ComplexType::XCN => "XCN",
+            V2ComplexType::XPN => "XPN",
+            V2ComplexType::XTN => "XTN",
+            V2ComplexType::DLD => "DLD",
+            V2ComplexType::DLN => "DLN",
+            V2ComplexType::EIP => "EIP",
+            V2ComplexType::FC => "FC",
+            V2ComplexType::JCC => "JCC",
+            V2ComplexType::MOC => "MOC",
+            V2ComplexType::MSG => "MSG",
+            V2ComplexType::NDL => "NDL",
+            V2ComplexType::PL => "PL",
+            V2ComplexType::PRL => "PRL",
+            V2ComplexType::PT => "PT",
+            V2ComplexType::VID => "VID",
+            V2ComplexType::XON => "XON",
+            V2ComplexType::ELD => "ELD",
+            V2ComplexType::ERL => "ERL",
+            V2ComplexType::TS => "TS",
+        }
+    }
+
+    ///
+    /// Return enumerator key corresponding to the type name as it appears in a message
+    /// (e.g. the value type in OBX-2).
+    ///
+    pub fn str_to_complex_type(type_name: &str) -> Option<V2ComplexType> {
+        match type_name {
+            "AD" => Some(V2ComplexType::AD),
+            "AUI" => Some(V2ComplexType::AUI),
+            "CCD" => Some(V2ComplexType::CCD),
+            "CCP" => Some(V2ComplexType::CCP),
+            "CD" => Some(V2ComplexType::CD),
+            "CE" => Some(V2ComplexType::CE),
+            "CF" => Some(V2ComplexType::CF),
+            "CNE" => Some(V2ComplexType::CNE),
+            "CNN" => Some(V2ComplexType::CNN),
+            "CP" => Some(V2ComplexType::CP),
+            "CQ" => Some(V2ComplexType::CQ),
+            "CSU" => Some(V2ComplexType::CSU),
+            "CWE" => Some(V2ComplexType::CWE),
+            "CX" => Some(V2ComplexType::CX),
+            "DLD" => Some(V2ComplexType::DLD),
+            "DLN" => Some(V2ComplexType::DLN),
+            "DR" => Some(V2ComplexType::DR),
+            "EI" => Some(V2ComplexType::EI),
+            "EIP" => Some(V2ComplexType::EIP),
+            "ELD" => Some(V2ComplexType::ELD),
+            "ERL" => Some(V2ComplexType::ERL),
+            "FC" => Some(V2ComplexType::FC),
+            "FN" => Some(V2ComplexType::FN),
+            "HD" => Some(V2ComplexType::HD),
+            "JCC" => Some(V2ComplexType::JCC),
+            "MO" => Some(V2ComplexType::MO),
+            "MOC" => Some(V2ComplexType::MOC),
+            "MSG" => Some(V2ComplexType::MSG),
+            "NDL" => Some(V2ComplexType::NDL),
+            "NR" => Some(V2ComplexType::NR),
+            "PL" => Some(V2ComplexType::PL),
+            "PRL" => Some(V2ComplexType::PRL),
+            "PT" => Some(V2ComplexType::PT),
+            "SAD" => Some(V2ComplexType::SAD),
+            "TS" => Some(V2ComplexType::TS),
+            "VID" => Some(V2ComplexType::VID),
+            "WVI" => Some(V2ComplexType::WVI),
+            "WVS" => Some(V2ComplexType::WVS),
+            "XAD" => Some(V2ComplexType::XAD),
+            "XCN" => Some(V2ComplexType::XCN),
+            "XON" => Some(V2ComplexType::XON),
+            "XPN" => Some(V2ComplexType::XPN),
+            "XTN" => Some(V2ComplexType::XTN),
+            _ => None,
         }
     }
 }
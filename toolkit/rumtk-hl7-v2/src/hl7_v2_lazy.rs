@@ -0,0 +1,411 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2026  Luis M. Santos, M.D. <lsantos@medicalmasses.com>
+ * Copyright (C) 2026  MedicalMasses L.L.C. <contact@medicalmasses.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+///
+/// Lazy parsing mode for routing workloads. [V2LazyMessage] only locates segment and field
+/// boundaries up front (see [cpu_tokenize]) and keeps them as offsets into the message buffer.
+/// Fields are split into repetitions and components the first time [V2LazySegment::get] or
+/// [V2LazyMessage::find_component] touches them. Nothing is copied. Every field is a
+/// [RUMBuffer] view into the original message.
+///
+/// The lazy message is read-only. Use [V2LazyMessage::into_message] when you need to edit it.
+///
+/// ## Example
+///
+/// ```
+/// use rumtk_hl7_v2::hl7_v2_lazy::v2_lazy::V2LazyMessage;
+///
+/// let raw = "MSH|^~\\&|LAB|FAC|||20250101120000||ORU^R01^ORU_R01|MSG0001|P|2.5.1\rPID|1||12345^^^HOSP^MR||DOE^JANE\rOBX|1|NM|GLU^Glucose^L||95\r";
+/// let message = V2LazyMessage::try_from(raw).unwrap();
+///
+/// assert_eq!(message.find_value("MSH8.1").unwrap(), "ORU", "Wrong message type!");
+/// assert_eq!(message.find_value("PID3.1").unwrap(), "12345", "Wrong patient id!");
+/// assert_eq!(message.materialized(), 2, "Only the fields we looked at should have been parsed!");
+/// ```
+///
+pub mod v2_lazy {
+    use crate::hl7_v2_base_types::v2_primitives::{V2ParserCharacters, V2Result, V2SearchIndex};
+    use crate::hl7_v2_charset::v2_charset::{v2_decode_message, V2MessageEncoding};
+    use crate::hl7_v2_constants::{V2_CUSTOM_SEGMENT_ID, V2_MSHEADER_ID, V2_SEGMENT_IDS};
+    use crate::hl7_v2_parser::v2_parser::{V2Component, V2Field, V2FieldGroup, V2Message, V2OptionalFieldGroup, V2Segment};
+    use rumtk_core::base::{clamp_index, RUMVec};
+    use rumtk_core::buffers::{buffer_to_str, RUMBuffer};
+    use rumtk_core::cache::{new_cache, LazyRUMCache};
+    use rumtk_core::cpu::{cpu_tokenize, CPU_SEARCH_WINDOW_64_SIZE};
+    use rumtk_core::rumtk_cache_fetch;
+    use rumtk_core::strings::{rumtk_format, string_to_buffer, AsStr, RUMString};
+    use std::borrow::Cow;
+    use std::sync::{LazyLock, OnceLock};
+
+    /**************************** Globals ***************************************/
+
+    static mut LAZY_SEARCH_CACHE: LazyRUMCache<RUMString, V2SearchIndex> = new_cache();
+    static EMPTY_FIELDGROUP: LazyLock<V2FieldGroup> = LazyLock::new(|| vec![V2Field::new()]);
+
+    /**************************** Types *****************************************/
+
+    ///
+    /// Segment whose fields are parsed on first access. Fields are numbered the same way as in
+    /// [V2Segment], so field 1 of MSH is MSH-2.
+    ///
+    #[derive(Debug)]
+    pub struct V2LazySegment {
+        id: u8,
+        raw: RUMBuffer,
+        /// Offsets of the field separators in `raw`.
+        bounds: RUMVec<u32>,
+        fields: RUMVec<OnceLock<V2OptionalFieldGroup>>,
+        sep: V2ParserCharacters,
+    }
+
+    impl V2LazySegment {
+        fn new(id: u8, raw: RUMBuffer, bounds: RUMVec<u32>, sep: &V2ParserCharacters) -> Self {
+            let fields = (0..bounds.len()).map(|_| OnceLock::new()).collect();
+            V2LazySegment {
+                id,
+                raw,
+                bounds,
+                fields,
+                sep: sep.clone(),
+            }
+        }
+
+        #[inline]
+        pub fn id(&self) -> u8 {
+            self.id
+        }
+
+        ///
+        /// Name of the segment as it appears in the message (e.g. `PID` or `ZPD`).
+        ///
+        pub fn name(&self) -> &str {
+            let end = match self.bounds.first() {
+                Some(end) => *end as usize,
+                None => self.raw.len(),
+            };
+            buffer_to_str(&self.raw[..end]).unwrap_or_default()
+        }
+
+        ///
+        /// Raw segment text, without the segment terminator.
+        ///
+        pub fn as_str(&self) -> &str {
+            buffer_to_str(&self.raw).unwrap_or_default()
+        }
+
+        #[inline]
+        pub fn len(&self) -> usize {
+            self.bounds.len()
+        }
+
+        #[inline]
+        pub fn is_empty(&self) -> bool {
+            self.bounds.is_empty()
+        }
+
+        ///
+        /// Same as [V2Segment::get]. The field is parsed the first time it is requested.
+        ///
+        pub fn get(&self, indx: isize) -> V2Result<&V2FieldGroup> {
+            let field_indx = clamp_index(&indx, &(self.len() as isize))? - 1;
+            let field = self.fields[field_indx].get_or_init(|| self.materialize(field_indx));
+            match field {
+                Some(field) => Ok(field),
+                None => Ok(&*EMPTY_FIELDGROUP),
+            }
+        }
+
+        ///
+        /// Number of fields that have been parsed so far.
+        ///
+        pub fn materialized(&self) -> usize {
+            self.fields.iter().filter(|field| field.get().is_some()).count()
+        }
+
+        ///
+        /// Fully parsed copy of this segment.
+        ///
+        pub fn to_segment(&self) -> V2Result<V2Segment> {
+            let (_, mut segment) = V2Segment::from(self.raw.freeze(), &self.sep)?;
+            if self.id == V2_MSHEADER_ID && !self.is_empty() {
+                if let Some(encoding_characters) = self.materialize(0) {
+                    segment[1] = encoding_characters;
+                }
+            }
+            Ok(segment)
+        }
+
+        fn materialize(&self, field_indx: usize) -> V2OptionalFieldGroup {
+            let start = self.bounds[field_indx] as usize + 1;
+            let end = match self.bounds.get(field_indx + 1) {
+                Some(end) => *end as usize,
+                None => self.raw.len(),
+            };
+            let field = view(&self.raw, start, end);
+            // MSH-2 holds the separators so it must not be split. See V2Message::patch_msh_pattern.
+            if self.id == V2_MSHEADER_ID && field_indx == 0 {
+                return Some(vec![V2Field::from_single_field(field, &self.sep)]);
+            }
+            V2Segment::generate_subfields(field, &self.sep)
+        }
+    }
+
+    ///
+    /// Read-only message that defers parsing fields until they are used. See the module
+    /// documentation.
+    ///
+    #[derive(Debug)]
+    pub struct V2LazyMessage {
+        data: RUMBuffer,
+        sep: V2ParserCharacters,
+        segments: RUMVec<V2LazySegment>,
+        enc: V2MessageEncoding,
+    }
+
+    impl V2LazyMessage {
+        ///
+        /// Index `raw_msg`. The bytes are decoded the same way [V2Message::try_from_buffer] does.
+        ///
+        pub fn try_from_buffer(raw_msg: RUMBuffer) -> V2Result<Self> {
+            let (decoded, encoding) = match v2_decode_message(&raw_msg)? {
+                (Cow::Borrowed(_), encoding) => (None, encoding),
+                (Cow::Owned(text), encoding) => (Some(text), encoding),
+            };
+            match decoded {
+                Some(text) => Self::index_buffer(string_to_buffer(&text), encoding),
+                None => Self::index_buffer(raw_msg, encoding),
+            }
+        }
+
+        ///
+        /// Index text that is already decoded. See [V2Message::try_from_text].
+        ///
+        pub fn try_from_text(text: &str) -> V2Result<Self> {
+            Self::index_buffer(string_to_buffer(text), V2MessageEncoding::from_text(text))
+        }
+
+        fn index_buffer(mut raw_msg: RUMBuffer, encoding: V2MessageEncoding) -> V2Result<Self> {
+            let sanitized = V2Message::sanitize(&mut raw_msg);
+            let sep = V2ParserCharacters::from(&sanitized)?;
+            let tokens = cpu_tokenize::<CPU_SEARCH_WINDOW_64_SIZE>(&sanitized, &[sep.segment_terminator, sep.field_separator]);
+            let mut segments = RUMVec::with_capacity(32);
+            let mut start = 0;
+            let mut bounds = RUMVec::with_capacity(32);
+
+            for (token, position) in tokens {
+                let position = position as usize;
+                if token == sep.field_separator {
+                    bounds.push((position - start) as u32);
+                    continue;
+                }
+                Self::push_segment(&mut segments, &sanitized, start, position, bounds, &sep);
+                bounds = RUMVec::with_capacity(32);
+                start = position + 1;
+            }
+            Self::push_segment(&mut segments, &sanitized, start, sanitized.len(), bounds, &sep);
+
+            if segments.first().map(|segment| segment.id) != Some(V2_MSHEADER_ID) {
+                return Err(RUMString::from("No MSH segment found! The message is malformed or incomplete!"));
+            }
+
+            Ok(V2LazyMessage {
+                data: raw_msg,
+                sep,
+                segments,
+                enc: encoding,
+            })
+        }
+
+        fn push_segment(
+            segments: &mut RUMVec<V2LazySegment>,
+            msg: &RUMBuffer,
+            start: usize,
+            end: usize,
+            bounds: RUMVec<u32>,
+            sep: &V2ParserCharacters,
+        ) {
+            if start >= end {
+                return;
+            }
+            let raw = view(msg, start, end);
+            let name_end = match bounds.first() {
+                Some(name_end) => *name_end as usize,
+                None => raw.len(),
+            };
+            let id = V2_SEGMENT_IDS(&raw[..name_end]);
+            segments.push(V2LazySegment::new(id, raw, bounds, sep));
+        }
+
+        ///
+        /// Parse every field and turn this into a regular, editable [V2Message].
+        ///
+        pub fn into_message(self) -> V2Result<V2Message> {
+            V2Message::parse_buffer(self.data, self.enc)
+        }
+
+        ///
+        /// Message text after sanitizing, i.e. with `\r` as the only segment terminator.
+        ///
+        pub fn as_str(&self) -> &str {
+            buffer_to_str(&self.data).unwrap_or_default()
+        }
+
+        #[inline]
+        pub fn parser_chars(&self) -> &V2ParserCharacters {
+            &self.sep
+        }
+
+        #[inline]
+        pub fn encoding(&self) -> &V2MessageEncoding {
+            &self.enc
+        }
+
+        ///
+        /// Segments in the order they appear in the message.
+        ///
+        #[inline]
+        pub fn segments(&self) -> &[V2LazySegment] {
+            &self.segments
+        }
+
+        ///
+        /// Same as [V2Message::get].
+        ///
+        pub fn get(&self, segment_id: u8, sub_segment: usize) -> V2Result<&V2LazySegment> {
+            let subsegment_indx = sub_segment.wrapping_sub(1);
+            match self.segments.iter().filter(|segment| segment.id == segment_id).nth(subsegment_indx) {
+                Some(segment) => Ok(segment),
+                None => Err(rumtk_format!(
+                    "Subsegment {} was not found in segment group {}!",
+                    subsegment_indx,
+                    segment_id
+                )),
+            }
+        }
+
+        ///
+        /// Same as [V2Message::get_named].
+        ///
+        pub fn get_named(&self, segment_name: &str, sub_segment: usize) -> V2Result<&V2LazySegment> {
+            match V2_SEGMENT_IDS(segment_name.as_bytes()) {
+                V2_CUSTOM_SEGMENT_ID => {
+                    let subsegment_indx = sub_segment.wrapping_sub(1);
+                    match self
+                        .segments
+                        .iter()
+                        .filter(|segment| segment.id == V2_CUSTOM_SEGMENT_ID && segment.name() == segment_name)
+                        .nth(subsegment_indx)
+                    {
+                        Some(segment) => Ok(segment),
+                        None => Err(rumtk_format!(
+                            "Subsegment {} was not found in custom segment group {}!",
+                            subsegment_indx,
+                            segment_name
+                        )),
+                    }
+                }
+                segment_id => self.get(segment_id, sub_segment),
+            }
+        }
+
+        pub fn segment_group_count(&self, segment_id: u8) -> usize {
+            self.segments.iter().filter(|segment| segment.id == segment_id).count()
+        }
+
+        #[inline]
+        pub fn segment_exists(&self, segment_id: u8) -> bool {
+            self.segments.iter().any(|segment| segment.id == segment_id)
+        }
+
+        ///
+        /// Same as [V2Message::find_component] for plain search patterns (e.g. `PID(1)3[2].1`).
+        /// Extended expressions need the full message. See [V2LazyMessage::into_message].
+        ///
+        pub fn find_component(&self, search_pattern: &str) -> V2Result<&V2Component> {
+            let index = rumtk_cache_fetch!(&raw mut LAZY_SEARCH_CACHE, &search_pattern.to_string(), || {V2SearchIndex::try_from(search_pattern)})?;
+            self.lookup(&index)
+        }
+
+        ///
+        /// Raw value at `search_pattern`. Unlike [V2LazyMessage::find_component], the pattern can
+        /// point at a subcomponent (e.g. `PID3.4.2`).
+        ///
+        pub fn find_value(&self, search_pattern: &str) -> V2Result<&str> {
+            let index = rumtk_cache_fetch!(&raw mut LAZY_SEARCH_CACHE, &search_pattern.to_string(), || {V2SearchIndex::try_from(search_pattern)})?;
+            let component = self.lookup(&index)?;
+            match index.subcomponent {
+                0 => Ok(component.as_str()),
+                subcomponent => component.get_subcomponent(subcomponent as isize, &self.sep),
+            }
+        }
+
+        fn lookup(&self, index: &V2SearchIndex) -> V2Result<&V2Component> {
+            let segment = self.get_named(&index.segment_name, index.segment_group as usize)?;
+            let field_group = segment.get(index.field as isize)?;
+            let field = match field_group.get((index.field_group as usize).wrapping_sub(1)) {
+                Some(field) => field,
+                None => return Err(rumtk_format!("Subfield provided is not 1 indexed or out of bounds. Did you give us a 0 when you meant 1? Got {}!", index.field_group))
+            };
+            field.get(index.component as isize)
+        }
+
+        ///
+        /// Number of fields parsed so far across all segments. Handy to confirm a route only paid
+        /// for what it looked at.
+        ///
+        pub fn materialized(&self) -> usize {
+            self.segments.iter().map(|segment| segment.materialized()).sum()
+        }
+    }
+
+    impl TryFrom<&str> for V2LazyMessage {
+        type Error = RUMString;
+        fn try_from(input: &str) -> V2Result<V2LazyMessage> {
+            V2LazyMessage::try_from_text(input)
+        }
+    }
+
+    impl TryFrom<&[u8]> for V2LazyMessage {
+        type Error = RUMString;
+        fn try_from(input: &[u8]) -> V2Result<V2LazyMessage> {
+            V2LazyMessage::try_from_buffer(RUMBuffer::from(input))
+        }
+    }
+
+    impl TryFrom<RUMBuffer> for V2LazyMessage {
+        type Error = RUMString;
+        fn try_from(input: RUMBuffer) -> V2Result<V2LazyMessage> {
+            V2LazyMessage::try_from_buffer(input)
+        }
+    }
+
+    /**************************** Helpers ***************************************/
+
+    ///
+    /// View of `buffer[start..end]` that shares its memory.
+    ///
+    #[inline(always)]
+    fn view(buffer: &RUMBuffer, start: usize, end: usize) -> RUMBuffer {
+        let mut rest = buffer.freeze();
+        let _ = rest.split_to(start);
+        rest.truncate(end - start);
+        rest
+    }
+}
@@ -0,0 +1,820 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2026  Luis M. Santos, M.D. <lsantos@medicalmasses.com>
+ * Copyright (C) 2026  MedicalMasses L.L.C. <contact@medicalmasses.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+///
+/// Message structures (the abstract message syntax of each trigger event) and the group tree we
+/// build from them.
+///
+/// [V2Message] buckets segments by id which is great for lookups but loses the nesting of segment
+/// groups. For example, in an ORU^R01 you cannot tell which OBX belongs to which OBR by looking at
+/// the OBR and OBX groups alone. Here we walk the segments in their original order and match them
+/// against the structure named in MSH-9 to rebuild the hierarchy.
+///
+/// Structures follow v2.5.1. Group names match the ones in the standard.
+///
+/// ## Per Section 2.5.2
+/// ```text
+/// Two or more segments MAY be organized as a logical unit called a segment group. A segment group
+/// MAY be required or optional and might or might not repeat. As of v 2.5, the first segment in a newly
+/// defined segment group will be required to help ensure that unparsable messages will not be
+/// inadvertently defined. This required first segment is known as the anchor segment.
+/// ```
+///
+pub mod v2_message_structure {
+    use crate::hl7_v2_complex_types::hl7_v2_complex_types::V2FieldToString;
+    use crate::hl7_v2_constants::{V2_CUSTOM_SEGMENT_ID, V2_MSHEADER_ID, V2_MSHEADER_PATTERN_STR};
+    use crate::hl7_v2_parser::v2_parser::{V2Message, V2Result, V2Segment, V2String};
+    use crate::hl7_v2_segment_descriptors::v2_segment_descriptor::segment_field_index;
+    use crate::hl7_v2_validation::v2_validation::{V2Severity, V2ValidationReport, V2ValidationRule};
+    use ::phf::Map;
+    use ::phf_macros::phf_map;
+    use rumtk_core::strings::{rumtk_format, RUMString};
+    use std::ops::Deref;
+    use V2Cardinality::*;
+    use V2StructureElement::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum V2Cardinality {
+        /// `SEG`
+        One,
+        /// `[SEG]`
+        ZeroOrOne,
+        /// `{SEG}`
+        OneOrMore,
+        /// `[{SEG}]`
+        ZeroOrMore,
+    }
+
+    impl V2Cardinality {
+        pub fn is_required(&self) -> bool {
+            matches!(self, One | OneOrMore)
+        }
+
+        pub fn is_repeating(&self) -> bool {
+            matches!(self, OneOrMore | ZeroOrMore)
+        }
+    }
+
+    ///
+    /// Element in a message structure. A [V2StructureElement::Choice] matches exactly one of its
+    /// elements (`<OBR|RQD|RXO>` in the standard) and does not create a group in the tree.
+    ///
+    #[derive(Debug)]
+    pub enum V2StructureElement {
+        Segment(&'static str, V2Cardinality),
+        Group(&'static str, V2Cardinality, &'static [V2StructureElement]),
+        Choice(&'static str, V2Cardinality, &'static [V2StructureElement]),
+    }
+
+    impl V2StructureElement {
+        pub fn name(&self) -> &'static str {
+            match self {
+                Segment(name, _) => name,
+                Group(name, _, _) => name,
+                Choice(name, _, _) => name,
+            }
+        }
+
+        pub fn cardinality(&self) -> V2Cardinality {
+            match self {
+                Segment(_, cardinality) => *cardinality,
+                Group(_, cardinality, _) => *cardinality,
+                Choice(_, cardinality, _) => *cardinality,
+            }
+        }
+
+        ///
+        /// Whether a segment named `segment_name` can start this element.
+        ///
+        pub fn starts_with(&self, segment_name: &str) -> bool {
+            match self {
+                Segment(name, _) => *name == segment_name,
+                Group(_, _, elements) => sequence_starts_with(elements, segment_name),
+                Choice(_, _, elements) => elements.iter().any(|e| e.starts_with(segment_name)),
+            }
+        }
+    }
+
+    ///
+    /// A sequence can start with any of its leading optional elements or with its first required
+    /// element.
+    ///
+    fn sequence_starts_with(elements: &[V2StructureElement], segment_name: &str) -> bool {
+        for element in elements.iter() {
+            if element.starts_with(segment_name) {
+                return true;
+            }
+            if element.cardinality().is_required() {
+                return false;
+            }
+        }
+        false
+    }
+
+    pub type V2MessageStructure = &'static [V2StructureElement];
+    pub type V2MessageStructures = Map<&'static str, V2MessageStructure>;
+    pub type V2EventStructures = Map<&'static str, &'static str>;
+
+    pub static V2_MESSAGE_STRUCTURES: V2MessageStructures = phf_map! {
+        "ACK" => &[
+            Segment("MSH", One),
+            Segment("SFT", ZeroOrMore),
+            Segment("MSA", One),
+            Segment("ERR", ZeroOrMore)
+        ],
+        "ADT_A01" => &[
+            Segment("MSH", One),
+            Segment("SFT", ZeroOrMore),
+            Segment("EVN", One),
+            Segment("PID", One),
+            Segment("PD1", ZeroOrOne),
+            Segment("ROL", ZeroOrMore),
+            Segment("NK1", ZeroOrMore),
+            Segment("PV1", One),
+            Segment("PV2", ZeroOrOne),
+            Segment("ROL", ZeroOrMore),
+            Segment("DB1", ZeroOrMore),
+            Segment("OBX", ZeroOrMore),
+            Segment("AL1", ZeroOrMore),
+            Segment("DG1", ZeroOrMore),
+            Segment("DRG", ZeroOrOne),
+            Group("PROCEDURE", ZeroOrMore, &[
+                Segment("PR1", One),
+                Segment("ROL", ZeroOrMore)
+            ]),
+            Segment("GT1", ZeroOrMore),
+            Group("INSURANCE", ZeroOrMore, &[
+                Segment("IN1", One),
+                Segment("IN2", ZeroOrOne),
+                Segment("IN3", ZeroOrMore),
+                Segment("ROL", ZeroOrMore)
+            ]),
+            Segment("ACC", ZeroOrOne),
+            Segment("UB1", ZeroOrOne),
+            Segment("UB2", ZeroOrOne),
+            Segment("PDA", ZeroOrOne)
+        ],
+        "OML_O21" => &[
+            Segment("MSH", One),
+            Segment("SFT", ZeroOrMore),
+            Segment("NTE", ZeroOrMore),
+            Group("PATIENT", ZeroOrOne, &[
+                Segment("PID", One),
+                Segment("PD1", ZeroOrOne),
+                Segment("NTE", ZeroOrMore),
+                Segment("NK1", ZeroOrMore),
+                Group("PATIENT_VISIT", ZeroOrOne, &[
+                    Segment("PV1", One),
+                    Segment("PV2", ZeroOrOne)
+                ]),
+                Group("INSURANCE", ZeroOrMore, &[
+                    Segment("IN1", One),
+                    Segment("IN2", ZeroOrOne),
+                    Segment("IN3", ZeroOrOne)
+                ]),
+                Segment("GT1", ZeroOrOne),
+                Segment("AL1", ZeroOrMore)
+            ]),
+            Group("ORDER", OneOrMore, &[
+                Segment("ORC", One),
+                Group("TIMING", ZeroOrMore, &[
+                    Segment("TQ1", One),
+                    Segment("TQ2", ZeroOrMore)
+                ]),
+                Group("OBSERVATION_REQUEST", ZeroOrOne, &[
+                    Segment("OBR", One),
+                    Segment("TCD", ZeroOrOne),
+                    Segment("NTE", ZeroOrMore),
+                    Segment("DG1", ZeroOrMore),
+                    Group("OBSERVATION", ZeroOrMore, &[
+                        Segment("OBX", One),
+                        Segment("TCD", ZeroOrOne),
+                        Segment("NTE", ZeroOrMore)
+                    ]),
+                    Group("SPECIMEN", ZeroOrMore, &[
+                        Segment("SPM", One),
+                        Segment("OBX", ZeroOrMore),
+                        Group("CONTAINER", ZeroOrMore, &[
+                            Segment("SAC", One),
+                            Segment("OBX", ZeroOrMore)
+                        ])
+                    ])
+                ]),
+                Segment("FT1", ZeroOrMore),
+                Segment("CTI", ZeroOrMore),
+                Segment("BLG", ZeroOrOne)
+            ])
+        ],
+        "ORM_O01" => &[
+            Segment("MSH", One),
+            Segment("NTE", ZeroOrMore),
+            Group("PATIENT", ZeroOrOne, &[
+                Segment("PID", One),
+                Segment("PD1", ZeroOrOne),
+                Segment("NTE", ZeroOrMore),
+                Group("PATIENT_VISIT", ZeroOrOne, &[
+                    Segment("PV1", One),
+                    Segment("PV2", ZeroOrOne)
+                ]),
+                Group("INSURANCE", ZeroOrMore, &[
+                    Segment("IN1", One),
+                    Segment("IN2", ZeroOrOne),
+                    Segment("IN3", ZeroOrOne)
+                ]),
+                Segment("GT1", ZeroOrOne),
+                Segment("AL1", ZeroOrMore)
+            ]),
+            Group("ORDER", OneOrMore, &[
+                Segment("ORC", One),
+                Group("ORDER_DETAIL", ZeroOrOne, &[
+                    Choice("ORDER_DETAIL_SEGMENT", One, &[
+                        Segment("OBR", One),
+                        Segment("RQD", One),
+                        Segment("RQ1", One),
+                        Segment("RXO", One),
+                        Segment("ODS", One),
+                        Segment("ODT", One)
+                    ]),
+                    Segment("NTE", ZeroOrMore),
+                    Segment("CTD", ZeroOrOne),
+                    Segment("DG1", ZeroOrMore),
+                    Group("OBSERVATION", ZeroOrMore, &[
+                        Segment("OBX", One),
+                        Segment("NTE", ZeroOrMore)
+                    ])
+                ]),
+                Segment("FT1", ZeroOrMore),
+                Segment("CTI", ZeroOrMore),
+                Segment("BLG", ZeroOrOne)
+            ])
+        ],
+        "ORU_R01" => &[
+            Segment("MSH", One),
+            Segment("SFT", ZeroOrMore),
+            Group("PATIENT_RESULT", OneOrMore, &[
+                Group("PATIENT", ZeroOrOne, &[
+                    Segment("PID", One),
+                    Segment("PD1", ZeroOrOne),
+                    Segment("NTE", ZeroOrMore),
+                    Segment("NK1", ZeroOrMore),
+                    Group("VISIT", ZeroOrOne, &[
+                        Segment("PV1", One),
+                        Segment("PV2", ZeroOrOne)
+                    ])
+                ]),
+                Group("ORDER_OBSERVATION", OneOrMore, &[
+                    Segment("ORC", ZeroOrOne),
+                    Segment("OBR", One),
+                    Segment("NTE", ZeroOrMore),
+                    Group("TIMING_QTY", ZeroOrMore, &[
+                        Segment("TQ1", One),
+                        Segment("TQ2", ZeroOrMore)
+                    ]),
+                    Segment("CTD", ZeroOrOne),
+                    Group("OBSERVATION", ZeroOrMore, &[
+                        Segment("OBX", One),
+                        Segment("NTE", ZeroOrMore)
+                    ]),
+                    Segment("FT1", ZeroOrMore),
+                    Segment("CTI", ZeroOrMore),
+                    Group("SPECIMEN", ZeroOrMore, &[
+                        Segment("SPM", One),
+                        Segment("OBX", ZeroOrMore)
+                    ])
+                ])
+            ]),
+            Segment("DSC", ZeroOrOne)
+        ],
+        "SIU_S12" => &[
+            Segment("MSH", One),
+            Segment("SFT", ZeroOrMore),
+            Segment("SCH", One),
+            Segment("TQ1", ZeroOrMore),
+            Segment("NTE", ZeroOrMore),
+            Group("PATIENT", ZeroOrMore, &[
+                Segment("PID", One),
+                Segment("PD1", ZeroOrOne),
+                Segment("PV1", ZeroOrOne),
+                Segment("PV2", ZeroOrOne),
+                Segment("OBX", ZeroOrMore),
+                Segment("DG1", ZeroOrMore)
+            ]),
+            Group("RESOURCES", OneOrMore, &[
+                Segment("RGS", One),
+                Group("SERVICE", ZeroOrMore, &[
+                    Segment("AIS", One),
+                    Segment("NTE", ZeroOrMore)
+                ]),
+                Group("GENERAL_RESOURCE", ZeroOrMore, &[
+                    Segment("AIG", One),
+                    Segment("NTE", ZeroOrMore)
+                ]),
+                Group("LOCATION_RESOURCE", ZeroOrMore, &[
+                    Segment("AIL", One),
+                    Segment("NTE", ZeroOrMore)
+                ]),
+                Group("PERSONNEL_RESOURCE", ZeroOrMore, &[
+                    Segment("AIP", One),
+                    Segment("NTE", ZeroOrMore)
+                ])
+            ])
+        ],
+        "VXU_V04" => &[
+            Segment("MSH", One),
+            Segment("SFT", ZeroOrMore),
+            Segment("PID", One),
+            Segment("PD1", ZeroOrOne),
+            Segment("NK1", ZeroOrMore),
+            Group("PATIENT", ZeroOrOne, &[
+                Segment("PV1", One),
+                Segment("PV2", ZeroOrOne)
+            ]),
+            Segment("GT1", ZeroOrMore),
+            Group("INSURANCE", ZeroOrMore, &[
+                Segment("IN1", One),
+                Segment("IN2", ZeroOrOne),
+                Segment("IN3", ZeroOrOne)
+            ]),
+            Group("ORDER", ZeroOrMore, &[
+                Segment("ORC", One),
+                Group("TIMING", ZeroOrMore, &[
+                    Segment("TQ1", One),
+                    Segment("TQ2", ZeroOrMore)
+                ]),
+                Segment("RXA", One),
+                Segment("RXR", ZeroOrOne),
+                Group("OBSERVATION", ZeroOrMore, &[
+                    Segment("OBX", One),
+                    Segment("NTE", ZeroOrMore)
+                ])
+            ])
+        ]
+    };
+
+    ///
+    /// Trigger events sharing a structure (HL7 Table 0354). Only needed when MSH-9.3 is not valued.
+    ///
+    pub static V2_EVENT_STRUCTURES: V2EventStructures = phf_map! {
+        "ADT_A01" => "ADT_A01",
+        "ADT_A04" => "ADT_A01",
+        "ADT_A08" => "ADT_A01",
+        "ADT_A13" => "ADT_A01",
+        "OML_O21" => "OML_O21",
+        "ORM_O01" => "ORM_O01",
+        "ORU_R01" => "ORU_R01",
+        "SIU_S12" => "SIU_S12",
+        "SIU_S13" => "SIU_S12",
+        "SIU_S14" => "SIU_S12",
+        "SIU_S15" => "SIU_S12",
+        "SIU_S16" => "SIU_S12",
+        "SIU_S17" => "SIU_S12",
+        "SIU_S18" => "SIU_S12",
+        "SIU_S19" => "SIU_S12",
+        "SIU_S20" => "SIU_S12",
+        "SIU_S21" => "SIU_S12",
+        "SIU_S22" => "SIU_S12",
+        "SIU_S23" => "SIU_S12",
+        "SIU_S24" => "SIU_S12",
+        "SIU_S26" => "SIU_S12",
+        "VXU_V04" => "VXU_V04"
+    };
+
+    pub fn get_message_structure(structure_id: &str) -> Option<V2MessageStructure> {
+        V2_MESSAGE_STRUCTURES.get(structure_id).copied()
+    }
+
+    ///
+    /// Minimum and maximum number of times each segment may appear in a message of this structure,
+    /// in order of first appearance. A maximum of `None` means the segment may repeat without
+    /// bound. Segments inside optional groups or choices have a minimum of 0.
+    ///
+    pub fn segment_occurrence_limits(structure: V2MessageStructure) -> Vec<(&'static str, usize, Option<usize>)> {
+        let mut limits = Vec::new();
+        collect_occurrence_limits(structure, true, true, &mut limits);
+        limits
+    }
+
+    fn collect_occurrence_limits(
+        elements: &'static [V2StructureElement],
+        required: bool,
+        bounded: bool,
+        limits: &mut Vec<(&'static str, usize, Option<usize>)>,
+    ) {
+        for element in elements.iter() {
+            let cardinality = element.cardinality();
+            let required = required && cardinality.is_required();
+            let bounded = bounded && !cardinality.is_repeating();
+            match element {
+                Segment(name, _) => {
+                    let indx = match limits.iter().position(|(n, _, _)| n == name) {
+                        Some(indx) => indx,
+                        None => {
+                            limits.push((*name, 0, Some(0)));
+                            limits.len() - 1
+                        }
+                    };
+                    let (_, min, max) = &mut limits[indx];
+                    *min += required as usize;
+                    *max = match bounded {
+                        true => max.map(|max| max + 1),
+                        false => None,
+                    };
+                }
+                Group(_, _, group_elements) => collect_occurrence_limits(group_elements, required, bounded, limits),
+                // Only one alternative is present so none of them is required on its own.
+                Choice(_, _, choices) => collect_occurrence_limits(choices, false, bounded, limits),
+            }
+        }
+    }
+
+    ///
+    /// Identify the message structure from MSH-9. We prefer MSH-9.3 (message structure) and fall back
+    /// to looking up the message code and trigger event in [V2_EVENT_STRUCTURES].
+    ///
+    pub fn message_structure_id(message: &V2Message) -> V2Result<&'static str> {
+        let msh = message.get(V2_MSHEADER_ID, 1)?;
+        let message_type = match msh.get(segment_field_index(V2_MSHEADER_PATTERN_STR, 9))?.first() {
+            Some(field) => field.to_component_list(),
+            None => return Err(RUMString::from("MSH-9 is missing!")),
+        };
+        let message_code = message_type.first().copied().unwrap_or_default();
+        let trigger_event = message_type.get(1).copied().unwrap_or_default();
+        let message_structure = message_type.get(2).copied().unwrap_or_default();
+
+        if let Some((structure_id, _)) = V2_MESSAGE_STRUCTURES.get_entry(message_structure) {
+            return Ok(*structure_id);
+        }
+        if message_code == "ACK" {
+            return Ok("ACK");
+        }
+        match V2_EVENT_STRUCTURES.get(rumtk_format!("{}_{}", message_code, trigger_event).as_str()) {
+            Some(structure_id) => Ok(*structure_id),
+            None => Err(rumtk_format!(
+                "No message structure available for {}^{}^{}!",
+                message_code,
+                trigger_event,
+                message_structure
+            )),
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct V2SegmentNode<'a> {
+        name: &'a str,
+        segment_id: u8,
+        sub_segment: usize,
+        segment: &'a V2Segment,
+    }
+
+    impl<'a> V2SegmentNode<'a> {
+        ///
+        /// Segment name as it appeared in the message. Z segments keep their original name.
+        ///
+        pub fn name(&self) -> &'a str {
+            self.name
+        }
+
+        pub fn segment_id(&self) -> u8 {
+            self.segment_id
+        }
+
+        ///
+        /// 1-indexed position of this segment in its segment group. See [V2Message::get].
+        ///
+        pub fn sub_segment(&self) -> usize {
+            self.sub_segment
+        }
+
+        pub fn segment(&self) -> &'a V2Segment {
+            self.segment
+        }
+
+        ///
+        /// Location of the segment as used in the validation reports, e.g. `OBX(3)`.
+        ///
+        pub fn location(&self) -> V2String {
+            match self.sub_segment {
+                1 => V2String::from(self.name),
+                _ => rumtk_format!("{}({})", self.name, self.sub_segment),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub enum V2StructureNode<'a> {
+        Segment(V2SegmentNode<'a>),
+        Group(V2GroupNode<'a>),
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct V2GroupNode<'a> {
+        name: &'static str,
+        children: Vec<V2StructureNode<'a>>,
+    }
+
+    impl<'a> V2GroupNode<'a> {
+        pub fn new(name: &'static str) -> Self {
+            V2GroupNode {
+                name,
+                children: Vec::new(),
+            }
+        }
+
+        pub fn name(&self) -> &'static str {
+            self.name
+        }
+
+        pub fn children(&self) -> &Vec<V2StructureNode<'a>> {
+            &self.children
+        }
+
+        pub fn len(&self) -> usize {
+            self.children.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.children.is_empty()
+        }
+
+        ///
+        /// Segments named `name` directly under this group.
+        ///
+        pub fn segments(&self, name: &str) -> Vec<&V2SegmentNode<'a>> {
+            self.children
+                .iter()
+                .filter_map(|child| match child {
+                    V2StructureNode::Segment(segment) if segment.name == name => Some(segment),
+                    _ => None,
+                })
+                .collect()
+        }
+
+        pub fn segment(&self, name: &str) -> Option<&V2SegmentNode<'a>> {
+            self.segments(name).into_iter().next()
+        }
+
+        ///
+        /// Groups named `name` directly under this group.
+        ///
+        pub fn groups(&self, name: &str) -> Vec<&V2GroupNode<'a>> {
+            self.children
+                .iter()
+                .filter_map(|child| match child {
+                    V2StructureNode::Group(group) if group.name == name => Some(group),
+                    _ => None,
+                })
+                .collect()
+        }
+
+        pub fn group(&self, name: &str) -> Option<&V2GroupNode<'a>> {
+            self.groups(name).into_iter().next()
+        }
+
+        ///
+        /// Groups named `name` anywhere under this group, depth first.
+        ///
+        pub fn find_groups(&self, name: &str) -> Vec<&V2GroupNode<'a>> {
+            let mut found = Vec::new();
+            for child in self.children.iter() {
+                if let V2StructureNode::Group(group) = child {
+                    if group.name == name {
+                        found.push(group);
+                    }
+                    found.extend(group.find_groups(name));
+                }
+            }
+            found
+        }
+
+        ///
+        /// Every segment under this group in message order.
+        ///
+        pub fn all_segments(&self) -> Vec<&V2SegmentNode<'a>> {
+            let mut found = Vec::new();
+            for child in self.children.iter() {
+                match child {
+                    V2StructureNode::Segment(segment) => found.push(segment),
+                    V2StructureNode::Group(group) => found.extend(group.all_segments()),
+                }
+            }
+            found
+        }
+    }
+
+    ///
+    /// Result of matching a message against its structure. Issues found while matching (missing
+    /// required segments or groups, segments out of place) are kept in [V2MessageTree::issues].
+    /// Segments we could not place are appended to the root so nothing is lost.
+    ///
+    #[derive(Debug, Clone)]
+    pub struct V2MessageTree<'a> {
+        structure_id: &'static str,
+        root: V2GroupNode<'a>,
+        issues: V2ValidationReport,
+    }
+
+    impl<'a> V2MessageTree<'a> {
+        pub fn structure_id(&self) -> &'static str {
+            self.structure_id
+        }
+
+        pub fn root(&self) -> &V2GroupNode<'a> {
+            &self.root
+        }
+
+        pub fn issues(&self) -> &V2ValidationReport {
+            &self.issues
+        }
+
+        pub fn into_issues(self) -> V2ValidationReport {
+            self.issues
+        }
+
+        pub fn is_valid(&self) -> bool {
+            self.issues.is_valid()
+        }
+    }
+
+    impl<'a> Deref for V2MessageTree<'a> {
+        type Target = V2GroupNode<'a>;
+        fn deref(&self) -> &V2GroupNode<'a> {
+            &self.root
+        }
+    }
+
+    struct V2SegmentCursor<'a> {
+        segments: Vec<V2SegmentNode<'a>>,
+        pos: usize,
+    }
+
+    impl<'a> V2SegmentCursor<'a> {
+        fn peek(&self) -> Option<&V2SegmentNode<'a>> {
+            self.segments.get(self.pos)
+        }
+
+        fn next(&mut self) -> Option<V2SegmentNode<'a>> {
+            let segment = self.segments.get(self.pos).cloned();
+            self.pos += 1;
+            segment
+        }
+
+        fn is_done(&self) -> bool {
+            self.pos >= self.segments.len()
+        }
+    }
+
+    ///
+    /// Build the group tree of a message using the structure named in its MSH-9.
+    ///
+    pub fn parse_message_structure(message: &V2Message) -> V2Result<V2MessageTree<'_>> {
+        let structure_id = message_structure_id(message)?;
+        parse_message_structure_as(message, structure_id)
+    }
+
+    ///
+    /// Build the group tree of a message using an explicit structure, e.g. `ORU_R01`.
+    ///
+    pub fn parse_message_structure_as<'a>(
+        message: &'a V2Message,
+        structure_id: &str,
+    ) -> V2Result<V2MessageTree<'a>> {
+        let (structure_id, structure) = match V2_MESSAGE_STRUCTURES.get_entry(structure_id) {
+            Some((structure_id, structure)) => (*structure_id, *structure),
+            None => return Err(rumtk_format!("Unknown message structure {}!", structure_id)),
+        };
+
+        let segments = message
+            .segment_order()
+            .into_iter()
+            .filter_map(|(segment_id, sub_segment)| {
+                let segment = message.get(segment_id, sub_segment).ok()?;
+                Some(V2SegmentNode {
+                    name: segment.name(segment_id),
+                    segment_id,
+                    sub_segment,
+                    segment,
+                })
+            })
+            .collect();
+        let mut cursor = V2SegmentCursor { segments, pos: 0 };
+        let mut root = V2GroupNode::new(structure_id);
+        let mut issues = V2ValidationReport::new();
+
+        match_sequence(structure, &mut cursor, &mut root.children, structure_id, &mut issues);
+
+        while let Some(segment) = cursor.next() {
+            issues.push(
+                segment.location(),
+                V2Severity::Error,
+                V2ValidationRule::MessageStructure,
+                rumtk_format!(
+                    "Segment {} is not expected at this position in {}!",
+                    segment.name,
+                    structure_id
+                ),
+            );
+            root.children.push(V2StructureNode::Segment(segment));
+        }
+
+        Ok(V2MessageTree {
+            structure_id,
+            root,
+            issues,
+        })
+    }
+
+    fn match_sequence<'a>(
+        elements: &'static [V2StructureElement],
+        cursor: &mut V2SegmentCursor<'a>,
+        children: &mut Vec<V2StructureNode<'a>>,
+        path: &str,
+        issues: &mut V2ValidationReport,
+    ) {
+        for element in elements.iter() {
+            let cardinality = element.cardinality();
+            let mut count = 0;
+
+            while count == 0 || cardinality.is_repeating() {
+                let start = cursor.pos;
+                match cursor.peek() {
+                    Some(segment) if element.starts_with(segment.name) => (),
+                    _ => break,
+                }
+
+                match element {
+                    Segment(_, _) => {
+                        if let Some(segment) = cursor.next() {
+                            children.push(V2StructureNode::Segment(segment));
+                        }
+                    }
+                    Group(name, _, group_elements) => {
+                        let mut group = V2GroupNode::new(name);
+                        let group_path = rumtk_format!("{}/{}[{}]", path, name, count + 1);
+                        match_sequence(group_elements, cursor, &mut group.children, &group_path, issues);
+                        children.push(V2StructureNode::Group(group));
+                    }
+                    Choice(_, _, choices) => {
+                        let choices: &'static [V2StructureElement] = choices;
+                        let segment_name = cursor.peek().map(|s| s.name).unwrap_or_default();
+                        if let Some(choice) = choices.iter().find(|c| c.starts_with(segment_name)) {
+                            match_sequence(std::slice::from_ref(choice), cursor, children, path, issues);
+                        }
+                    }
+                }
+
+                take_private_segments(cursor, children);
+                count += 1;
+
+                // Guard against structures that could match without consuming anything.
+                if cursor.pos == start {
+                    break;
+                }
+            }
+
+            if count == 0 && cardinality.is_required() {
+                issues.push(
+                    rumtk_format!("{}/{}", path, element.name()),
+                    V2Severity::Error,
+                    V2ValidationRule::SegmentCardinality,
+                    rumtk_format!("Required {} is missing!", element.name()),
+                );
+            }
+        }
+
+        take_private_segments(cursor, children);
+    }
+
+    ///
+    /// Z segments are not part of any structure but are allowed anywhere. Attach them to the group
+    /// we are currently building.
+    ///
+    fn take_private_segments<'a>(cursor: &mut V2SegmentCursor<'a>, children: &mut Vec<V2StructureNode<'a>>) {
+        while !cursor.is_done() {
+            match cursor.peek() {
+                Some(segment) if segment.segment_id == V2_CUSTOM_SEGMENT_ID => {
+                    if let Some(segment) = cursor.next() {
+                        children.push(V2StructureNode::Segment(segment));
+                    }
+                }
+                _ => break,
+            }
+        }
+    }
+}
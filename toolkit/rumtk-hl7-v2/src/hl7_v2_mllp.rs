@@ -189,21 +189,31 @@ pub mod mllp_v2 {
     //!     <SB><NAK><EB><CR>
     //! ```
 
-    use crate::hl7_v2_parser::v2_parser::rumtk_format;
+    use crate::hl7_v2_ack::v2_ack::{
+        ack_code, acknowledge, is_ack_message, V2AckCode, V2AckOutcome, V2AckResponse,
+    };
+    use crate::hl7_v2_charset::v2_charset::{v2_decode_message, v2_encode_message};
+    use crate::hl7_v2_mllp_journal::mllp_v2_journal::{MLLPJournalEntry, SafeMLLPJournal};
+    use crate::hl7_v2_mllp_queue::mllp_v2_queue::SafeMLLPQueue;
+    use crate::hl7_v2_parser::v2_parser::{rumtk_format, V2Message};
+    use crate::hl7_v2_types::v2_types::MSH;
     use rumtk_core::base::{RUMResult, RUMVec};
+    use rumtk_core::dependencies::rand::random;
     pub use rumtk_core::net::tcp::{
         AsyncMutex, AsyncMutexGuard, ClientIDList, RUMClientHandle, RUMNetMessage, RUMServerHandle,
-        ANYHOST, LOCALHOST,
+        RUMTLSConfig, ANYHOST, LOCALHOST,
     };
     use rumtk_core::net::tcp::{
         AsyncRwLock, RUMClient, RUMNetClient, RUMNetClientMessageQueue, RUMNetMessageQueue,
         RUMServer, SafeServer,
     };
-    use rumtk_core::strings::{basic_escape, filter_non_printable_ascii, try_decode, RUMArrayConversions, RUMString, EMPTY_RUMSTRING};
+    use rumtk_core::strings::{basic_escape, filter_non_printable_ascii, RUMArrayConversions, RUMString, EMPTY_RUMSTRING};
+    use rumtk_core::threading::threading_functions::block_on_task;
     use rumtk_core::threading::threading_manager::SafeTaskArgs;
     use rumtk_core::types::RUMOrderedMap;
     use rumtk_core::{rumtk_async_sleep, rumtk_create_task, rumtk_exec_task, rumtk_resolve_task};
     use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
     use tokio::task::JoinHandle;
 
     pub type MLLPClientMessages = RUMVec<RUMString>;
@@ -212,7 +222,8 @@ pub mod mllp_v2 {
     type MLLPClientMessageQueue = RUMNetClientMessageQueue<MLLPMessageResult>;
     type MLLPMessageQueue = RUMNetMessageQueue<MLLPMessageResult>;
 
-    /// Times to attempt sending message again upon initial error or lack of ACK
+    /// Times to attempt sending message again upon initial error or lack of ACK. These defaults can
+    /// be overridden per connection with [MLLPConfig].
     pub const RETRY_SOURCE: u8 = 5;
     /// Timeouts have to be agreed upon by the communicating parties. It is recommended that the
     /// Source use a timeout of between 5 and 30 seconds before giving up on listening for a Commit
@@ -231,6 +242,13 @@ pub mod mllp_v2 {
     pub const TIMEOUT_DESTINATION: u32 = 60000;
     /// Same as [TIMEOUT_STEP_SOURCE], but with a cut off relative to [TIMEOUT_DESTINATION].
     pub const TIMEOUT_STEP_DESTINATION: f32 = 0.001;
+    /// Seconds to wait for the connection to a peer, TLS handshake included, before giving up.
+    pub const TIMEOUT_CONNECT: f32 = 30.0;
+    /// Cap, in seconds, on the delay between send attempts.
+    pub const BACKOFF_MAX: f32 = 60.0;
+    /// Fraction of each delay between send attempts that is randomized so that peers recovering
+    /// from the same outage do not retry in lockstep.
+    pub const BACKOFF_JITTER: f32 = 0.2;
     /// Start Block character (1 byte). ASCII <VT>, i.e., <0x0B>.
     /// This should not be confused with the ASCII characters SOH or STX.
     pub const SB: u8 = 0x0b;
@@ -270,9 +288,8 @@ pub mod mllp_v2 {
 
     ///
     /// Opposite of [mllp_encode]. Strips the incoming message off the \<[SB]\>, \<[EB]\>, and \<[CR]\>.
-    /// The remaining data is considered an ASCII or UTF-8 payload. However, to be on the safe side,
-    /// we use the [try_decode] function from the strings module to attempt auto-detection of encoding
-    /// and forcing the output to be in UTF-8.
+    /// The remaining data is decoded to UTF-8 with [v2_decode_message], i.e. with the character set
+    /// in MSH-18 when the payload has one we know, and with an auto-detected encoding otherwise.
     ///
     /// # Steps
     ///
@@ -311,7 +328,7 @@ pub mod mllp_v2 {
         if contents.len() == 1 {
             Ok(contents.to_vec().to_string()?)
         } else {
-            Ok(try_decode(&contents)?)
+            Ok(v2_decode_message(contents)?.0.into_owned())
         }
     }
 
@@ -351,6 +368,60 @@ pub mod mllp_v2 {
         msg.len() == 1 && msg == NACK_STR
     }
 
+    ///
+    /// Acknowledgment code of an HL7 ACK message. Yields [None] if `msg` is not an ACK.
+    ///
+    pub fn hl7_ack_code(msg: &RUMString) -> Option<V2AckCode> {
+        match V2Message::try_from(msg.as_str()) {
+            Ok(message) if is_ack_message(&message) => ack_code(&message),
+            _ => None,
+        }
+    }
+
+    ///
+    /// What `response` tells [AsyncMLLP::wait_for_send_ack] about our message. [None] if it is
+    /// not an acknowledgement at all.
+    ///
+    fn send_ack_verdict(response: &RUMString, endpoint: &RUMString) -> Option<RUMResult<bool>> {
+        if is_ack(response) {
+            return Some(Ok(true));
+        }
+
+        if is_nack(response) {
+            return Some(Err(rumtk_format!(
+                "Endpoint {} responded with a negative acknowledgement. That means they failed to parse or store our message!",
+                endpoint
+            )));
+        }
+
+        match hl7_ack_code(response) {
+            Some(code) if code.is_accept() => Some(Ok(true)),
+            Some(code) => Some(Err(rumtk_format!(
+                "Endpoint {} responded with acknowledgment code {}. That means they rejected our message!",
+                endpoint,
+                code.as_str()
+            ))),
+            None => None,
+        }
+    }
+
+    ///
+    /// MSH-10 of the outbound `message`. We need it to recognize the acknowledgement the peer
+    /// sends back.
+    ///
+    fn outbound_control_id(message: &str) -> RUMResult<RUMString> {
+        let parsed = match V2Message::try_from(message) {
+            Ok(parsed) => parsed,
+            Err(e) => return Err(rumtk_format!("Unable to parse outbound message because {}", e)),
+        };
+        match MSH::from_message(&parsed, 1)?.message_control_id().as_str() {
+            Some(control_id) if !control_id.is_empty() => Ok(RUMString::from(control_id)),
+            _ => Err(RUMString::from(
+                "Outbound message has no control id in MSH-10 to match the acknowledgement against!",
+            )),
+        }
+    }
+
     pub type ServerRunner = Option<JoinHandle<RUMResult<()>>>;
 
     ///
@@ -363,15 +434,45 @@ pub mod mllp_v2 {
     }
 
     impl LowerLayer {
-        pub async fn init(ip: &str, port: u16, as_server: bool) -> RUMResult<LowerLayer> {
+        ///
+        /// Bind or connect the transport. Pass a [RUMTLSConfig] in `tls` to run the connection
+        /// over TLS (MLLPS). Connecting gives up after [MLLPConfig::connect_timeout].
+        ///
+        pub async fn init(
+            ip: &str,
+            port: u16,
+            as_server: bool,
+            tls: Option<&RUMTLSConfig>,
+            config: &MLLPConfig,
+        ) -> RUMResult<LowerLayer> {
             match as_server {
                 true => {
-                    let server = RUMServer::new(&ip, port).await?;
+                    let server = match tls {
+                        Some(tls) => RUMServer::new_tls(ip, port, tls).await?,
+                        None => RUMServer::new(ip, port).await?,
+                    };
                     let safe_server = SafeServer::new(AsyncRwLock::new(server));
                     Ok(LowerLayer::SERVER(safe_server))
                 }
                 false => {
-                    let client = RUMClient::connect(&ip, port).await?;
+                    let connecting = async {
+                        match tls {
+                            Some(tls) => RUMClient::connect_tls(ip, port, tls).await,
+                            None => RUMClient::connect(ip, port).await,
+                        }
+                    };
+                    let timeout = Duration::from_secs_f32(config.connect_timeout.max(0.0));
+                    let client = match tokio::time::timeout(timeout, connecting).await {
+                        Ok(client) => client?,
+                        Err(_) => {
+                            return Err(rumtk_format!(
+                                "Timeout reached after {}s attempting to connect to {}:{}!",
+                                config.connect_timeout,
+                                ip,
+                                port
+                            ))
+                        }
+                    };
                     let safe_client = RUMNetClient::new(AsyncRwLock::new(client));
                     Ok(LowerLayer::CLIENT(safe_client))
                 }
@@ -405,6 +506,37 @@ pub mod mllp_v2 {
             }
         }
 
+        ///
+        /// Enable TCP keep-alive on the connection to `client_id`. See
+        /// [MLLPConfig::keep_alive].
+        ///
+        pub async fn set_keepalive(&self, client_id: &RUMString, idle: f32) -> RUMResult<()> {
+            match *self {
+                LowerLayer::SERVER(ref server) => server
+                    .read()
+                    .await
+                    .get_client(client_id)
+                    .await?
+                    .read()
+                    .await
+                    .set_keepalive(idle),
+                LowerLayer::CLIENT(ref client) => client.read().await.set_keepalive(idle),
+            }
+        }
+
+        ///
+        /// Close the connection to `client_id`. On the client side, that is our only connection.
+        ///
+        pub async fn close_client(&mut self, client_id: &RUMString) -> RUMResult<()> {
+            match *self {
+                LowerLayer::SERVER(ref server) => server.read().await.close_client(client_id).await,
+                LowerLayer::CLIENT(ref client) => {
+                    client.write().await.close().await;
+                    Ok(())
+                }
+            }
+        }
+
         pub async fn get_client_ids(&self) -> ClientIDList {
             match *self {
                 LowerLayer::SERVER(ref server) => {
@@ -432,6 +564,24 @@ pub mod mllp_v2 {
                 LowerLayer::CLIENT(ref client) => client.read().await.get_address(true).await,
             }
         }
+
+        ///
+        /// See [AsyncMLLP::peer_key].
+        ///
+        pub async fn get_peer_key(&self, client_id: &RUMString) -> RUMString {
+            match *self {
+                LowerLayer::SERVER(_) => match client_id.rsplit_once(':') {
+                    Some((host, _)) => RUMString::from(host),
+                    None => client_id.clone(),
+                },
+                LowerLayer::CLIENT(ref client) => client
+                    .read()
+                    .await
+                    .get_address(false)
+                    .await
+                    .unwrap_or_default(),
+            }
+        }
     }
 
     ///
@@ -455,6 +605,130 @@ pub mod mllp_v2 {
         FILTER_INPUT = 2,
     }
 
+    ///
+    /// How [AsyncMLLP::send_message_with_ack] retries a message. Rejections (AR/CR) are never
+    /// retried since the peer already told us it will not take the message.
+    ///
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct MLLPRetryPolicy {
+        /// Times to send the message before giving up. The message is sent at least once even if
+        /// this is 0.
+        pub attempts: u8,
+        /// Number of [MLLPConfig::poll_interval] steps to wait for the matching acknowledgement
+        /// after each attempt.
+        pub timeout: u32,
+        /// Send the message again if the peer answers with AE or CE.
+        pub retry_on_error: bool,
+        /// Send the message again if no matching acknowledgement arrives within `timeout`.
+        pub retry_on_timeout: bool,
+    }
+
+    impl Default for MLLPRetryPolicy {
+        fn default() -> Self {
+            MLLPRetryPolicy {
+                attempts: RETRY_SOURCE,
+                timeout: TIMEOUT_SOURCE,
+                retry_on_error: true,
+                retry_on_timeout: true,
+            }
+        }
+    }
+
+    ///
+    /// Timeouts, retries and connection upkeep for a single [AsyncMLLP]. All times are in seconds.
+    /// The defaults are the timeouts recommended by the MLLP spec, i.e. the constants at the top of
+    /// this module.
+    ///
+    /// Partners with tight latency SLAs want a short `ack_timeout` and few retries. Slow or batch
+    /// oriented partners want the opposite plus some `backoff`.
+    ///
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct MLLPConfig {
+        /// Time allowed to establish the connection, TLS handshake included. Ignored by listeners.
+        pub connect_timeout: f32,
+        /// Time to wait for the acknowledgement after each send attempt.
+        pub ack_timeout: f32,
+        /// Time to keep looking for a message from a peer that only sent us acknowledgements.
+        pub receive_timeout: f32,
+        /// Time between checks while waiting on acknowledgements or messages.
+        pub poll_interval: f32,
+        /// Times to send a message before giving up. A message is always sent at least once.
+        pub retries: u8,
+        /// Delay before the second attempt. It doubles with every attempt after that. Set to 0 to
+        /// retry right away.
+        pub backoff: f32,
+        /// Cap on the delay between attempts.
+        pub max_backoff: f32,
+        /// Fraction (0 to 1) of each delay that is randomized.
+        pub jitter: f32,
+        /// Enable TCP keep-alive with the first probe sent after this much idle time.
+        pub keep_alive: Option<f32>,
+        /// Close connections that saw no traffic for this long. Checked every time we receive
+        /// messages. Only applies to listeners. A client never drops its only connection.
+        pub idle_close: Option<f32>,
+    }
+
+    impl Default for MLLPConfig {
+        fn default() -> Self {
+            MLLPConfig {
+                connect_timeout: TIMEOUT_CONNECT,
+                ack_timeout: TIMEOUT_SOURCE as f32 * TIMEOUT_STEP_SOURCE,
+                receive_timeout: TIMEOUT_DESTINATION as f32 * TIMEOUT_STEP_DESTINATION,
+                poll_interval: TIMEOUT_STEP_SOURCE,
+                retries: RETRY_SOURCE,
+                backoff: 0.0,
+                max_backoff: BACKOFF_MAX,
+                jitter: BACKOFF_JITTER,
+                keep_alive: None,
+                idle_close: None,
+            }
+        }
+    }
+
+    impl MLLPConfig {
+        ///
+        /// Number of [MLLPConfig::poll_interval] steps that fit in `timeout`. Always at least one.
+        ///
+        pub fn steps(&self, timeout: f32) -> u32 {
+            match self.poll_interval > 0.0 {
+                true => ((timeout / self.poll_interval).round() as u32).max(1),
+                false => 1,
+            }
+        }
+
+        pub fn ack_steps(&self) -> u32 {
+            self.steps(self.ack_timeout)
+        }
+
+        pub fn receive_steps(&self) -> u32 {
+            self.steps(self.receive_timeout)
+        }
+
+        ///
+        /// Seconds to wait before send attempt number `attempt`, counting from 0. The first attempt
+        /// goes out right away.
+        ///
+        pub fn delay(&self, attempt: u8) -> f32 {
+            if attempt == 0 || self.backoff <= 0.0 {
+                return 0.0;
+            }
+            let exponent = (attempt - 1).min(31) as i32;
+            let delay = (self.backoff * 2f32.powi(exponent)).min(self.max_backoff);
+            delay * (1.0 - self.jitter.clamp(0.0, 1.0) * random::<f32>())
+        }
+
+        ///
+        /// The [MLLPRetryPolicy] matching the attempts and acknowledgement timeout configured here.
+        ///
+        pub fn retry_policy(&self) -> MLLPRetryPolicy {
+            MLLPRetryPolicy {
+                attempts: self.retries,
+                timeout: self.ack_steps(),
+                ..MLLPRetryPolicy::default()
+            }
+        }
+    }
+
     pub type SafeLowerLayer = Arc<AsyncMutex<LowerLayer>>;
     pub type GuardedLowerLayer<'a> = AsyncMutexGuard<'a, LowerLayer>;
     type SafeClientIDList = Arc<AsyncRwLock<ClientIDList>>;
@@ -474,6 +748,13 @@ pub mod mllp_v2 {
         transport_layer: SafeLowerLayer,
         filter_policy: MLLP_FILTER_POLICY,
         server: bool,
+        auto_ack: bool,
+        retry_policy: MLLPRetryPolicy,
+        journal: Option<SafeMLLPJournal>,
+        config: MLLPConfig,
+        activity: RUMOrderedMap<RUMString, Instant>,
+        inbox: MLLPMessages,
+        unsettled: RUMOrderedMap<RUMString, RUMVec<u64>>,
     }
 
     impl AsyncMLLP {
@@ -484,8 +765,9 @@ pub mod mllp_v2 {
             port: u16,
             filter_policy: MLLP_FILTER_POLICY,
             server: bool,
+            config: MLLPConfig,
         ) -> RUMResult<AsyncMLLP> {
-            AsyncMLLP::new(ANYHOST.to_string(), port, filter_policy, server).await
+            AsyncMLLP::new(ANYHOST.to_string(), port, filter_policy, server, config).await
         }
 
         ///
@@ -495,21 +777,51 @@ pub mod mllp_v2 {
             port: u16,
             filter_policy: MLLP_FILTER_POLICY,
             server: bool,
+            config: MLLPConfig,
         ) -> RUMResult<AsyncMLLP> {
-            AsyncMLLP::new(LOCALHOST.to_string(), port, filter_policy, server).await
+            AsyncMLLP::new(LOCALHOST.to_string(), port, filter_policy, server, config).await
         }
 
         ///
-        /// Establish an [AsyncMLLP] connection on the specified IP/Host and Port.
+        /// Establish an [AsyncMLLP] connection on the specified IP/Host and Port. Use
+        /// [MLLPConfig::default] unless the peer needs different timeouts or retries.
         ///
         pub async fn new(
             ip: RUMString,
             port: u16,
             filter_policy: MLLP_FILTER_POLICY,
             server: bool,
+            config: MLLPConfig,
         ) -> RUMResult<AsyncMLLP> {
-            let transport_layer =
-                Arc::new(AsyncMutex::new(LowerLayer::init(&ip, port, server).await?));
+            AsyncMLLP::init(ip, port, filter_policy, server, config, None).await
+        }
+
+        ///
+        /// Establish an [AsyncMLLP] connection secured with TLS (MLLPS) on the specified IP/Host
+        /// and Port. See [RUMTLSConfig] for what needs to be provided as a server or as a client.
+        ///
+        pub async fn new_tls(
+            ip: RUMString,
+            port: u16,
+            filter_policy: MLLP_FILTER_POLICY,
+            server: bool,
+            config: MLLPConfig,
+            tls: &RUMTLSConfig,
+        ) -> RUMResult<AsyncMLLP> {
+            AsyncMLLP::init(ip, port, filter_policy, server, config, Some(tls)).await
+        }
+
+        async fn init(
+            ip: RUMString,
+            port: u16,
+            filter_policy: MLLP_FILTER_POLICY,
+            server: bool,
+            config: MLLPConfig,
+            tls: Option<&RUMTLSConfig>,
+        ) -> RUMResult<AsyncMLLP> {
+            let transport_layer = Arc::new(AsyncMutex::new(
+                LowerLayer::init(&ip, port, server, tls, &config).await?,
+            ));
             let connection_info = transport_layer.lock().await.get_address_info().await;
 
             Ok(AsyncMLLP {
@@ -517,6 +829,13 @@ pub mod mllp_v2 {
                 transport_layer,
                 filter_policy,
                 server,
+                auto_ack: false,
+                retry_policy: config.retry_policy(),
+                journal: None,
+                config,
+                activity: RUMOrderedMap::default(),
+                inbox: MLLPMessages::default(),
+                unsettled: RUMOrderedMap::default(),
             })
         }
 
@@ -527,14 +846,16 @@ pub mod mllp_v2 {
         ///
         /// Attempts to send a message and then waits for a response.
         /// This method returns successfully if neither the response is a [NACK](NACK) nor the timeout
-        /// [TIMEOUT_SOURCE](TIMEOUT_SOURCE) is reached.
+        /// [MLLPConfig::ack_timeout] is reached.
         ///
-        /// We reattempt sending the message every [TIMEOUT_STEP_SOURCE](TIMEOUT_STEP_SOURCE) seconds until we receive
-        /// a valid response or reach the maximum timeout defined in [TIMEOUT_SOURCE](TIMEOUT_SOURCE).
+        /// We reattempt sending the message up to [MLLPConfig::retries] times, waiting
+        /// [MLLPConfig::delay] seconds between attempts, until we receive a valid response.
         ///
         pub async fn send_message(&mut self, message: &str, endpoint: &RUMString) -> RUMResult<()> {
             let mut last_error = EMPTY_RUMSTRING.to_owned();
-            for i in 0..RETRY_SOURCE {
+            let attempts = self.config.retries.max(1);
+            for attempt in 0..attempts {
+                self.backoff(attempt).await;
                 self.send(message, endpoint).await?;
                 match self.wait_for_send_ack(endpoint).await {
                     Ok(_) => return Ok(()),
@@ -548,7 +869,7 @@ pub mod mllp_v2 {
                 "Attempted to send message to {} {} times, but they all failed! Last error \
                 message => {}",
                 &endpoint,
-                &RETRY_SOURCE,
+                attempts,
                 last_error
             ))
         }
@@ -559,31 +880,31 @@ pub mod mllp_v2 {
         ///
         /// * If [ACK](ACK) is received, we kill the timeout loop and return true.
         /// * If [NACK](NACK) is received, we kill the timeout loop and return an Error making it clear
-        ///     there was a response but the target had issues processing it.
-        /// * For all other cases, we sleep [TIMEOUT_STEP_SOURCE](TIMEOUT_STEP_SOURCE) seconds and check again for
-        ///     [TIMEOUT_SOURCE](TIMEOUT_SOURCE) times. Upon meeting this overall timeout, error out with message
-        ///     explaining we reached the timeout.
+        ///   there was a response but the target had issues processing it.
+        /// * If an HL7 ACK message is received, AA and CA count as an [ACK](ACK). Any other
+        ///   acknowledgment code counts as a [NACK](NACK).
+        /// * For all other cases, we sleep [MLLPConfig::poll_interval] seconds and check again until
+        ///   [MLLPConfig::ack_timeout] runs out. Upon meeting this overall timeout, error out with
+        ///   message explaining we reached the timeout.
+        ///
+        /// Any other message received in the meantime is kept for the next receive call, see
+        /// [AsyncMLLP::wait_for_app_ack].
         ///
         pub async fn wait_for_send_ack(&mut self, endpoint: &RUMString) -> RUMResult<bool> {
-            for i in 0..TIMEOUT_SOURCE {
-                let responses = self.receive_client_messages(endpoint).await?;
-                match responses.get(0) {
-                    Some(response) => {
-                        let acked = is_ack(response);
-                        if acked {
-                            return Ok(true);
-                        }
-
-                        if is_nack(&response) {
-                            return Err(rumtk_format!(
-                                "Endpoint {} responded with a negative acknowledgement. That means they failed to parse or store our message!",
-                                &endpoint
-                            ));
-                        }
-                    }
-                    None => {}
+            for _ in 0..self.config.ack_steps() {
+                let mut responses = self.read_client_messages(endpoint).await?;
+                let verdict = match responses.first() {
+                    Some(response) => send_ack_verdict(response, endpoint),
+                    None => None,
                 };
-                rumtk_async_sleep!(TIMEOUT_STEP_SOURCE).await;
+                if verdict.is_some() {
+                    responses.remove(0);
+                }
+                self.keep(endpoint, responses);
+                if let Some(verdict) = verdict {
+                    return verdict;
+                }
+                rumtk_async_sleep!(self.config.poll_interval).await;
             }
             Err(rumtk_format!(
                 "Timeout reached attempting to send message to {}!",
@@ -591,9 +912,116 @@ pub mod mllp_v2 {
             ))
         }
 
+        ///
+        /// Sends an HL7 message and waits for the peer's HL7 acknowledgement, i.e. the ACK whose
+        /// MSA-2 matches the MSH-10 of `message`. Unlike [AsyncMLLP::send_message], the decoded
+        /// acknowledgement is handed back as a [V2AckOutcome] so the caller can see the ERR details.
+        ///
+        /// Attempts, timeouts, and which outcomes are worth sending again are governed by the
+        /// [MLLPRetryPolicy] set with [AsyncMLLP::set_retry_policy]. If we run out of attempts after
+        /// the peer answered with an error, that last error outcome is returned. If the peer never
+        /// answered, we error out.
+        ///
+        pub async fn send_message_with_ack(
+            &mut self,
+            message: &str,
+            endpoint: &RUMString,
+        ) -> RUMResult<V2AckOutcome> {
+            let policy = self.retry_policy;
+            self.send_message_with_policy(message, endpoint, policy).await
+        }
+
+        ///
+        /// Same as [AsyncMLLP::send_message_with_ack], but retries according to `policy` instead of
+        /// the instance's retry policy. The message is always sent at least once.
+        ///
+        pub async fn send_message_with_policy(
+            &mut self,
+            message: &str,
+            endpoint: &RUMString,
+            policy: MLLPRetryPolicy,
+        ) -> RUMResult<V2AckOutcome> {
+            let control_id = outbound_control_id(message)?;
+            let mut last_outcome = None;
+
+            for attempt in 0..policy.attempts.max(1) {
+                self.backoff(attempt).await;
+                self.send(message, endpoint).await?;
+                match self.wait_for_app_ack(endpoint, &control_id, policy.timeout).await? {
+                    Some(outcome @ V2AckOutcome::Error(_)) if policy.retry_on_error => {
+                        last_outcome = Some(outcome);
+                    }
+                    Some(outcome) => return Ok(outcome),
+                    None if policy.retry_on_timeout => continue,
+                    None => break,
+                }
+            }
+
+            match last_outcome {
+                Some(outcome) => Ok(outcome),
+                None => Err(rumtk_format!(
+                    "Timeout reached waiting for {} to acknowledge message {}!",
+                    endpoint,
+                    control_id
+                )),
+            }
+        }
+
+        ///
+        /// Waits up to `timeout` [MLLPConfig::poll_interval] steps for the HL7 acknowledgement whose
+        /// MSA-2 matches `control_id`. Yields [None] on timeout.
+        ///
+        /// Anything else received from the endpoint in the meantime, including acknowledgements
+        /// for other messages, is kept and handed out by the next
+        /// [AsyncMLLP::receive_client_messages] or [AsyncMLLP::receive_messages] call.
+        ///
+        pub async fn wait_for_app_ack(
+            &mut self,
+            endpoint: &RUMString,
+            control_id: &str,
+            timeout: u32,
+        ) -> RUMResult<Option<V2AckOutcome>> {
+            let mut unrelated = MLLPClientMessages::default();
+            let mut outcome = None;
+
+            'waiting: for _ in 0..timeout {
+                let mut responses = self.read_client_messages(endpoint).await?.into_iter();
+                while let Some(response) = responses.next() {
+                    let ack = V2Message::try_from(response.as_str())
+                        .ok()
+                        .and_then(|ack| V2AckResponse::from_message(&ack).ok());
+                    match ack {
+                        Some(ack) if ack.acknowledges(control_id) => {
+                            outcome = Some(ack.outcome());
+                            unrelated.extend(responses);
+                            break 'waiting;
+                        }
+                        _ => unrelated.push(response),
+                    }
+                }
+                rumtk_async_sleep!(self.config.poll_interval).await;
+            }
+
+            self.keep(endpoint, unrelated);
+            Ok(outcome)
+        }
+
+        ///
+        /// Set `messages` aside for the next receive call for `endpoint`.
+        ///
+        fn keep(&mut self, endpoint: &RUMString, messages: MLLPClientMessages) {
+            if !messages.is_empty() {
+                self.inbox
+                    .entry(self.inbox_key(endpoint))
+                    .or_default()
+                    .extend(messages);
+            }
+        }
+
         pub async fn send(&mut self, message: &str, endpoint: &RUMString) -> RUMResult<()> {
+            self.touch(endpoint).await;
             let filtered = mllp_filter_message(message, &self.filter_policy)?;
-            let encoded = mllp_encode(&filtered);
+            let encoded = mllp_encode_bytes(&v2_encode_message(&filtered)?);
             self.next_layer()
                 .await
                 .send_message(&encoded, endpoint)
@@ -607,21 +1035,78 @@ pub mod mllp_v2 {
         ///
         /// See [_pop_client_messages](Self::_pop_client_messages)
         ///
+        /// With a journal attached, the messages handed out here are consumed from the journal
+        /// since the caller has no entry ids to do it with. Use
+        /// [AsyncMLLP::receive_journaled_messages] to keep them until they are processed.
+        ///
         pub async fn receive_client_messages(
             &mut self,
             endpoint: &RUMString,
+        ) -> RUMResult<MLLPClientMessages> {
+            let messages = self.collect_client_messages(endpoint).await?;
+            self.settle_journal(endpoint).await?;
+            Ok(messages)
+        }
+
+        async fn collect_client_messages(
+            &mut self,
+            endpoint: &RUMString,
+        ) -> RUMResult<MLLPClientMessages> {
+            let key = self.inbox_key(endpoint);
+            let mut messages = self.inbox.shift_remove(&key).unwrap_or_default();
+            messages.extend(self.read_client_messages(endpoint).await?);
+            Ok(messages)
+        }
+
+        ///
+        /// A client has a single connection, so whatever endpoint the caller named refers to it.
+        ///
+        fn inbox_key(&self, endpoint: &RUMString) -> RUMString {
+            match self.server {
+                true => endpoint.clone(),
+                false => RUMString::default(),
+            }
+        }
+
+        ///
+        /// Reads whatever the endpoint sent since we last checked, skipping the messages kept aside
+        /// by [AsyncMLLP::wait_for_app_ack].
+        ///
+        async fn read_client_messages(
+            &mut self,
+            endpoint: &RUMString,
         ) -> RUMResult<MLLPClientMessages> {
             let mut messages = MLLPClientMessages::default();
 
             loop {
                 match self
-                    .wait_on_message(endpoint, false, TIMEOUT_DESTINATION)
+                    .wait_on_message(endpoint, false, self.config.receive_steps())
                     .await
                 {
                     Ok(message) => {
                         if message.is_empty() {
                             return Ok(messages);
                         }
+                        self.touch(endpoint).await;
+                        match self.commit(&message, endpoint).await {
+                            Ok(Some(id)) => self
+                                .unsettled
+                                .entry(self.inbox_key(endpoint))
+                                .or_default()
+                                .push(id),
+                            Ok(None) => {}
+                            Err(_) => {
+                                // We could not make the message durable, so we must not let the
+                                // peer think we have it. It will resend.
+                                let _ = self.nack(endpoint).await;
+                                continue;
+                            }
+                        }
+                        if self.auto_ack {
+                            // Failing to acknowledge should not cost us the message. The peer will
+                            // resend it.
+                            let _ = self.auto_acknowledge(&message, endpoint).await;
+                        }
                         messages.push(message);
                     }
                     Err(_) => {
@@ -641,12 +1126,24 @@ pub mod mllp_v2 {
         ///
         /// See [_pop_client_messages](Self::_pop_client_messages)
         ///
+        /// Journaled messages are consumed just like in [AsyncMLLP::receive_client_messages].
+        ///
         pub async fn receive_messages(&mut self) -> RUMResult<MLLPMessages> {
+            let messages = self.collect_messages().await?;
+            for endpoint in messages.keys() {
+                self.settle_journal(endpoint).await?;
+            }
+            Ok(messages)
+        }
+
+        async fn collect_messages(&mut self) -> RUMResult<MLLPMessages> {
             let mut messages = MLLPMessages::default();
+            self.close_idle_connections().await?;
             let clients = self.get_client_ids().await;
 
             for endpoint in clients {
-                match self.receive_client_messages(&endpoint).await {
+                self.watch(&endpoint).await;
+                match self.collect_client_messages(&endpoint).await {
                     Ok(client_messages) => messages.insert(endpoint.clone(), client_messages),
                     Err(_) => continue,
                 };
@@ -655,6 +1152,56 @@ pub mod mllp_v2 {
             Ok(messages)
         }
 
+        ///
+        /// Receive from every client and return the journaled messages not handed out yet in this
+        /// run. Right after startup, this includes whatever the previous run left unconsumed.
+        /// Call [AsyncMLLP::consume] once a message has been processed, otherwise it will be
+        /// handed out again the next time the journal is opened.
+        ///
+        /// Messages set aside by [AsyncMLLP::wait_for_app_ack] or [AsyncMLLP::wait_for_send_ack]
+        /// are left to [AsyncMLLP::receive_client_messages] and [AsyncMLLP::receive_messages],
+        /// which consume their journal entries once they hand them out.
+        ///
+        /// Errors out if no journal was attached with [AsyncMLLP::set_journal].
+        ///
+        pub async fn receive_journaled_messages(&mut self) -> RUMResult<Vec<MLLPJournalEntry>> {
+            let journal = match &self.journal {
+                Some(journal) => journal.clone(),
+                None => return Err(RUMString::from("No journal attached to this MLLP instance!")),
+            };
+            let held: RUMVec<u64> = self.unsettled.values().flatten().copied().collect();
+            self.close_idle_connections().await?;
+            for endpoint in self.get_client_ids().await {
+                self.watch(&endpoint).await;
+                // The journal hands these out below, so there is no need to keep them around.
+                let _ = self.read_client_messages(&endpoint).await;
+            }
+
+            let entries: Vec<MLLPJournalEntry> = journal
+                .lock()
+                .await
+                .take_undelivered()
+                .into_iter()
+                .filter(|entry| !held.contains(&entry.id))
+                .collect();
+            // Only what the journal handed out is no longer ours to consume.
+            for ids in self.unsettled.values_mut() {
+                ids.retain(|id| !entries.iter().any(|entry| entry.id == *id));
+            }
+            self.unsettled.retain(|_, ids| !ids.is_empty());
+            Ok(entries)
+        }
+
+        ///
+        /// Mark a journaled message as processed. See [AsyncMLLP::receive_journaled_messages].
+        ///
+        pub async fn consume(&mut self, id: u64) -> RUMResult<()> {
+            match &self.journal {
+                Some(journal) => journal.lock().await.consume(id),
+                None => Err(RUMString::from("No journal attached to this MLLP instance!")),
+            }
+        }
+
         ///
         /// Handles the actual logic for receiving messages.
         ///
@@ -682,7 +1229,7 @@ pub mod mllp_v2 {
                 if !(is_ack(&message) || is_nack(&message)) || message.is_empty() {
                     return Ok(message);
                 }
-                rumtk_async_sleep!(TIMEOUT_STEP_DESTINATION).await
+                rumtk_async_sleep!(self.config.poll_interval).await
             }
             Err(rumtk_format!("Timeout reached while awaiting for message!"))
         }
@@ -712,10 +1259,194 @@ pub mod mllp_v2 {
                 .await
         }
 
+        ///
+        /// Sends an HL7 acknowledgement message to endpoint. See [acknowledge].
+        ///
+        pub async fn send_ack(&mut self, ack: &V2Message, endpoint: &RUMString) -> RUMResult<()> {
+            self.send(&ack.to_string(), endpoint).await
+        }
+
+        ///
+        /// Enable or disable automatic acknowledgements. Off by default. When enabled, every
+        /// message returned by [AsyncMLLP::receive_client_messages] is answered with the ACK
+        /// generated by [acknowledge]. Messages we cannot parse are answered with a [NACK](NACK).
+        /// Inbound ACK messages are never acknowledged.
+        ///
+        pub fn set_auto_ack(&mut self, enabled: bool) {
+            self.auto_ack = enabled;
+        }
+
+        pub fn is_auto_ack(&self) -> bool {
+            self.auto_ack
+        }
+
+        ///
+        /// Replace the [MLLPRetryPolicy] used by [AsyncMLLP::send_message_with_ack].
+        ///
+        pub fn set_retry_policy(&mut self, policy: MLLPRetryPolicy) {
+            self.retry_policy = policy;
+        }
+
+        pub fn get_retry_policy(&self) -> MLLPRetryPolicy {
+            self.retry_policy
+        }
+
+        ///
+        /// Replace the [MLLPConfig]. The attempts and timeout of the current [MLLPRetryPolicy] are
+        /// updated to match. The connect timeout and keep-alive only apply to connections made
+        /// afterwards.
+        ///
+        pub fn set_config(&mut self, config: MLLPConfig) {
+            self.config = config;
+            self.retry_policy = MLLPRetryPolicy {
+                attempts: config.retries,
+                timeout: config.ack_steps(),
+                ..self.retry_policy
+            };
+        }
+
+        pub fn get_config(&self) -> MLLPConfig {
+            self.config
+        }
+
+        ///
+        /// Close every connection that saw no traffic for [MLLPConfig::idle_close] seconds and
+        /// return their ids. Does nothing if `idle_close` is not set or if we are a client, since
+        /// closing our only connection would leave us unable to send.
+        ///
+        pub async fn close_idle_connections(&mut self) -> RUMResult<ClientIDList> {
+            let mut closed = ClientIDList::new();
+            let idle_close = match self.config.idle_close {
+                Some(idle_close) if self.server => Duration::from_secs_f32(idle_close.max(0.0)),
+                _ => return Ok(closed),
+            };
+            let idle = self
+                .activity
+                .iter()
+                .filter(|(_, last_seen)| last_seen.elapsed() >= idle_close)
+                .map(|(endpoint, _)| endpoint.clone())
+                .collect::<ClientIDList>();
+
+            for endpoint in idle {
+                self.activity.shift_remove(&endpoint);
+                // The peer might have hung up on its own already.
+                let _ = self.next_layer().await.close_client(&endpoint).await;
+                closed.push(endpoint);
+            }
+            Ok(closed)
+        }
+
+        ///
+        /// Start tracking a connection the first time we see it.
+        ///
+        async fn watch(&mut self, endpoint: &RUMString) {
+            if self.activity.contains_key(endpoint) {
+                return;
+            }
+            if let Some(idle) = self.config.keep_alive {
+                // Keep-alive is best effort. The connection works without it.
+                let _ = self.next_layer().await.set_keepalive(endpoint, idle).await;
+            }
+            self.activity.insert(endpoint.clone(), Instant::now());
+        }
+
+        async fn touch(&mut self, endpoint: &RUMString) {
+            self.watch(endpoint).await;
+            self.activity.insert(endpoint.clone(), Instant::now());
+        }
+
+        async fn backoff(&self, attempt: u8) {
+            let delay = self.config.delay(attempt);
+            if delay > 0.0 {
+                rumtk_async_sleep!(delay).await;
+            }
+        }
+
+        ///
+        /// Attach (or detach with [None]) the inbound journal. While attached, every message
+        /// received is written to the journal before it is acknowledged, and answered with a
+        /// [NACK](NACK) if that write fails. Inbound ACK messages are not journaled.
+        ///
+        /// Once a journal is attached, prefer [AsyncMLLP::receive_journaled_messages] so you get the
+        /// entry ids needed to [consume](AsyncMLLP::consume) messages. The plain receive calls
+        /// consume whatever they hand out right away.
+        ///
+        pub fn set_journal(&mut self, journal: Option<SafeMLLPJournal>) {
+            self.journal = journal;
+        }
+
+        pub fn get_journal(&self) -> Option<SafeMLLPJournal> {
+            self.journal.clone()
+        }
+
+        ///
+        /// Journal `message` if a journal is attached. Returns the id of the new journal entry.
+        ///
+        async fn commit(
+            &mut self,
+            message: &RUMString,
+            endpoint: &RUMString,
+        ) -> RUMResult<Option<u64>> {
+            let journal = match &self.journal {
+                Some(journal) => journal.clone(),
+                None => return Ok(None),
+            };
+            if let Ok(inbound) = V2Message::try_from(message.as_str()) {
+                if is_ack_message(&inbound) {
+                    return Ok(None);
+                }
+            }
+            let id = journal.lock().await.record(endpoint, message)?;
+            Ok(Some(id))
+        }
+
+        ///
+        /// Consume the journal entries of the messages a plain receive call just handed out for
+        /// `endpoint`. Entries left over from a previous run are not touched.
+        ///
+        async fn settle_journal(&mut self, endpoint: &RUMString) -> RUMResult<()> {
+            let ids = self
+                .unsettled
+                .shift_remove(&self.inbox_key(endpoint))
+                .unwrap_or_default();
+            let journal = match &self.journal {
+                Some(journal) => journal.clone(),
+                None => return Ok(()),
+            };
+            let mut journal = journal.lock().await;
+            for id in ids {
+                journal.consume(id)?;
+            }
+            Ok(())
+        }
+
+        async fn auto_acknowledge(&mut self, message: &RUMString, endpoint: &RUMString) -> RUMResult<()> {
+            let inbound = match V2Message::try_from(message.as_str()) {
+                Ok(inbound) => inbound,
+                Err(_) => return self.nack(endpoint).await,
+            };
+            if is_ack_message(&inbound) {
+                return Ok(());
+            }
+            match acknowledge(&inbound) {
+                Ok(ack) => self.send_ack(&ack, endpoint).await,
+                Err(_) => self.nack(endpoint).await,
+            }
+        }
+
         pub async fn get_client_ids(&self) -> ClientIDList {
             self.transport_layer.lock().await.get_client_ids().await
         }
 
+        ///
+        /// Name for the peer behind `endpoint` that stays the same across reconnects, unlike the
+        /// endpoint itself. Listeners only get the peer's host since the port changes with every
+        /// connection. Clients get the address they connected to.
+        ///
+        pub async fn peer_key(&self, endpoint: &RUMString) -> RUMString {
+            self.transport_layer.lock().await.get_peer_key(endpoint).await
+        }
+
         pub async fn is_server(&self) -> bool {
             self.server
         }
@@ -782,9 +1513,15 @@ pub mod mllp_v2 {
     /// Using the [AsyncMLLP] layer and the [LowerLayer] as the lowest layer, create the concept of a
     /// bidirectional channel such that an application can talk to another.
     ///
+    /// A channel opened with [MLLPChannel::open_queued] hands outbound messages to a durable
+    /// [MLLPQueue](crate::hl7_v2_mllp_queue::mllp_v2_queue::MLLPQueue) instead of sending them
+    /// directly.
+    ///
     pub struct MLLPChannel {
         channel: SafeAsyncMLLP,
         peer: RUMString,
+        queue: Option<SafeMLLPQueue>,
+        queue_key: RUMString,
     }
 
     type MLLPSendArgs = (SafeAsyncMLLP, RUMString, RUMString);
@@ -795,10 +1532,54 @@ pub mod mllp_v2 {
             MLLPChannel {
                 peer: endpoint.clone(),
                 channel: Arc::clone(mllp_instance),
+                queue: None,
+                queue_key: RUMString::default(),
+            }
+        }
+
+        ///
+        /// Same as [MLLPChannel::open], but [MLLPChannel::send_message] goes through `queue`.
+        /// Messages are queued under `queue_key`. Pass the [peer key](AsyncMLLP::peer_key) of
+        /// `endpoint` so a channel opened after the peer reconnects picks up what is still queued
+        /// for it.
+        ///
+        pub fn open_queued(
+            endpoint: &RUMString,
+            mllp_instance: &SafeAsyncMLLP,
+            queue: &SafeMLLPQueue,
+            queue_key: &RUMString,
+        ) -> MLLPChannel {
+            MLLPChannel {
+                peer: endpoint.clone(),
+                channel: Arc::clone(mllp_instance),
+                queue: Some(Arc::clone(queue)),
+                queue_key: queue_key.clone(),
             }
         }
 
+        ///
+        /// Send `message` to the peer.
+        ///
+        /// If the channel has a queue, the message is durably queued first and then everything
+        /// due for this peer in the queue is delivered, waiting on the peer's acknowledgements. In that case,
+        /// success means the message is safely queued. It may still be waiting for delivery
+        /// if the peer is unreachable. Call [MLLPChannel::flush_queue] periodically to keep
+        /// delivering.
+        ///
         pub fn send_message(&mut self, message: &str) -> RUMResult<()> {
+            if let Some(queue) = &self.queue {
+                let queue = Arc::clone(queue);
+                let channel = Arc::clone(&self.channel);
+                let message = message.to_string();
+                let peer = self.peer.clone();
+                let key = self.queue_key.clone();
+                return block_on_task(async move {
+                    queue.lock().await.enqueue(&key, &message)?;
+                    Self::deliver_queued(&queue, &channel, &key, &peer).await?;
+                    Ok(())
+                });
+            }
+
             rumtk_exec_task!(
                 async |args: &SafeTaskArgs<MLLPSendArgs>| -> RUMResult<()> {
                     let owned_args = args.write().await;
@@ -814,6 +1595,48 @@ pub mod mllp_v2 {
             )
         }
 
+        ///
+        /// Deliver whatever is due for this channel's peer in its queue. Returns how many messages
+        /// were delivered. Channels without a queue have nothing to flush.
+        ///
+        pub fn flush_queue(&mut self) -> RUMResult<usize> {
+            let queue = match &self.queue {
+                Some(queue) => Arc::clone(queue),
+                None => return Ok(0),
+            };
+            let channel = Arc::clone(&self.channel);
+            let peer = self.peer.clone();
+            let key = self.queue_key.clone();
+            block_on_task(async move { Self::deliver_queued(&queue, &channel, &key, &peer).await })
+        }
+
+        ///
+        /// Deliver the messages due under `key` to `peer` one at a time. The queue is only locked
+        /// to pick the next message and to record the outcome, never while sending, so other
+        /// channels can keep queueing while this peer is retried.
+        ///
+        async fn deliver_queued(
+            queue: &SafeMLLPQueue,
+            channel: &SafeAsyncMLLP,
+            key: &RUMString,
+            peer: &RUMString,
+        ) -> RUMResult<usize> {
+            let mut delivered = 0;
+            loop {
+                let (id, message) = {
+                    let queue = queue.lock().await;
+                    match queue.due_for(key).and_then(|id| queue.get(id)) {
+                        Some(entry) => (entry.id, entry.message.clone()),
+                        None => return Ok(delivered),
+                    }
+                };
+                let result = channel.lock().await.send_message(&message, peer).await;
+                if queue.lock().await.record_attempt(id, &result)? {
+                    delivered += 1;
+                }
+            }
+        }
+
         pub fn receive_messages(&mut self) -> RUMResult<MLLPMessages> {
             rumtk_exec_task!(
                 async |args: &SafeTaskArgs<SafeAsyncMLLP>| -> RUMResult<MLLPMessages> {
@@ -863,10 +1686,17 @@ pub mod mllp_v2 {
 }
 
 pub mod mllp_v2_helpers {
+    use crate::hl7_v2_ack::v2_ack::V2AckOutcome;
     use crate::hl7_v2_mllp::mllp_v2::{
-        AsyncMLLP, AsyncMutex, MLLPChannel, MLLPChannels, MLLPClientMessages, MLLPMessages,
-        SafeAsyncMLLP, SafeMLLPChannel, MLLP_FILTER_POLICY,
+        AsyncMLLP, AsyncMutex, MLLPChannel, MLLPChannels, MLLPClientMessages, MLLPConfig,
+        MLLPMessages, MLLPRetryPolicy, RUMTLSConfig, SafeAsyncMLLP, SafeMLLPChannel,
+        MLLP_FILTER_POLICY,
+    };
+    use crate::hl7_v2_mllp_journal::mllp_v2_journal::{
+        MLLPJournal, MLLPJournalEntry, SafeMLLPJournal,
     };
+    use crate::hl7_v2_mllp_queue::mllp_v2_queue::{MLLPQueue, MLLPQueuePolicy, SafeMLLPQueue};
+    use crate::hl7_v2_parser::v2_parser::V2Message;
     use rumtk_core::base::RUMResult;
     use rumtk_core::net::tcp::{ClientIDList, ConnectionInfo, NET_SLEEP_TIMEOUT};
     use rumtk_core::net::tcp_helpers::to_ip_port;
@@ -885,12 +1715,46 @@ pub mod mllp_v2_helpers {
         port: u16,
         policy: MLLP_FILTER_POLICY,
         server: bool,
+    ) -> RUMResult<SafeAsyncMLLP> {
+        create_async_mllp_with_config(ip, port, policy, server, MLLPConfig::default(), None)
+    }
+
+    ///
+    /// Same as [create_async_mllp], but with the timeouts and retries given in `config`. Pass a
+    /// [RUMTLSConfig] in `tls` to secure the connection with TLS (MLLPS).
+    ///
+    pub fn create_async_mllp_with_config(
+        ip: &str,
+        port: u16,
+        policy: MLLP_FILTER_POLICY,
+        server: bool,
+        config: MLLPConfig,
+        tls: Option<&RUMTLSConfig>,
     ) -> RUMResult<SafeAsyncMLLP> {
         let ip = RUMString::from(ip);
-        let result = block_on_task(AsyncMLLP::new(ip, port, policy, server))?;
+        let tls = tls.cloned();
+        let result = block_on_task(async move {
+            match tls {
+                Some(tls) => AsyncMLLP::new_tls(ip, port, policy, server, config, &tls).await,
+                None => AsyncMLLP::new(ip, port, policy, server, config).await,
+            }
+        })?;
         Ok(SafeAsyncMLLP::new(AsyncMutex::new(result)))
     }
 
+    ///
+    /// Same as [create_async_mllp], but the listener or connection is secured with TLS (MLLPS).
+    ///
+    pub fn create_async_mllp_tls(
+        ip: &str,
+        port: u16,
+        policy: MLLP_FILTER_POLICY,
+        server: bool,
+        tls: &RUMTLSConfig,
+    ) -> RUMResult<SafeAsyncMLLP> {
+        create_async_mllp_with_config(ip, port, policy, server, MLLPConfig::default(), Some(tls))
+    }
+
     pub fn mllp_get_ip_port(mllp: SafeAsyncMLLP) -> RUMResult<ConnectionInfo> {
         let address_str = block_on_task(async move {
             match mllp.lock().await.get_address_info().await {
@@ -911,6 +1775,10 @@ pub mod mllp_v2_helpers {
         block_on_task(async move { mllp.lock().await.is_server().await })
     }
 
+    pub fn mllp_set_auto_ack(mllp: SafeAsyncMLLP, enabled: bool) {
+        block_on_task(async move { mllp.lock().await.set_auto_ack(enabled) })
+    }
+
     pub fn mllp_receive(mllp: SafeAsyncMLLP) -> RUMResult<MLLPMessages> {
         block_on_task(async move { mllp.lock().await.receive_messages().await })
     }
@@ -926,6 +1794,74 @@ pub mod mllp_v2_helpers {
         block_on_task(async move { mllp.lock().await.send_message(&message, &endpoint).await })
     }
 
+    pub fn mllp_send_with_ack(mllp: SafeAsyncMLLP, ep: &str, msg: &str) -> RUMResult<V2AckOutcome> {
+        let endpoint = RUMString::from(ep);
+        let message = RUMString::from(msg);
+        block_on_task(async move {
+            mllp.lock()
+                .await
+                .send_message_with_ack(&message, &endpoint)
+                .await
+        })
+    }
+
+    pub fn mllp_send_with_policy(
+        mllp: SafeAsyncMLLP,
+        ep: &str,
+        msg: &str,
+        policy: MLLPRetryPolicy,
+    ) -> RUMResult<V2AckOutcome> {
+        let endpoint = RUMString::from(ep);
+        let message = RUMString::from(msg);
+        block_on_task(async move {
+            mllp.lock()
+                .await
+                .send_message_with_policy(&message, &endpoint, policy)
+                .await
+        })
+    }
+
+    pub fn mllp_send_ack(mllp: SafeAsyncMLLP, ep: &str, ack: &V2Message) -> RUMResult<()> {
+        let endpoint = RUMString::from(ep);
+        let ack = ack.clone();
+        block_on_task(async move { mllp.lock().await.send_ack(&ack, &endpoint).await })
+    }
+
+    pub fn mllp_set_retry_policy(mllp: SafeAsyncMLLP, policy: MLLPRetryPolicy) {
+        block_on_task(async move { mllp.lock().await.set_retry_policy(policy) })
+    }
+
+    pub fn mllp_get_retry_policy(mllp: SafeAsyncMLLP) -> MLLPRetryPolicy {
+        block_on_task(async move { mllp.lock().await.get_retry_policy() })
+    }
+
+    pub fn mllp_set_config(mllp: SafeAsyncMLLP, config: MLLPConfig) {
+        block_on_task(async move { mllp.lock().await.set_config(config) })
+    }
+
+    pub fn mllp_close_idle(mllp: SafeAsyncMLLP) -> RUMResult<ClientIDList> {
+        block_on_task(async move { mllp.lock().await.close_idle_connections().await })
+    }
+
+    ///
+    /// Open the inbound journal stored at `path` and attach it to `mllp`. See
+    /// [AsyncMLLP::set_journal].
+    ///
+    pub fn mllp_open_journal(mllp: SafeAsyncMLLP, path: &str) -> RUMResult<SafeMLLPJournal> {
+        let journal = SafeMLLPJournal::new(AsyncMutex::new(MLLPJournal::open(path)?));
+        let attached = journal.clone();
+        block_on_task(async move { mllp.lock().await.set_journal(Some(attached)) });
+        Ok(journal)
+    }
+
+    pub fn mllp_receive_journaled(mllp: SafeAsyncMLLP) -> RUMResult<Vec<MLLPJournalEntry>> {
+        block_on_task(async move { mllp.lock().await.receive_journaled_messages().await })
+    }
+
+    pub fn mllp_consume(mllp: SafeAsyncMLLP, id: u64) -> RUMResult<()> {
+        block_on_task(async move { mllp.lock().await.consume(id).await })
+    }
+
     pub fn mllp_open_channels(mllp: SafeAsyncMLLP) -> RUMResult<MLLPChannels> {
         let endpoints = mllp_get_client_ids(mllp.clone())?;
         let mut channels = MLLPChannels::with_capacity(endpoints.len());
@@ -937,6 +1873,33 @@ pub mod mllp_v2_helpers {
         Ok(channels)
     }
 
+    ///
+    /// Open the durable outbound queue stored at `path` so it can be shared between channels.
+    /// See [MLLPChannel::open_queued].
+    ///
+    pub fn mllp_open_queue(path: &str, policy: MLLPQueuePolicy) -> RUMResult<SafeMLLPQueue> {
+        Ok(SafeMLLPQueue::new(AsyncMutex::new(MLLPQueue::open(path, policy)?)))
+    }
+
+    pub fn mllp_open_queued_channels(
+        mllp: SafeAsyncMLLP,
+        queue: &SafeMLLPQueue,
+    ) -> RUMResult<MLLPChannels> {
+        let endpoints = mllp_get_client_ids(mllp.clone())?;
+        let mut channels = MLLPChannels::with_capacity(endpoints.len());
+        for endpoint in endpoints.iter() {
+            let mllp_instance = mllp.clone();
+            let peer = endpoint.clone();
+            let queue_key =
+                block_on_task(async move { mllp_instance.lock().await.peer_key(&peer).await });
+            let new_channel = SafeMLLPChannel::new(SyncMutex::new(MLLPChannel::open_queued(
+                endpoint, &mllp, queue, &queue_key,
+            )));
+            channels.push(new_channel);
+        }
+        Ok(channels)
+    }
+
     pub fn mllp_iter_channels(
         mllp: SafeAsyncMLLP,
         wait_for_clients: bool,
@@ -959,8 +1922,14 @@ pub mod mllp_v2_helpers {
         local: bool,
     ) -> RUMResult<SafeAsyncMLLP> {
         let handle = match local {
-            true => block_on_task(AsyncMLLP::local(port, policy, server))?,
-            false => block_on_task(AsyncMLLP::new(RUMString::from(ip), port, policy, server))?,
+            true => block_on_task(AsyncMLLP::local(port, policy, server, MLLPConfig::default()))?,
+            false => block_on_task(AsyncMLLP::new(
+                RUMString::from(ip),
+                port,
+                policy,
+                server,
+                MLLPConfig::default(),
+            ))?,
         };
         Ok(SafeAsyncMLLP::new(AsyncMutex::new(handle)))
     }
@@ -1030,6 +1999,10 @@ pub mod mllp_v2_api {
     /// If you want to specify an ip address, then the signature is `ip`, `port`, and
     /// [MLLP_FILTER_POLICY](crate::hl7_v2_mllp::mllp_v2::MLLP_FILTER_POLICY)
     ///
+    /// To connect over TLS (MLLPS), append a reference to a
+    /// [RUMTLSConfig](crate::hl7_v2_mllp::mllp_v2::RUMTLSConfig) to the `ip` signature. The
+    /// config must at least name the CA used to verify the server certificate.
+    ///
     /// See [mllp_connect](crate::hl7_v2_mllp::mllp_v2_helpers::mllp_connect).
     ///
     /// # Example Usage
@@ -1081,6 +2054,10 @@ pub mod mllp_v2_api {
             use $crate::hl7_v2_mllp::mllp_v2_helpers::mllp_client_connect;
             mllp_client_connect($ip, $port, $policy, false, false)
         }};
+        ( $ip:expr, $port:expr, $policy:expr, $tls:expr ) => {{
+            use $crate::hl7_v2_mllp::mllp_v2_helpers::create_async_mllp_tls;
+            create_async_mllp_tls($ip, $port, $policy, false, $tls)
+        }};
     }
 
     ///
@@ -1094,6 +2071,12 @@ pub mod mllp_v2_api {
     /// If you want to specify a port, then the signature is `port`,
     /// [MLLP_FILTER_POLICY](crate::hl7_v2_mllp::mllp_v2::MLLP_FILTER_POLICY), `local`.
     ///
+    /// To listen over TLS (MLLPS), append a reference to a
+    /// [RUMTLSConfig](crate::hl7_v2_mllp::mllp_v2::RUMTLSConfig) to the `ip` signature. The
+    /// config must at least name the server certificate and key. Naming a CA as well requires
+    /// clients to present a certificate (mutual TLS). Passing `true` for `local` binds to
+    /// `localhost` instead of `ip`.
+    ///
     /// See [create_async_mllp](crate::hl7_v2_mllp::mllp_v2_helpers::create_async_mllp).
     ///
     /// ## Example Usage
@@ -1161,6 +2144,14 @@ pub mod mllp_v2_api {
             use $crate::hl7_v2_mllp::mllp_v2_helpers::create_async_mllp;
             create_async_mllp($ip, $port, $policy, true)
         }};
+        ( $ip:expr, $port:expr, $policy:expr, $local:expr, $tls:expr ) => {{
+            use rumtk_core::net::tcp::LOCALHOST;
+            use $crate::hl7_v2_mllp::mllp_v2_helpers::create_async_mllp_tls;
+            match $local {
+                true => create_async_mllp_tls(LOCALHOST, $port, $policy, true, $tls),
+                false => create_async_mllp_tls($ip, $port, $policy, true, $tls),
+            }
+        }};
     }
 
     ///
@@ -1436,4 +2427,28 @@ pub mod mllp_v2_api {
             mllp_send($safe_mllp.clone(), $endpoint, $message)
         }};
     }
+
+    ///
+    /// Convenience macro for sending an HL7 message via an
+    /// [AsyncMLLP](crate::hl7_v2_mllp::mllp_v2::AsyncMLLP) instance and waiting for the HL7
+    /// acknowledgement that matches its MSH-10. Returns the
+    /// [V2AckOutcome](crate::hl7_v2_ack::v2_ack::V2AckOutcome).
+    ///
+    /// Pass an [MLLPRetryPolicy](crate::hl7_v2_mllp::mllp_v2::MLLPRetryPolicy) as the last
+    /// argument to use it for this message only. The instance's retry policy is left untouched.
+    ///
+    /// See [mllp_send_with_ack](crate::hl7_v2_mllp::mllp_v2_helpers::mllp_send_with_ack) and
+    /// [mllp_send_with_policy](crate::hl7_v2_mllp::mllp_v2_helpers::mllp_send_with_policy).
+    ///
+    #[macro_export]
+    macro_rules! rumtk_v2_mllp_send_with_ack {
+        ( $safe_mllp:expr, $endpoint:expr, $message:expr ) => {{
+            use $crate::hl7_v2_mllp::mllp_v2_helpers::mllp_send_with_ack;
+            mllp_send_with_ack($safe_mllp.clone(), $endpoint, $message)
+        }};
+        ( $safe_mllp:expr, $endpoint:expr, $message:expr, $policy:expr ) => {{
+            use $crate::hl7_v2_mllp::mllp_v2_helpers::mllp_send_with_policy;
+            mllp_send_with_policy($safe_mllp.clone(), $endpoint, $message, $policy)
+        }};
+    }
 }
@@ -0,0 +1,247 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2026  Luis M. Santos, M.D. <lsantos@medicalmasses.com>
+ * Copyright (C) 2026  MedicalMasses L.L.C. <contact@medicalmasses.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+///
+/// Durable journal for inbound MLLP messages.
+///
+/// An [AsyncMLLP](crate::hl7_v2_mllp::mllp_v2::AsyncMLLP) with a journal attached writes every
+/// message it receives to the journal, and flushes it to disk, before acknowledging it. If the
+/// write fails, the sender gets a NACK and will send the message again. Once acknowledged, the
+/// message stays in the journal until the consumer marks it consumed. Whatever is left
+/// unconsumed when the process stops is handed out again the next time the journal is opened.
+///
+/// ## Example
+///
+/// ```
+/// use rumtk_hl7_v2::hl7_v2_mllp_journal::mllp_v2_journal::MLLPJournal;
+///
+/// let path = std::env::temp_dir().join("rumtk_mllp_journal_example.log");
+/// let _ = std::fs::remove_file(&path);
+/// let mut journal = MLLPJournal::open(path.to_str().unwrap()).unwrap();
+/// let first = journal.record("127.0.0.1:40000", "MSG0001").unwrap();
+/// let second = journal.record("127.0.0.1:40000", "MSG0002").unwrap();
+/// assert_eq!(journal.take_undelivered().len(), 2, "Expected both messages!");
+/// journal.consume(first).unwrap();
+///
+/// // The consumer crashed before it got to the second message.
+/// let mut journal = MLLPJournal::open(path.to_str().unwrap()).unwrap();
+/// let recovered = journal.take_undelivered();
+/// assert_eq!(recovered.len(), 1, "Only the unconsumed message should come back!");
+/// assert_eq!(recovered[0].id, second, "Wrong message recovered!");
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+///
+pub mod mllp_v2_journal {
+    use crate::hl7_v2_mllp::mllp_v2::AsyncMutex;
+    use crate::hl7_v2_mllp_queue::mllp_v2_queue::{now, MLLPStoreLog};
+    use rumtk_core::base::RUMResult;
+    use rumtk_core::serde::json::{RUMDeJson, RUMSerJson};
+    use rumtk_core::strings::{rumtk_format, RUMString};
+    use rumtk_core::types::RUMOrderedMap;
+    use std::sync::Arc;
+
+    /**************************** Types *****************************************/
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct MLLPJournalEntry {
+        pub id: u64,
+        /// Client the message came from.
+        pub endpoint: RUMString,
+        pub message: RUMString,
+        /// Milliseconds since the Unix epoch.
+        pub received_at: u64,
+    }
+
+    #[derive(RUMSerJson, RUMDeJson, Debug, Clone, PartialEq)]
+    enum MLLPJournalRecord {
+        Received {
+            id: u64,
+            endpoint: RUMString,
+            message: RUMString,
+            received_at: u64,
+        },
+        Consumed {
+            id: u64,
+        },
+    }
+
+    ///
+    /// The inbound journal. See the [module](self) documentation.
+    ///
+    /// Besides what is on disk, the journal remembers which entries it already handed out in this
+    /// run so [MLLPJournal::take_undelivered] does not return the same message twice.
+    ///
+    pub struct MLLPJournal {
+        log: MLLPStoreLog<MLLPJournalRecord>,
+        entries: RUMOrderedMap<u64, MLLPJournalEntry>,
+        delivered: RUMOrderedMap<u64, bool>,
+        next_id: u64,
+    }
+
+    pub type SafeMLLPJournal = Arc<AsyncMutex<MLLPJournal>>;
+
+    impl MLLPJournal {
+        ///
+        /// Open the journal stored at `path`, creating it if needed. Entries a previous run did
+        /// not consume are kept and will be handed out by [MLLPJournal::take_undelivered]. The
+        /// log is compacted on open.
+        ///
+        pub fn open(path: &str) -> RUMResult<MLLPJournal> {
+            let (log, records) = MLLPStoreLog::open(path)?;
+            let mut journal = MLLPJournal {
+                log,
+                entries: RUMOrderedMap::default(),
+                delivered: RUMOrderedMap::default(),
+                next_id: 1,
+            };
+            for record in records {
+                journal.apply(record);
+            }
+            journal.compact()?;
+            Ok(journal)
+        }
+
+        pub fn path(&self) -> &str {
+            self.log.path()
+        }
+
+        ///
+        /// Durably write a message received from `endpoint`. Only acknowledge the message once
+        /// this returns successfully.
+        ///
+        pub fn record(&mut self, endpoint: &str, message: &str) -> RUMResult<u64> {
+            let id = self.next_id;
+            self.write(MLLPJournalRecord::Received {
+                id,
+                endpoint: RUMString::from(endpoint),
+                message: RUMString::from(message),
+                received_at: now(),
+            })?;
+            Ok(id)
+        }
+
+        ///
+        /// Mark a message as processed. It will not be handed out again.
+        ///
+        pub fn consume(&mut self, id: u64) -> RUMResult<()> {
+            if !self.entries.contains_key(&id) {
+                return Err(rumtk_format!("Journal entry {} not found!", id));
+            }
+            self.write(MLLPJournalRecord::Consumed { id })?;
+            if self.entries.is_empty() {
+                self.compact()?;
+            }
+            Ok(())
+        }
+
+        pub fn get(&self, id: u64) -> Option<&MLLPJournalEntry> {
+            self.entries.get(&id)
+        }
+
+        ///
+        /// Every entry not consumed yet, in the order received.
+        ///
+        pub fn unconsumed(&self) -> Vec<&MLLPJournalEntry> {
+            self.entries.values().collect()
+        }
+
+        ///
+        /// Unconsumed entries not handed out yet in this run, in the order received. Right after
+        /// opening the journal, this includes everything the previous run left unconsumed.
+        ///
+        pub fn take_undelivered(&mut self) -> Vec<MLLPJournalEntry> {
+            let undelivered = self
+                .entries
+                .values()
+                .filter(|entry| !self.delivered.contains_key(&entry.id))
+                .cloned()
+                .collect::<Vec<MLLPJournalEntry>>();
+            for entry in undelivered.iter() {
+                self.delivered.insert(entry.id, true);
+            }
+            undelivered
+        }
+
+        ///
+        /// Forget what was handed out in this run so every unconsumed entry is delivered again.
+        /// Use this if the consumer was restarted without restarting the process.
+        ///
+        pub fn redeliver(&mut self) {
+            self.delivered.clear();
+        }
+
+        pub fn len(&self) -> usize {
+            self.entries.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.entries.is_empty()
+        }
+
+        ///
+        /// Rewrite the log so it only holds the unconsumed entries.
+        ///
+        pub fn compact(&mut self) -> RUMResult<()> {
+            let records = self
+                .entries
+                .values()
+                .map(|entry| MLLPJournalRecord::Received {
+                    id: entry.id,
+                    endpoint: entry.endpoint.clone(),
+                    message: entry.message.clone(),
+                    received_at: entry.received_at,
+                })
+                .collect::<Vec<MLLPJournalRecord>>();
+            self.log.rewrite(&records)
+        }
+
+        fn write(&mut self, record: MLLPJournalRecord) -> RUMResult<()> {
+            self.log.append(&record)?;
+            self.apply(record);
+            Ok(())
+        }
+
+        fn apply(&mut self, record: MLLPJournalRecord) {
+            match record {
+                MLLPJournalRecord::Received {
+                    id,
+                    endpoint,
+                    message,
+                    received_at,
+                } => {
+                    self.next_id = self.next_id.max(id + 1);
+                    self.entries.insert(
+                        id,
+                        MLLPJournalEntry {
+                            id,
+                            endpoint,
+                            message,
+                            received_at,
+                        },
+                    );
+                }
+                MLLPJournalRecord::Consumed { id } => {
+                    self.entries.shift_remove(&id);
+                    self.delivered.shift_remove(&id);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,672 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2026  Luis M. Santos, M.D. <lsantos@medicalmasses.com>
+ * Copyright (C) 2026  MedicalMasses L.L.C. <contact@medicalmasses.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+///
+/// Durable store-and-forward queue for outbound MLLP messages.
+///
+/// Every change to the queue is appended to a JSON lines log and flushed to disk before we return,
+/// so queued messages survive process restarts. Delivery is at-least-once. A message is only
+/// removed once the peer acknowledges it, which means a crash between the peer's ACK and our log
+/// write results in a duplicate rather than a loss.
+///
+/// Messages are delivered in order per endpoint. If the head of an endpoint's queue fails, the
+/// messages behind it wait while the head backs off exponentially. Once the head runs out of
+/// attempts, it is moved to the dead letters so the rest of the queue can move on. Dead letters
+/// stay on disk until they are replayed or purged.
+///
+/// ## Example
+///
+/// ```
+/// use rumtk_hl7_v2::hl7_v2_mllp_queue::mllp_v2_queue::{MLLPQueue, MLLPQueuePolicy};
+///
+/// let path = std::env::temp_dir().join("rumtk_mllp_queue_example.log");
+/// let _ = std::fs::remove_file(&path);
+/// let mut queue = MLLPQueue::open(path.to_str().unwrap(), MLLPQueuePolicy::default()).unwrap();
+/// let id = queue.enqueue("127.0.0.1:2575", "MSH|^~\\&|LAB|FAC|||20250101120000||ORU^R01|MSG0001|P|2.5.1\r").unwrap();
+/// queue.mark_failed(id, "Connection refused").unwrap();
+///
+/// let queue = MLLPQueue::open(path.to_str().unwrap(), MLLPQueuePolicy::default()).unwrap();
+/// assert_eq!(queue.get(id).unwrap().attempts, 1, "The failed attempt should survive a restart!");
+/// assert!(queue.due().is_empty(), "The message should be backing off!");
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+///
+pub mod mllp_v2_queue {
+    use crate::hl7_v2_mllp::mllp_v2::{AsyncMLLP, AsyncMutex};
+    use rumtk_core::base::RUMResult;
+    use rumtk_core::serde::json::{from_json, to_json, RUMDeJson, RUMSerJson};
+    use rumtk_core::strings::{rumtk_format, RUMString};
+    use rumtk_core::types::RUMOrderedMap;
+    use std::fs::{rename, File, OpenOptions};
+    use std::io::{ErrorKind, Read, Write};
+    use std::marker::PhantomData;
+    use std::sync::Arc;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    /// Attempts before a message is moved to the dead letters.
+    pub const QUEUE_MAX_ATTEMPTS: u32 = 10;
+    /// Seconds to wait before the first retry. Doubles with every failed attempt.
+    pub const QUEUE_BACKOFF: f32 = 1.0;
+    /// Upper bound in seconds for the wait between retries.
+    pub const QUEUE_MAX_BACKOFF: f32 = 300.0;
+    /// Records the log may hold before delivering a message triggers a compaction.
+    pub const QUEUE_COMPACT_RECORDS: usize = 1024;
+    /// Past [QUEUE_COMPACT_RECORDS], compact once the log holds this many records per queued
+    /// message.
+    pub const QUEUE_COMPACT_RATIO: usize = 4;
+
+    /**************************** Types *****************************************/
+
+    ///
+    /// Append-only log of JSON records, one per line. Each append is flushed to disk before
+    /// returning.
+    ///
+    /// A crash in the middle of an append leaves a partial last line, which might not even be
+    /// valid UTF-8. That line is dropped the next time the log is opened. A corrupt line anywhere
+    /// else is an error since we cannot tell what was lost.
+    ///
+    pub struct MLLPStoreLog<T> {
+        path: RUMString,
+        file: File,
+        records: usize,
+        record: PhantomData<T>,
+    }
+
+    impl<T> MLLPStoreLog<T>
+    where
+        T: RUMSerJson + for<'a> RUMDeJson<'a>,
+    {
+        ///
+        /// Open or create the log at `path`. Returns the log along with every record in it.
+        ///
+        pub fn open(path: &str) -> RUMResult<(MLLPStoreLog<T>, Vec<T>)> {
+            let (records, torn) = read_records::<T>(path)?;
+            let mut log = MLLPStoreLog {
+                path: RUMString::from(path),
+                file: open_append(path)?,
+                records: records.len(),
+                record: PhantomData,
+            };
+            if torn {
+                log.rewrite(&records)?;
+            }
+            Ok((log, records))
+        }
+
+        pub fn path(&self) -> &str {
+            &self.path
+        }
+
+        ///
+        /// Number of records currently in the log.
+        ///
+        pub fn record_count(&self) -> usize {
+            self.records
+        }
+
+        pub fn append(&mut self, record: &T) -> RUMResult<()> {
+            let mut line = to_json(record)?;
+            line.push('\n');
+            match self
+                .file
+                .write_all(line.as_bytes())
+                .and_then(|_| self.file.sync_data())
+            {
+                Ok(_) => {
+                    self.records += 1;
+                    Ok(())
+                }
+                Err(e) => Err(rumtk_format!("Unable to write to {} because {}", self.path, e)),
+            }
+        }
+
+        ///
+        /// Replace the whole log with `records`. The new log is written next to the old one and
+        /// renamed over it, so a crash leaves either the old or the new log in place.
+        ///
+        pub fn rewrite(&mut self, records: &[T]) -> RUMResult<()> {
+            let staging = rumtk_format!("{}.tmp", self.path);
+            let mut contents = RUMString::new();
+            for record in records {
+                contents.push_str(&to_json(record)?);
+                contents.push('\n');
+            }
+
+            let written = File::create(&staging).and_then(|mut file| {
+                file.write_all(contents.as_bytes())?;
+                file.sync_all()
+            });
+            if let Err(e) = written.and_then(|_| rename(&staging, &self.path)) {
+                return Err(rumtk_format!("Unable to rewrite {} because {}", self.path, e));
+            }
+
+            self.file = open_append(&self.path)?;
+            self.records = records.len();
+            Ok(())
+        }
+    }
+
+    ///
+    /// Where a queued message stands. Delivered messages are removed from the queue altogether.
+    ///
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum MLLPQueueState {
+        Pending,
+        DeadLetter,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct MLLPQueueEntry {
+        pub id: u64,
+        pub endpoint: RUMString,
+        pub message: RUMString,
+        /// Milliseconds since the Unix epoch.
+        pub enqueued_at: u64,
+        pub attempts: u32,
+        pub last_error: RUMString,
+        /// Milliseconds since the Unix epoch. The message is not sent again before this time.
+        pub next_attempt: u64,
+        pub state: MLLPQueueState,
+    }
+
+    ///
+    /// Retry schedule of the queue. After the n-th failed attempt, a message waits
+    /// `backoff * 2^(n-1)` seconds capped at `max_backoff`. It is moved to the dead letters once
+    /// it fails `max_attempts` times.
+    ///
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct MLLPQueuePolicy {
+        pub max_attempts: u32,
+        pub backoff: f32,
+        pub max_backoff: f32,
+    }
+
+    impl Default for MLLPQueuePolicy {
+        fn default() -> Self {
+            MLLPQueuePolicy {
+                max_attempts: QUEUE_MAX_ATTEMPTS,
+                backoff: QUEUE_BACKOFF,
+                max_backoff: QUEUE_MAX_BACKOFF,
+            }
+        }
+    }
+
+    impl MLLPQueuePolicy {
+        ///
+        /// Milliseconds to wait after the `attempts`-th failed attempt.
+        ///
+        pub fn delay(&self, attempts: u32) -> u64 {
+            let exponent = attempts.saturating_sub(1).min(31) as i32;
+            let seconds = (self.backoff * 2f32.powi(exponent)).min(self.max_backoff);
+            (seconds.max(0.0) * 1000.0) as u64
+        }
+    }
+
+    #[derive(RUMSerJson, RUMDeJson, Debug, Clone, PartialEq)]
+    enum MLLPQueueRecord {
+        Enqueued {
+            id: u64,
+            endpoint: RUMString,
+            message: RUMString,
+            enqueued_at: u64,
+        },
+        Failed {
+            id: u64,
+            attempts: u32,
+            error: RUMString,
+            next_attempt: u64,
+        },
+        DeadLettered {
+            id: u64,
+        },
+        Replayed {
+            id: u64,
+        },
+        Delivered {
+            id: u64,
+        },
+        Purged {
+            id: u64,
+        },
+    }
+
+    ///
+    /// The outbound queue. See the [module](self) documentation.
+    ///
+    pub struct MLLPQueue {
+        log: MLLPStoreLog<MLLPQueueRecord>,
+        entries: RUMOrderedMap<u64, MLLPQueueEntry>,
+        next_id: u64,
+        policy: MLLPQueuePolicy,
+    }
+
+    pub type SafeMLLPQueue = Arc<AsyncMutex<MLLPQueue>>;
+
+    impl MLLPQueue {
+        ///
+        /// Open the queue stored at `path`, creating it if needed. Messages queued by a previous
+        /// run are picked up where they left off. The log is compacted on open.
+        ///
+        pub fn open(path: &str, policy: MLLPQueuePolicy) -> RUMResult<MLLPQueue> {
+            let (log, records) = MLLPStoreLog::open(path)?;
+            let mut queue = MLLPQueue {
+                log,
+                entries: RUMOrderedMap::default(),
+                next_id: 1,
+                policy,
+            };
+            for record in records {
+                queue.apply(record);
+            }
+            queue.compact()?;
+            Ok(queue)
+        }
+
+        pub fn path(&self) -> &str {
+            self.log.path()
+        }
+
+        pub fn policy(&self) -> MLLPQueuePolicy {
+            self.policy
+        }
+
+        pub fn set_policy(&mut self, policy: MLLPQueuePolicy) {
+            self.policy = policy;
+        }
+
+        ///
+        /// Durably queue `message` for `endpoint`. Returns the id of the queued message.
+        ///
+        pub fn enqueue(&mut self, endpoint: &str, message: &str) -> RUMResult<u64> {
+            let id = self.next_id;
+            self.record(MLLPQueueRecord::Enqueued {
+                id,
+                endpoint: RUMString::from(endpoint),
+                message: RUMString::from(message),
+                enqueued_at: now(),
+            })?;
+            Ok(id)
+        }
+
+        pub fn get(&self, id: u64) -> Option<&MLLPQueueEntry> {
+            self.entries.get(&id)
+        }
+
+        ///
+        /// Messages waiting to be delivered in the order they were queued.
+        ///
+        pub fn pending(&self) -> Vec<&MLLPQueueEntry> {
+            self.in_state(MLLPQueueState::Pending)
+        }
+
+        pub fn pending_for(&self, endpoint: &str) -> Vec<&MLLPQueueEntry> {
+            self.in_state(MLLPQueueState::Pending)
+                .into_iter()
+                .filter(|entry| entry.endpoint == endpoint)
+                .collect()
+        }
+
+        pub fn dead_letters(&self) -> Vec<&MLLPQueueEntry> {
+            self.in_state(MLLPQueueState::DeadLetter)
+        }
+
+        ///
+        /// Number of messages waiting to be delivered. Dead letters are not counted.
+        ///
+        pub fn len(&self) -> usize {
+            self.pending().len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        ///
+        /// Ids of the messages that can be sent right now, at most one per endpoint. Only the
+        /// oldest pending message of each endpoint is eligible so that order is preserved.
+        ///
+        pub fn due(&self) -> Vec<u64> {
+            let now = now();
+            let mut heads = RUMOrderedMap::<&str, &MLLPQueueEntry>::default();
+            for entry in self.pending() {
+                heads.entry(entry.endpoint.as_str()).or_insert(entry);
+            }
+            heads
+                .values()
+                .filter(|entry| entry.next_attempt <= now)
+                .map(|entry| entry.id)
+                .collect()
+        }
+
+        ///
+        /// Id of the message for `endpoint` that can be sent right now, if any. See
+        /// [MLLPQueue::due].
+        ///
+        pub fn due_for(&self, endpoint: &str) -> Option<u64> {
+            self.pending_for(endpoint)
+                .first()
+                .filter(|entry| entry.next_attempt <= now())
+                .map(|entry| entry.id)
+        }
+
+        pub fn mark_delivered(&mut self, id: u64) -> RUMResult<()> {
+            self.pending_entry(id)?;
+            self.record(MLLPQueueRecord::Delivered { id })?;
+            self.compact_if_bloated()
+        }
+
+        ///
+        /// Record a failed delivery attempt. The message backs off according to the policy or is
+        /// moved to the dead letters if it ran out of attempts.
+        ///
+        pub fn mark_failed(&mut self, id: u64, error: &str) -> RUMResult<()> {
+            let attempts = self.pending_entry(id)?.attempts + 1;
+            self.record(MLLPQueueRecord::Failed {
+                id,
+                attempts,
+                error: RUMString::from(error),
+                next_attempt: now() + self.policy.delay(attempts),
+            })?;
+            if attempts >= self.policy.max_attempts {
+                self.record(MLLPQueueRecord::DeadLettered { id })?;
+            }
+            Ok(())
+        }
+
+        ///
+        /// Move a dead letter back to the queue with a fresh set of attempts. It is delivered in
+        /// its original position relative to the other messages for the same endpoint.
+        ///
+        pub fn replay(&mut self, id: u64) -> RUMResult<()> {
+            match self.entries.get(&id) {
+                Some(entry) if entry.state == MLLPQueueState::DeadLetter => {
+                    self.record(MLLPQueueRecord::Replayed { id })
+                }
+                Some(_) => Err(rumtk_format!("Queued message {} is not a dead letter!", id)),
+                None => Err(rumtk_format!("Queued message {} not found!", id)),
+            }
+        }
+
+        ///
+        /// Replay every dead letter. Returns how many were replayed.
+        ///
+        pub fn replay_dead_letters(&mut self) -> RUMResult<usize> {
+            let ids = self
+                .dead_letters()
+                .iter()
+                .map(|entry| entry.id)
+                .collect::<Vec<u64>>();
+            for id in ids.iter() {
+                self.replay(*id)?;
+            }
+            Ok(ids.len())
+        }
+
+        ///
+        /// Drop a message for good, whatever its state.
+        ///
+        pub fn purge(&mut self, id: u64) -> RUMResult<()> {
+            match self.entries.contains_key(&id) {
+                true => {
+                    self.record(MLLPQueueRecord::Purged { id })?;
+                    self.compact_if_bloated()
+                }
+                false => Err(rumtk_format!("Queued message {} not found!", id)),
+            }
+        }
+
+        ///
+        /// Rewrite the log so it only describes the messages still in the queue.
+        ///
+        pub fn compact(&mut self) -> RUMResult<()> {
+            let mut records = Vec::with_capacity(self.entries.len() * 2);
+            for entry in self.entries.values() {
+                records.push(MLLPQueueRecord::Enqueued {
+                    id: entry.id,
+                    endpoint: entry.endpoint.clone(),
+                    message: entry.message.clone(),
+                    enqueued_at: entry.enqueued_at,
+                });
+                if entry.attempts > 0 {
+                    records.push(MLLPQueueRecord::Failed {
+                        id: entry.id,
+                        attempts: entry.attempts,
+                        error: entry.last_error.clone(),
+                        next_attempt: entry.next_attempt,
+                    });
+                }
+                if entry.state == MLLPQueueState::DeadLetter {
+                    records.push(MLLPQueueRecord::DeadLettered { id: entry.id });
+                }
+            }
+            self.log.rewrite(&records)
+        }
+
+        ///
+        /// Send every message that is due through `mllp`, waiting for the peer to acknowledge
+        /// each one. Keeps going down an endpoint's queue until a message fails or is not due yet.
+        /// Returns how many messages were delivered.
+        ///
+        /// Delivery failures are recorded in the queue rather than returned. Errors only come
+        /// from failing to update the log.
+        ///
+        pub async fn deliver(&mut self, mllp: &mut AsyncMLLP) -> RUMResult<usize> {
+            let mut delivered = 0;
+            let mut due = self.due();
+            while !due.is_empty() {
+                for id in due {
+                    let endpoint = match self.entries.get(&id) {
+                        Some(entry) => entry.endpoint.clone(),
+                        None => continue,
+                    };
+                    if self.deliver_one(mllp, id, &endpoint).await? {
+                        delivered += 1;
+                    }
+                }
+                due = self.due();
+            }
+            Ok(delivered)
+        }
+
+        ///
+        /// Send message `id` to `peer` and record the outcome. Returns whether it was delivered.
+        ///
+        async fn deliver_one(
+            &mut self,
+            mllp: &mut AsyncMLLP,
+            id: u64,
+            peer: &RUMString,
+        ) -> RUMResult<bool> {
+            let message = match self.entries.get(&id) {
+                Some(entry) => entry.message.clone(),
+                None => return Ok(false),
+            };
+            let result = mllp.send_message(&message, peer).await;
+            self.record_attempt(id, &result)
+        }
+
+        ///
+        /// Record the outcome of sending message `id` ourselves, marking it delivered or failed.
+        /// Returns whether it was delivered. Messages that left the queue or became dead letters
+        /// in the meantime (e.g. another channel delivered them first) are left alone.
+        ///
+        /// This lets callers sharing the queue send without holding on to it. See
+        /// [MLLPChannel::send_message](crate::hl7_v2_mllp::mllp_v2::MLLPChannel::send_message).
+        ///
+        pub fn record_attempt(&mut self, id: u64, result: &RUMResult<()>) -> RUMResult<bool> {
+            if self.pending_entry(id).is_err() {
+                return Ok(false);
+            }
+            match result {
+                Ok(_) => {
+                    self.mark_delivered(id)?;
+                    Ok(true)
+                }
+                Err(e) => {
+                    self.mark_failed(id, e)?;
+                    Ok(false)
+                }
+            }
+        }
+
+        ///
+        /// Compact once the queue drained or the log outgrew what is left in the queue. See
+        /// [QUEUE_COMPACT_RECORDS] and [QUEUE_COMPACT_RATIO].
+        ///
+        fn compact_if_bloated(&mut self) -> RUMResult<()> {
+            let records = self.log.record_count();
+            let bloated = records >= QUEUE_COMPACT_RECORDS
+                && records >= self.entries.len() * QUEUE_COMPACT_RATIO;
+            match self.entries.is_empty() || bloated {
+                true => self.compact(),
+                false => Ok(()),
+            }
+        }
+
+        fn in_state(&self, state: MLLPQueueState) -> Vec<&MLLPQueueEntry> {
+            self.entries
+                .values()
+                .filter(|entry| entry.state == state)
+                .collect()
+        }
+
+        fn pending_entry(&self, id: u64) -> RUMResult<&MLLPQueueEntry> {
+            match self.entries.get(&id) {
+                Some(entry) if entry.state == MLLPQueueState::Pending => Ok(entry),
+                Some(_) => Err(rumtk_format!("Queued message {} is a dead letter!", id)),
+                None => Err(rumtk_format!("Queued message {} not found!", id)),
+            }
+        }
+
+        fn record(&mut self, record: MLLPQueueRecord) -> RUMResult<()> {
+            self.log.append(&record)?;
+            self.apply(record);
+            Ok(())
+        }
+
+        fn apply(&mut self, record: MLLPQueueRecord) {
+            match record {
+                MLLPQueueRecord::Enqueued {
+                    id,
+                    endpoint,
+                    message,
+                    enqueued_at,
+                } => {
+                    self.next_id = self.next_id.max(id + 1);
+                    self.entries.insert(
+                        id,
+                        MLLPQueueEntry {
+                            id,
+                            endpoint,
+                            message,
+                            enqueued_at,
+                            attempts: 0,
+                            last_error: RUMString::new(),
+                            next_attempt: 0,
+                            state: MLLPQueueState::Pending,
+                        },
+                    );
+                }
+                MLLPQueueRecord::Failed {
+                    id,
+                    attempts,
+                    error,
+                    next_attempt,
+                } => {
+                    if let Some(entry) = self.entries.get_mut(&id) {
+                        entry.attempts = attempts;
+                        entry.last_error = error;
+                        entry.next_attempt = next_attempt;
+                    }
+                }
+                MLLPQueueRecord::DeadLettered { id } => {
+                    if let Some(entry) = self.entries.get_mut(&id) {
+                        entry.state = MLLPQueueState::DeadLetter;
+                    }
+                }
+                MLLPQueueRecord::Replayed { id } => {
+                    if let Some(entry) = self.entries.get_mut(&id) {
+                        entry.state = MLLPQueueState::Pending;
+                        entry.attempts = 0;
+                        entry.next_attempt = 0;
+                    }
+                }
+                MLLPQueueRecord::Delivered { id } | MLLPQueueRecord::Purged { id } => {
+                    self.entries.shift_remove(&id);
+                }
+            }
+        }
+    }
+
+    /****************************** API *****************************************/
+
+    ///
+    /// Milliseconds since the Unix epoch.
+    ///
+    pub fn now() -> u64 {
+        match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => elapsed.as_millis() as u64,
+            Err(_) => 0,
+        }
+    }
+
+    fn open_append(path: &str) -> RUMResult<File> {
+        match OpenOptions::new().create(true).append(true).open(path) {
+            Ok(file) => Ok(file),
+            Err(e) => Err(rumtk_format!("Unable to open {} because {}", path, e)),
+        }
+    }
+
+    ///
+    /// Records in the log at `path` and whether its last line was cut short.
+    ///
+    fn read_records<T>(path: &str) -> RUMResult<(Vec<T>, bool)>
+    where
+        T: for<'a> RUMDeJson<'a>,
+    {
+        let mut contents = Vec::new();
+        match File::open(path).and_then(|mut file| file.read_to_end(&mut contents)) {
+            Ok(_) => {}
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok((Vec::new(), false)),
+            Err(e) => return Err(rumtk_format!("Unable to read {} because {}", path, e)),
+        }
+
+        // Work on bytes since a torn write can split a multibyte character.
+        let lines = contents.split(|byte| *byte == b'\n').collect::<Vec<&[u8]>>();
+        let last = lines.iter().rposition(|line| !line.is_empty()).unwrap_or(0);
+        let mut records = Vec::with_capacity(lines.len());
+        let mut torn = false;
+        for (i, line) in lines.iter().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+            let record = match std::str::from_utf8(line) {
+                Ok(line) => from_json::<T>(line).map_err(|e| e.to_string()),
+                Err(e) => Err(rumtk_format!("{}", e)),
+            };
+            match record {
+                Ok(record) => records.push(record),
+                Err(_) if i == last => torn = true,
+                Err(e) => return Err(rumtk_format!("Line {} of {} is corrupt! {}", i + 1, path, e)),
+            }
+        }
+        Ok((records, torn))
+    }
+}
@@ -193,14 +193,16 @@ pub const CONDITION_CNE8: V2ComponentConditionFn = |c: &V2ComponentList| c[20].l
 ///
 /// If component 1 is valued, either CNN.8 or CNN.9, or both CNN.10 and CNN.11, must be valued.
 ///
-pub const CONDITION_CNN1: V2ComponentConditionFn =
-    |c: &V2ComponentList| (c[0].len() > 0 && c[8].len() > 0) || !CONDITION_CNN2(&c);
+pub const CONDITION_CNN1: V2ComponentConditionFn = |c: &V2ComponentList| {
+    !c[0].is_empty() && c[8].is_empty() && (c[9].is_empty() || c[10].is_empty())
+};
 
 ///
 /// If component 1 is valued, either CNN.8 or CNN.9, or both CNN.10 and CNN.11, must be valued.
 ///
-pub const CONDITION_CNN2: V2ComponentConditionFn =
-    |c: &V2ComponentList| (c[0].len() > 0 && c[7].len() > 0) || !CONDITION_CNN1(&c);
+pub const CONDITION_CNN2: V2ComponentConditionFn = |c: &V2ComponentList| {
+    !c[0].is_empty() && c[7].is_empty() && (c[9].is_empty() || c[10].is_empty())
+};
 
 ///
 /// If CNN.11 is valued, this component must be valued
@@ -217,3 +219,111 @@ pub const CONDITION_CNN4: V2ComponentConditionFn = |c: &V2ComponentList| c[9].le
 /// This component is required if CP.3 From Value and/or CP.4 To Value are present.
 ///
 pub const CONDITION_CP: V2ComponentConditionFn = |c: &V2ComponentList| c[2].len() > 0;
+
+/***************CWE Conditions*************/
+///
+/// This component is required when CWE.1 is populated and CWE.14 is not populated. Both CWE.3 and
+/// CWE.14 may be populated.
+///
+pub const CONDITION_CWE1: V2ComponentConditionFn =
+    |c: &V2ComponentList| !c[0].is_empty() && c[13].is_empty();
+///
+/// This component is required when CWE.4 is populated and CWE.17 is not populated. Both CWE.6 and
+/// CWE.17 may be populated.
+///
+pub const CONDITION_CWE2: V2ComponentConditionFn =
+    |c: &V2ComponentList| !c[3].is_empty() && c[16].is_empty();
+///
+/// See [CONDITION_CNE1]
+///
+pub const CONDITION_CWE3: V2ComponentConditionFn = CONDITION_NOOP;
+///
+/// This component is required when CWE.1 is populated and CWE.3 is not populated. Both CWE.3 and
+/// CWE.14 may be populated.
+///
+pub const CONDITION_CWE4: V2ComponentConditionFn =
+    |c: &V2ComponentList| !c[0].is_empty() && c[2].is_empty();
+///
+/// Value set version ID is required if CWE.15 is populated.
+///
+pub const CONDITION_CWE5: V2ComponentConditionFn = |c: &V2ComponentList| !c[14].is_empty();
+///
+/// This component is required when CWE.4 is populated and CWE.6 is not populated. Both CWE.6 and
+/// CWE.17 may be populated.
+///
+pub const CONDITION_CWE6: V2ComponentConditionFn =
+    |c: &V2ComponentList| !c[3].is_empty() && c[5].is_empty();
+///
+/// Value set version ID is required if CWE.18 is populated.
+///
+pub const CONDITION_CWE7: V2ComponentConditionFn = |c: &V2ComponentList| !c[17].is_empty();
+///
+/// This component is required when CWE.10 is populated and CWE.12 is not populated. Both CWE.12
+/// and CWE.20 may be populated.
+///
+pub const CONDITION_CWE8: V2ComponentConditionFn =
+    |c: &V2ComponentList| !c[9].is_empty() && c[11].is_empty();
+///
+/// Value set version ID is required if CWE.21 is populated.
+///
+pub const CONDITION_CWE9: V2ComponentConditionFn = |c: &V2ComponentList| !c[20].is_empty();
+
+/***************EI Conditions**************/
+///
+/// EI.3 is required if EI.4 is valued.
+///
+pub const CONDITION_EI1: V2ComponentConditionFn = |c: &V2ComponentList| !c[3].is_empty();
+///
+/// EI.4 is required if EI.3 is valued.
+///
+pub const CONDITION_EI2: V2ComponentConditionFn = |c: &V2ComponentList| !c[2].is_empty();
+
+/***************HD Conditions**************/
+///
+/// HD.1 must be valued if HD.2 and HD.3 are not valued.
+///
+pub const CONDITION_HD1: V2ComponentConditionFn =
+    |c: &V2ComponentList| c[1].is_empty() && c[2].is_empty();
+///
+/// HD.2 must be valued if HD.1 is not valued or if HD.3 is valued.
+///
+pub const CONDITION_HD2: V2ComponentConditionFn =
+    |c: &V2ComponentList| c[0].is_empty() || !c[2].is_empty();
+///
+/// HD.3 must be valued if HD.2 is valued.
+///
+pub const CONDITION_HD3: V2ComponentConditionFn = |c: &V2ComponentList| !c[1].is_empty();
+
+/***************XCN Conditions*************/
+///
+/// If XCN.1 is valued and XCN.9 is not valued, the source table must be valued.
+///
+pub const CONDITION_XCN1: V2ComponentConditionFn =
+    |c: &V2ComponentList| !c[0].is_empty() && c[8].is_empty();
+///
+/// If XCN.1 is valued and XCN.8 is not valued, the assigning authority must be valued.
+///
+pub const CONDITION_XCN2: V2ComponentConditionFn =
+    |c: &V2ComponentList| !c[0].is_empty() && c[7].is_empty();
+///
+/// The check digit scheme is required if XCN.11 is valued.
+///
+pub const CONDITION_XCN3: V2ComponentConditionFn = |c: &V2ComponentList| !c[10].is_empty();
+
+/***************XTN Conditions*************/
+///
+/// The communication address is required if XTN.3 is Internet or X.400.
+///
+pub const CONDITION_XTN1: V2ComponentConditionFn =
+    |c: &V2ComponentList| matches!(c[2].first(), Some(&"Internet") | Some(&"X.400"));
+///
+/// The local number is required if XTN.3 names a phone device (PH, FX, MD, CP, SAT, BP) and
+/// XTN.12 is not valued.
+///
+pub const CONDITION_XTN2: V2ComponentConditionFn = |c: &V2ComponentList| {
+    let is_phone = matches!(
+        c[2].first(),
+        Some(&"PH") | Some(&"FX") | Some(&"MD") | Some(&"CP") | Some(&"SAT") | Some(&"BP")
+    );
+    is_phone && c[11].is_empty()
+};
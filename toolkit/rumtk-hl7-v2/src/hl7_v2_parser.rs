@@ -40,7 +40,21 @@ pub mod v2_parser {
         V2_DELETE_FIELD, V2_EMPTY_STRING, V2_MSHEADER_PATTERN, V2_SEGMENT_DESC, V2_SEGMENT_IDS,
         V2_SEGMENT_TERMINATOR, V2_SEGMENT_TERMINATORS
     };
-    use crate::hl7_v2_constants::{V2_MSHEADER_ID, V2_SEGMENT_NAMES, V2_TOTAL_VALID_SEGMENTS};
+    use crate::hl7_v2_ack::v2_ack::acknowledge;
+    use crate::hl7_v2_charset::v2_charset::{v2_decode_message, v2_encode_message_with, V2EncodingSource, V2MessageEncoding};
+    use crate::hl7_v2_complex_types::hl7_v2_complex_types::{cast_complex_type, cast_complex_type_as, V2FieldToString, V2Type};
+    use crate::hl7_v2_constants::{V2_CUSTOM_SEGMENT_ID, V2_MSHEADER_ID, V2_MSHEADER_PATTERN_STR, V2_SEARCH_EXPR_TYPE, V2_SEGMENT_NAMES, V2_TOTAL_VALID_SEGMENTS};
+    use crate::hl7_v2_escape::v2_escape::{V2EscapeOptions, V2EscapedText};
+    use crate::hl7_v2_field_descriptors::v2_field_descriptor::{V2ComplexType, V2Version};
+    use crate::hl7_v2_message_structures::v2_message_structure::{parse_message_structure, V2MessageTree};
+    use crate::hl7_v2_repair::v2_repair::{v2_repair, V2ParseFixes, V2ParseOptions};
+    use crate::hl7_v2_search::{
+        v2_search_expr_type, V2SearchExpression, V2SearchOperator, V2SearchPath, V2SearchPredicate,
+        V2SearchSelector,
+    };
+    use crate::hl7_v2_segment_descriptors::v2_segment_descriptor::segment_field_index;
+    use crate::hl7_v2_translation::v2_translation::{translate_message, V2TranslationReport};
+    use crate::hl7_v2_validation::v2_validation::{validate_message, V2ValidationReport};
     use pyo3::prelude::*;
     use rumtk_core::base::RUMResult;
     use rumtk_core::base::{clamp_index, RUMVec};
@@ -55,15 +69,17 @@ pub mod v2_parser {
     use rumtk_core::serde::{RUMJsonDeserializer, RUMJsonSerializer, RUMSerJsonSerializeSequence};
     use rumtk_core::strings::{string_to_buffer, AsString};
     use rumtk_core::{rumtk_cache_fetch, rumtk_mem_quick_array_init};
+    use std::borrow::Cow;
     use std::ops::{Index, IndexMut};
     use std::sync::LazyLock;
     /**************************** Globals ***************************************/
 
     static mut search_cache: LazyRUMCache<RUMString, V2SearchIndex> = new_cache();
+    static mut SEARCH_EXPRESSION_CACHE: LazyRUMCache<RUMString, V2SearchExpression> = new_cache();
 
     /**************************** Helpers ***************************************/
     fn compile_search_index(search_pattern: &str) -> RUMResult<V2SearchIndex> {
-        Ok(V2SearchIndex::from(search_pattern))
+        V2SearchIndex::try_from(search_pattern)
     }
 
     /**************************** Types *****************************************/
@@ -151,7 +167,8 @@ pub mod v2_parser {
         /// If all things go right, the UTF-8 string should be a faithful representative of the
         /// intended string per section 2.7 of the standard.
         ///
-        /// Will not support 2.7.8 Local encodings (\Zxxyy) until needed in the wild.
+        /// Components keep their text escaped. Use [V2Component::text] or [V2EscapedText] to read
+        /// it, including 2.7.8 local encodings (\Zxxyy).
         ///
         #[inline(always)]
         fn from(component: RUMBuffer) -> Self {
@@ -168,6 +185,92 @@ pub mod v2_parser {
             self.as_str() == V2_DELETE_FIELD
         }
 
+        ///
+        /// Replace the contents of this component. `value` is stored as is, so it must already be
+        /// escaped. See [V2ParserCharacters::escape].
+        ///
+        pub fn set(&mut self, value: &str) {
+            self.c = string_to_buffer(value);
+        }
+
+        ///
+        /// Plain text of this component with the default [V2EscapeOptions]. See
+        /// [V2Component::text_with] to decide how each class of escape sequence is handled.
+        ///
+        pub fn text(&self, parser_chars: &V2ParserCharacters) -> V2String {
+            self.escaped_text(parser_chars).render(&V2EscapeOptions::default())
+        }
+
+        pub fn text_with(&self, parser_chars: &V2ParserCharacters, options: &V2EscapeOptions) -> V2String {
+            self.escaped_text(parser_chars).render(options)
+        }
+
+        pub fn escaped_text(&self, parser_chars: &V2ParserCharacters) -> V2EscapedText {
+            V2EscapedText::parse(self.as_str(), parser_chars)
+        }
+
+        ///
+        /// Replace the contents of this component with plain `text`, escaping its delimiters.
+        ///
+        pub fn set_text(&mut self, text: &str, parser_chars: &V2ParserCharacters) {
+            self.set(&parser_chars.escape(text));
+        }
+
+        ///
+        /// Replace the contents of this component with already tokenized text.
+        ///
+        pub fn set_escaped_text(&mut self, text: &V2EscapedText) {
+            self.set(&text.encode());
+        }
+
+        ///
+        /// Subcomponents of this component, split on the subcomponent separator (`&` by
+        /// default). A component without separators is its own single subcomponent.
+        ///
+        pub fn subcomponents(&self, parser_chars: &V2ParserCharacters) -> RUMVec<&str> {
+            self.as_str().split(parser_chars.subcomponent_separator as char).collect()
+        }
+
+        pub fn subcomponent_count(&self, parser_chars: &V2ParserCharacters) -> usize {
+            self.subcomponents(parser_chars).len()
+        }
+
+        ///
+        /// Subcomponent at `indx` (1-indexed). Negative values count from the end.
+        ///
+        pub fn get_subcomponent(&self, indx: isize, parser_chars: &V2ParserCharacters) -> V2Result<&str> {
+            let subcomponents = self.subcomponents(parser_chars);
+            let subcomponent_indx = clamp_index(&indx, &(subcomponents.len() as isize))? - 1;
+            match subcomponents.get(subcomponent_indx) {
+                Some(subcomponent) => Ok(subcomponent),
+                None => Err(rumtk_format!("Subcomponent index {} is out of bounds!", indx)),
+            }
+        }
+
+        ///
+        /// Replace the subcomponent at `indx` (1-indexed, negative values count from the end).
+        /// Positive indices past the last subcomponent pad the component with empty ones. Like
+        /// [V2Component::set], `value` must already be escaped.
+        ///
+        pub fn set_subcomponent(&mut self, indx: isize, value: &str, parser_chars: &V2ParserCharacters) -> V2Result<()> {
+            let mut subcomponents: RUMVec<V2String> = self
+                .subcomponents(parser_chars)
+                .iter()
+                .map(|subcomponent| V2String::from(*subcomponent))
+                .collect();
+            let subcomponent_indx = match indx > subcomponents.len() as isize {
+                true => {
+                    subcomponents.resize(indx as usize, V2String::new());
+                    indx as usize - 1
+                }
+                false => clamp_index(&indx, &(subcomponents.len() as isize))? - 1,
+            };
+            subcomponents[subcomponent_indx] = V2String::from(value);
+            let separator = (parser_chars.subcomponent_separator as char).to_string();
+            self.set(&subcomponents.join(&separator));
+            Ok(())
+        }
+
         pub fn as_datetime(&self) -> RUMResult<V2DateTime> {
             Ok(V2DateTime::from_str(self.as_str())?)
         }
@@ -286,6 +389,55 @@ pub mod v2_parser {
             }
         }
 
+        ///
+        /// Same as [V2Field::get_mut] but appends empty components until component `indx`
+        /// (1-indexed) exists.
+        ///
+        pub fn get_or_insert_mut(&mut self, indx: usize) -> &mut V2Component {
+            if self.cs.len() < indx {
+                self.cs.resize_with(indx, V2Component::new);
+            }
+            &mut self.cs[indx.max(1) - 1]
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.cs.iter().all(|c| c.is_empty())
+        }
+
+        ///
+        /// Cast this field into the requested complex data type using the field descriptors.
+        ///
+        /// ## Example
+        /// ```
+        /// use rumtk_hl7_v2::hl7_v2_parser::v2_parser::{V2Field, V2ParserCharacters};
+        /// use rumtk_hl7_v2::hl7_v2_field_descriptors::v2_field_descriptor::V2ComplexType;
+        /// use rumtk_core::buffers::RUMBuffer;
+        ///
+        /// let chars = V2ParserCharacters::new();
+        /// let field = V2Field::from(RUMBuffer::from("EVERYMAN^ADAM^A^III".as_bytes()).freeze(), &chars);
+        /// let name = field.to_complex(&V2ComplexType::XPN, &chars);
+        /// let given = name.as_complex().unwrap().get("given_name").unwrap();
+        ///
+        /// assert_eq!(given.as_str(), Some("ADAM"), "Expected the given name to be ADAM!");
+        /// ```
+        ///
+        pub fn to_complex(&self, complex_type: &V2ComplexType, parser_chars: &V2ParserCharacters) -> V2Type {
+            cast_complex_type(self.to_component_list(), complex_type, parser_chars)
+        }
+
+        ///
+        /// Same as [V2Field::to_complex] but using the definition of the type in `version`, e.g.
+        /// the one returned by [V2Message::version].
+        ///
+        pub fn to_complex_as(
+            &self,
+            complex_type: &V2ComplexType,
+            parser_chars: &V2ParserCharacters,
+            version: V2Version,
+        ) -> V2Type {
+            cast_complex_type_as(self.to_component_list(), complex_type, parser_chars, version)
+        }
+
         #[inline(always)]
         pub fn as_ptr(&self) -> *const V2Component {
             self.cs.as_ptr()
@@ -310,6 +462,12 @@ pub mod v2_parser {
         }
     }
 
+    impl V2FieldToString for V2Field {
+        fn to_component_list(&self) -> Vec<&str> {
+            self.cs.iter().map(|c| c.as_str()).collect()
+        }
+    }
+
     impl Drop for V2Field {
         #[inline]
         fn drop(&mut self) {
@@ -342,12 +500,16 @@ pub mod v2_parser {
     #[derive(Default, Debug, RUMSerJson, RUMDeJson, PartialEq, Clone)]
     pub struct V2Segment {
         f: V2FieldList,
+        /// Original name of segments outside the standard. They all share [V2_CUSTOM_SEGMENT_ID].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        n: Option<V2String>,
     }
 
     impl V2Segment {
         pub fn new() -> Self {
             Self {
-                f: vec![]
+                f: vec![],
+                n: None,
             }
         }
 
@@ -383,8 +545,13 @@ pub mod v2_parser {
             }
             field_list.push(Self::generate_subfields(raw_fields.remainder, parser_chars));
 
+            let name = match segment_id {
+                V2_CUSTOM_SEGMENT_ID => Some(buffer_to_str(&segment_id_field).unwrap_or_default().to_string()),
+                _ => None,
+            };
             let segment = V2Segment {
                 f: field_list,
+                n: name,
             };
 
             Ok((segment_id, segment))
@@ -436,6 +603,36 @@ pub mod v2_parser {
             )
         }
 
+        ///
+        /// Name this segment had in the original message if it is not a standard segment (e.g. `ZPD`).
+        ///
+        pub fn custom_name(&self) -> Option<&str> {
+            self.n.as_deref()
+        }
+
+        ///
+        /// Name of this segment given the id it is stored under.
+        ///
+        pub fn name(&self, segment_id: u8) -> &str {
+            match (segment_id, &self.n) {
+                (V2_CUSTOM_SEGMENT_ID, Some(name)) => name.as_str(),
+                _ => buffer_to_str(V2_SEGMENT_NAMES(segment_id)).unwrap_or_default(),
+            }
+        }
+
+        ///
+        /// Position in [V2Segment::get] of field `seq` as numbered in the standard. The field
+        /// separator is consumed while splitting, so MSH-1 is never stored and MSH-2 is the first
+        /// field of the MSH.
+        ///
+        #[inline(always)]
+        pub fn field_index(segment_id: u8, seq: u16) -> isize {
+            match segment_id == V2_MSHEADER_ID {
+                true => seq as isize - 1,
+                false => seq as isize,
+            }
+        }
+
         pub fn get(&self, indx: isize) -> V2Result<&V2FieldGroup> {
             let field_indx = clamp_index(&indx, &(self.f.len() as isize))? - 1;
             match self.f.get(field_indx) {
@@ -466,6 +663,31 @@ pub mod v2_parser {
             self.f[indx].as_mut().unwrap()
         }
 
+        ///
+        /// Same as [V2Segment::get_mut] but appends unvalued fields until field `indx` (1-indexed)
+        /// exists.
+        ///
+        pub fn get_or_insert_mut(&mut self, indx: usize) -> &mut V2FieldGroup {
+            let field_indx = indx.max(1) - 1;
+            if self.f.len() <= field_indx {
+                self.f.resize_with(field_indx + 1, || None);
+            }
+            match self.f[field_indx] {
+                Some(_) => self.f[field_indx].as_mut().unwrap(),
+                None => self.init_deffered_slot(field_indx),
+            }
+        }
+
+        ///
+        /// Mark field `indx` (1-indexed) as not populated. Fields past the end of the segment are
+        /// already unvalued so there is nothing to do for them.
+        ///
+        pub fn clear(&mut self, indx: usize) {
+            if indx > 0 && indx <= self.f.len() {
+                self.f[indx - 1] = None;
+            }
+        }
+
         pub fn len(&self) -> usize {
             self.f.len()
         }
@@ -526,12 +748,47 @@ pub mod v2_parser {
     ///
     pub type V2SegmentMap = Vec<Option<V2SegmentGroup>>;
 
+    ///
+    /// Position of each segment as it appeared in the original message. Each entry is the segment id
+    /// and the 1-indexed position of the segment in its group, i.e. the same pair you would pass to
+    /// [V2Message::get].
+    ///
+    pub type V2SegmentOrder = RUMVec<(u8, usize)>;
+
+    ///
+    /// Value found by [V2Message::find_all]. The location is fully qualified in search notation
+    /// (e.g. `OBX(3)-5[1].1`) so it can be fed back into the search and editing methods.
+    ///
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct V2SearchMatch<'a> {
+        pub location: V2String,
+        pub component: &'a V2Component,
+        pub subcomponent: Option<usize>,
+        /// The component, or only the requested subcomponent of it.
+        pub value: &'a str,
+    }
+
     #[derive(Debug, RUMSerJson, RUMDeJson, Clone)]
     pub struct V2Message {
         #[serde(skip)]
         data: RUMBuffer,
         sep: V2ParserCharacters,
         sg: V2SegmentMap,
+        #[serde(default)]
+        so: V2SegmentOrder,
+        /// Whether the original message ended with a segment terminator.
+        #[serde(default)]
+        tt: bool,
+        /// Segment terminator of the original message when it was not the standard `\r`, i.e.
+        /// `\n` or `\r\n`. See [V2Message::to_string].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        lt: Option<V2String>,
+        /// Character set the message was decoded with. See [V2Message::encoding].
+        #[serde(default)]
+        enc: V2MessageEncoding,
+        /// Version forced by [V2Message::set_version_override] over the one in MSH-12.
+        #[serde(skip)]
+        ver: Option<V2Version>,
     }
 
     impl V2Message {
@@ -539,30 +796,86 @@ pub mod v2_parser {
             Self {
                 data: RUMBuffer::new(),
                 sep: V2ParserCharacters::new(),
-                sg: rumtk_mem_quick_array_init!(Option<V2SegmentGroup>, V2_TOTAL_VALID_SEGMENTS as usize, None).into()
+                sg: rumtk_mem_quick_array_init!(Option<V2SegmentGroup>, V2_TOTAL_VALID_SEGMENTS as usize, None).into(),
+                so: V2SegmentOrder::new(),
+                tt: false,
+                lt: None,
+                enc: V2MessageEncoding::default(),
+                ver: None,
             }
         }
         ///
         /// Attempts to parse incoming raw HL7 v2 message into an instance of [V2Message](V2Message).
+        /// The bytes are decoded with the character set in MSH-18, falling back to UTF-8 and then
+        /// to detecting the encoding. See [V2Message::encoding].
         ///
         /// ## Example
         ///
         /// ```
+        /// use rumtk_hl7_v2::hl7_v2_charset::v2_charset::V2EncodingSource;
+        /// use rumtk_hl7_v2::hl7_v2_parser::v2_parser::V2Message;
+        /// use rumtk_core::buffers::RUMBuffer;
+        ///
+        /// let raw = b"MSH|^~\\&|APP|FAC|||20250101120000||ADT^A01^ADT_A01|MSG0001|P|2.5.1||||||8859/1\rPID|1||12345||Jos\xe9\r";
+        /// let message = V2Message::try_from_buffer(RUMBuffer::from(&raw[..])).unwrap();
+        ///
+        /// assert_eq!(message.find_value("PID-5").unwrap(), "José", "Latin-1 was not decoded!");
+        /// assert_eq!(message.encoding().source, V2EncodingSource::Declared, "MSH-18 should have been honored!");
+        /// assert_eq!(message.to_bytes().unwrap(), raw.to_vec(), "Message should encode back to Latin-1!");
         /// ```
         ///
         #[inline]
-        pub fn try_from_buffer(mut raw_msg: RUMBuffer) -> V2Result<Self> {
+        pub fn try_from_buffer(raw_msg: RUMBuffer) -> V2Result<Self> {
+            let (decoded, encoding) = match v2_decode_message(&raw_msg)? {
+                (Cow::Borrowed(_), encoding) => (None, encoding),
+                (Cow::Owned(text), encoding) => (Some(text), encoding),
+            };
+            match decoded {
+                Some(text) => Self::parse_buffer(string_to_buffer(&text), encoding),
+                None => Self::parse_buffer(raw_msg, encoding),
+            }
+        }
+
+        ///
+        /// Parse text that is already decoded. MSH-18 is only recorded. See [V2Message::encoding].
+        ///
+        pub fn try_from_text(text: &str) -> V2Result<Self> {
+            Self::parse_buffer(string_to_buffer(text), V2MessageEncoding::from_text(text))
+        }
+
+        ///
+        /// Same as [V2Message::try_from_buffer] but malformed input is repaired or rejected
+        /// according to `options` first. The fixes made are returned alongside the message. See
+        /// [v2_repair].
+        ///
+        pub fn try_from_bytes_with(raw_msg: &[u8], options: &V2ParseOptions) -> V2Result<(Self, V2ParseFixes)> {
+            let (repaired, fixes) = v2_repair(raw_msg, options)?;
+            Ok((Self::try_from_buffer(RUMBuffer::from(repaired))?, fixes))
+        }
+
+        pub(crate) fn parse_buffer(mut raw_msg: RUMBuffer, encoding: V2MessageEncoding) -> V2Result<Self> {
+            let line_terminator = Self::find_line_terminator(raw_msg.as_ref());
+            let terminated = match &line_terminator {
+                Some(line_terminator) => raw_msg.as_ref().ends_with(line_terminator.as_bytes()),
+                None => raw_msg.as_ref().ends_with(&[V2_SEGMENT_TERMINATOR]),
+            };
             let sanitized = Self::sanitize(&mut raw_msg);
             let parse_characters = V2ParserCharacters::from(&sanitized)?;
+            let encoding_characters = Self::extract_encoding_characters(&sanitized)?;
             let sanitized_view = sanitized.freeze();
-            let segments = Self::extract_segments(sanitized_view, &parse_characters)?;
+            let (segments, order) = Self::extract_ordered_segments(sanitized_view, &parse_characters)?;
             let mut message = Self {
                 data: raw_msg,
                 sep: parse_characters.clone(),
                 sg: segments,
+                so: order,
+                tt: terminated,
+                lt: line_terminator,
+                enc: encoding,
+                ver: None,
             };
 
-            Self::patch_msh_pattern(&mut message, &parse_characters)?;
+            Self::patch_msh_pattern(&mut message, encoding_characters, &parse_characters)?;
 
             Ok(message)
         }
@@ -573,24 +886,123 @@ pub mod v2_parser {
         /// but this is an artifact of following the standard and forcing all linefeed characters into
         /// carriage return characters as terminator.
         ///
+        /// Segments are written in the order they were parsed (see [V2Message::segment_order]) and
+        /// joined with the terminator the original message used, so parsing a message and generating
+        /// it back yields the same bytes whether it used `\r`, `\n` or `\r\n`. Messages built from
+        /// scratch use `\r` as the standard requires.
+        ///
+        /// ## Example
+        ///
+        /// ```
+        /// use rumtk_hl7_v2::hl7_v2_parser::v2_parser::V2Message;
+        /// use rumtk_core::buffers::RUMBuffer;
+        ///
+        /// let raw = "MSH|^~\\&#|LAB|FAC|||20250101120000||ORU^R01^ORU_R01|MSG0001|P|2.5.1\rPID|1||12345^^^HOSP^MR||DOE^JANE\rOBR|1||ORD1|CBC^Complete Blood Count^L\rNTE|1||Fasting\rOBX|1|NM|WBC^White Cells^L||7.2\rZPD|1|custom\r";
+        /// let message = V2Message::try_from_buffer(RUMBuffer::from(raw.as_bytes()).freeze()).unwrap();
+        ///
+        /// assert_eq!(message.to_string(), raw, "Generated message differs from the original!");
+        /// ```
+        ///
         #[inline(always)]
         pub fn to_string(&self) -> V2String {
-            let mut msg: RUMVec<V2String> = RUMVec::with_capacity(self.sg.len());
-            for (indx, group) in self.sg.iter().enumerate() {
-                match group {
-                    Some(segment_group) => {
-                        for segment in segment_group {
-                            msg.push(rumtk_format!("{}{}{}",
-                            buffer_to_str(V2_SEGMENT_NAMES((indx + 1) as u8)).unwrap_or_default(),
-                            buffer_to_str(&[self.sep.field_separator]).unwrap_or_default(),
-                            segment.to_string(&self.sep)));
-                        }
-                    },
-                    None => {}
+            let order = self.segment_order();
+            let mut msg: RUMVec<V2String> = RUMVec::with_capacity(order.len());
+            for (segment_id, sub_segment) in order {
+                if let Ok(segment) = self.get(segment_id, sub_segment) {
+                    msg.push(rumtk_format!("{}{}{}",
+                    segment.name(segment_id),
+                    buffer_to_str(&[self.sep.field_separator]).unwrap_or_default(),
+                    segment.to_string(&self.sep)));
                 }
             }
 
-            msg.join(&self.sep.segment_terminator.as_string())
+            let terminator = match &self.lt {
+                Some(line_terminator) => line_terminator.clone(),
+                None => self.sep.segment_terminator.as_string(),
+            };
+            let mut generated = msg.join(&terminator);
+            if self.tt {
+                generated.push_str(&terminator);
+            }
+            generated
+        }
+
+        ///
+        /// [V2Message::to_string] encoded for the wire. The character set in MSH-18 is used if we
+        /// know it, so edits to MSH-18 are honored. Otherwise, the message is encoded the way it was
+        /// decoded.
+        ///
+        pub fn to_bytes(&self) -> V2Result<RUMVec<u8>> {
+            let text = self.to_string();
+            let declared = V2MessageEncoding::from_text(&text);
+            match declared.source {
+                V2EncodingSource::Declared => v2_encode_message_with(&text, &declared),
+                _ => v2_encode_message_with(&text, &self.enc),
+            }
+        }
+
+        ///
+        /// Character set this message was decoded with and whether it came from MSH-18 or had to
+        /// be guessed.
+        ///
+        #[inline]
+        pub fn encoding(&self) -> &V2MessageEncoding {
+            &self.enc
+        }
+
+        ///
+        /// HL7 release used to pick the data type definitions when casting and validating this
+        /// message. This is the override set with [V2Message::set_version_override] if any, else
+        /// the release declared in MSH-12. Messages declaring no release or one we carry no
+        /// descriptors for use the latest one.
+        ///
+        /// Only the layout of each data type follows the release. Which data type a segment field
+        /// has is not versioned (see [segment descriptors](crate::hl7_v2_segment_descriptors)), so
+        /// PID-7 is cast as DTM even in v2.3 to v2.5 messages where it was a TS.
+        ///
+        /// ## Example
+        ///
+        /// ```
+        /// use rumtk_hl7_v2::hl7_v2_field_descriptors::v2_field_descriptor::V2Version;
+        /// use rumtk_hl7_v2::hl7_v2_parser::v2_parser::V2Message;
+        ///
+        /// let raw = "MSH|^~\\&|APP|FAC|||20250101120000||ADT^A01^ADT_A01|MSG0001|P|2.4\rPID|1||12345^^^HOSP^MR||DOE^JANE\r";
+        /// let mut message = V2Message::try_from(raw).unwrap();
+        ///
+        /// assert_eq!(message.version(), V2Version::V2_4, "MSH-12 should have been honored!");
+        ///
+        /// message.set_version_override(Some(V2Version::V2_5_1));
+        /// assert_eq!(message.version(), V2Version::V2_5_1, "Override should win over MSH-12!");
+        /// assert_eq!(message.declared_version(), Some(V2Version::V2_4));
+        /// ```
+        ///
+        pub fn version(&self) -> V2Version {
+            match self.ver {
+                Some(version) => version,
+                None => self.declared_version().unwrap_or_default(),
+            }
+        }
+
+        ///
+        /// Release declared in MSH-12.1, if it is one we carry descriptors for.
+        ///
+        pub fn declared_version(&self) -> Option<V2Version> {
+            let msh = self.get(V2_MSHEADER_ID, 1).ok()?;
+            let field = msh.get(segment_field_index(V2_MSHEADER_PATTERN_STR, 12)).ok()?.first()?;
+            V2Version::from_version_id(field.to_component_list().first()?)
+        }
+
+        ///
+        /// Force the release used for this message regardless of MSH-12. Meant for senders that
+        /// label their messages with a version they do not actually follow. Pass [None] to go
+        /// back to MSH-12.
+        ///
+        pub fn set_version_override(&mut self, version: Option<V2Version>) {
+            self.ver = version;
+        }
+
+        pub fn version_override(&self) -> Option<V2Version> {
+            self.ver
         }
 
         #[inline]
@@ -598,11 +1010,87 @@ pub mod v2_parser {
             self.sg.len()
         }
 
+        ///
+        /// Sanitized bytes this message was parsed from. Segments are terminated by
+        /// [V2ParserCharacters::segment_terminator] and appear in their original order.
+        ///
+        #[inline]
+        pub fn raw(&self) -> &[u8] {
+            self.data.as_ref()
+        }
+
+        ///
+        /// Separator characters this message was parsed with.
+        ///
+        #[inline]
+        pub fn parser_chars(&self) -> &V2ParserCharacters {
+            &self.sep
+        }
+
         #[inline]
         pub fn is_empty(&self) -> bool {
             self.sg.is_empty()
         }
 
+        ///
+        /// Check this message against the segment schemas and report every conformance issue found.
+        /// See [validate_message].
+        ///
+        /// ## Example
+        ///
+        /// ```
+        /// use rumtk_hl7_v2::hl7_v2_parser::v2_parser::V2Message;
+        /// use rumtk_core::buffers::RUMBuffer;
+        ///
+        /// let raw = "MSH|^~\\&|APP|FAC|||20250101120000||ADT^A01^ADT_A01|MSG0001|P|2.5.1\rPID|1||12345^^^HOSP^MR\r";
+        /// let message = V2Message::try_from_buffer(RUMBuffer::from(raw.as_bytes()).freeze()).unwrap();
+        /// let report = message.validate();
+        ///
+        /// assert!(!report.is_valid(), "PID-5 is required but missing!");
+        /// assert_eq!(report.at("PID-5").len(), 1, "Expected a single issue at PID-5!");
+        /// ```
+        ///
+        pub fn validate(&self) -> V2ValidationReport {
+            validate_message(self)
+        }
+
+        ///
+        /// Validate this message and generate the acknowledgement for it. See [acknowledge].
+        ///
+        pub fn ack(&self) -> V2Result<V2Message> {
+            acknowledge(self)
+        }
+
+        ///
+        /// Translate this message to the `target` HL7 release. See [translate_message].
+        ///
+        pub fn translate(&self, target: V2Version) -> V2Result<(V2Message, V2TranslationReport)> {
+            translate_message(self, target)
+        }
+
+        ///
+        /// Rebuild the segment group hierarchy of this message using the message structure named in
+        /// MSH-9. See [V2MessageTree].
+        ///
+        /// ## Example
+        ///
+        /// ```
+        /// use rumtk_hl7_v2::hl7_v2_parser::v2_parser::V2Message;
+        /// use rumtk_core::buffers::RUMBuffer;
+        ///
+        /// let raw = "MSH|^~\\&|LAB|FAC|||20250101120000||ORU^R01^ORU_R01|MSG0001|P|2.5.1\rPID|1||12345^^^HOSP^MR||DOE^JANE\rOBR|1||ORD1|CBC^Complete Blood Count^L\rOBX|1|NM|WBC^White Cells^L||7.2\rOBR|2||ORD2|BMP^Basic Metabolic Panel^L\rOBX|1|NM|NA^Sodium^L||140\rOBX|2|NM|K^Potassium^L||4.1\r";
+        /// let message = V2Message::try_from_buffer(RUMBuffer::from(raw.as_bytes()).freeze()).unwrap();
+        /// let tree = message.structure().unwrap();
+        /// let orders = tree.group("PATIENT_RESULT").unwrap().groups("ORDER_OBSERVATION");
+        ///
+        /// assert_eq!(orders.len(), 2, "Expected two orders!");
+        /// assert_eq!(orders[1].groups("OBSERVATION").len(), 2, "Expected two results for the second order!");
+        /// ```
+        ///
+        pub fn structure(&self) -> V2Result<V2MessageTree<'_>> {
+            parse_message_structure(self)
+        }
+
         ///
         /// Needed because otherwise we have to incur a couple hundred microseconds of performance penalty
         /// for checking if the segment being built is the MSH.
@@ -611,12 +1099,33 @@ pub mod v2_parser {
         /// interferes with different splitting events to the point that what should remain as a single field
         /// with a single component becomes a field with multiple components.
         ///
+        /// We restore MSH-2 exactly as received instead of regenerating it from the parser characters
+        /// so optional encoding characters (e.g. the truncation character) survive a round trip.
+        ///
         #[inline(always)]
-        fn patch_msh_pattern(message: &mut V2Message, parser_chars: &V2ParserCharacters) -> RUMResult<()> {
-            message.get_mut_group(V2_MSHEADER_ID)?[0][1] = vec![V2Field::from_single_field(parser_chars.to_buffer(), parser_chars)].into();
+        fn patch_msh_pattern(message: &mut V2Message, encoding_characters: RUMBuffer, parser_chars: &V2ParserCharacters) -> RUMResult<()> {
+            message.get_mut_group(V2_MSHEADER_ID)?[0][1] = vec![V2Field::from_single_field(encoding_characters, parser_chars)];
             Ok(())
         }
 
+        ///
+        /// Copy of the raw MSH-2 contents, i.e. everything between the first and second field
+        /// separators of the header.
+        ///
+        fn extract_encoding_characters(message: &[u8]) -> V2Result<RUMBuffer> {
+            let field_separator_indx = V2ParserCharacters::find_msh(message)? + V2_MSHEADER_PATTERN.len();
+            let field_separator = match message.get(field_separator_indx) {
+                Some(field_separator) => *field_separator,
+                None => return Err(RUMString::from("MSH segment is missing its field separator! The message is malformed!")),
+            };
+            let encoding_start = field_separator_indx + 1;
+            let encoding_end = match buffer_find_byte(&message[encoding_start..], field_separator) {
+                Some(len) => encoding_start + len,
+                None => message.len(),
+            };
+            Ok(RUMBuffer::from(&message[encoding_start..encoding_end]))
+        }
+
         pub fn get(&self, segment_id: u8, sub_segment: usize) -> V2Result<&V2Segment> {
             let segment_group = self.get_group(segment_id)?;
             let subsegment_indx = sub_segment - 1;
@@ -671,9 +1180,86 @@ pub mod v2_parser {
             }
         }
 
+        ///
+        /// Grab the `sub_segment` instance (1-indexed) of the custom segment `segment_name`. Custom
+        /// segments share [V2_CUSTOM_SEGMENT_ID] so instances are counted among the segments with
+        /// the same name only.
+        ///
+        pub fn get_custom(&self, segment_name: &str, sub_segment: usize) -> V2Result<&V2Segment> {
+            let segment_group = self.get_group(V2_CUSTOM_SEGMENT_ID)?;
+            let subsegment_indx = sub_segment.wrapping_sub(1);
+            match segment_group.iter().filter(|s| s.custom_name() == Some(segment_name)).nth(subsegment_indx) {
+                Some(segment) => Ok(segment),
+                None => Err(rumtk_format!(
+                    "Subsegment {} was not found in custom segment group {}!",
+                    subsegment_indx,
+                    segment_name
+                )),
+            }
+        }
+
+        pub fn get_custom_mut(&mut self, segment_name: &str, sub_segment: usize) -> V2Result<&mut V2Segment> {
+            let segment_group = self.get_mut_group(V2_CUSTOM_SEGMENT_ID)?;
+            let subsegment_indx = sub_segment.wrapping_sub(1);
+            match segment_group.iter_mut().filter(|s| s.custom_name() == Some(segment_name)).nth(subsegment_indx) {
+                Some(segment) => Ok(segment),
+                None => Err(rumtk_format!(
+                    "Subsegment {} was not found in custom segment group {}!",
+                    subsegment_indx,
+                    segment_name
+                )),
+            }
+        }
+
+        ///
+        /// Same as [V2Message::get] but takes the segment name. Works for standard and custom
+        /// segments alike.
+        ///
+        pub fn get_named(&self, segment_name: &str, sub_segment: usize) -> V2Result<&V2Segment> {
+            match V2_SEGMENT_IDS(segment_name.as_bytes()) {
+                V2_CUSTOM_SEGMENT_ID => self.get_custom(segment_name, sub_segment),
+                segment_id => self.get(segment_id, sub_segment),
+            }
+        }
+
+        pub fn get_named_mut(&mut self, segment_name: &str, sub_segment: usize) -> V2Result<&mut V2Segment> {
+            match V2_SEGMENT_IDS(segment_name.as_bytes()) {
+                V2_CUSTOM_SEGMENT_ID => self.get_custom_mut(segment_name, sub_segment),
+                segment_id => self.get_mut(segment_id, sub_segment),
+            }
+        }
+
+        ///
+        /// Number of segments named `segment_name` in this message.
+        ///
+        pub fn named_segment_count(&self, segment_name: &str) -> usize {
+            match V2_SEGMENT_IDS(segment_name.as_bytes()) {
+                V2_CUSTOM_SEGMENT_ID => match self.get_group(V2_CUSTOM_SEGMENT_ID) {
+                    Ok(grp) => grp.iter().filter(|s| s.custom_name() == Some(segment_name)).count(),
+                    Err(_) => 0,
+                },
+                segment_id => self.segment_group_count(segment_id),
+            }
+        }
+
+        ///
+        /// Find the component at `search_pattern`. Extended expressions (see
+        /// [V2SearchExpression]) resolve to their first match. Use [V2Message::find_value] or
+        /// [V2Message::find_all] for subcomponents.
+        ///
         pub fn find_component(&self, search_pattern: &str) -> V2Result<&V2Component> {
+            if let V2_SEARCH_EXPR_TYPE::V2_EXTENDED = v2_search_expr_type(search_pattern) {
+                return match self.find_all(search_pattern)?.into_iter().next() {
+                    Some(found) if found.subcomponent.is_none() => Ok(found.component),
+                    Some(_) => Err(rumtk_format!(
+                        "Search expression {} points to a subcomponent! Use find_value or find_all instead.",
+                        search_pattern
+                    )),
+                    None => Err(rumtk_format!("Nothing matched search expression {}!", search_pattern)),
+                };
+            }
             let index = rumtk_cache_fetch!(&mut search_cache, &search_pattern.to_string(), || {compile_search_index(search_pattern)})?;
-            let segment = self.get(index.segment, index.segment_group as usize)?;
+            let segment = self.get_named(&index.segment_name, index.segment_group as usize)?;
             let field_group = segment.get(index.field as isize)?;
             let field = match field_group.get((index.field_group - 1) as usize) {
                 Some(field) => field,
@@ -683,12 +1269,157 @@ pub mod v2_parser {
             component
         }
 
+        ///
+        /// Value of the first match of `search_pattern`. Unlike [V2Message::find_component], it
+        /// can point at a subcomponent.
+        ///
+        pub fn find_value(&self, search_pattern: &str) -> V2Result<&str> {
+            match self.find_all(search_pattern)?.into_iter().next() {
+                Some(found) => Ok(found.value),
+                None => Err(rumtk_format!("Nothing matched search expression {}!", search_pattern)),
+            }
+        }
+
+        ///
+        /// Every value matching `search_pattern`, in segment then repetition order. Expressions are
+        /// read with the extended grammar of [V2SearchExpression], where only the field may be
+        /// negative. Locations that do not exist in the message simply yield no match.
+        ///
+        /// ## Example
+        ///
+        /// ```
+        /// use rumtk_hl7_v2::hl7_v2_parser::v2_parser::V2Message;
+        ///
+        /// let raw = "MSH|^~\\&|LAB|FAC|||20250101120000||ORU^R01^ORU_R01|MSG0001|P|2.5.1\rPID|1||12345^^^HOSP&1.2.3&ISO^MR\rOBR|1||ORD1|CHEM^Chemistry^L\rOBX|1|NM|GLU^Glucose^L||95\rOBX|2|NM|NA^Sodium^L||140\rOBX|3|NM|GLU^Glucose^L||101\r";
+        /// let message = V2Message::try_from(raw).unwrap();
+        ///
+        /// let results: Vec<&str> = message.find_all("OBX(*)-5").unwrap().iter().map(|m| m.value).collect();
+        /// assert_eq!(results, vec!["95", "140", "101"], "Expected every OBX-5!");
+        ///
+        /// let glucose = message.find_all("OBX[OBX-3.1='GLU']-5").unwrap();
+        /// assert_eq!(glucose.len(), 2, "Expected both glucose results!");
+        /// assert_eq!(glucose[1].location, "OBX(3)-5[1].1", "Wrong location!");
+        ///
+        /// assert_eq!(message.find_value("PID-3.4.2").unwrap(), "1.2.3", "Wrong assigning authority id!");
+        /// ```
+        ///
+        pub fn find_all(&self, search_pattern: &str) -> V2Result<RUMVec<V2SearchMatch<'_>>> {
+            let expr = rumtk_cache_fetch!(&raw mut SEARCH_EXPRESSION_CACHE, &search_pattern.to_string(), || {V2SearchExpression::parse(search_pattern)})?;
+            let mut matches = RUMVec::new();
+            let sub_segments = match expr.segment_group {
+                V2SearchSelector::Index(sub_segment) => sub_segment..=sub_segment,
+                V2SearchSelector::All => 1..=self.named_segment_count(&expr.segment_name),
+            };
+
+            for sub_segment in sub_segments {
+                let segment = match self.get_named(&expr.segment_name, sub_segment) {
+                    Ok(segment) => segment,
+                    Err(_) => continue,
+                };
+                if !expr.predicates.iter().all(|predicate| self.predicate_holds(segment, predicate)) {
+                    continue;
+                }
+                let field = match Self::absolute_field(segment, expr.path.field) {
+                    Some(field) => field,
+                    None => continue,
+                };
+                for (repetition, component) in Self::resolve_path(segment, &expr.path) {
+                    let value = match self.subcomponent_value(component, expr.path.subcomponent) {
+                        Some(value) => value,
+                        None => continue,
+                    };
+                    let mut location = rumtk_format!(
+                        "{}({})-{}[{}].{}",
+                        expr.segment_name,
+                        sub_segment,
+                        field,
+                        repetition,
+                        expr.path.component
+                    );
+                    if let Some(subcomponent) = expr.path.subcomponent {
+                        location.push_str(&rumtk_format!(".{}", subcomponent));
+                    }
+                    matches.push(V2SearchMatch {
+                        location,
+                        component,
+                        subcomponent: expr.path.subcomponent,
+                        value,
+                    });
+                }
+            }
+            Ok(matches)
+        }
+
+        ///
+        /// Components selected by `path` in one segment instance along with their repetition.
+        ///
+        fn resolve_path<'a>(segment: &'a V2Segment, path: &V2SearchPath) -> RUMVec<(usize, &'a V2Component)> {
+            let field = match Self::absolute_field(segment, path.field) {
+                Some(field) => field,
+                None => return RUMVec::new(),
+            };
+            let field_group = match segment.get(field as isize) {
+                Ok(field_group) => field_group,
+                Err(_) => return RUMVec::new(),
+            };
+            field_group
+                .iter()
+                .enumerate()
+                .filter(|(indx, field)| path.repetition.matches(indx + 1) && path.component <= field.len())
+                .filter_map(|(indx, field)| match field.get(path.component as isize) {
+                    Ok(component) => Some((indx + 1, component)),
+                    Err(_) => None,
+                })
+                .collect()
+        }
+
+        ///
+        /// 1-indexed field number of `field` in `segment`, counting negative fields from the end.
+        ///
+        fn absolute_field(segment: &V2Segment, field: isize) -> Option<usize> {
+            let field = match field < 0 {
+                true => segment.len() as isize + field + 1,
+                false => field,
+            };
+            match field >= 1 && field as usize <= segment.len() {
+                true => Some(field as usize),
+                false => None,
+            }
+        }
+
+        fn subcomponent_value<'a>(&self, component: &'a V2Component, subcomponent: Option<usize>) -> Option<&'a str> {
+            match subcomponent {
+                Some(subcomponent) => component.get_subcomponent(subcomponent as isize, &self.sep).ok(),
+                None => Some(component.as_str()),
+            }
+        }
+
+        fn predicate_holds(&self, segment: &V2Segment, predicate: &V2SearchPredicate) -> bool {
+            let mut values: RUMVec<&str> = Self::resolve_path(segment, &predicate.path)
+                .into_iter()
+                .filter_map(|(_, component)| self.subcomponent_value(component, predicate.path.subcomponent))
+                .collect();
+            if values.is_empty() {
+                values.push("");
+            }
+            match predicate.operator {
+                V2SearchOperator::Equal => values.iter().any(|value| *value == predicate.value),
+                V2SearchOperator::NotEqual => values.iter().all(|value| *value != predicate.value),
+            }
+        }
+
         pub fn find_component_mut(
             &mut self,
             search_pattern: &str,
         ) -> V2Result<&mut V2Component> {
             let index = rumtk_cache_fetch!(&mut search_cache, &search_pattern.to_string(), || {compile_search_index(search_pattern)})?;
-            let segment = self.get_mut(index.segment, index.segment_group as usize)?;
+            if index.subcomponent > 0 {
+                return Err(rumtk_format!(
+                    "Search expression {} points to a subcomponent! Use V2Component::set_subcomponent instead.",
+                    search_pattern
+                ));
+            }
+            let segment = self.get_named_mut(&index.segment_name, index.segment_group as usize)?;
             let mut field = match segment.get_mut(index.field as isize)?.get_mut((index.field_group - 1) as usize) {
                 Some(field) => field,
                 None => return Err(rumtk_format!("Subfield provided is not 1 indexed or out of bounds. Did you give us a 0 when you meant 1? Got {}!", index.field_group))
@@ -696,6 +1427,299 @@ pub mod v2_parser {
             field.get_mut(index.component as isize)
         }
 
+        ///
+        /// Set the component at `search_pattern` to `value`. Missing fields, repetitions and
+        /// components are created along the way. If the pattern names the next instance of a segment
+        /// (e.g. `OBX(3)5` in a message with two OBX), that segment is appended to the message. The
+        /// value is escaped so separators in it are kept as data. A trailing subcomponent index
+        /// (e.g. `PID3.4.2`) only replaces that subcomponent.
+        ///
+        /// ## Example
+        ///
+        /// ```
+        /// use rumtk_hl7_v2::hl7_v2_parser::v2_parser::V2Message;
+        ///
+        /// let raw = "MSH|^~\\&|APP|FAC|||20250101120000||ADT^A01^ADT_A01|MSG0001|P|2.5.1\rPID|1||12345^^^HOSP^MR||DOE^JANE\r";
+        /// let mut message = V2Message::try_from(raw).unwrap();
+        /// message.set("PID5.3", "Q").unwrap();
+        /// message.set("PID3.4.2", "1.2.3").unwrap();
+        /// message.set("PID8", "F").unwrap();
+        /// message.insert_repetition("PID3[2]", "999-99-9999").unwrap();
+        /// message.insert_segment_after("PID", "PV1").unwrap();
+        /// message.set("PV1-2", "I").unwrap();
+        ///
+        /// assert_eq!(
+        ///     message.to_string(),
+        ///     "MSH|^~\\&|APP|FAC|||20250101120000||ADT^A01^ADT_A01|MSG0001|P|2.5.1\rPID|1||12345^^^HOSP&1.2.3^MR~999-99-9999||DOE^JANE^Q|||F\rPV1||I\r",
+        ///     "Edited message is wrong!"
+        /// );
+        /// ```
+        ///
+        pub fn set(&mut self, search_pattern: &str, value: &str) -> V2Result<()> {
+            let index = Self::compile_edit_index(search_pattern)?;
+            let escaped = self.sep.escape(value);
+            let sep = self.sep.clone();
+            let segment = self.get_or_append_mut(&index.segment_name, index.segment_group as usize)?;
+            let field_group = segment.get_or_insert_mut(index.field as usize);
+            let repetition = index.field_group as usize;
+            if field_group.len() < repetition {
+                field_group.resize_with(repetition, V2Field::new);
+            }
+            let component = field_group[repetition - 1].get_or_insert_mut(index.component as usize);
+            Self::set_component(component, &index, &escaped, &sep)
+        }
+
+        ///
+        /// Insert a new repetition holding `value` in the field at `search_pattern`. The repetition
+        /// index in the pattern is the position the new repetition takes and the component index is
+        /// where `value` goes in it. Later repetitions are shifted. An unvalued field simply gets
+        /// its first repetition.
+        ///
+        pub fn insert_repetition(&mut self, search_pattern: &str, value: &str) -> V2Result<()> {
+            let index = Self::compile_edit_index(search_pattern)?;
+            let escaped = self.sep.escape(value);
+            let sep = self.sep.clone();
+            let segment = self.get_or_append_mut(&index.segment_name, index.segment_group as usize)?;
+            let field_group = segment.get_or_insert_mut(index.field as usize);
+            if field_group.iter().all(|field| field.is_empty()) {
+                field_group.clear();
+            }
+
+            let position = index.field_group as usize;
+            if position > field_group.len() + 1 {
+                return Err(rumtk_format!(
+                    "Cannot insert repetition {} in {} because the field only has {} repetitions!",
+                    position,
+                    search_pattern,
+                    field_group.len()
+                ));
+            }
+            let mut field = V2Field::new();
+            Self::set_component(field.get_or_insert_mut(index.component as usize), &index, &escaped, &sep)?;
+            field_group.insert(position - 1, field);
+            Ok(())
+        }
+
+        fn set_component(component: &mut V2Component, index: &V2SearchIndex, escaped: &str, sep: &V2ParserCharacters) -> V2Result<()> {
+            match index.subcomponent {
+                0 => {
+                    component.set(escaped);
+                    Ok(())
+                }
+                subcomponent => component.set_subcomponent(subcomponent as isize, escaped, sep),
+            }
+        }
+
+        ///
+        /// Mark the field at `search_pattern` as not populated. Repetition and component indices
+        /// in the pattern are ignored.
+        ///
+        pub fn clear_field(&mut self, search_pattern: &str) -> V2Result<()> {
+            let index = Self::compile_edit_index(search_pattern)?;
+            let segment = self.get_named_mut(&index.segment_name, index.segment_group as usize)?;
+            segment.clear(index.field as usize);
+            Ok(())
+        }
+
+        ///
+        /// Insert an empty segment named `segment_name` right after the segment at `anchor` (e.g.
+        /// `OBX(2)`). Returns the instance number of the new segment among the segments with the
+        /// same name so it can be addressed with the search syntax.
+        ///
+        pub fn insert_segment_after(&mut self, anchor: &str, segment_name: &str) -> V2Result<usize> {
+            let anchor_index = Self::compile_segment_index(anchor)?;
+            let new_segment = Self::new_segment(segment_name)?;
+            let anchor_id = V2_SEGMENT_IDS(anchor_index.segment_name.as_bytes());
+            let anchor_position =
+                self.group_position(&anchor_index.segment_name, anchor_index.segment_group as usize)?;
+            let segment_id = V2_SEGMENT_IDS(segment_name.as_bytes());
+
+            let mut order = self.segment_order();
+            let anchor_order = match order.iter().position(|entry| *entry == (anchor_id, anchor_position)) {
+                Some(anchor_order) => anchor_order,
+                None => return Err(rumtk_format!("Segment {} is not part of the message order!", anchor)),
+            };
+            let position = order[..=anchor_order]
+                .iter()
+                .filter(|(id, _)| *id == segment_id)
+                .count()
+                + 1;
+            for entry in order.iter_mut() {
+                if entry.0 == segment_id && entry.1 >= position {
+                    entry.1 += 1;
+                }
+            }
+            order.insert(anchor_order + 1, (segment_id, position));
+
+            let segment_group = self.sg[(segment_id - 1) as usize].get_or_insert_with(V2SegmentGroup::new);
+            segment_group.insert(position - 1, new_segment);
+            self.so = order;
+
+            let instance = self.get_group(segment_id)?[..position]
+                .iter()
+                .filter(|segment| segment.name(segment_id) == segment_name)
+                .count();
+            Ok(instance)
+        }
+
+        ///
+        /// Remove the segment at `address` (e.g. `NTE(2)`) from the message and hand it back. The
+        /// MSH cannot be removed.
+        ///
+        pub fn remove_segment(&mut self, address: &str) -> V2Result<V2Segment> {
+            let index = Self::compile_segment_index(address)?;
+            let segment_id = V2_SEGMENT_IDS(index.segment_name.as_bytes());
+            if segment_id == V2_MSHEADER_ID {
+                return Err(RUMString::from("The MSH segment cannot be removed!"));
+            }
+            let position = self.group_position(&index.segment_name, index.segment_group as usize)?;
+
+            let mut order = self.segment_order();
+            order.retain(|entry| *entry != (segment_id, position));
+            for entry in order.iter_mut() {
+                if entry.0 == segment_id && entry.1 > position {
+                    entry.1 -= 1;
+                }
+            }
+
+            let indx = (segment_id - 1) as usize;
+            let segment_group = self.get_mut_group(segment_id)?;
+            let segment = segment_group.remove(position - 1);
+            if segment_group.is_empty() {
+                self.sg[indx] = None;
+            }
+            self.so = order;
+            Ok(segment)
+        }
+
+        ///
+        /// Grab the named segment instance for editing. Naming the instance right after the last
+        /// one appends a new segment to the end of the message.
+        ///
+        fn get_or_append_mut(&mut self, segment_name: &str, sub_segment: usize) -> V2Result<&mut V2Segment> {
+            if sub_segment == self.named_segment_count(segment_name) + 1 {
+                let segment = Self::new_segment(segment_name)?;
+                let segment_id = V2_SEGMENT_IDS(segment_name.as_bytes());
+                let mut order = self.segment_order();
+                let segment_group = self.sg[(segment_id - 1) as usize].get_or_insert_with(V2SegmentGroup::new);
+                segment_group.push(segment);
+                order.push((segment_id, segment_group.len()));
+                self.so = order;
+            }
+            self.get_named_mut(segment_name, sub_segment)
+        }
+
+        ///
+        /// 1-indexed position of the named segment instance inside its segment group. Differs from
+        /// the instance only for custom segments since they all share one group.
+        ///
+        fn group_position(&self, segment_name: &str, sub_segment: usize) -> V2Result<usize> {
+            let segment_id = V2_SEGMENT_IDS(segment_name.as_bytes());
+            let segment_group = self.get_group(segment_id)?;
+            let position = segment_group
+                .iter()
+                .enumerate()
+                .filter(|(_, segment)| segment.name(segment_id) == segment_name)
+                .nth(sub_segment.wrapping_sub(1))
+                .map(|(position, _)| position + 1);
+            match position {
+                Some(position) => Ok(position),
+                None => Err(rumtk_format!(
+                    "Segment {}({}) was not found in message!",
+                    segment_name,
+                    sub_segment
+                )),
+            }
+        }
+
+        fn new_segment(segment_name: &str) -> V2Result<V2Segment> {
+            if segment_name.len() != 3 || !segment_name.bytes().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()) {
+                return Err(rumtk_format!(
+                    "Segment name {} is invalid! Expected 3 uppercase letters or digits.",
+                    segment_name
+                ));
+            }
+            if segment_name == V2_MSHEADER_PATTERN_STR {
+                return Err(RUMString::from("A message has a single MSH!"));
+            }
+            let name = match V2_SEGMENT_IDS(segment_name.as_bytes()) {
+                V2_CUSTOM_SEGMENT_ID => Some(V2String::from(segment_name)),
+                _ => None,
+            };
+            Ok(V2Segment { f: V2FieldList::new(), n: name })
+        }
+
+        fn compile_segment_index(search_pattern: &str) -> V2Result<V2SearchIndex> {
+            let index = rumtk_cache_fetch!(&raw mut search_cache, &search_pattern.to_string(), || {compile_search_index(search_pattern)})?;
+            if index.segment_group < 1 {
+                return Err(rumtk_format!("Segment instances are 1-indexed! Got {}!", search_pattern));
+            }
+            Ok(index)
+        }
+
+        ///
+        /// Search index for the editing methods. Unlike lookups, edits need every index to point
+        /// forward and MSH-1/MSH-2 are off limits since they hold the separators.
+        ///
+        fn compile_edit_index(search_pattern: &str) -> V2Result<V2SearchIndex> {
+            let index = Self::compile_segment_index(search_pattern)?;
+            if index.field < 1 || index.field_group < 1 || index.component < 1 {
+                return Err(rumtk_format!(
+                    "Field, repetition and component indices must be positive when editing! Got {}!",
+                    search_pattern
+                ));
+            }
+            if index.segment == V2_MSHEADER_ID && (index.field as isize) < V2Segment::field_index(V2_MSHEADER_ID, 3) {
+                return Err(RUMString::from(
+                    "MSH-1 and MSH-2 hold the separators of the message and cannot be edited!",
+                ));
+            }
+            Ok(index)
+        }
+
+        ///
+        /// Segments in the order they appeared in the original message. Segments added after parsing
+        /// that we have no position for are appended in segment id order. Segments removed after
+        /// parsing are skipped.
+        ///
+        pub fn segment_order(&self) -> V2SegmentOrder {
+            let mut seen = [0usize; V2_TOTAL_VALID_SEGMENTS as usize];
+            let mut order = V2SegmentOrder::with_capacity(self.so.len());
+
+            for (segment_id, sub_segment) in self.so.iter() {
+                let count = self.segment_group_count(*segment_id);
+                if *sub_segment <= count {
+                    order.push((*segment_id, *sub_segment));
+                    let indx = (*segment_id - 1) as usize;
+                    seen[indx] = seen[indx].max(*sub_segment);
+                }
+            }
+
+            for (indx, group) in self.sg.iter().enumerate() {
+                if let Some(segment_group) = group {
+                    for sub_segment in (seen[indx] + 1)..=segment_group.len() {
+                        order.push(((indx + 1) as u8, sub_segment));
+                    }
+                }
+            }
+
+            order
+        }
+
+        ///
+        /// Iterate segments in the order they appeared in the original message. See
+        /// [V2Message::segment_order].
+        ///
+        pub fn ordered_segments(&self) -> RUMVec<(u8, &V2Segment)> {
+            self.segment_order()
+                .into_iter()
+                .filter_map(|(segment_id, sub_segment)| match self.get(segment_id, sub_segment) {
+                    Ok(segment) => Some((segment_id, segment)),
+                    Err(_) => None,
+                })
+                .collect()
+        }
+
         pub fn is_repeat_segment(&self, segment_id: u8) -> bool {
             let _segment_group = self.get_group(segment_id).unwrap();
             _segment_group.len() > 1
@@ -714,6 +1738,19 @@ pub mod v2_parser {
             }
         }
 
+        ///
+        /// Segment terminator used by a raw message when it is not the standard `\r`. Only the first
+        /// terminator is looked at since [V2Message::sanitize] treats them all alike anyway.
+        ///
+        fn find_line_terminator(raw_msg: &[u8]) -> Option<V2String> {
+            let indx = raw_msg.iter().position(|c| *c == b'\r' || *c == b'\n')?;
+            match (raw_msg[indx], raw_msg.get(indx + 1)) {
+                (b'\r', Some(b'\n')) => Some(V2String::from("\r\n")),
+                (b'\n', _) => Some(V2String::from("\n")),
+                _ => None,
+            }
+        }
+
         ///
         /// Sanitizes incoming raw HL7 V2 message. In particular, this method ensures that the message
         /// only contains [V2_SEGMENT_TERMINATOR](V2_SEGMENT_TERMINATOR) as the newline terminator
@@ -775,6 +1812,43 @@ pub mod v2_parser {
             Ok(segments.into())
         }
 
+        ///
+        /// Same as [V2Message::extract_segments] but also records the position of each segment. See
+        /// [V2SegmentOrder].
+        ///
+        #[inline(always)]
+        pub fn extract_ordered_segments(
+            msg: RUMBuffer,
+            parser_chars: &V2ParserCharacters,
+        ) -> V2Result<(V2SegmentMap, V2SegmentOrder)> {
+            debug_assert!(msg.is_view(), "Somewhere you forgot to call freeze() on RUMBuffer to generate a copy in View mode!");
+            let mut segments = rumtk_mem_quick_array_init!(Option<V2SegmentGroup>, V2_TOTAL_VALID_SEGMENTS as usize, None);
+            let mut order = V2SegmentOrder::with_capacity(32);
+
+            let mut splitter = msg.split_fast(parser_chars.segment_terminator);
+            for segment in &mut splitter {
+                if !segment.is_empty() {
+                    order.push(V2Message::push_to_ordered_group(&mut segments, V2Segment::from(segment, parser_chars)?));
+                }
+            }
+            if !splitter.remainder.is_empty() {
+                order.push(V2Message::push_to_ordered_group(&mut segments, V2Segment::from(splitter.remainder, parser_chars)?));
+            }
+
+            Ok((segments.into(), order))
+        }
+
+        #[inline(always)]
+        fn push_to_ordered_group(group: &mut V2SegmentArray, segment: (u8, V2Segment)) -> (u8, usize) {
+            let segment_id = segment.0;
+            V2Message::push_to_group(group, segment);
+            let sub_segment = match &group[(segment_id - 1) as usize] {
+                Some(segment_set) => segment_set.len(),
+                None => 0,
+            };
+            (segment_id, sub_segment)
+        }
+
         #[inline(always)]
         pub fn push_to_group(group: &mut V2SegmentArray, segment: (u8, V2Segment)) {
             let indx = segment.0 - 1;
@@ -799,7 +1873,7 @@ pub mod v2_parser {
 
     impl PartialEq for V2Message {
         fn eq(&self, other: &V2Message) -> bool {
-            self.sep == other.sep && self.sg == other.sg
+            self.sep == other.sep && self.sg == other.sg && self.segment_order() == other.segment_order()
         }
     }
 
@@ -854,28 +1928,28 @@ pub mod v2_parser {
     impl<'a> TryFrom<V2String> for V2Message {
         type Error = RUMString;
         fn try_from(input: V2String) -> V2Result<V2Message> {
-            V2Message::try_from(string_to_buffer(input.as_str()))
+            V2Message::try_from_text(&input)
         }
     }
 
     impl<'a> TryFrom<&V2String> for V2Message {
         type Error = RUMString;
         fn try_from(input: &V2String) -> V2Result<V2Message> {
-            V2Message::try_from(input.as_str())
+            V2Message::try_from_text(input)
         }
     }
 
     impl<'a> TryFrom<&str> for V2Message {
         type Error = RUMString;
         fn try_from(input: &str) -> V2Result<V2Message> {
-            V2Message::try_from(input.as_bytes())
+            V2Message::try_from_text(input)
         }
     }
 
     impl<'a> TryFrom<&&str> for V2Message {
         type Error = RUMString;
         fn try_from(input: &&str) -> V2Result<V2Message> {
-            V2Message::try_from(input.as_bytes())
+            V2Message::try_from_text(input)
         }
     }
 }
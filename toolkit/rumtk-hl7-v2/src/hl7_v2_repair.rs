@@ -0,0 +1,348 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2026  Luis M. Santos, M.D. <lsantos@medicalmasses.com>
+ * Copyright (C) 2026  MedicalMasses L.L.C. <contact@medicalmasses.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+///
+/// Repairs for malformed messages coming from vendor feeds. [v2_repair] runs before parsing and
+/// either fixes the raw bytes ([V2ParseMode::Lenient]) or rejects the message
+/// ([V2ParseMode::Strict]). Every repair is recorded as a [V2ParseFix] so interfaces can audit
+/// what was changed in a message they accepted.
+///
+/// Defects handled:
+///
+/// * Byte order marks and anything before `MSH` (e.g. leftover MLLP framing).
+/// * `\n` and `\r\n` segment terminators, and blank segments.
+/// * Control characters inside segments.
+/// * Missing or incomplete encoding characters in MSH-2.
+/// * Segments whose name is not a valid segment id (e.g. truncated lines).
+/// * Trailing field separators. These are allowed by the standard so they are only trimmed in
+///   lenient mode and never rejected.
+///
+/// ## Example
+///
+/// ```
+/// use rumtk_hl7_v2::hl7_v2_parser::v2_parser::V2Message;
+/// use rumtk_hl7_v2::hl7_v2_repair::v2_repair::{V2ParseFixKind, V2ParseOptions};
+///
+/// let raw = b"\xef\xbb\xbfMSH|^~\\&|LAB|FAC|||20250101120000||ORU^R01^ORU_R01|MSG0001|P|2.5.1\r\nPID|1||12345|||\r\n";
+/// let (message, fixes) = V2Message::try_from_bytes_with(raw, &V2ParseOptions::lenient()).unwrap();
+///
+/// assert_eq!(message.find_value("PID3").unwrap(), "12345", "Repaired message should parse!");
+/// assert_eq!(fixes[0].kind, V2ParseFixKind::ByteOrderMark, "BOM removal should be recorded!");
+/// assert!(V2Message::try_from_bytes_with(raw, &V2ParseOptions::strict()).is_err(), "Strict mode must reject the message!");
+/// ```
+///
+pub mod v2_repair {
+    use crate::hl7_v2_base_types::v2_primitives::{V2Result, V2String};
+    use crate::hl7_v2_constants::{V2_MSHEADER_PATTERN, V2_SEGMENT_TERMINATOR};
+    use rumtk_core::base::RUMVec;
+    use rumtk_core::strings::{rumtk_format, RUMString};
+    use std::fmt::{Display, Formatter};
+
+    pub const V2_UTF8_BOM: &[u8; 3] = b"\xef\xbb\xbf";
+    pub const V2_DEFAULT_ENCODING_CHARACTERS: &[u8; 4] = b"^~\\&";
+
+    /**************************** Types *****************************************/
+
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub enum V2ParseMode {
+        /// Reject messages with any of the defects listed in [crate::hl7_v2_repair::v2_repair].
+        Strict,
+        /// Repair what we can and record each fix.
+        #[default]
+        Lenient,
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq)]
+    pub struct V2ParseOptions {
+        pub mode: V2ParseMode,
+    }
+
+    impl V2ParseOptions {
+        pub fn strict() -> Self {
+            V2ParseOptions {
+                mode: V2ParseMode::Strict,
+            }
+        }
+
+        pub fn lenient() -> Self {
+            V2ParseOptions {
+                mode: V2ParseMode::Lenient,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum V2ParseFixKind {
+        ByteOrderMark,
+        LeadingData,
+        SegmentTerminator,
+        EmptySegment,
+        ControlCharacter,
+        EncodingCharacters,
+        InvalidSegment,
+        TrailingSeparator,
+    }
+
+    ///
+    /// One repair made to a message.
+    ///
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct V2ParseFix {
+        pub kind: V2ParseFixKind,
+        /// 1-indexed line of the original message the fix applies to. 0 for the message as a whole.
+        pub line: usize,
+        pub description: V2String,
+    }
+
+    impl V2ParseFix {
+        fn new(kind: V2ParseFixKind, line: usize, description: V2String) -> Self {
+            V2ParseFix {
+                kind,
+                line,
+                description,
+            }
+        }
+    }
+
+    impl Display for V2ParseFix {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self.line {
+                0 => write!(f, "{}", self.description),
+                line => write!(f, "Line {}: {}", line, self.description),
+            }
+        }
+    }
+
+    pub type V2ParseFixes = RUMVec<V2ParseFix>;
+
+    /**************************** API *******************************************/
+
+    ///
+    /// Repair `raw` according to `options`. Returns the bytes to parse along with the fixes made.
+    /// In strict mode, any defect is an error listing everything that would have been fixed.
+    /// Messages without an MSH segment or field separator cannot be repaired in either mode.
+    ///
+    pub fn v2_repair(raw: &[u8], options: &V2ParseOptions) -> V2Result<(RUMVec<u8>, V2ParseFixes)> {
+        let mut fixes = V2ParseFixes::new();
+        let mut data = raw;
+
+        if data.starts_with(V2_UTF8_BOM) {
+            fixes.push(V2ParseFix::new(V2ParseFixKind::ByteOrderMark, 0, V2String::from("Removed UTF-8 byte order mark.")));
+            data = &data[V2_UTF8_BOM.len()..];
+        }
+
+        let msh_start = match data.windows(V2_MSHEADER_PATTERN.len()).position(|window| window == V2_MSHEADER_PATTERN) {
+            Some(msh_start) => msh_start,
+            None => return Err(RUMString::from("No MSH segment found! The message cannot be repaired!")),
+        };
+        if msh_start > 0 {
+            fixes.push(V2ParseFix::new(
+                V2ParseFixKind::LeadingData,
+                0,
+                rumtk_format!("Removed {} bytes before MSH.", msh_start),
+            ));
+            data = &data[msh_start..];
+        }
+
+        let field_separator = match data.get(V2_MSHEADER_PATTERN.len()) {
+            Some(field_separator) if is_delimiter(*field_separator) => *field_separator,
+            _ => return Err(RUMString::from("MSH segment is missing its field separator! The message cannot be repaired!")),
+        };
+
+        let line_feeds = data.iter().filter(|c| **c == b'\n').count();
+        if line_feeds > 0 {
+            fixes.push(V2ParseFix::new(
+                V2ParseFixKind::SegmentTerminator,
+                0,
+                rumtk_format!("Replaced {} line feeds with carriage returns.", line_feeds),
+            ));
+        }
+
+        let lenient = options.mode == V2ParseMode::Lenient;
+        let mut repaired = RUMVec::with_capacity(data.len() + V2_DEFAULT_ENCODING_CHARACTERS.len());
+        for (indx, raw_line) in split_lines(data).into_iter().enumerate() {
+            let line = indx + 1;
+            if raw_line.is_empty() {
+                fixes.push(V2ParseFix::new(V2ParseFixKind::EmptySegment, line, V2String::from("Removed blank segment.")));
+                continue;
+            }
+
+            let mut segment: RUMVec<u8> = raw_line.iter().filter(|c| !is_control(**c)).copied().collect();
+            let removed = raw_line.len() - segment.len();
+            if removed > 0 {
+                fixes.push(V2ParseFix::new(
+                    V2ParseFixKind::ControlCharacter,
+                    line,
+                    rumtk_format!("Removed {} control characters.", removed),
+                ));
+            }
+            if segment.is_empty() {
+                continue;
+            }
+
+            if !is_segment_name(&segment, field_separator) {
+                fixes.push(V2ParseFix::new(
+                    V2ParseFixKind::InvalidSegment,
+                    line,
+                    rumtk_format!("Dropped segment with invalid name: {:?}.", V2String::from_utf8_lossy(&segment[..segment.len().min(16)])),
+                ));
+                continue;
+            }
+
+            let is_msh = repaired.is_empty();
+            if is_msh {
+                if let Some(fix) = repair_encoding_characters(&mut segment, field_separator) {
+                    fixes.push(V2ParseFix::new(V2ParseFixKind::EncodingCharacters, line, fix));
+                }
+            }
+
+            if lenient {
+                let minimum = match is_msh {
+                    true => msh_minimum_len(&segment, field_separator),
+                    false => V2_MSHEADER_PATTERN.len(),
+                };
+                let mut trimmed = segment.len();
+                while trimmed > minimum && segment[trimmed - 1] == field_separator {
+                    trimmed -= 1;
+                }
+                if trimmed < segment.len() {
+                    fixes.push(V2ParseFix::new(
+                        V2ParseFixKind::TrailingSeparator,
+                        line,
+                        rumtk_format!("Removed {} trailing field separators.", segment.len() - trimmed),
+                    ));
+                    segment.truncate(trimmed);
+                }
+            }
+
+            repaired.extend_from_slice(&segment);
+            repaired.push(V2_SEGMENT_TERMINATOR);
+        }
+
+        if !lenient && !fixes.is_empty() {
+            let defects: RUMVec<V2String> = fixes.iter().map(|fix| fix.to_string()).collect();
+            return Err(rumtk_format!("Message rejected by strict parsing! {}", defects.join(" ")));
+        }
+
+        Ok((repaired, fixes))
+    }
+
+    /**************************** Helpers ***************************************/
+
+    #[inline(always)]
+    fn is_control(c: u8) -> bool {
+        (c < 0x20 && c != b'\t') || c == 0x7f
+    }
+
+    #[inline(always)]
+    fn is_delimiter(c: u8) -> bool {
+        c.is_ascii_punctuation()
+    }
+
+    fn is_segment_name(segment: &[u8], field_separator: u8) -> bool {
+        let name_len = V2_MSHEADER_PATTERN.len();
+        segment.len() >= name_len
+            && segment[..name_len].iter().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+            && segment[0].is_ascii_uppercase()
+            && (segment.len() == name_len || segment[name_len] == field_separator)
+    }
+
+    ///
+    /// Lines of `data`. `\r\n` and `\n\r` count as a single terminator and the terminator at the
+    /// end of the message does not start a new line.
+    ///
+    fn split_lines(data: &[u8]) -> RUMVec<&[u8]> {
+        let mut lines = RUMVec::with_capacity(32);
+        let mut start = 0;
+        let mut indx = 0;
+        while indx < data.len() {
+            let c = data[indx];
+            if c == b'\r' || c == b'\n' {
+                lines.push(&data[start..indx]);
+                let pair = if c == b'\r' { b'\n' } else { b'\r' };
+                if data.get(indx + 1) == Some(&pair) {
+                    indx += 1;
+                }
+                start = indx + 1;
+            }
+            indx += 1;
+        }
+        if start < data.len() {
+            lines.push(&data[start..]);
+        }
+        lines
+    }
+
+    ///
+    /// Shortest length MSH can be trimmed to without losing MSH-2 and the separator after it.
+    ///
+    fn msh_minimum_len(segment: &[u8], field_separator: u8) -> usize {
+        let encoding_start = V2_MSHEADER_PATTERN.len() + 1;
+        match segment[encoding_start..].iter().position(|c| *c == field_separator) {
+            Some(len) => encoding_start + len + 1,
+            None => segment.len(),
+        }
+    }
+
+    ///
+    /// Make sure MSH-2 holds at least the component, repetition, escape and subcomponent
+    /// characters. Missing ones are filled in with the defaults. If MSH-2 does not look like
+    /// encoding characters at all, the defaults are inserted as a new field.
+    ///
+    fn repair_encoding_characters(segment: &mut RUMVec<u8>, field_separator: u8) -> Option<V2String> {
+        let encoding_start = V2_MSHEADER_PATTERN.len() + 1;
+        let encoding_end = match segment.get(encoding_start..).and_then(|rest| rest.iter().position(|c| *c == field_separator)) {
+            Some(len) => encoding_start + len,
+            None => segment.len(),
+        };
+        let encoding = &segment[encoding_start.min(encoding_end)..encoding_end];
+        if encoding.is_empty() {
+            segment.splice(encoding_start..encoding_start, V2_DEFAULT_ENCODING_CHARACTERS.iter().copied());
+            return Some(V2String::from("Filled empty MSH-2 with the default encoding characters."));
+        }
+
+        let mut seen = vec![field_separator];
+        let looks_like_encoding = encoding.len() <= 5
+            && encoding.iter().all(|c| {
+                let unique = !seen.contains(c);
+                seen.push(*c);
+                is_delimiter(*c) && unique
+            });
+        if !looks_like_encoding {
+            let mut inserted = V2_DEFAULT_ENCODING_CHARACTERS.to_vec();
+            inserted.push(field_separator);
+            segment.splice(encoding_start..encoding_start, inserted);
+            return Some(V2String::from("Inserted missing encoding characters in MSH-2."));
+        }
+
+        if encoding.len() >= V2_DEFAULT_ENCODING_CHARACTERS.len() {
+            return None;
+        }
+        let mut missing = RUMVec::new();
+        for c in V2_DEFAULT_ENCODING_CHARACTERS[encoding.len()..].iter() {
+            if !encoding.contains(c) && *c != field_separator {
+                missing.push(*c);
+            }
+        }
+        let added = missing.len();
+        segment.splice(encoding_end..encoding_end, missing);
+        Some(rumtk_format!("Completed MSH-2 with {} default encoding characters.", added))
+    }
+}
@@ -19,6 +19,9 @@
  */
 
 pub use rumtk_core::search::rumtk_search::*;
+use crate::hl7_v2_base_types::v2_primitives::{V2Result, V2String};
+use crate::hl7_v2_constants::V2_SEARCH_EXPR_TYPE;
+use rumtk_core::strings::rumtk_format;
 
 /**************************** Globals **************************************/
 
@@ -29,6 +32,301 @@ pub const REGEX_V2_SEARCH_DEFAULT: &str = r"(?<segment>\w{3})|(\((?<segment_grou
 
 /**************************** Types *****************************************/
 
+///
+/// Picks one instance (1-indexed) or every instance (`*`) of a segment or repetition.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum V2SearchSelector {
+    Index(usize),
+    All,
+}
+
+impl V2SearchSelector {
+    pub fn matches(&self, indx: usize) -> bool {
+        match self {
+            V2SearchSelector::Index(selected) => *selected == indx,
+            V2SearchSelector::All => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum V2SearchOperator {
+    Equal,
+    NotEqual,
+}
+
+///
+/// Location of a value inside a segment instance. Fields are numbered like in
+/// [REGEX_V2_SEARCH_DEFAULT] expressions, so a negative field counts from the end.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct V2SearchPath {
+    pub field: isize,
+    pub repetition: V2SearchSelector,
+    pub component: usize,
+    pub subcomponent: Option<usize>,
+}
+
+///
+/// Condition a segment instance has to meet to be searched, e.g. `OBX-3.1='GLU'`. If the path
+/// selects several repetitions, `=` holds if any of them matches and `!=` if none does.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct V2SearchPredicate {
+    pub path: V2SearchPath,
+    pub operator: V2SearchOperator,
+    pub value: V2String,
+}
+
+///
+/// Extended search expression.
+///
+/// ```text
+/// SEG[(instance)][[predicate]...][-]field[[repetition]][.component[.subcomponent]]
+/// ```
+///
+/// * `instance` and `repetition` are a 1-indexed number or `*` for all of them.
+/// * A single dash before the field is the usual HL7 notation, like in the default grammar, so
+///   `OBX(2)-5` and `OBX(2)5` are both OBX-5. The field may be negative to count from the end,
+///   which takes a second dash (e.g. `OBX(2)--1` is the last field).
+/// * The instance defaults to 1 unless predicates are given, in which case every instance whose
+///   predicates hold is searched.
+/// * Predicates are a path within the same segment instance, optionally prefixed with the segment
+///   name, an `=` or `!=` and a quoted value, e.g. `OBX[OBX-3.1='GLU']-5`.
+///
+/// ## Example
+///
+/// ```
+/// use rumtk_hl7_v2::hl7_v2_search::{V2SearchExpression, V2SearchSelector};
+///
+/// let expr = V2SearchExpression::parse("OBX(*)-5[*].1.2").unwrap();
+///
+/// assert_eq!(expr.segment_group, V2SearchSelector::All, "Expected every OBX!");
+/// assert_eq!(expr.path.field, 5, "Wrong field!");
+/// assert_eq!(expr.path.subcomponent, Some(2), "Wrong subcomponent!");
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct V2SearchExpression {
+    pub segment_name: V2String,
+    pub segment_group: V2SearchSelector,
+    pub predicates: Vec<V2SearchPredicate>,
+    pub path: V2SearchPath,
+}
+
+impl V2SearchExpression {
+    pub fn parse(expr: &str) -> V2Result<V2SearchExpression> {
+        let mut cursor = V2SearchCursor::new(expr);
+        let segment_name = cursor.take_segment_name()?;
+        let segment_group = match cursor.eat("(") {
+            true => {
+                let selector = cursor.take_selector()?;
+                cursor.expect(")")?;
+                Some(selector)
+            }
+            false => None,
+        };
+
+        let mut predicates = Vec::new();
+        while cursor.eat("[") {
+            predicates.push(cursor.take_predicate(&segment_name)?);
+            cursor.expect("]")?;
+        }
+
+        cursor.eat("-");
+        let path = cursor.take_path()?;
+        if !cursor.at_end() {
+            return Err(cursor.error("Unexpected trailing characters"));
+        }
+
+        let segment_group = match (segment_group, predicates.is_empty()) {
+            (Some(segment_group), _) => segment_group,
+            (None, true) => V2SearchSelector::Index(1),
+            (None, false) => V2SearchSelector::All,
+        };
+        Ok(V2SearchExpression {
+            segment_name,
+            segment_group,
+            predicates,
+            path,
+        })
+    }
+}
+
+
 /**************************** Traits ****************************************/
 
 /**************************** Helpers ***************************************/
+
+///
+/// Tell which grammar `expr` is written in. Wildcards, predicates and subcomponents are only
+/// understood by [V2SearchExpression].
+///
+pub fn v2_search_expr_type(expr: &str) -> V2_SEARCH_EXPR_TYPE {
+    match expr.contains('*') || expr.contains('=') || expr.matches('.').count() > 1 {
+        true => V2_SEARCH_EXPR_TYPE::V2_EXTENDED,
+        false => V2_SEARCH_EXPR_TYPE::V2_DEFAULT,
+    }
+}
+
+///
+/// Hand rolled scanner shared by the search grammars so errors can point at the offending
+/// position.
+///
+pub(crate) struct V2SearchCursor<'a> {
+    expr: &'a str,
+    pos: usize,
+}
+
+impl<'a> V2SearchCursor<'a> {
+    pub(crate) fn new(expr: &'a str) -> Self {
+        V2SearchCursor { expr, pos: 0 }
+    }
+
+    pub(crate) fn rest(&self) -> &'a str {
+        &self.expr[self.pos..]
+    }
+
+    pub(crate) fn position(&self) -> usize {
+        self.pos
+    }
+
+    pub(crate) fn expr(&self) -> &'a str {
+        self.expr
+    }
+
+    pub(crate) fn at_end(&self) -> bool {
+        self.pos >= self.expr.len()
+    }
+
+    pub(crate) fn eat(&mut self, token: &str) -> bool {
+        match self.rest().starts_with(token) {
+            true => {
+                self.pos += token.len();
+                true
+            }
+            false => false,
+        }
+    }
+
+    pub(crate) fn expect(&mut self, token: &str) -> V2Result<()> {
+        match self.eat(token) {
+            true => Ok(()),
+            false => Err(self.error(&rumtk_format!("Expected '{}'", token))),
+        }
+    }
+
+    pub(crate) fn error(&self, reason: &str) -> V2String {
+        rumtk_format!(
+            "{} at position {} of search expression {}!",
+            reason,
+            self.pos,
+            self.expr
+        )
+    }
+
+    pub(crate) fn take_segment_name(&mut self) -> V2Result<V2String> {
+        match self.rest().get(..3) {
+            Some(name) if name.bytes().all(|c| c.is_ascii_alphanumeric()) => {
+                self.pos += 3;
+                Ok(V2String::from(name))
+            }
+            _ => Err(self.error("Expected a 3 character segment name")),
+        }
+    }
+
+    fn take_number(&mut self) -> V2Result<usize> {
+        let digits = self.rest().bytes().take_while(|c| c.is_ascii_digit()).count();
+        match self.rest()[..digits].parse::<usize>() {
+            Ok(number) if number > 0 => {
+                self.pos += digits;
+                Ok(number)
+            }
+            _ => Err(self.error("Expected a 1-indexed number")),
+        }
+    }
+
+    ///
+    /// Optionally signed integer. Zero is accepted so the caller can decide what it means.
+    ///
+    pub(crate) fn take_integer(&mut self) -> V2Result<i64> {
+        let start = self.pos;
+        self.eat("-");
+        let digits = self.rest().bytes().take_while(|c| c.is_ascii_digit()).count();
+        match self.expr[start..self.pos + digits].parse::<i64>() {
+            Ok(number) => {
+                self.pos += digits;
+                Ok(number)
+            }
+            Err(_) => {
+                self.pos = start;
+                Err(self.error("Expected an integer"))
+            }
+        }
+    }
+
+    fn take_selector(&mut self) -> V2Result<V2SearchSelector> {
+        match self.eat("*") {
+            true => Ok(V2SearchSelector::All),
+            false => Ok(V2SearchSelector::Index(self.take_number()?)),
+        }
+    }
+
+    fn take_path(&mut self) -> V2Result<V2SearchPath> {
+        let field = match self.take_integer()? {
+            0 => return Err(self.error("Fields are 1-indexed")),
+            field => field as isize,
+        };
+        let repetition = match self.eat("[") {
+            true => {
+                let selector = self.take_selector()?;
+                self.expect("]")?;
+                selector
+            }
+            false => V2SearchSelector::Index(1),
+        };
+        let component = match self.eat(".") {
+            true => self.take_number()?,
+            false => 1,
+        };
+        let subcomponent = match self.eat(".") {
+            true => Some(self.take_number()?),
+            false => None,
+        };
+        Ok(V2SearchPath {
+            field,
+            repetition,
+            component,
+            subcomponent,
+        })
+    }
+
+    fn take_predicate(&mut self, segment_name: &str) -> V2Result<V2SearchPredicate> {
+        if self.rest().starts_with(segment_name) {
+            self.pos += segment_name.len();
+            self.eat("-");
+        }
+        let path = self.take_path()?;
+        let operator = match (self.eat("!="), self.eat("=")) {
+            (true, _) => V2SearchOperator::NotEqual,
+            (false, true) => V2SearchOperator::Equal,
+            _ => return Err(self.error("Expected '=' or '!='")),
+        };
+        let quote = match self.rest().chars().next() {
+            Some(quote) if quote == '\'' || quote == '"' => quote,
+            _ => return Err(self.error("Expected a quoted value")),
+        };
+        self.pos += 1;
+        let value = match self.rest().find(quote) {
+            Some(len) => V2String::from(&self.rest()[..len]),
+            None => return Err(self.error("Unterminated value")),
+        };
+        self.pos += value.len() + 1;
+        Ok(V2SearchPredicate {
+            path,
+            operator,
+            value,
+        })
+    }
+}
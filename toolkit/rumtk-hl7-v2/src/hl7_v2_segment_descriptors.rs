@@ -0,0 +1,621 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2026  Luis M. Santos, M.D. <lsantos@medicalmasses.com>
+ * Copyright (C) 2026  MedicalMasses L.L.C. <contact@medicalmasses.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+///
+/// Segment schemas. Each entry maps the fields of a segment, in sequence order, to the data type
+/// we should cast them into. Entries reuse [V2ComponentTypeDescriptor] so the same casting logic
+/// used for components in complex types applies to whole fields.
+///
+/// Field definitions follow v2.5.1 since it is the version we see the most in the wild.
+///
+/// Segment schemas are mostly not versioned. Casting with a [V2Version](crate::hl7_v2_field_descriptors::v2_field_descriptor::V2Version)
+/// picks the components of each data type for that release, but the data type of a field is the
+/// one listed here. Fields whose type changed between releases are therefore cast with the
+/// type below for every version, e.g. PID-7 is TS in v2.3 to v2.5 and DTM from v2.6 on. The
+/// exception are fields that were reserved in v2.5.1 and defined later, such as OBX-20 to OBX-22.
+/// They are listed with their current definition and the reserved placeholders are kept in
+/// [V2_SEGMENT_DESCRIPTORS_PRE_V2_6](v2_segment_descriptor::V2_SEGMENT_DESCRIPTORS_PRE_V2_6).
+///
+/// ## Notes
+///
+/// * Withdrawn fields whose data type was also withdrawn (TQ in ORC-7 and OBR-27, SPS in OBR-15) are
+///   kept as backwards compatible ST placeholders so field numbering stays aligned.
+/// * MSH is described per the standard, meaning MSH-1 is the field separator. The parser does not
+///   store MSH-1 as a field so consumers must shift the index. See [segment_field_index].
+/// * Site specific segments (Z segments) are declared at runtime. See [register_custom_segment].
+///
+pub mod v2_segment_descriptor {
+    use crate::hl7_v2_base_types::v2_primitives::{V2PrimitiveType, V2Result, V2String};
+    use crate::hl7_v2_constants::{V2_CUSTOM_SEGMENT_ID, V2_SEGMENT_IDS};
+    pub use crate::hl7_v2_field_descriptors::v2_field_descriptor::*;
+    use crate::hl7_v2_parser::v2_parser::V2Segment;
+    use crate::v2_component_descriptor;
+    use ::phf::Map;
+    use ::phf_macros::phf_map;
+    use rumtk_core::cache::{new_cache, LazyRUMCache};
+    use rumtk_core::strings::rumtk_format;
+    use rumtk_core::{rumtk_cache_get, rumtk_cache_push};
+
+    static mut CUSTOM_SEGMENT_REGISTRY: LazyRUMCache<V2String, V2CustomSegment> = new_cache();
+
+    pub type V2SegmentDescriptors = Map<&'static str, &'static V2ComponentDescriptor>;
+
+    pub static V2_SEGMENT_DESCRIPTORS: V2SegmentDescriptors = phf_map! {
+        "MSH" => &[
+            v2_component_descriptor!("field_separator", "Field Separator", V2ComponentType::Primitive(V2PrimitiveType::ST), 1, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("encoding_characters", "Encoding Characters", V2ComponentType::Primitive(V2PrimitiveType::ST), 4, 2, 0, Optionality::R, false),
+            v2_component_descriptor!("sending_application", "Sending Application", V2ComponentType::Complex(V2ComplexType::HD), 0, 3, 361, Optionality::O, false),
+            v2_component_descriptor!("sending_facility", "Sending Facility", V2ComponentType::Complex(V2ComplexType::HD), 0, 4, 362, Optionality::O, false),
+            v2_component_descriptor!("receiving_application", "Receiving Application", V2ComponentType::Complex(V2ComplexType::HD), 0, 5, 361, Optionality::O, false),
+            v2_component_descriptor!("receiving_facility", "Receiving Facility", V2ComponentType::Complex(V2ComplexType::HD), 0, 6, 362, Optionality::O, false),
+            v2_component_descriptor!("message_datetime", "Date/Time of Message", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 7, 0, Optionality::R, false),
+            v2_component_descriptor!("security", "Security", V2ComponentType::Primitive(V2PrimitiveType::ST), 40, 8, 0, Optionality::O, false),
+            v2_component_descriptor!("message_type", "Message Type", V2ComponentType::Complex(V2ComplexType::MSG), 0, 9, 0, Optionality::R, false),
+            v2_component_descriptor!("message_control_id", "Message Control ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 10, 0, Optionality::R, false),
+            v2_component_descriptor!("processing_id", "Processing ID", V2ComponentType::Complex(V2ComplexType::PT), 0, 11, 0, Optionality::R, false),
+            v2_component_descriptor!("version_id", "Version ID", V2ComponentType::Complex(V2ComplexType::VID), 0, 12, 0, Optionality::R, false),
+            v2_component_descriptor!("sequence_number", "Sequence Number", V2ComponentType::Primitive(V2PrimitiveType::NM), 15, 13, 0, Optionality::O, false),
+            v2_component_descriptor!("continuation_pointer", "Continuation Pointer", V2ComponentType::Primitive(V2PrimitiveType::ST), 180, 14, 0, Optionality::O, true),
+            v2_component_descriptor!("accept_ack_type", "Accept Acknowledgment Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 15, 155, Optionality::O, false),
+            v2_component_descriptor!("application_ack_type", "Application Acknowledgment Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 16, 155, Optionality::O, false),
+            v2_component_descriptor!("country_code", "Country Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 17, 399, Optionality::O, false),
+            v2_component_descriptor!("character_set", "Character Set", V2ComponentType::Primitive(V2PrimitiveType::ID), 16, 18, 211, Optionality::O, false),
+            v2_component_descriptor!("principal_language", "Principal Language Of Message", V2ComponentType::Complex(V2ComplexType::CE), 0, 19, 0, Optionality::O, false),
+            v2_component_descriptor!("alt_character_set_handling", "Alternate Character Set Handling Scheme", V2ComponentType::Primitive(V2PrimitiveType::ID), 20, 20, 356, Optionality::O, false),
+            v2_component_descriptor!("message_profile_id", "Message Profile Identifier", V2ComponentType::Complex(V2ComplexType::EI), 0, 21, 0, Optionality::O, false)
+        ],
+        "PID" => &[
+            v2_component_descriptor!("set_id", "Set ID - PID", V2ComponentType::Primitive(V2PrimitiveType::SI), 4, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("patient_id", "Patient ID", V2ComponentType::Complex(V2ComplexType::CX), 0, 2, 0, Optionality::B, false),
+            v2_component_descriptor!("patient_identifier_list", "Patient Identifier List", V2ComponentType::Complex(V2ComplexType::CX), 0, 3, 0, Optionality::R, false),
+            v2_component_descriptor!("alternate_patient_id", "Alternate Patient ID - PID", V2ComponentType::Complex(V2ComplexType::CX), 0, 4, 0, Optionality::B, false),
+            v2_component_descriptor!("patient_name", "Patient Name", V2ComponentType::Complex(V2ComplexType::XPN), 0, 5, 0, Optionality::R, false),
+            v2_component_descriptor!("mothers_maiden_name", "Mother's Maiden Name", V2ComponentType::Complex(V2ComplexType::XPN), 0, 6, 0, Optionality::O, false),
+            v2_component_descriptor!("datetime_of_birth", "Date/Time of Birth", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 7, 0, Optionality::O, false),
+            v2_component_descriptor!("administrative_sex", "Administrative Sex", V2ComponentType::Primitive(V2PrimitiveType::IS), 1, 8, 1, Optionality::O, false),
+            v2_component_descriptor!("patient_alias", "Patient Alias", V2ComponentType::Complex(V2ComplexType::XPN), 0, 9, 0, Optionality::B, false),
+            v2_component_descriptor!("race", "Race", V2ComponentType::Complex(V2ComplexType::CE), 0, 10, 5, Optionality::O, false),
+            v2_component_descriptor!("patient_address", "Patient Address", V2ComponentType::Complex(V2ComplexType::XAD), 0, 11, 0, Optionality::O, false),
+            v2_component_descriptor!("county_code", "County Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 4, 12, 289, Optionality::B, false),
+            v2_component_descriptor!("phone_number_home", "Phone Number - Home", V2ComponentType::Complex(V2ComplexType::XTN), 0, 13, 0, Optionality::O, false),
+            v2_component_descriptor!("phone_number_business", "Phone Number - Business", V2ComponentType::Complex(V2ComplexType::XTN), 0, 14, 0, Optionality::O, false),
+            v2_component_descriptor!("primary_language", "Primary Language", V2ComponentType::Complex(V2ComplexType::CE), 0, 15, 296, Optionality::O, false),
+            v2_component_descriptor!("marital_status", "Marital Status", V2ComponentType::Complex(V2ComplexType::CE), 0, 16, 2, Optionality::O, false),
+            v2_component_descriptor!("religion", "Religion", V2ComponentType::Complex(V2ComplexType::CE), 0, 17, 6, Optionality::O, false),
+            v2_component_descriptor!("patient_account_number", "Patient Account Number", V2ComponentType::Complex(V2ComplexType::CX), 0, 18, 0, Optionality::O, false),
+            v2_component_descriptor!("ssn_number", "SSN Number - Patient", V2ComponentType::Primitive(V2PrimitiveType::ST), 16, 19, 0, Optionality::B, false),
+            v2_component_descriptor!("drivers_license_number", "Driver's License Number - Patient", V2ComponentType::Complex(V2ComplexType::DLN), 0, 20, 0, Optionality::B, false),
+            v2_component_descriptor!("mothers_identifier", "Mother's Identifier", V2ComponentType::Complex(V2ComplexType::CX), 0, 21, 0, Optionality::O, false),
+            v2_component_descriptor!("ethnic_group", "Ethnic Group", V2ComponentType::Complex(V2ComplexType::CE), 0, 22, 189, Optionality::O, false),
+            v2_component_descriptor!("birth_place", "Birth Place", V2ComponentType::Primitive(V2PrimitiveType::ST), 250, 23, 0, Optionality::O, true),
+            v2_component_descriptor!("multiple_birth_indicator", "Multiple Birth Indicator", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 24, 136, Optionality::O, false),
+            v2_component_descriptor!("birth_order", "Birth Order", V2ComponentType::Primitive(V2PrimitiveType::NM), 2, 25, 0, Optionality::O, false),
+            v2_component_descriptor!("citizenship", "Citizenship", V2ComponentType::Complex(V2ComplexType::CE), 0, 26, 171, Optionality::O, false),
+            v2_component_descriptor!("veterans_military_status", "Veterans Military Status", V2ComponentType::Complex(V2ComplexType::CE), 0, 27, 172, Optionality::O, false),
+            v2_component_descriptor!("nationality", "Nationality", V2ComponentType::Complex(V2ComplexType::CE), 0, 28, 212, Optionality::B, false),
+            v2_component_descriptor!("patient_death_datetime", "Patient Death Date and Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 29, 0, Optionality::O, false),
+            v2_component_descriptor!("patient_death_indicator", "Patient Death Indicator", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 30, 136, Optionality::O, false),
+            v2_component_descriptor!("identity_unknown_indicator", "Identity Unknown Indicator", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 31, 136, Optionality::O, false),
+            v2_component_descriptor!("identity_reliability_code", "Identity Reliability Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 32, 445, Optionality::O, false),
+            v2_component_descriptor!("last_update_datetime", "Last Update Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 33, 0, Optionality::O, false),
+            v2_component_descriptor!("last_update_facility", "Last Update Facility", V2ComponentType::Complex(V2ComplexType::HD), 0, 34, 0, Optionality::O, false),
+            v2_component_descriptor!("species_code", "Species Code", V2ComponentType::Complex(V2ComplexType::CE), 0, 35, 446, Optionality::O, false),
+            v2_component_descriptor!("breed_code", "Breed Code", V2ComponentType::Complex(V2ComplexType::CE), 0, 36, 447, Optionality::O, false),
+            v2_component_descriptor!("strain", "Strain", V2ComponentType::Primitive(V2PrimitiveType::ST), 80, 37, 0, Optionality::O, true),
+            v2_component_descriptor!("production_class_code", "Production Class Code", V2ComponentType::Complex(V2ComplexType::CE), 0, 38, 429, Optionality::O, false),
+            v2_component_descriptor!("tribal_citizenship", "Tribal Citizenship", V2ComponentType::Complex(V2ComplexType::CWE), 0, 39, 171, Optionality::O, false)
+        ],
+        "PV1" => &[
+            v2_component_descriptor!("set_id", "Set ID - PV1", V2ComponentType::Primitive(V2PrimitiveType::SI), 4, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("patient_class", "Patient Class", V2ComponentType::Primitive(V2PrimitiveType::IS), 1, 2, 4, Optionality::R, false),
+            v2_component_descriptor!("assigned_patient_location", "Assigned Patient Location", V2ComponentType::Complex(V2ComplexType::PL), 0, 3, 0, Optionality::O, false),
+            v2_component_descriptor!("admission_type", "Admission Type", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 4, 7, Optionality::O, false),
+            v2_component_descriptor!("preadmit_number", "Preadmit Number", V2ComponentType::Complex(V2ComplexType::CX), 0, 5, 0, Optionality::O, false),
+            v2_component_descriptor!("prior_patient_location", "Prior Patient Location", V2ComponentType::Complex(V2ComplexType::PL), 0, 6, 0, Optionality::O, false),
+            v2_component_descriptor!("attending_doctor", "Attending Doctor", V2ComponentType::Complex(V2ComplexType::XCN), 0, 7, 10, Optionality::O, false),
+            v2_component_descriptor!("referring_doctor", "Referring Doctor", V2ComponentType::Complex(V2ComplexType::XCN), 0, 8, 10, Optionality::O, false),
+            v2_component_descriptor!("consulting_doctor", "Consulting Doctor", V2ComponentType::Complex(V2ComplexType::XCN), 0, 9, 10, Optionality::B, false),
+            v2_component_descriptor!("hospital_service", "Hospital Service", V2ComponentType::Primitive(V2PrimitiveType::IS), 3, 10, 69, Optionality::O, false),
+            v2_component_descriptor!("temporary_location", "Temporary Location", V2ComponentType::Complex(V2ComplexType::PL), 0, 11, 0, Optionality::O, false),
+            v2_component_descriptor!("preadmit_test_indicator", "Preadmit Test Indicator", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 12, 87, Optionality::O, false),
+            v2_component_descriptor!("readmission_indicator", "Re-admission Indicator", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 13, 92, Optionality::O, false),
+            v2_component_descriptor!("admit_source", "Admit Source", V2ComponentType::Primitive(V2PrimitiveType::IS), 6, 14, 23, Optionality::O, false),
+            v2_component_descriptor!("ambulatory_status", "Ambulatory Status", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 15, 9, Optionality::O, false),
+            v2_component_descriptor!("vip_indicator", "VIP Indicator", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 16, 99, Optionality::O, false),
+            v2_component_descriptor!("admitting_doctor", "Admitting Doctor", V2ComponentType::Complex(V2ComplexType::XCN), 0, 17, 10, Optionality::O, false),
+            v2_component_descriptor!("patient_type", "Patient Type", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 18, 18, Optionality::O, false),
+            v2_component_descriptor!("visit_number", "Visit Number", V2ComponentType::Complex(V2ComplexType::CX), 0, 19, 0, Optionality::O, false),
+            v2_component_descriptor!("financial_class", "Financial Class", V2ComponentType::Complex(V2ComplexType::FC), 0, 20, 0, Optionality::O, false),
+            v2_component_descriptor!("charge_price_indicator", "Charge Price Indicator", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 21, 32, Optionality::O, false),
+            v2_component_descriptor!("courtesy_code", "Courtesy Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 22, 45, Optionality::O, false),
+            v2_component_descriptor!("credit_rating", "Credit Rating", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 23, 46, Optionality::O, false),
+            v2_component_descriptor!("contract_code", "Contract Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 24, 44, Optionality::O, false),
+            v2_component_descriptor!("contract_effective_date", "Contract Effective Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 0, 25, 0, Optionality::O, false),
+            v2_component_descriptor!("contract_amount", "Contract Amount", V2ComponentType::Primitive(V2PrimitiveType::NM), 12, 26, 0, Optionality::O, false),
+            v2_component_descriptor!("contract_period", "Contract Period", V2ComponentType::Primitive(V2PrimitiveType::NM), 3, 27, 0, Optionality::O, false),
+            v2_component_descriptor!("interest_code", "Interest Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 28, 73, Optionality::O, false),
+            v2_component_descriptor!("transfer_to_bad_debt_code", "Transfer to Bad Debt Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 4, 29, 110, Optionality::O, false),
+            v2_component_descriptor!("transfer_to_bad_debt_date", "Transfer to Bad Debt Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 0, 30, 0, Optionality::O, false),
+            v2_component_descriptor!("bad_debt_agency_code", "Bad Debt Agency Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 10, 31, 21, Optionality::O, false),
+            v2_component_descriptor!("bad_debt_transfer_amount", "Bad Debt Transfer Amount", V2ComponentType::Primitive(V2PrimitiveType::NM), 12, 32, 0, Optionality::O, false),
+            v2_component_descriptor!("bad_debt_recovery_amount", "Bad Debt Recovery Amount", V2ComponentType::Primitive(V2PrimitiveType::NM), 12, 33, 0, Optionality::O, false),
+            v2_component_descriptor!("delete_account_indicator", "Delete Account Indicator", V2ComponentType::Primitive(V2PrimitiveType::IS), 1, 34, 111, Optionality::O, false),
+            v2_component_descriptor!("delete_account_date", "Delete Account Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 0, 35, 0, Optionality::O, false),
+            v2_component_descriptor!("discharge_disposition", "Discharge Disposition", V2ComponentType::Primitive(V2PrimitiveType::IS), 3, 36, 112, Optionality::O, false),
+            v2_component_descriptor!("discharged_to_location", "Discharged to Location", V2ComponentType::Complex(V2ComplexType::DLD), 0, 37, 113, Optionality::O, false),
+            v2_component_descriptor!("diet_type", "Diet Type", V2ComponentType::Complex(V2ComplexType::CE), 0, 38, 114, Optionality::O, false),
+            v2_component_descriptor!("servicing_facility", "Servicing Facility", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 39, 115, Optionality::O, false),
+            v2_component_descriptor!("bed_status", "Bed Status", V2ComponentType::Primitive(V2PrimitiveType::IS), 1, 40, 116, Optionality::B, false),
+            v2_component_descriptor!("account_status", "Account Status", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 41, 117, Optionality::O, false),
+            v2_component_descriptor!("pending_location", "Pending Location", V2ComponentType::Complex(V2ComplexType::PL), 0, 42, 0, Optionality::O, false),
+            v2_component_descriptor!("prior_temporary_location", "Prior Temporary Location", V2ComponentType::Complex(V2ComplexType::PL), 0, 43, 0, Optionality::O, false),
+            v2_component_descriptor!("admit_datetime", "Admit Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 44, 0, Optionality::O, false),
+            v2_component_descriptor!("discharge_datetime", "Discharge Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 45, 0, Optionality::O, false),
+            v2_component_descriptor!("current_patient_balance", "Current Patient Balance", V2ComponentType::Primitive(V2PrimitiveType::NM), 12, 46, 0, Optionality::O, false),
+            v2_component_descriptor!("total_charges", "Total Charges", V2ComponentType::Primitive(V2PrimitiveType::NM), 12, 47, 0, Optionality::O, false),
+            v2_component_descriptor!("total_adjustments", "Total Adjustments", V2ComponentType::Primitive(V2PrimitiveType::NM), 12, 48, 0, Optionality::O, false),
+            v2_component_descriptor!("total_payments", "Total Payments", V2ComponentType::Primitive(V2PrimitiveType::NM), 12, 49, 0, Optionality::O, false),
+            v2_component_descriptor!("alternate_visit_id", "Alternate Visit ID", V2ComponentType::Complex(V2ComplexType::CX), 0, 50, 203, Optionality::O, false),
+            v2_component_descriptor!("visit_indicator", "Visit Indicator", V2ComponentType::Primitive(V2PrimitiveType::IS), 1, 51, 326, Optionality::O, false),
+            v2_component_descriptor!("other_healthcare_provider", "Other Healthcare Provider", V2ComponentType::Complex(V2ComplexType::XCN), 0, 52, 10, Optionality::B, false)
+        ],
+        "ORC" => &[
+            v2_component_descriptor!("order_control", "Order Control", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 1, 119, Optionality::R, false),
+            v2_component_descriptor!("placer_order_number", "Placer Order Number", V2ComponentType::Complex(V2ComplexType::EI), 0, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("filler_order_number", "Filler Order Number", V2ComponentType::Complex(V2ComplexType::EI), 0, 3, 0, Optionality::O, false),
+            v2_component_descriptor!("placer_group_number", "Placer Group Number", V2ComponentType::Complex(V2ComplexType::EI), 0, 4, 0, Optionality::O, false),
+            v2_component_descriptor!("order_status", "Order Status", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 5, 38, Optionality::O, false),
+            v2_component_descriptor!("response_flag", "Response Flag", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 6, 121, Optionality::O, false),
+            v2_component_descriptor!("quantity_timing", "Quantity/Timing", V2ComponentType::Primitive(V2PrimitiveType::ST), 0, 7, 0, Optionality::B, false),
+            v2_component_descriptor!("parent_order", "Parent Order", V2ComponentType::Complex(V2ComplexType::EIP), 0, 8, 0, Optionality::O, false),
+            v2_component_descriptor!("datetime_of_transaction", "Date/Time of Transaction", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 9, 0, Optionality::O, false),
+            v2_component_descriptor!("entered_by", "Entered By", V2ComponentType::Complex(V2ComplexType::XCN), 0, 10, 0, Optionality::O, false),
+            v2_component_descriptor!("verified_by", "Verified By", V2ComponentType::Complex(V2ComplexType::XCN), 0, 11, 0, Optionality::O, false),
+            v2_component_descriptor!("ordering_provider", "Ordering Provider", V2ComponentType::Complex(V2ComplexType::XCN), 0, 12, 0, Optionality::O, false),
+            v2_component_descriptor!("enterers_location", "Enterer's Location", V2ComponentType::Complex(V2ComplexType::PL), 0, 13, 0, Optionality::O, false),
+            v2_component_descriptor!("call_back_phone_number", "Call Back Phone Number", V2ComponentType::Complex(V2ComplexType::XTN), 0, 14, 0, Optionality::O, false),
+            v2_component_descriptor!("order_effective_datetime", "Order Effective Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 15, 0, Optionality::O, false),
+            v2_component_descriptor!("order_control_code_reason", "Order Control Code Reason", V2ComponentType::Complex(V2ComplexType::CE), 0, 16, 0, Optionality::O, false),
+            v2_component_descriptor!("entering_organization", "Entering Organization", V2ComponentType::Complex(V2ComplexType::CE), 0, 17, 0, Optionality::O, false),
+            v2_component_descriptor!("entering_device", "Entering Device", V2ComponentType::Complex(V2ComplexType::CE), 0, 18, 0, Optionality::O, false),
+            v2_component_descriptor!("action_by", "Action By", V2ComponentType::Complex(V2ComplexType::XCN), 0, 19, 0, Optionality::O, false),
+            v2_component_descriptor!("advanced_beneficiary_notice_code", "Advanced Beneficiary Notice Code", V2ComponentType::Complex(V2ComplexType::CE), 0, 20, 339, Optionality::O, false),
+            v2_component_descriptor!("ordering_facility_name", "Ordering Facility Name", V2ComponentType::Complex(V2ComplexType::XON), 0, 21, 0, Optionality::O, false),
+            v2_component_descriptor!("ordering_facility_address", "Ordering Facility Address", V2ComponentType::Complex(V2ComplexType::XAD), 0, 22, 0, Optionality::O, false),
+            v2_component_descriptor!("ordering_facility_phone_number", "Ordering Facility Phone Number", V2ComponentType::Complex(V2ComplexType::XTN), 0, 23, 0, Optionality::O, false),
+            v2_component_descriptor!("ordering_provider_address", "Ordering Provider Address", V2ComponentType::Complex(V2ComplexType::XAD), 0, 24, 0, Optionality::O, false),
+            v2_component_descriptor!("order_status_modifier", "Order Status Modifier", V2ComponentType::Complex(V2ComplexType::CWE), 0, 25, 0, Optionality::O, false),
+            v2_component_descriptor!("abn_override_reason", "Advanced Beneficiary Notice Override Reason", V2ComponentType::Complex(V2ComplexType::CWE), 0, 26, 552, Optionality::O, false),
+            v2_component_descriptor!("fillers_expected_availability_datetime", "Filler's Expected Availability Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 27, 0, Optionality::O, false),
+            v2_component_descriptor!("confidentiality_code", "Confidentiality Code", V2ComponentType::Complex(V2ComplexType::CWE), 0, 28, 177, Optionality::O, false),
+            v2_component_descriptor!("order_type", "Order Type", V2ComponentType::Complex(V2ComplexType::CWE), 0, 29, 482, Optionality::O, false),
+            v2_component_descriptor!("enterer_authorization_mode", "Enterer Authorization Mode", V2ComponentType::Complex(V2ComplexType::CNE), 0, 30, 483, Optionality::O, false),
+            v2_component_descriptor!("parent_universal_service_id", "Parent Universal Service Identifier", V2ComponentType::Complex(V2ComplexType::CWE), 0, 31, 0, Optionality::O, false)
+        ],
+        "OBR" => &[
+            v2_component_descriptor!("set_id", "Set ID - OBR", V2ComponentType::Primitive(V2PrimitiveType::SI), 4, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("placer_order_number", "Placer Order Number", V2ComponentType::Complex(V2ComplexType::EI), 0, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("filler_order_number", "Filler Order Number", V2ComponentType::Complex(V2ComplexType::EI), 0, 3, 0, Optionality::O, false),
+            v2_component_descriptor!("universal_service_id", "Universal Service Identifier", V2ComponentType::Complex(V2ComplexType::CE), 0, 4, 0, Optionality::R, false),
+            v2_component_descriptor!("priority", "Priority - OBR", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 5, 0, Optionality::B, false),
+            v2_component_descriptor!("requested_datetime", "Requested Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 6, 0, Optionality::B, false),
+            v2_component_descriptor!("observation_datetime", "Observation Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 7, 0, Optionality::O, false),
+            v2_component_descriptor!("observation_end_datetime", "Observation End Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 8, 0, Optionality::O, false),
+            v2_component_descriptor!("collection_volume", "Collection Volume", V2ComponentType::Complex(V2ComplexType::CQ), 0, 9, 0, Optionality::O, false),
+            v2_component_descriptor!("collector_id", "Collector Identifier", V2ComponentType::Complex(V2ComplexType::XCN), 0, 10, 0, Optionality::O, false),
+            v2_component_descriptor!("specimen_action_code", "Specimen Action Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 11, 65, Optionality::O, false),
+            v2_component_descriptor!("danger_code", "Danger Code", V2ComponentType::Complex(V2ComplexType::CE), 0, 12, 0, Optionality::O, false),
+            v2_component_descriptor!("relevant_clinical_information", "Relevant Clinical Information", V2ComponentType::Primitive(V2PrimitiveType::ST), 300, 13, 0, Optionality::O, true),
+            v2_component_descriptor!("specimen_received_datetime", "Specimen Received Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 14, 0, Optionality::B, false),
+            v2_component_descriptor!("specimen_source", "Specimen Source", V2ComponentType::Primitive(V2PrimitiveType::ST), 0, 15, 0, Optionality::B, false),
+            v2_component_descriptor!("ordering_provider", "Ordering Provider", V2ComponentType::Complex(V2ComplexType::XCN), 0, 16, 0, Optionality::O, false),
+            v2_component_descriptor!("order_callback_phone_number", "Order Callback Phone Number", V2ComponentType::Complex(V2ComplexType::XTN), 0, 17, 0, Optionality::O, false),
+            v2_component_descriptor!("placer_field_1", "Placer Field 1", V2ComponentType::Primitive(V2PrimitiveType::ST), 60, 18, 0, Optionality::O, true),
+            v2_component_descriptor!("placer_field_2", "Placer Field 2", V2ComponentType::Primitive(V2PrimitiveType::ST), 60, 19, 0, Optionality::O, true),
+            v2_component_descriptor!("filler_field_1", "Filler Field 1", V2ComponentType::Primitive(V2PrimitiveType::ST), 60, 20, 0, Optionality::O, true),
+            v2_component_descriptor!("filler_field_2", "Filler Field 2", V2ComponentType::Primitive(V2PrimitiveType::ST), 60, 21, 0, Optionality::O, true),
+            v2_component_descriptor!("results_status_change_datetime", "Results Rpt/Status Chng - Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 22, 0, Optionality::O, false),
+            v2_component_descriptor!("charge_to_practice", "Charge to Practice", V2ComponentType::Complex(V2ComplexType::MOC), 0, 23, 0, Optionality::O, false),
+            v2_component_descriptor!("diagnostic_service_section_id", "Diagnostic Serv Sect ID", V2ComponentType::Primitive(V2PrimitiveType::ID), 10, 24, 74, Optionality::O, false),
+            v2_component_descriptor!("result_status", "Result Status", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 25, 123, Optionality::O, false),
+            v2_component_descriptor!("parent_result", "Parent Result", V2ComponentType::Complex(V2ComplexType::PRL), 0, 26, 0, Optionality::O, false),
+            v2_component_descriptor!("quantity_timing", "Quantity/Timing", V2ComponentType::Primitive(V2PrimitiveType::ST), 0, 27, 0, Optionality::B, false),
+            v2_component_descriptor!("result_copies_to", "Result Copies To", V2ComponentType::Complex(V2ComplexType::XCN), 0, 28, 0, Optionality::O, false),
+            v2_component_descriptor!("parent", "Parent", V2ComponentType::Complex(V2ComplexType::EIP), 0, 29, 0, Optionality::O, false),
+            v2_component_descriptor!("transportation_mode", "Transportation Mode", V2ComponentType::Primitive(V2PrimitiveType::ID), 20, 30, 124, Optionality::O, false),
+            v2_component_descriptor!("reason_for_study", "Reason for Study", V2ComponentType::Complex(V2ComplexType::CE), 0, 31, 0, Optionality::O, false),
+            v2_component_descriptor!("principal_result_interpreter", "Principal Result Interpreter", V2ComponentType::Complex(V2ComplexType::NDL), 0, 32, 0, Optionality::O, false),
+            v2_component_descriptor!("assistant_result_interpreter", "Assistant Result Interpreter", V2ComponentType::Complex(V2ComplexType::NDL), 0, 33, 0, Optionality::O, false),
+            v2_component_descriptor!("technician", "Technician", V2ComponentType::Complex(V2ComplexType::NDL), 0, 34, 0, Optionality::O, false),
+            v2_component_descriptor!("transcriptionist", "Transcriptionist", V2ComponentType::Complex(V2ComplexType::NDL), 0, 35, 0, Optionality::O, false),
+            v2_component_descriptor!("scheduled_datetime", "Scheduled Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 36, 0, Optionality::O, false),
+            v2_component_descriptor!("number_of_sample_containers", "Number of Sample Containers", V2ComponentType::Primitive(V2PrimitiveType::NM), 4, 37, 0, Optionality::O, false),
+            v2_component_descriptor!("transport_logistics", "Transport Logistics of Collected Sample", V2ComponentType::Complex(V2ComplexType::CE), 0, 38, 0, Optionality::O, false),
+            v2_component_descriptor!("collectors_comment", "Collector's Comment", V2ComponentType::Complex(V2ComplexType::CE), 0, 39, 0, Optionality::O, false),
+            v2_component_descriptor!("transport_arrangement_responsibility", "Transport Arrangement Responsibility", V2ComponentType::Complex(V2ComplexType::CE), 0, 40, 0, Optionality::O, false),
+            v2_component_descriptor!("transport_arranged", "Transport Arranged", V2ComponentType::Primitive(V2PrimitiveType::ID), 30, 41, 224, Optionality::O, false),
+            v2_component_descriptor!("escort_required", "Escort Required", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 42, 225, Optionality::O, false),
+            v2_component_descriptor!("planned_patient_transport_comment", "Planned Patient Transport Comment", V2ComponentType::Complex(V2ComplexType::CE), 0, 43, 0, Optionality::O, false),
+            v2_component_descriptor!("procedure_code", "Procedure Code", V2ComponentType::Complex(V2ComplexType::CE), 0, 44, 88, Optionality::O, false),
+            v2_component_descriptor!("procedure_code_modifier", "Procedure Code Modifier", V2ComponentType::Complex(V2ComplexType::CE), 0, 45, 340, Optionality::O, false),
+            v2_component_descriptor!("placer_supplemental_service_information", "Placer Supplemental Service Information", V2ComponentType::Complex(V2ComplexType::CE), 0, 46, 411, Optionality::O, false),
+            v2_component_descriptor!("filler_supplemental_service_information", "Filler Supplemental Service Information", V2ComponentType::Complex(V2ComplexType::CE), 0, 47, 411, Optionality::O, false),
+            v2_component_descriptor!("duplicate_procedure_reason", "Medically Necessary Duplicate Procedure Reason", V2ComponentType::Complex(V2ComplexType::CWE), 0, 48, 476, Optionality::O, false),
+            v2_component_descriptor!("result_handling", "Result Handling", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 49, 507, Optionality::O, false),
+            v2_component_descriptor!("parent_universal_service_id", "Parent Universal Service Identifier", V2ComponentType::Complex(V2ComplexType::CWE), 0, 50, 0, Optionality::O, false)
+        ],
+        "OBX" => &[
+            v2_component_descriptor!("set_id", "Set ID - OBX", V2ComponentType::Primitive(V2PrimitiveType::SI), 4, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("value_type", "Value Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 2, 125, Optionality::O, false),
+            v2_component_descriptor!("observation_id", "Observation Identifier", V2ComponentType::Complex(V2ComplexType::CE), 0, 3, 0, Optionality::R, false),
+            v2_component_descriptor!("observation_sub_id", "Observation Sub-ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 4, 0, Optionality::O, false),
+            v2_component_descriptor!("observation_value", "Observation Value", V2ComponentType::Primitive(V2PrimitiveType::String), 0, 5, 0, Optionality::O, false),
+            v2_component_descriptor!("units", "Units", V2ComponentType::Complex(V2ComplexType::CE), 0, 6, 0, Optionality::O, false),
+            v2_component_descriptor!("references_range", "References Range", V2ComponentType::Primitive(V2PrimitiveType::ST), 60, 7, 0, Optionality::O, true),
+            v2_component_descriptor!("abnormal_flags", "Abnormal Flags", V2ComponentType::Primitive(V2PrimitiveType::IS), 5, 8, 78, Optionality::O, false),
+            v2_component_descriptor!("probability", "Probability", V2ComponentType::Primitive(V2PrimitiveType::NM), 5, 9, 0, Optionality::O, false),
+            v2_component_descriptor!("nature_of_abnormal_test", "Nature of Abnormal Test", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 10, 80, Optionality::O, false),
+            v2_component_descriptor!("observation_result_status", "Observation Result Status", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 11, 85, Optionality::R, false),
+            v2_component_descriptor!("reference_range_effective_date", "Effective Date of Reference Range", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 12, 0, Optionality::O, false),
+            v2_component_descriptor!("user_defined_access_checks", "User Defined Access Checks", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 13, 0, Optionality::O, false),
+            v2_component_descriptor!("observation_datetime", "Date/Time of the Observation", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 14, 0, Optionality::O, false),
+            v2_component_descriptor!("producers_id", "Producer's ID", V2ComponentType::Complex(V2ComplexType::CE), 0, 15, 0, Optionality::O, false),
+            v2_component_descriptor!("responsible_observer", "Responsible Observer", V2ComponentType::Complex(V2ComplexType::XCN), 0, 16, 0, Optionality::O, false),
+            v2_component_descriptor!("observation_method", "Observation Method", V2ComponentType::Complex(V2ComplexType::CE), 0, 17, 0, Optionality::O, false),
+            v2_component_descriptor!("equipment_instance_id", "Equipment Instance Identifier", V2ComponentType::Complex(V2ComplexType::EI), 0, 18, 0, Optionality::O, false),
+            v2_component_descriptor!("analysis_datetime", "Date/Time of the Analysis", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 19, 0, Optionality::O, false),
+            v2_component_descriptor!("observation_site", "Observation Site", V2ComponentType::Complex(V2ComplexType::CWE), 0, 20, 163, Optionality::O, false),
+            v2_component_descriptor!("observation_instance_id", "Observation Instance Identifier", V2ComponentType::Complex(V2ComplexType::EI), 0, 21, 0, Optionality::O, false),
+            v2_component_descriptor!("mood_code", "Mood Code", V2ComponentType::Complex(V2ComplexType::CNE), 0, 22, 725, Optionality::O, false),
+            v2_component_descriptor!("performing_organization_name", "Performing Organization Name", V2ComponentType::Complex(V2ComplexType::XON), 0, 23, 0, Optionality::O, false),
+            v2_component_descriptor!("performing_organization_address", "Performing Organization Address", V2ComponentType::Complex(V2ComplexType::XAD), 0, 24, 0, Optionality::O, false),
+            v2_component_descriptor!("performing_organization_medical_director", "Performing Organization Medical Director", V2ComponentType::Complex(V2ComplexType::XCN), 0, 25, 0, Optionality::O, false)
+        ],
+        "NK1" => &[
+            v2_component_descriptor!("set_id", "Set ID - NK1", V2ComponentType::Primitive(V2PrimitiveType::SI), 4, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("name", "Name", V2ComponentType::Complex(V2ComplexType::XPN), 0, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("relationship", "Relationship", V2ComponentType::Complex(V2ComplexType::CE), 0, 3, 63, Optionality::O, false),
+            v2_component_descriptor!("address", "Address", V2ComponentType::Complex(V2ComplexType::XAD), 0, 4, 0, Optionality::O, false),
+            v2_component_descriptor!("phone_number", "Phone Number", V2ComponentType::Complex(V2ComplexType::XTN), 0, 5, 0, Optionality::O, false),
+            v2_component_descriptor!("business_phone_number", "Business Phone Number", V2ComponentType::Complex(V2ComplexType::XTN), 0, 6, 0, Optionality::O, false),
+            v2_component_descriptor!("contact_role", "Contact Role", V2ComponentType::Complex(V2ComplexType::CE), 0, 7, 131, Optionality::O, false),
+            v2_component_descriptor!("start_date", "Start Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 0, 8, 0, Optionality::O, false),
+            v2_component_descriptor!("end_date", "End Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 0, 9, 0, Optionality::O, false),
+            v2_component_descriptor!("job_title", "Next of Kin / Associated Parties Job Title", V2ComponentType::Primitive(V2PrimitiveType::ST), 60, 10, 0, Optionality::O, true),
+            v2_component_descriptor!("job_code_class", "Next of Kin / Associated Parties Job Code/Class", V2ComponentType::Complex(V2ComplexType::JCC), 0, 11, 0, Optionality::O, false),
+            v2_component_descriptor!("employee_number", "Next of Kin / Associated Parties Employee Number", V2ComponentType::Complex(V2ComplexType::CX), 0, 12, 0, Optionality::O, false),
+            v2_component_descriptor!("organization_name", "Organization Name - NK1", V2ComponentType::Complex(V2ComplexType::XON), 0, 13, 0, Optionality::O, false),
+            v2_component_descriptor!("marital_status", "Marital Status", V2ComponentType::Complex(V2ComplexType::CE), 0, 14, 2, Optionality::O, false),
+            v2_component_descriptor!("administrative_sex", "Administrative Sex", V2ComponentType::Primitive(V2PrimitiveType::IS), 1, 15, 1, Optionality::O, false),
+            v2_component_descriptor!("datetime_of_birth", "Date/Time of Birth", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 16, 0, Optionality::O, false),
+            v2_component_descriptor!("living_dependency", "Living Dependency", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 17, 223, Optionality::O, false),
+            v2_component_descriptor!("ambulatory_status", "Ambulatory Status", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 18, 9, Optionality::O, false),
+            v2_component_descriptor!("citizenship", "Citizenship", V2ComponentType::Complex(V2ComplexType::CE), 0, 19, 171, Optionality::O, false),
+            v2_component_descriptor!("primary_language", "Primary Language", V2ComponentType::Complex(V2ComplexType::CE), 0, 20, 296, Optionality::O, false),
+            v2_component_descriptor!("living_arrangement", "Living Arrangement", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 21, 220, Optionality::O, false),
+            v2_component_descriptor!("publicity_code", "Publicity Code", V2ComponentType::Complex(V2ComplexType::CE), 0, 22, 215, Optionality::O, false),
+            v2_component_descriptor!("protection_indicator", "Protection Indicator", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 23, 136, Optionality::O, false),
+            v2_component_descriptor!("student_indicator", "Student Indicator", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 24, 231, Optionality::O, false),
+            v2_component_descriptor!("religion", "Religion", V2ComponentType::Complex(V2ComplexType::CE), 0, 25, 6, Optionality::O, false),
+            v2_component_descriptor!("mothers_maiden_name", "Mother's Maiden Name", V2ComponentType::Complex(V2ComplexType::XPN), 0, 26, 0, Optionality::O, false),
+            v2_component_descriptor!("nationality", "Nationality", V2ComponentType::Complex(V2ComplexType::CE), 0, 27, 212, Optionality::O, false),
+            v2_component_descriptor!("ethnic_group", "Ethnic Group", V2ComponentType::Complex(V2ComplexType::CE), 0, 28, 189, Optionality::O, false),
+            v2_component_descriptor!("contact_reason", "Contact Reason", V2ComponentType::Complex(V2ComplexType::CE), 0, 29, 222, Optionality::O, false),
+            v2_component_descriptor!("contact_persons_name", "Contact Person's Name", V2ComponentType::Complex(V2ComplexType::XPN), 0, 30, 0, Optionality::O, false),
+            v2_component_descriptor!("contact_persons_phone_number", "Contact Person's Telephone Number", V2ComponentType::Complex(V2ComplexType::XTN), 0, 31, 0, Optionality::O, false),
+            v2_component_descriptor!("contact_persons_address", "Contact Person's Address", V2ComponentType::Complex(V2ComplexType::XAD), 0, 32, 0, Optionality::O, false),
+            v2_component_descriptor!("next_of_kin_identifiers", "Next of Kin/Associated Party's Identifiers", V2ComponentType::Complex(V2ComplexType::CX), 0, 33, 0, Optionality::O, false),
+            v2_component_descriptor!("job_status", "Job Status", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 34, 311, Optionality::O, false),
+            v2_component_descriptor!("race", "Race", V2ComponentType::Complex(V2ComplexType::CE), 0, 35, 5, Optionality::O, false),
+            v2_component_descriptor!("handicap", "Handicap", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 36, 295, Optionality::O, false),
+            v2_component_descriptor!("contact_person_ssn", "Contact Person Social Security Number", V2ComponentType::Primitive(V2PrimitiveType::ST), 16, 37, 0, Optionality::O, false),
+            v2_component_descriptor!("birth_place", "Next of Kin Birth Place", V2ComponentType::Primitive(V2PrimitiveType::ST), 250, 38, 0, Optionality::O, true),
+            v2_component_descriptor!("vip_indicator", "VIP Indicator", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 39, 99, Optionality::O, false)
+        ],
+        "IN1" => &[
+            v2_component_descriptor!("set_id", "Set ID - IN1", V2ComponentType::Primitive(V2PrimitiveType::SI), 4, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("insurance_plan_id", "Insurance Plan ID", V2ComponentType::Complex(V2ComplexType::CE), 0, 2, 72, Optionality::R, false),
+            v2_component_descriptor!("insurance_company_id", "Insurance Company ID", V2ComponentType::Complex(V2ComplexType::CX), 0, 3, 0, Optionality::R, false),
+            v2_component_descriptor!("insurance_company_name", "Insurance Company Name", V2ComponentType::Complex(V2ComplexType::XON), 0, 4, 0, Optionality::O, false),
+            v2_component_descriptor!("insurance_company_address", "Insurance Company Address", V2ComponentType::Complex(V2ComplexType::XAD), 0, 5, 0, Optionality::O, false),
+            v2_component_descriptor!("insurance_company_contact_person", "Insurance Co Contact Person", V2ComponentType::Complex(V2ComplexType::XPN), 0, 6, 0, Optionality::O, false),
+            v2_component_descriptor!("insurance_company_phone_number", "Insurance Co Phone Number", V2ComponentType::Complex(V2ComplexType::XTN), 0, 7, 0, Optionality::O, false),
+            v2_component_descriptor!("group_number", "Group Number", V2ComponentType::Primitive(V2PrimitiveType::ST), 12, 8, 0, Optionality::O, false),
+            v2_component_descriptor!("group_name", "Group Name", V2ComponentType::Complex(V2ComplexType::XON), 0, 9, 0, Optionality::O, false),
+            v2_component_descriptor!("insureds_group_employer_id", "Insured's Group Emp ID", V2ComponentType::Complex(V2ComplexType::CX), 0, 10, 0, Optionality::O, false),
+            v2_component_descriptor!("insureds_group_employer_name", "Insured's Group Emp Name", V2ComponentType::Complex(V2ComplexType::XON), 0, 11, 0, Optionality::O, false),
+            v2_component_descriptor!("plan_effective_date", "Plan Effective Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 0, 12, 0, Optionality::O, false),
+            v2_component_descriptor!("plan_expiration_date", "Plan Expiration Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 0, 13, 0, Optionality::O, false),
+            v2_component_descriptor!("authorization_information", "Authorization Information", V2ComponentType::Complex(V2ComplexType::AUI), 0, 14, 0, Optionality::O, false),
+            v2_component_descriptor!("plan_type", "Plan Type", V2ComponentType::Primitive(V2PrimitiveType::IS), 3, 15, 86, Optionality::O, false),
+            v2_component_descriptor!("name_of_insured", "Name Of Insured", V2ComponentType::Complex(V2ComplexType::XPN), 0, 16, 0, Optionality::O, false),
+            v2_component_descriptor!("insureds_relationship_to_patient", "Insured's Relationship To Patient", V2ComponentType::Complex(V2ComplexType::CE), 0, 17, 63, Optionality::O, false),
+            v2_component_descriptor!("insureds_date_of_birth", "Insured's Date Of Birth", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 18, 0, Optionality::O, false),
+            v2_component_descriptor!("insureds_address", "Insured's Address", V2ComponentType::Complex(V2ComplexType::XAD), 0, 19, 0, Optionality::O, false),
+            v2_component_descriptor!("assignment_of_benefits", "Assignment Of Benefits", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 20, 135, Optionality::O, false),
+            v2_component_descriptor!("coordination_of_benefits", "Coordination Of Benefits", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 21, 173, Optionality::O, false),
+            v2_component_descriptor!("coordination_of_benefits_priority", "Coord Of Ben. Priority", V2ComponentType::Primitive(V2PrimitiveType::ST), 2, 22, 0, Optionality::O, false),
+            v2_component_descriptor!("notice_of_admission_flag", "Notice Of Admission Flag", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 23, 136, Optionality::O, false),
+            v2_component_descriptor!("notice_of_admission_date", "Notice Of Admission Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 0, 24, 0, Optionality::O, false),
+            v2_component_descriptor!("report_of_eligibility_flag", "Report Of Eligibility Flag", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 25, 136, Optionality::O, false),
+            v2_component_descriptor!("report_of_eligibility_date", "Report Of Eligibility Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 0, 26, 0, Optionality::O, false),
+            v2_component_descriptor!("release_information_code", "Release Information Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 27, 93, Optionality::O, false),
+            v2_component_descriptor!("pre_admit_cert", "Pre-Admit Cert (PAC)", V2ComponentType::Primitive(V2PrimitiveType::ST), 15, 28, 0, Optionality::O, false),
+            v2_component_descriptor!("verification_datetime", "Verification Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 29, 0, Optionality::O, false),
+            v2_component_descriptor!("verification_by", "Verification By", V2ComponentType::Complex(V2ComplexType::XCN), 0, 30, 0, Optionality::O, false),
+            v2_component_descriptor!("type_of_agreement_code", "Type Of Agreement Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 31, 98, Optionality::O, false),
+            v2_component_descriptor!("billing_status", "Billing Status", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 32, 22, Optionality::O, false),
+            v2_component_descriptor!("lifetime_reserve_days", "Lifetime Reserve Days", V2ComponentType::Primitive(V2PrimitiveType::NM), 4, 33, 0, Optionality::O, false),
+            v2_component_descriptor!("delay_before_lr_day", "Delay Before L.R. Day", V2ComponentType::Primitive(V2PrimitiveType::NM), 4, 34, 0, Optionality::O, false),
+            v2_component_descriptor!("company_plan_code", "Company Plan Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 8, 35, 42, Optionality::O, false),
+            v2_component_descriptor!("policy_number", "Policy Number", V2ComponentType::Primitive(V2PrimitiveType::ST), 15, 36, 0, Optionality::O, false),
+            v2_component_descriptor!("policy_deductible", "Policy Deductible", V2ComponentType::Complex(V2ComplexType::CP), 0, 37, 0, Optionality::O, false),
+            v2_component_descriptor!("policy_limit_amount", "Policy Limit - Amount", V2ComponentType::Complex(V2ComplexType::CP), 0, 38, 0, Optionality::B, false),
+            v2_component_descriptor!("policy_limit_days", "Policy Limit - Days", V2ComponentType::Primitive(V2PrimitiveType::NM), 4, 39, 0, Optionality::O, false),
+            v2_component_descriptor!("room_rate_semi_private", "Room Rate - Semi-Private", V2ComponentType::Complex(V2ComplexType::CP), 0, 40, 0, Optionality::B, false),
+            v2_component_descriptor!("room_rate_private", "Room Rate - Private", V2ComponentType::Complex(V2ComplexType::CP), 0, 41, 0, Optionality::B, false),
+            v2_component_descriptor!("insureds_employment_status", "Insured's Employment Status", V2ComponentType::Complex(V2ComplexType::CE), 0, 42, 66, Optionality::O, false),
+            v2_component_descriptor!("insureds_administrative_sex", "Insured's Administrative Sex", V2ComponentType::Primitive(V2PrimitiveType::IS), 1, 43, 1, Optionality::O, false),
+            v2_component_descriptor!("insureds_employers_address", "Insured's Employer's Address", V2ComponentType::Complex(V2ComplexType::XAD), 0, 44, 0, Optionality::O, false),
+            v2_component_descriptor!("verification_status", "Verification Status", V2ComponentType::Primitive(V2PrimitiveType::ST), 2, 45, 0, Optionality::O, false),
+            v2_component_descriptor!("prior_insurance_plan_id", "Prior Insurance Plan ID", V2ComponentType::Primitive(V2PrimitiveType::IS), 8, 46, 72, Optionality::O, false),
+            v2_component_descriptor!("coverage_type", "Coverage Type", V2ComponentType::Primitive(V2PrimitiveType::IS), 3, 47, 309, Optionality::O, false),
+            v2_component_descriptor!("handicap", "Handicap", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 48, 295, Optionality::O, false),
+            v2_component_descriptor!("insureds_id_number", "Insured's ID Number", V2ComponentType::Complex(V2ComplexType::CX), 0, 49, 0, Optionality::O, false),
+            v2_component_descriptor!("signature_code", "Signature Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 1, 50, 535, Optionality::O, false),
+            v2_component_descriptor!("signature_code_date", "Signature Code Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 0, 51, 0, Optionality::O, false),
+            v2_component_descriptor!("insureds_birth_place", "Insured's Birth Place", V2ComponentType::Primitive(V2PrimitiveType::ST), 250, 52, 0, Optionality::O, true),
+            v2_component_descriptor!("vip_indicator", "VIP Indicator", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 53, 99, Optionality::O, false)
+        ],
+        "MSA" => &[
+            v2_component_descriptor!("ack_code", "Acknowledgment Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 1, 8, Optionality::R, false),
+            v2_component_descriptor!("message_control_id", "Message Control ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 2, 0, Optionality::R, false),
+            v2_component_descriptor!("text_message", "Text Message", V2ComponentType::Primitive(V2PrimitiveType::ST), 80, 3, 0, Optionality::B, true),
+            v2_component_descriptor!("expected_sequence_number", "Expected Sequence Number", V2ComponentType::Primitive(V2PrimitiveType::NM), 15, 4, 0, Optionality::O, false),
+            v2_component_descriptor!("delayed_ack_type", "Delayed Acknowledgment Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 5, 102, Optionality::B, false),
+            v2_component_descriptor!("error_condition", "Error Condition", V2ComponentType::Complex(V2ComplexType::CE), 0, 6, 357, Optionality::B, false)
+        ],
+        "ERR" => &[
+            v2_component_descriptor!("error_code_and_location", "Error Code and Location", V2ComponentType::Complex(V2ComplexType::ELD), 0, 1, 0, Optionality::B, false),
+            v2_component_descriptor!("error_location", "Error Location", V2ComponentType::Complex(V2ComplexType::ERL), 0, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("hl7_error_code", "HL7 Error Code", V2ComponentType::Complex(V2ComplexType::CWE), 0, 3, 357, Optionality::R, false),
+            v2_component_descriptor!("severity", "Severity", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 4, 516, Optionality::R, false),
+            v2_component_descriptor!("application_error_code", "Application Error Code", V2ComponentType::Complex(V2ComplexType::CWE), 0, 5, 533, Optionality::O, false),
+            v2_component_descriptor!("application_error_parameter", "Application Error Parameter", V2ComponentType::Primitive(V2PrimitiveType::ST), 80, 6, 0, Optionality::O, true),
+            v2_component_descriptor!("diagnostic_information", "Diagnostic Information", V2ComponentType::Primitive(V2PrimitiveType::Text), 2048, 7, 0, Optionality::O, true),
+            v2_component_descriptor!("user_message", "User Message", V2ComponentType::Primitive(V2PrimitiveType::Text), 250, 8, 0, Optionality::O, true),
+            v2_component_descriptor!("inform_person_indicator", "Inform Person Indicator", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 9, 517, Optionality::O, false),
+            v2_component_descriptor!("override_type", "Override Type", V2ComponentType::Complex(V2ComplexType::CWE), 0, 10, 518, Optionality::O, false),
+            v2_component_descriptor!("override_reason_code", "Override Reason Code", V2ComponentType::Complex(V2ComplexType::CWE), 0, 11, 519, Optionality::O, false),
+            v2_component_descriptor!("help_desk_contact_point", "Help Desk Contact Point", V2ComponentType::Complex(V2ComplexType::XTN), 0, 12, 0, Optionality::O, false)
+        ]
+    };
+
+    pub type V2FieldRepetitions = Map<&'static str, &'static [(u16, u16)]>;
+
+    ///
+    /// Segments whose fields were reserved before v2.6 and defined from then on. The reserved
+    /// placeholders keep older messages from being cast into, or validated against, fields their
+    /// release did not have. See [get_segment_descriptor_as].
+    ///
+    pub static V2_SEGMENT_DESCRIPTORS_PRE_V2_6: V2SegmentDescriptors = phf_map! {
+        "OBX" => &[
+            v2_component_descriptor!("set_id", "Set ID - OBX", V2ComponentType::Primitive(V2PrimitiveType::SI), 4, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("value_type", "Value Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 2, 125, Optionality::O, false),
+            v2_component_descriptor!("observation_id", "Observation Identifier", V2ComponentType::Complex(V2ComplexType::CE), 0, 3, 0, Optionality::R, false),
+            v2_component_descriptor!("observation_sub_id", "Observation Sub-ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 4, 0, Optionality::O, false),
+            v2_component_descriptor!("observation_value", "Observation Value", V2ComponentType::Primitive(V2PrimitiveType::String), 0, 5, 0, Optionality::O, false),
+            v2_component_descriptor!("units", "Units", V2ComponentType::Complex(V2ComplexType::CE), 0, 6, 0, Optionality::O, false),
+            v2_component_descriptor!("references_range", "References Range", V2ComponentType::Primitive(V2PrimitiveType::ST), 60, 7, 0, Optionality::O, true),
+            v2_component_descriptor!("abnormal_flags", "Abnormal Flags", V2ComponentType::Primitive(V2PrimitiveType::IS), 5, 8, 78, Optionality::O, false),
+            v2_component_descriptor!("probability", "Probability", V2ComponentType::Primitive(V2PrimitiveType::NM), 5, 9, 0, Optionality::O, false),
+            v2_component_descriptor!("nature_of_abnormal_test", "Nature of Abnormal Test", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 10, 80, Optionality::O, false),
+            v2_component_descriptor!("observation_result_status", "Observation Result Status", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 11, 85, Optionality::R, false),
+            v2_component_descriptor!("reference_range_effective_date", "Effective Date of Reference Range", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 12, 0, Optionality::O, false),
+            v2_component_descriptor!("user_defined_access_checks", "User Defined Access Checks", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 13, 0, Optionality::O, false),
+            v2_component_descriptor!("observation_datetime", "Date/Time of the Observation", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 14, 0, Optionality::O, false),
+            v2_component_descriptor!("producers_id", "Producer's ID", V2ComponentType::Complex(V2ComplexType::CE), 0, 15, 0, Optionality::O, false),
+            v2_component_descriptor!("responsible_observer", "Responsible Observer", V2ComponentType::Complex(V2ComplexType::XCN), 0, 16, 0, Optionality::O, false),
+            v2_component_descriptor!("observation_method", "Observation Method", V2ComponentType::Complex(V2ComplexType::CE), 0, 17, 0, Optionality::O, false),
+            v2_component_descriptor!("equipment_instance_id", "Equipment Instance Identifier", V2ComponentType::Complex(V2ComplexType::EI), 0, 18, 0, Optionality::O, false),
+            v2_component_descriptor!("analysis_datetime", "Date/Time of the Analysis", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 19, 0, Optionality::O, false),
+            v2_component_descriptor!("reserved_20", "Reserved for harmonization with V2.6", V2ComponentType::Primitive(V2PrimitiveType::ST), 0, 20, 0, Optionality::X, false),
+            v2_component_descriptor!("reserved_21", "Reserved for harmonization with V2.6", V2ComponentType::Primitive(V2PrimitiveType::ST), 0, 21, 0, Optionality::X, false),
+            v2_component_descriptor!("reserved_22", "Reserved for harmonization with V2.6", V2ComponentType::Primitive(V2PrimitiveType::ST), 0, 22, 0, Optionality::X, false),
+            v2_component_descriptor!("performing_organization_name", "Performing Organization Name", V2ComponentType::Complex(V2ComplexType::XON), 0, 23, 0, Optionality::O, false),
+            v2_component_descriptor!("performing_organization_address", "Performing Organization Address", V2ComponentType::Complex(V2ComplexType::XAD), 0, 24, 0, Optionality::O, false),
+            v2_component_descriptor!("performing_organization_medical_director", "Performing Organization Medical Director", V2ComponentType::Complex(V2ComplexType::XCN), 0, 25, 0, Optionality::O, false)
+        ]
+    };
+
+    ///
+    /// Fields allowed to repeat in each segment as `(seq, max repetitions)`. A limit of `0` means
+    /// the standard does not bound the number of repetitions. Fields not listed here may only
+    /// appear once.
+    ///
+    pub static V2_FIELD_REPETITIONS: V2FieldRepetitions = phf_map! {
+        "MSH" => &[(18, 0), (21, 0)],
+        "PID" => &[(3, 0), (4, 0), (5, 0), (6, 0), (9, 0), (10, 0), (11, 0), (13, 0), (14, 0), (21, 0), (22, 0), (26, 0), (32, 0), (39, 0)],
+        "PV1" => &[(7, 0), (8, 0), (9, 0), (17, 0), (22, 0), (23, 0), (24, 0), (25, 0), (26, 0), (27, 0), (52, 0)],
+        "ORC" => &[(10, 0), (11, 0), (12, 0), (14, 2), (19, 0), (21, 0), (22, 0), (23, 0), (24, 0)],
+        "OBR" => &[(10, 0), (16, 0), (17, 2), (28, 5), (31, 0), (34, 0), (35, 0), (38, 0), (39, 0), (40, 0), (43, 0), (45, 0), (46, 0), (47, 0), (48, 0)],
+        "OBX" => &[(5, 0), (8, 0), (10, 0), (16, 0), (17, 0), (18, 0), (20, 0)],
+        "NK1" => &[(2, 0), (4, 0), (5, 0), (6, 0), (13, 0), (19, 0), (30, 0), (31, 0), (32, 0), (33, 0), (35, 0)],
+        "IN1" => &[(4, 0), (5, 0), (6, 0), (7, 0), (9, 0), (10, 0), (11, 0), (16, 0), (19, 0), (44, 0), (49, 0)],
+        "ERR" => &[(1, 0), (2, 0), (6, 10), (9, 0), (11, 0), (12, 0)],
+    };
+
+    ///
+    /// Definition of a segment outside the standard. The schema follows the same layout as the
+    /// entries in [V2_SEGMENT_DESCRIPTORS] so custom segments get the same casting and validation.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rumtk_hl7_v2::hl7_v2_segment_descriptors::v2_segment_descriptor::*;
+    /// use rumtk_hl7_v2::hl7_v2_base_types::v2_primitives::V2PrimitiveType;
+    /// use rumtk_hl7_v2::hl7_v2_optionality_rules::Optionality;
+    /// use rumtk_hl7_v2::v2_component_descriptor;
+    ///
+    /// static ZPD: V2CustomSegment = V2CustomSegment::new(
+    ///     "ZPD",
+    ///     "Patient Demographics Extension",
+    ///     &[
+    ///         v2_component_descriptor!("set_id", "Set ID - ZPD", V2ComponentType::Primitive(V2PrimitiveType::SI), 4, 1, 0, Optionality::R, false),
+    ///         v2_component_descriptor!("preferred_language", "Preferred Language", V2ComponentType::Complex(V2ComplexType::CWE), 0, 2, 0, Optionality::O, false),
+    ///     ],
+    ///     &[(2, 0)],
+    /// );
+    ///
+    /// register_custom_segment(&ZPD).unwrap();
+    /// assert!(get_segment_descriptor("ZPD").is_some(), "ZPD was not registered!");
+    /// ```
+    ///
+    #[derive(Debug, Clone, Copy)]
+    pub struct V2CustomSegment {
+        pub name: &'static str,
+        pub description: &'static str,
+        pub descriptors: &'static V2ComponentDescriptor,
+        /// Same layout as the entries in [V2_FIELD_REPETITIONS].
+        pub repetitions: &'static [(u16, u16)],
+    }
+
+    impl V2CustomSegment {
+        pub const fn new(
+            name: &'static str,
+            description: &'static str,
+            descriptors: &'static V2ComponentDescriptor,
+            repetitions: &'static [(u16, u16)],
+        ) -> V2CustomSegment {
+            V2CustomSegment {
+                name,
+                description,
+                descriptors,
+                repetitions,
+            }
+        }
+    }
+
+    ///
+    /// Register a custom segment, replacing any previous definition with the same name. Names must
+    /// be three uppercase letters or digits and cannot shadow a standard segment.
+    ///
+    pub fn register_custom_segment(segment: &V2CustomSegment) -> V2Result<()> {
+        let name = segment.name;
+        let well_formed = name.len() == 3
+            && name
+                .bytes()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+        if !well_formed {
+            return Err(rumtk_format!(
+                "Custom segment name {} must be 3 uppercase letters or digits!",
+                name
+            ));
+        }
+        if V2_SEGMENT_IDS(name.as_bytes()) != V2_CUSTOM_SEGMENT_ID {
+            return Err(rumtk_format!(
+                "Custom segment {} would shadow the standard segment with the same name!",
+                name
+            ));
+        }
+        rumtk_cache_push!(&raw mut CUSTOM_SEGMENT_REGISTRY, &V2String::from(name), *segment);
+        Ok(())
+    }
+
+    pub fn get_custom_segment(segment_name: &str) -> Option<V2CustomSegment> {
+        rumtk_cache_get!(&raw mut CUSTOM_SEGMENT_REGISTRY, &V2String::from(segment_name))
+    }
+
+    ///
+    /// Get the schema for a segment by name. Standard segments are looked up first, followed by
+    /// the registered custom segments.
+    ///
+    pub fn get_segment_descriptor(segment_name: &str) -> Option<&'static V2ComponentDescriptor> {
+        match V2_SEGMENT_DESCRIPTORS.get(segment_name) {
+            Some(descriptor) => Some(*descriptor),
+            None => get_custom_segment(segment_name).map(|segment| segment.descriptors),
+        }
+    }
+
+    ///
+    /// Same as [get_segment_descriptor] but also hands back the name with a `'static` lifetime.
+    ///
+    pub fn get_segment_descriptor_entry(
+        segment_name: &str,
+    ) -> Option<(&'static str, &'static V2ComponentDescriptor)> {
+        match V2_SEGMENT_DESCRIPTORS.get_entry(segment_name) {
+            Some((name, descriptor)) => Some((*name, *descriptor)),
+            None => get_custom_segment(segment_name).map(|segment| (segment.name, segment.descriptors)),
+        }
+    }
+
+    ///
+    /// Same as [get_segment_descriptor] but with the fields as defined in `version`. Only the
+    /// segments in [V2_SEGMENT_DESCRIPTORS_PRE_V2_6] differ between releases.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rumtk_hl7_v2::hl7_v2_segment_descriptors::v2_segment_descriptor::{get_segment_descriptor_as, V2Version};
+    ///
+    /// assert_eq!(get_segment_descriptor_as("OBX", V2Version::V2_9).unwrap()[19].name, "observation_site");
+    /// assert_eq!(get_segment_descriptor_as("OBX", V2Version::V2_5_1).unwrap()[19].name, "reserved_20");
+    /// ```
+    ///
+    pub fn get_segment_descriptor_as(
+        segment_name: &str,
+        version: V2Version,
+    ) -> Option<&'static V2ComponentDescriptor> {
+        match version < V2Version::V2_6 {
+            true => V2_SEGMENT_DESCRIPTORS_PRE_V2_6
+                .get(segment_name)
+                .copied()
+                .or_else(|| get_segment_descriptor(segment_name)),
+            false => get_segment_descriptor(segment_name),
+        }
+    }
+
+    ///
+    /// Translate a field sequence number as given in the standard to the 1-indexed position used by
+    /// [V2Segment::get]. See [V2Segment::field_index].
+    ///
+    pub fn segment_field_index(segment_name: &str, seq: u16) -> isize {
+        V2Segment::field_index(V2_SEGMENT_IDS(segment_name.as_bytes()), seq)
+    }
+
+    ///
+    /// Maximum number of repetitions allowed for field `seq` of a segment. Returns [None] if the
+    /// field may repeat without bound.
+    ///
+    pub fn field_repetition_limit(segment_name: &str, seq: u16) -> Option<u16> {
+        let repeatable = match V2_FIELD_REPETITIONS.get(segment_name) {
+            Some(repeatable) => *repeatable,
+            None => match get_custom_segment(segment_name) {
+                Some(segment) => segment.repetitions,
+                None => return Some(1),
+            },
+        };
+        match repeatable.iter().find(|(field, _)| *field == seq) {
+            Some((_, 0)) => None,
+            Some((_, limit)) => Some(*limit),
+            None => Some(1),
+        }
+    }
+}
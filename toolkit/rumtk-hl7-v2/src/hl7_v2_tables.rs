@@ -0,0 +1,958 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2026  Luis M. Santos, M.D. <lsantos@medicalmasses.com>
+ * Copyright (C) 2026  MedicalMasses L.L.C. <contact@medicalmasses.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+///
+/// HL7 tables referenced by [V2ComponentTypeDescriptor::valid_table](crate::hl7_v2_field_descriptors::v2_field_descriptor::V2ComponentTypeDescriptor).
+///
+/// We ship the HL7 defined tables (the ones backing ID fields) that have a small and stable list of
+/// values. User defined tables (the ones backing IS fields, e.g. 0001 Administrative Sex) are site
+/// specific so they are left empty until a site registers them from JSON or CSV. Sites can also
+/// extend the shipped tables with local codes.
+///
+/// Lookups go through a global registry which is seeded lazily from [V2_HL7_TABLES]. Tables we do
+/// not know about are never validated.
+///
+pub mod v2_tables {
+    use crate::hl7_v2_base_types::v2_primitives::{V2Result, V2String};
+    use ::phf::Map;
+    use ::phf_macros::phf_map;
+    use rumtk_core::cache::{new_cache, LazyRUMCache};
+    use rumtk_core::serde::json::{from_json, RUMDeJson, RUMSerJson};
+    use rumtk_core::strings::rumtk_format;
+    use rumtk_core::types::RUMHashMap;
+    use rumtk_core::{rumtk_cache_fetch, rumtk_cache_push};
+    use std::sync::Arc;
+
+    pub type V2TableValues = &'static [(&'static str, &'static str)];
+    pub type V2HL7Tables = Map<u16, (&'static str, V2TableValues)>;
+
+    static mut TABLE_REGISTRY: LazyRUMCache<u16, Arc<V2Table>> = new_cache();
+
+    ///
+    /// A table of coded values. `values` maps each code to its description.
+    ///
+    /// JSON representation:
+    ///
+    /// ```json
+    /// {"id": 1, "name": "Administrative Sex", "values": {"F": "Female", "M": "Male"}}
+    /// ```
+    ///
+    #[derive(Default, Debug, RUMSerJson, RUMDeJson, PartialEq, Clone)]
+    pub struct V2Table {
+        id: u16,
+        name: V2String,
+        values: RUMHashMap<V2String, V2String>,
+    }
+
+    impl V2Table {
+        pub fn new(id: u16, name: &str) -> Self {
+            V2Table {
+                id,
+                name: V2String::from(name),
+                values: RUMHashMap::new(),
+            }
+        }
+
+        ///
+        /// Build a table from one of the tables shipped in [V2_HL7_TABLES].
+        ///
+        pub fn from_hl7_table(id: u16) -> V2Result<Self> {
+            match V2_HL7_TABLES.get(&id) {
+                Some((name, values)) => {
+                    let mut table = V2Table::new(id, name);
+                    for (code, description) in values.iter() {
+                        table.insert(code, description);
+                    }
+                    Ok(table)
+                }
+                None => Err(rumtk_format!("HL7 table {:04} is not available!", id)),
+            }
+        }
+
+        pub fn from_json(json: &str) -> V2Result<Self> {
+            from_json(json)
+        }
+
+        ///
+        /// Build a table from CSV with one `code,description` pair per line. The description is
+        /// optional. Blank lines, lines starting with `#` and a `code,description` header ahead of
+        /// the first value are skipped. Values may be wrapped in double quotes, which is required
+        /// for values containing commas. A quote inside a quoted value is written as `""`.
+        ///
+        pub fn from_csv(id: u16, name: &str, csv: &str) -> V2Result<Self> {
+            let mut table = V2Table::new(id, name);
+            for (i, line) in csv.lines().enumerate() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                let columns = match split_csv_line(line) {
+                    Some(columns) => columns,
+                    None => {
+                        return Err(rumtk_format!(
+                            "Line {} in CSV for table {:04} has an unterminated quote!",
+                            i + 1,
+                            id
+                        ))
+                    }
+                };
+                let code = columns.first().map(|c| c.as_str()).unwrap_or_default();
+                let description = columns.get(1).map(|c| c.as_str()).unwrap_or_default();
+                if code.is_empty() {
+                    return Err(rumtk_format!(
+                        "Line {} in CSV for table {:04} has no code!",
+                        i + 1,
+                        id
+                    ));
+                }
+                if table.is_empty() && code.eq_ignore_ascii_case("code") {
+                    continue;
+                }
+                table.insert(code, description);
+            }
+            Ok(table)
+        }
+
+        pub fn id(&self) -> u16 {
+            self.id
+        }
+
+        pub fn name(&self) -> &str {
+            &self.name
+        }
+
+        pub fn insert(&mut self, code: &str, description: &str) {
+            self.values
+                .insert(V2String::from(code), V2String::from(description));
+        }
+
+        pub fn contains(&self, code: &str) -> bool {
+            self.values.contains_key(code)
+        }
+
+        pub fn description(&self, code: &str) -> Option<&str> {
+            self.values.get(code).map(|d| d.as_str())
+        }
+
+        pub fn len(&self) -> usize {
+            self.values.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.values.is_empty()
+        }
+    }
+
+    ///
+    /// Split a CSV line into trimmed columns. Commas inside double quotes do not split. Returns
+    /// [None] if a quote is left open.
+    ///
+    fn split_csv_line(line: &str) -> Option<Vec<V2String>> {
+        let mut columns = Vec::new();
+        let mut column = V2String::new();
+        let mut quoted = false;
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    column.push('"');
+                    chars.next();
+                }
+                '"' if quoted => quoted = false,
+                '"' if column.trim().is_empty() => {
+                    column.clear();
+                    quoted = true;
+                }
+                ',' if !quoted => columns.push(std::mem::take(&mut column).trim().to_string()),
+                c => column.push(c),
+            }
+        }
+        if quoted {
+            return None;
+        }
+        columns.push(column.trim().to_string());
+        Some(columns)
+    }
+
+    ///
+    /// Register a table, replacing any table previously registered or shipped under the same id.
+    ///
+    pub fn register_table(table: V2Table) {
+        let id = table.id();
+        rumtk_cache_push!(&raw mut TABLE_REGISTRY, &id, Arc::new(table));
+    }
+
+    ///
+    /// Add codes to a table. If the table is one we ship, the local codes are added on top of the
+    /// standard ones. Otherwise, a new table is created.
+    ///
+    pub fn extend_table(id: u16, name: &str, values: &[(&str, &str)]) {
+        let mut table = match get_table(id) {
+            Some(table) => table.as_ref().clone(),
+            None => V2Table::new(id, name),
+        };
+        for (code, description) in values.iter() {
+            table.insert(code, description);
+        }
+        register_table(table);
+    }
+
+    ///
+    /// Register a table from its JSON representation. See [V2Table]. Returns the id of the table.
+    ///
+    pub fn load_table_json(json: &str) -> V2Result<u16> {
+        let table = V2Table::from_json(json)?;
+        let id = table.id();
+        register_table(table);
+        Ok(id)
+    }
+
+    ///
+    /// Register a table from CSV. See [V2Table::from_csv].
+    ///
+    pub fn load_table_csv(id: u16, name: &str, csv: &str) -> V2Result<()> {
+        register_table(V2Table::from_csv(id, name, csv)?);
+        Ok(())
+    }
+
+    pub fn get_table(id: u16) -> Option<Arc<V2Table>> {
+        rumtk_cache_fetch!(&raw mut TABLE_REGISTRY, &id, || {
+            V2Table::from_hl7_table(id).map(Arc::new)
+        })
+        .ok()
+    }
+
+    ///
+    /// Check `value` against table `id`. Unknown and empty tables accept any value since there is
+    /// nothing to check against.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rumtk_hl7_v2::hl7_v2_tables::v2_tables::validate_table_value;
+    ///
+    /// assert!(validate_table_value(190, "H").is_ok(), "H is a valid address type!");
+    /// assert!(validate_table_value(190, "XYZ").is_err(), "XYZ is not a valid address type!");
+    /// assert!(validate_table_value(9999, "XYZ").is_ok(), "Table 9999 does not exist!");
+    /// ```
+    ///
+    pub fn validate_table_value(id: u16, value: &str) -> V2Result<()> {
+        let table = match get_table(id) {
+            Some(table) => table,
+            None => return Ok(()),
+        };
+        match table.is_empty() || table.contains(value) {
+            true => Ok(()),
+            false => Err(rumtk_format!(
+                "Value {} is not in HL7 table {:04} ({})!",
+                value,
+                id,
+                table.name()
+            )),
+        }
+    }
+
+    ///
+    /// HL7 defined tables shipped with the crate. Values follow v2.5.1 plus the codes added in later
+    /// versions so newer messages are not rejected.
+    ///
+    pub static V2_HL7_TABLES: V2HL7Tables = phf_map! {
+        8u16 => ("Acknowledgment Code", &[
+            ("AA", "Original mode: Application Accept - Enhanced mode: Application acknowledgment: Accept"),
+            ("AE", "Original mode: Application Error - Enhanced mode: Application acknowledgment: Error"),
+            ("AR", "Original mode: Application Reject - Enhanced mode: Application acknowledgment: Reject"),
+            ("CA", "Enhanced mode: Accept acknowledgment: Commit Accept"),
+            ("CE", "Enhanced mode: Accept acknowledgment: Commit Error"),
+            ("CR", "Enhanced mode: Accept acknowledgment: Commit Reject")
+        ]),
+        38u16 => ("Order Status", &[
+            ("A", "Some, but not all, results available"),
+            ("CA", "Order was canceled"),
+            ("CM", "Order is completed"),
+            ("DC", "Order was discontinued"),
+            ("ER", "Error, order not found"),
+            ("HD", "Order is on hold"),
+            ("IP", "In process, unspecified"),
+            ("RP", "Order has been replaced"),
+            ("SC", "In process, scheduled")
+        ]),
+        61u16 => ("Check Digit Scheme", &[
+            ("BCV", "Bank Card Validation Number"),
+            ("ISO", "ISO 7064: 1983"),
+            ("M10", "Mod 10 algorithm"),
+            ("M11", "Mod 11 algorithm"),
+            ("NPI", "Check digit algorithm in the US National Provider Identifier")
+        ]),
+        65u16 => ("Specimen Action Code", &[
+            ("A", "Add ordered tests to the existing specimen"),
+            ("G", "Generated order; reflex order"),
+            ("L", "Lab to obtain specimen from patient"),
+            ("O", "Specimen obtained by service other than Lab"),
+            ("P", "Pending specimen; Order sent prior to delivery"),
+            ("R", "Revised order"),
+            ("S", "Schedule the tests specified below")
+        ]),
+        74u16 => ("Diagnostic Service Section ID", &[
+            ("AU", "Audiology"),
+            ("BG", "Blood Gases"),
+            ("BLB", "Blood Bank"),
+            ("CG", "Cytogenetics"),
+            ("CH", "Chemistry"),
+            ("CP", "Cytopathology"),
+            ("CT", "CAT Scan"),
+            ("CTH", "Cardiac Catheterization"),
+            ("CUS", "Cardiac Ultrasound"),
+            ("EC", "Electrocardiac (e.g. EKG, EEC, Holter)"),
+            ("EN", "Electroneuro (EEG, EMG,EP,PSG)"),
+            ("HM", "Hematology"),
+            ("ICU", "Bedside ICU Monitoring"),
+            ("IMM", "Immunology"),
+            ("LAB", "Laboratory"),
+            ("MB", "Microbiology"),
+            ("MCB", "Mycobacteriology"),
+            ("MYC", "Mycology"),
+            ("NMR", "Nuclear Magnetic Resonance"),
+            ("NMS", "Nuclear Medicine Scan"),
+            ("NRS", "Nursing Service Measures"),
+            ("OSL", "Outside Lab"),
+            ("OT", "Occupational Therapy"),
+            ("OTH", "Other"),
+            ("OUS", "OB Ultrasound"),
+            ("PF", "Pulmonary Function"),
+            ("PHR", "Pharmacy"),
+            ("PHY", "Physician (Hx. Dx, admission note, etc.)"),
+            ("PT", "Physical Therapy"),
+            ("RAD", "Radiology"),
+            ("RC", "Respiratory Care (therapy)"),
+            ("RT", "Radiation Therapy"),
+            ("RUS", "Radiology Ultrasound"),
+            ("RX", "Radiograph"),
+            ("SP", "Surgical Pathology"),
+            ("SR", "Serology"),
+            ("TX", "Toxicology"),
+            ("URN", "Urinalysis"),
+            ("VR", "Virology"),
+            ("VUS", "Vascular Ultrasound"),
+            ("XRC", "Cineradiograph")
+        ]),
+        80u16 => ("Nature of Abnormal Testing", &[
+            ("A", "An age-based population"),
+            ("B", "Breed"),
+            ("N", "None - generic normal range"),
+            ("R", "A race-based population"),
+            ("S", "A sex-based population"),
+            ("SP", "Species"),
+            ("ST", "Strain")
+        ]),
+        85u16 => ("Observation Result Status Codes Interpretation", &[
+            ("C", "Record coming over is a correction and thus replaces a final result"),
+            ("D", "Deletes the OBX record"),
+            ("F", "Final results; Can only be changed with a corrected result."),
+            ("I", "Specimen in lab; results pending"),
+            ("N", "Not asked; used to affirmatively document that the observation identified in the OBX was not sought when the universal service ID in OBR-4 implies that it would be sought."),
+            ("O", "Order detail description only (no result)"),
+            ("P", "Preliminary results"),
+            ("R", "Results entered -- not verified"),
+            ("S", "Partial results"),
+            ("U", "Results status change to final without retransmitting results already sent as 'preliminary.'"),
+            ("W", "Post original as wrong, e.g., transmitted for wrong patient"),
+            ("X", "Results cannot be obtained for this observation")
+        ]),
+        103u16 => ("Processing ID", &[
+            ("D", "Debugging"),
+            ("P", "Production"),
+            ("T", "Training")
+        ]),
+        104u16 => ("Version ID", &[
+            ("2.0", "Release 2.0"),
+            ("2.0D", "Demo 2.0"),
+            ("2.1", "Release 2.1"),
+            ("2.2", "Release 2.2"),
+            ("2.3", "Release 2.3"),
+            ("2.3.1", "Release 2.3.1"),
+            ("2.4", "Release 2.4"),
+            ("2.5", "Release 2.5"),
+            ("2.5.1", "Release 2.5.1"),
+            ("2.6", "Release 2.6"),
+            ("2.7", "Release 2.7"),
+            ("2.7.1", "Release 2.7.1"),
+            ("2.8", "Release 2.8"),
+            ("2.8.1", "Release 2.8.1"),
+            ("2.8.2", "Release 2.8.2"),
+            ("2.9", "Release 2.9")
+        ]),
+        119u16 => ("Order Control Codes", &[
+            ("AF", "Order/service refill request approval"),
+            ("CA", "Cancel order/service request"),
+            ("CH", "Child order/service"),
+            ("CN", "Combined result"),
+            ("CR", "Canceled as requested"),
+            ("DC", "Discontinue order/service request"),
+            ("DE", "Data errors"),
+            ("DF", "Order/service refill request denied"),
+            ("DR", "Discontinued as requested"),
+            ("FU", "Order/service refilled, unsolicited"),
+            ("HD", "Hold order request"),
+            ("HR", "On hold as requested"),
+            ("LI", "Link order/service to patient care problem or goal"),
+            ("MC", "Miscellaneous Charge - not associated with an order"),
+            ("NA", "Number assigned"),
+            ("NW", "New order/service"),
+            ("OC", "Order/service canceled"),
+            ("OD", "Order/service discontinued"),
+            ("OE", "Order/service released"),
+            ("OF", "Order/service refilled as requested"),
+            ("OH", "Order/service held"),
+            ("OK", "Order/service accepted & OK"),
+            ("OP", "Notification of order for outside dispense"),
+            ("OR", "Released as requested"),
+            ("PA", "Parent order/service"),
+            ("PR", "Previous Results with new order/service"),
+            ("PY", "Notification of replacement order for outside dispense"),
+            ("RE", "Observations/Performed Service to follow"),
+            ("RF", "Refill order/service request"),
+            ("RL", "Release previous hold"),
+            ("RO", "Replacement order"),
+            ("RP", "Order/service replace request"),
+            ("RQ", "Replaced as requested"),
+            ("RR", "Request received"),
+            ("RU", "Replaced unsolicited"),
+            ("SC", "Status changed"),
+            ("SN", "Send order/service number"),
+            ("SR", "Response to send order/service status request"),
+            ("SS", "Send order/service status request"),
+            ("UA", "Unable to accept order/service"),
+            ("UC", "Unable to cancel"),
+            ("UD", "Unable to discontinue"),
+            ("UF", "Unable to refill"),
+            ("UH", "Unable to put on hold"),
+            ("UM", "Unable to replace"),
+            ("UN", "Unlink order/service from patient care problem or goal"),
+            ("UR", "Unable to release"),
+            ("UX", "Unable to change"),
+            ("XO", "Change order/service request"),
+            ("XR", "Changed as requested"),
+            ("XX", "Order/service changed, unsol.")
+        ]),
+        121u16 => ("Response Flag", &[
+            ("D", "Same as R, also other associated segments"),
+            ("E", "Report exceptions only"),
+            ("F", "Same as D, plus confirmations explicitly"),
+            ("N", "Only the MSA segment is returned"),
+            ("R", "Same as E, also Replacement and Parent-Child")
+        ]),
+        123u16 => ("Result Status", &[
+            ("A", "Some, but not all, results available"),
+            ("C", "Correction to results"),
+            ("F", "Final results; results stored and verified. Can only be changed with a corrected result."),
+            ("I", "No results available; specimen received, procedure incomplete"),
+            ("O", "Order received; specimen not yet received"),
+            ("P", "Preliminary: A verified early result is available, final results not yet obtained"),
+            ("R", "Results stored; not yet verified"),
+            ("S", "No results available; procedure scheduled, but not done"),
+            ("X", "No results available; Order canceled."),
+            ("Y", "No order on record for this test. (Used only on queries)"),
+            ("Z", "No record of this patient. (Used only on queries)")
+        ]),
+        124u16 => ("Transportation Mode", &[
+            ("CART", "Cart - patient travels on cart or gurney"),
+            ("PORT", "The examining device goes to patient's location"),
+            ("WALK", "Patient walks to diagnostic service"),
+            ("WHLC", "Wheelchair")
+        ]),
+        125u16 => ("Value Type", &[
+            ("AD", "Address"),
+            ("CE", "Coded Entry"),
+            ("CF", "Coded Element With Formatted Values"),
+            ("CK", "Composite ID With Check Digit"),
+            ("CN", "Composite ID And Name"),
+            ("CNE", "Coded with No Exceptions"),
+            ("CP", "Composite Price"),
+            ("CWE", "Coded Entry"),
+            ("CX", "Extended Composite ID With Check Digit"),
+            ("DR", "Date/Time Range"),
+            ("DT", "Date"),
+            ("DTM", "Time Stamp (Date & Time)"),
+            ("ED", "Encapsulated Data"),
+            ("FT", "Formatted Text (Display)"),
+            ("ID", "Coded Value for HL7 Defined Tables"),
+            ("IS", "Coded Value for User-Defined Tables"),
+            ("MA", "Multiplexed Array"),
+            ("MO", "Money"),
+            ("NA", "Numeric Array"),
+            ("NM", "Numeric"),
+            ("NR", "Numeric Range"),
+            ("PN", "Person Name"),
+            ("RP", "Reference Pointer"),
+            ("SN", "Structured Numeric"),
+            ("ST", "String Data."),
+            ("TM", "Time"),
+            ("TN", "Telephone Number"),
+            ("TS", "Time Stamp (Date & Time)"),
+            ("TX", "Text Data (Display)"),
+            ("XAD", "Extended Address"),
+            ("XCN", "Extended Composite Name And Number For Persons"),
+            ("XON", "Extended Composite Name And Number For Organizations"),
+            ("XPN", "Extended Person Name"),
+            ("XTN", "Extended Telecommunications Number")
+        ]),
+        136u16 => ("Yes/no Indicator", &[
+            ("N", "No"),
+            ("Y", "Yes")
+        ]),
+        155u16 => ("Accept/Application Acknowledgment Conditions", &[
+            ("AL", "Always"),
+            ("ER", "Error/reject conditions only"),
+            ("NE", "Never"),
+            ("SU", "Successful completion only")
+        ]),
+        190u16 => ("Address Type", &[
+            ("B", "Firm/Business"),
+            ("BA", "Bad address"),
+            ("BDL", "Birth delivery location (address where birth occurred)"),
+            ("BI", "Billing Address"),
+            ("BR", "Residence at birth (home address at time of birth)"),
+            ("C", "Current Or Temporary"),
+            ("F", "Country Of Origin"),
+            ("H", "Home"),
+            ("HV", "Vacation Home"),
+            ("L", "Legal Address"),
+            ("M", "Mailing"),
+            ("N", "Birth (nee) (birth address, not otherwise specified)"),
+            ("O", "Office"),
+            ("P", "Permanent"),
+            ("RH", "Registry home"),
+            ("S", "Service Location"),
+            ("SH", "Shipping Address"),
+            ("TM", "Tube Address"),
+            ("V", "Visit")
+        ]),
+        200u16 => ("Name Type", &[
+            ("A", "Alias Name"),
+            ("B", "Name at Birth"),
+            ("BAD", "Bad Name"),
+            ("C", "Adopted Name"),
+            ("D", "Display Name"),
+            ("F", "Fathers Name"),
+            ("I", "Licensing Name"),
+            ("K", "Business name"),
+            ("L", "Official Registry Name"),
+            ("M", "Maiden Name"),
+            ("MSK", "Masked"),
+            ("N", "Nickname"),
+            ("NAV", "Temporarily Unavailable"),
+            ("NB", "Newborn Name"),
+            ("NOUSE", "No Longer To Be Used"),
+            ("P", "Name of Partner/Spouse"),
+            ("R", "Registered Name (animals only)"),
+            ("REL", "Religious"),
+            ("S", "Pseudonym"),
+            ("T", "Indigenous/Tribal"),
+            ("TEMP", "Temporary Name"),
+            ("U", "Unknown")
+        ]),
+        201u16 => ("Telecommunication Use Code", &[
+            ("ASN", "Answering Service Number"),
+            ("BPN", "Beeper Number"),
+            ("EMR", "Emergency Number"),
+            ("NET", "Network (email) Address"),
+            ("ORN", "Other Residence Number"),
+            ("PRN", "Primary Residence Number"),
+            ("PRS", "Personal"),
+            ("VHN", "Vacation Home Number"),
+            ("WPN", "Work Number")
+        ]),
+        202u16 => ("Telecommunication Equipment Type", &[
+            ("BP", "Beeper"),
+            ("CP", "Cellular or Mobile Phone"),
+            ("FX", "Fax"),
+            ("Internet", "Internet Address"),
+            ("MD", "Modem"),
+            ("PH", "Telephone"),
+            ("SAT", "Satellite Phone"),
+            ("TDD", "Telecommunications Device for the Deaf"),
+            ("TTY", "Teletypewriter"),
+            ("X.400", "X.400 email address")
+        ]),
+        205u16 => ("Price Type", &[
+            ("AP", "administrative price or handling fee"),
+            ("DC", "direct unit cost"),
+            ("IC", "indirect unit cost"),
+            ("PF", "professional fee for performing provider"),
+            ("TF", "technology fee for use of equipment"),
+            ("TP", "total price"),
+            ("UP", "unit price, may be based on length of procedure or service")
+        ]),
+        207u16 => ("Processing Mode", &[
+            ("A", "Archive"),
+            ("I", "Initial load"),
+            ("R", "Restore from archive"),
+            ("T", "Current processing, transmitted at intervals (scheduled or on demand)")
+        ]),
+        211u16 => ("Alternate Character Sets", &[
+            ("8859/1", "The printable characters from the ISO 8859/1 Character set"),
+            ("8859/15", "The printable characters from the ISO 8859/15 (Latin-15)"),
+            ("8859/2", "The printable characters from the ISO 8859/2 Character set"),
+            ("8859/3", "The printable characters from the ISO 8859/3 Character set"),
+            ("8859/4", "The printable characters from the ISO 8859/4 Character set"),
+            ("8859/5", "The printable characters from the ISO 8859/5 Character set"),
+            ("8859/6", "The printable characters from the ISO 8859/6 Character set"),
+            ("8859/7", "The printable characters from the ISO 8859/7 Character set"),
+            ("8859/8", "The printable characters from the ISO 8859/8 Character set"),
+            ("8859/9", "The printable characters from the ISO 8859/9 Character set"),
+            ("ASCII", "The printable 7-bit ASCII character set."),
+            ("BIG-5", "Code for Taiwanese Character Set (BIG-5)"),
+            ("CNS 11643-1992", "Code for Taiwanese Character Set (CNS 11643-1992)"),
+            ("GB 18030-2000", "Code for Chinese Character Set (GB 18030-2000)"),
+            ("ISO IR14", "Code for Information Exchange (one byte)(JIS X 0201-1976)."),
+            ("ISO IR159", "Code of the supplementary Japanese Graphic Character set for information interchange (JIS X 0212-1990)."),
+            ("ISO IR6", "ASCII graphic character set consisting of 94 characters."),
+            ("ISO IR87", "Code for the Japanese Graphic Character set for information interchange (JIS X 0208-1990)"),
+            ("KS X 1001", "Code for Korean Character Set (KS X 1001)"),
+            ("UNICODE", "The world wide character standard from ISO/IEC 10646-1-1993"),
+            ("UNICODE UTF-16", "UCS Transformation Format, 16-bit form"),
+            ("UNICODE UTF-32", "UCS Transformation Format, 32-bit form"),
+            ("UNICODE UTF-8", "UCS Transformation Format, 8-bit form")
+        ]),
+        224u16 => ("Transport Arranged", &[
+            ("A", "Arranged"),
+            ("N", "Not Arranged"),
+            ("U", "Unknown")
+        ]),
+        225u16 => ("Escort Required", &[
+            ("N", "Not Required"),
+            ("R", "Required"),
+            ("U", "Unknown")
+        ]),
+        298u16 => ("CP Range Type", &[
+            ("F", "Flat-rate. Apply the entire price to this interval, do not pro-rate the price if the full interval has not occurred/been consumed"),
+            ("P", "Pro-rate. Apply this price to this interval, pro-rated by whatever portion of the interval has occurred/been consumed")
+        ]),
+        301u16 => ("Universal ID Type", &[
+            ("CLIA", "Clinical Laboratory Improvement Amendments."),
+            ("CLIP", "Clinical laboratory Improvement Program."),
+            ("DNS", "An Internet dotted name."),
+            ("EUI64", "IEEE 64-bit Extended Unique Identifier"),
+            ("GUID", "Same as UUID."),
+            ("HCD", "The CEN Healthcare Coding Scheme Designator."),
+            ("HL7", "Reserved for future HL7 registration schemes."),
+            ("ISO", "An International Standards Organization Object Identifier."),
+            ("L", "Locally defined coding entity identifier."),
+            ("M", "Locally defined coding entity identifier."),
+            ("N", "Locally defined coding entity identifier."),
+            ("Random", "Usually a base64 encoded string of random bits."),
+            ("URI", "Uniform Resource Identifier"),
+            ("UUID", "The DCE Universal Unique Identifier"),
+            ("x400", "An X.400 MHS identifier."),
+            ("x500", "An X.500 directory name")
+        ]),
+        356u16 => ("Alternate Character Set Handling Scheme", &[
+            ("2.3", "The character set switching mode specified in HL7 2.5, section 2.7.2 and section 2.A.46, \"XPN - extended person name\"."),
+            ("ISO 2022-1994", "This standard is titled \"Information Technology - Character Code Structure and Extension Technique\".")
+        ]),
+        357u16 => ("Message Error Condition Codes", &[
+            ("0", "Message accepted"),
+            ("100", "Segment sequence error"),
+            ("101", "Required field missing"),
+            ("102", "Data type error"),
+            ("103", "Table value not found"),
+            ("104", "Value too long"),
+            ("200", "Unsupported message type"),
+            ("201", "Unsupported event code"),
+            ("202", "Unsupported processing id"),
+            ("203", "Unsupported version id"),
+            ("204", "Unknown key identifier"),
+            ("205", "Duplicate key identifier"),
+            ("206", "Application record locked"),
+            ("207", "Application internal error")
+        ]),
+        399u16 => ("Country Code", &[
+            ("ABW", "Aruba"),
+            ("AFG", "Afghanistan"),
+            ("AGO", "Angola"),
+            ("AIA", "Anguilla"),
+            ("ALA", "Åland Islands"),
+            ("ALB", "Albania"),
+            ("AND", "Andorra"),
+            ("ARE", "United Arab Emirates"),
+            ("ARG", "Argentina"),
+            ("ARM", "Armenia"),
+            ("ASM", "American Samoa"),
+            ("ATA", "Antarctica"),
+            ("ATF", "French Southern Territories"),
+            ("ATG", "Antigua and Barbuda"),
+            ("AUS", "Australia"),
+            ("AUT", "Austria"),
+            ("AZE", "Azerbaijan"),
+            ("BDI", "Burundi"),
+            ("BEL", "Belgium"),
+            ("BEN", "Benin"),
+            ("BES", "Bonaire, Sint Eustatius and Saba"),
+            ("BFA", "Burkina Faso"),
+            ("BGD", "Bangladesh"),
+            ("BGR", "Bulgaria"),
+            ("BHR", "Bahrain"),
+            ("BHS", "Bahamas"),
+            ("BIH", "Bosnia and Herzegovina"),
+            ("BLM", "Saint Barthélemy"),
+            ("BLR", "Belarus"),
+            ("BLZ", "Belize"),
+            ("BMU", "Bermuda"),
+            ("BOL", "Bolivia, Plurinational State of"),
+            ("BRA", "Brazil"),
+            ("BRB", "Barbados"),
+            ("BRN", "Brunei Darussalam"),
+            ("BTN", "Bhutan"),
+            ("BVT", "Bouvet Island"),
+            ("BWA", "Botswana"),
+            ("CAF", "Central African Republic"),
+            ("CAN", "Canada"),
+            ("CCK", "Cocos (Keeling) Islands"),
+            ("CHE", "Switzerland"),
+            ("CHL", "Chile"),
+            ("CHN", "China"),
+            ("CIV", "Côte d'Ivoire"),
+            ("CMR", "Cameroon"),
+            ("COD", "Congo, The Democratic Republic of the"),
+            ("COG", "Congo"),
+            ("COK", "Cook Islands"),
+            ("COL", "Colombia"),
+            ("COM", "Comoros"),
+            ("CPV", "Cabo Verde"),
+            ("CRI", "Costa Rica"),
+            ("CUB", "Cuba"),
+            ("CUW", "Curaçao"),
+            ("CXR", "Christmas Island"),
+            ("CYM", "Cayman Islands"),
+            ("CYP", "Cyprus"),
+            ("CZE", "Czechia"),
+            ("DEU", "Germany"),
+            ("DJI", "Djibouti"),
+            ("DMA", "Dominica"),
+            ("DNK", "Denmark"),
+            ("DOM", "Dominican Republic"),
+            ("DZA", "Algeria"),
+            ("ECU", "Ecuador"),
+            ("EGY", "Egypt"),
+            ("ERI", "Eritrea"),
+            ("ESH", "Western Sahara"),
+            ("ESP", "Spain"),
+            ("EST", "Estonia"),
+            ("ETH", "Ethiopia"),
+            ("FIN", "Finland"),
+            ("FJI", "Fiji"),
+            ("FLK", "Falkland Islands (Malvinas)"),
+            ("FRA", "France"),
+            ("FRO", "Faroe Islands"),
+            ("FSM", "Micronesia, Federated States of"),
+            ("GAB", "Gabon"),
+            ("GBR", "United Kingdom"),
+            ("GEO", "Georgia"),
+            ("GGY", "Guernsey"),
+            ("GHA", "Ghana"),
+            ("GIB", "Gibraltar"),
+            ("GIN", "Guinea"),
+            ("GLP", "Guadeloupe"),
+            ("GMB", "Gambia"),
+            ("GNB", "Guinea-Bissau"),
+            ("GNQ", "Equatorial Guinea"),
+            ("GRC", "Greece"),
+            ("GRD", "Grenada"),
+            ("GRL", "Greenland"),
+            ("GTM", "Guatemala"),
+            ("GUF", "French Guiana"),
+            ("GUM", "Guam"),
+            ("GUY", "Guyana"),
+            ("HKG", "Hong Kong"),
+            ("HMD", "Heard Island and McDonald Islands"),
+            ("HND", "Honduras"),
+            ("HRV", "Croatia"),
+            ("HTI", "Haiti"),
+            ("HUN", "Hungary"),
+            ("IDN", "Indonesia"),
+            ("IMN", "Isle of Man"),
+            ("IND", "India"),
+            ("IOT", "British Indian Ocean Territory"),
+            ("IRL", "Ireland"),
+            ("IRN", "Iran, Islamic Republic of"),
+            ("IRQ", "Iraq"),
+            ("ISL", "Iceland"),
+            ("ISR", "Israel"),
+            ("ITA", "Italy"),
+            ("JAM", "Jamaica"),
+            ("JEY", "Jersey"),
+            ("JOR", "Jordan"),
+            ("JPN", "Japan"),
+            ("KAZ", "Kazakhstan"),
+            ("KEN", "Kenya"),
+            ("KGZ", "Kyrgyzstan"),
+            ("KHM", "Cambodia"),
+            ("KIR", "Kiribati"),
+            ("KNA", "Saint Kitts and Nevis"),
+            ("KOR", "Korea, Republic of"),
+            ("KWT", "Kuwait"),
+            ("LAO", "Lao People's Democratic Republic"),
+            ("LBN", "Lebanon"),
+            ("LBR", "Liberia"),
+            ("LBY", "Libya"),
+            ("LCA", "Saint Lucia"),
+            ("LIE", "Liechtenstein"),
+            ("LKA", "Sri Lanka"),
+            ("LSO", "Lesotho"),
+            ("LTU", "Lithuania"),
+            ("LUX", "Luxembourg"),
+            ("LVA", "Latvia"),
+            ("MAC", "Macao"),
+            ("MAF", "Saint Martin (French part)"),
+            ("MAR", "Morocco"),
+            ("MCO", "Monaco"),
+            ("MDA", "Moldova, Republic of"),
+            ("MDG", "Madagascar"),
+            ("MDV", "Maldives"),
+            ("MEX", "Mexico"),
+            ("MHL", "Marshall Islands"),
+            ("MKD", "North Macedonia"),
+            ("MLI", "Mali"),
+            ("MLT", "Malta"),
+            ("MMR", "Myanmar"),
+            ("MNE", "Montenegro"),
+            ("MNG", "Mongolia"),
+            ("MNP", "Northern Mariana Islands"),
+            ("MOZ", "Mozambique"),
+            ("MRT", "Mauritania"),
+            ("MSR", "Montserrat"),
+            ("MTQ", "Martinique"),
+            ("MUS", "Mauritius"),
+            ("MWI", "Malawi"),
+            ("MYS", "Malaysia"),
+            ("MYT", "Mayotte"),
+            ("NAM", "Namibia"),
+            ("NCL", "New Caledonia"),
+            ("NER", "Niger"),
+            ("NFK", "Norfolk Island"),
+            ("NGA", "Nigeria"),
+            ("NIC", "Nicaragua"),
+            ("NIU", "Niue"),
+            ("NLD", "Netherlands"),
+            ("NOR", "Norway"),
+            ("NPL", "Nepal"),
+            ("NRU", "Nauru"),
+            ("NZL", "New Zealand"),
+            ("OMN", "Oman"),
+            ("PAK", "Pakistan"),
+            ("PAN", "Panama"),
+            ("PCN", "Pitcairn"),
+            ("PER", "Peru"),
+            ("PHL", "Philippines"),
+            ("PLW", "Palau"),
+            ("PNG", "Papua New Guinea"),
+            ("POL", "Poland"),
+            ("PRI", "Puerto Rico"),
+            ("PRK", "Korea, Democratic People's Republic of"),
+            ("PRT", "Portugal"),
+            ("PRY", "Paraguay"),
+            ("PSE", "Palestine, State of"),
+            ("PYF", "French Polynesia"),
+            ("QAT", "Qatar"),
+            ("REU", "Réunion"),
+            ("ROU", "Romania"),
+            ("RUS", "Russian Federation"),
+            ("RWA", "Rwanda"),
+            ("SAU", "Saudi Arabia"),
+            ("SDN", "Sudan"),
+            ("SEN", "Senegal"),
+            ("SGP", "Singapore"),
+            ("SGS", "South Georgia and the South Sandwich Islands"),
+            ("SHN", "Saint Helena, Ascension and Tristan da Cunha"),
+            ("SJM", "Svalbard and Jan Mayen"),
+            ("SLB", "Solomon Islands"),
+            ("SLE", "Sierra Leone"),
+            ("SLV", "El Salvador"),
+            ("SMR", "San Marino"),
+            ("SOM", "Somalia"),
+            ("SPM", "Saint Pierre and Miquelon"),
+            ("SRB", "Serbia"),
+            ("SSD", "South Sudan"),
+            ("STP", "Sao Tome and Principe"),
+            ("SUR", "Suriname"),
+            ("SVK", "Slovakia"),
+            ("SVN", "Slovenia"),
+            ("SWE", "Sweden"),
+            ("SWZ", "Eswatini"),
+            ("SXM", "Sint Maarten (Dutch part)"),
+            ("SYC", "Seychelles"),
+            ("SYR", "Syrian Arab Republic"),
+            ("TCA", "Turks and Caicos Islands"),
+            ("TCD", "Chad"),
+            ("TGO", "Togo"),
+            ("THA", "Thailand"),
+            ("TJK", "Tajikistan"),
+            ("TKL", "Tokelau"),
+            ("TKM", "Turkmenistan"),
+            ("TLS", "Timor-Leste"),
+            ("TON", "Tonga"),
+            ("TTO", "Trinidad and Tobago"),
+            ("TUN", "Tunisia"),
+            ("TUR", "Türkiye"),
+            ("TUV", "Tuvalu"),
+            ("TWN", "Taiwan, Province of China"),
+            ("TZA", "Tanzania, United Republic of"),
+            ("UGA", "Uganda"),
+            ("UKR", "Ukraine"),
+            ("UMI", "United States Minor Outlying Islands"),
+            ("URY", "Uruguay"),
+            ("USA", "United States"),
+            ("UZB", "Uzbekistan"),
+            ("VAT", "Holy See (Vatican City State)"),
+            ("VCT", "Saint Vincent and the Grenadines"),
+            ("VEN", "Venezuela, Bolivarian Republic of"),
+            ("VGB", "Virgin Islands, British"),
+            ("VIR", "Virgin Islands, U.S."),
+            ("VNM", "Viet Nam"),
+            ("VUT", "Vanuatu"),
+            ("WLF", "Wallis and Futuna"),
+            ("WSM", "Samoa"),
+            ("YEM", "Yemen"),
+            ("ZAF", "South Africa"),
+            ("ZMB", "Zambia"),
+            ("ZWE", "Zimbabwe")
+        ]),
+        444u16 => ("Name Assembly Order", &[
+            ("F", "Prefix Family Middle Given Suffix"),
+            ("G", "Prefix Given Middle Family Suffix")
+        ]),
+        465u16 => ("Name/Address Representation", &[
+            ("A", "Alphabetic (i.e., Default or some single-byte)"),
+            ("I", "Ideographic (i.e., Kanji)"),
+            ("P", "Phonetic (i.e., ASCII, Katakana, Hirigana, etc.)")
+        ]),
+        516u16 => ("Error Severity", &[
+            ("E", "Error"),
+            ("F", "Fatal Error"),
+            ("I", "Information"),
+            ("W", "Warning")
+        ]),
+        617u16 => ("Address Usage", &[
+            ("C", "Classified"),
+            ("M", "Confidential"),
+            ("V", "Visit")
+        ])
+    };
+}
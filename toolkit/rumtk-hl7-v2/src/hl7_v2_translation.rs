@@ -0,0 +1,637 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2026  Luis M. Santos, M.D. <lsantos@medicalmasses.com>
+ * Copyright (C) 2026  MedicalMasses L.L.C. <contact@medicalmasses.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+///
+/// Translation of messages between HL7 v2.x releases. The data types of every field we have a
+/// schema for are rewritten from their definition in the release of the message (see
+/// [V2Message::version]) to their definition in the target release:
+///
+/// * Components a data type does not define in the target release are dropped (e.g. CWE.7 and
+///   later when going down to v2.4).
+/// * TS values are folded into DTM when going up across v2.7. The degree of precision is applied
+///   to the time stamp itself. A DTM is a valid TS so nothing is folded back when going down.
+/// * CE fields are reported as sent as CWE when going up across v2.7 and the reverse when going
+///   down, in which case the CWE components CE does not define are dropped.
+/// * Fields deprecated in favor of another location are moved there when going up (see
+///   [V2_FIELD_MOVES]), e.g. PID-2 and PID-4 become repetitions of PID-3. Going down, values
+///   that can be told apart by their identifier type (e.g. the SS repetition of PID-3) are moved
+///   back. The others stay where they are since that location is valid in older releases too.
+/// * MSH-12 is set to the target release.
+///
+/// Every change is recorded in a [V2TranslationReport] and flagged as lossy when data had to be
+/// discarded.
+///
+/// ## Example
+///
+/// ```
+/// use rumtk_hl7_v2::hl7_v2_field_descriptors::v2_field_descriptor::V2Version;
+/// use rumtk_hl7_v2::hl7_v2_parser::v2_parser::V2Message;
+///
+/// let raw = "MSH|^~\\&|LAB|FAC|||20250101120000||ORU^R01|MSG0001|P|2.3\rPID|1|EXT123^^^LAB|12345^^^HOSP^MR||DOE^JANE||19610615^D\r";
+/// let message = V2Message::try_from(raw).unwrap();
+/// let (translated, report) = message.translate(V2Version::V2_5_1).unwrap();
+///
+/// assert_eq!(translated.find_value("PID3[2].1").unwrap(), "EXT123", "PID-2 should have moved into PID-3!");
+/// assert_eq!(translated.version(), V2Version::V2_5_1, "MSH-12 was not updated!");
+/// assert!(report.is_lossless(), "Nothing was discarded!");
+/// ```
+///
+pub mod v2_translation {
+    use crate::hl7_v2_base_types::v2_primitives::{V2ParserCharacters, V2PrimitiveType, V2Result, V2String};
+    use crate::hl7_v2_complex_types::hl7_v2_complex_types::V2FieldToString;
+    use crate::hl7_v2_constants::{V2_CUSTOM_SEGMENT_ID, V2_MSHEADER_ID, V2_MSHEADER_PATTERN_STR, V2_TOTAL_VALID_SEGMENTS};
+    use crate::hl7_v2_parser::v2_parser::{V2Field, V2Message};
+    use crate::hl7_v2_segment_descriptors::v2_segment_descriptor::*;
+    use crate::hl7_v2_types::v2_types::V2SegmentView;
+    use rumtk_core::strings::rumtk_format;
+    use std::fmt::{Display, Formatter};
+
+    /**************************** Types *****************************************/
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum V2TranslationKind {
+        /// MSH-12 was set to the target release.
+        VersionId,
+        /// The data type of the field changed name between releases (e.g. CE to CWE).
+        Retyped,
+        /// A TS degree of precision was folded into the time stamp.
+        Precision,
+        /// Components not defined in the target release were dropped.
+        Truncated,
+        /// A deprecated field was moved to its replacement location.
+        Moved,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct V2TranslationChange {
+        pub location: V2String,
+        pub kind: V2TranslationKind,
+        /// Whether data was discarded to make the change.
+        pub lossy: bool,
+        pub description: V2String,
+    }
+
+    impl Display for V2TranslationChange {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            let lossy = match self.lossy {
+                true => " (lossy)",
+                false => "",
+            };
+            write!(f, "{:?}{} at {}: {}", self.kind, lossy, self.location, self.description)
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct V2TranslationReport {
+        source: V2Version,
+        target: V2Version,
+        changes: Vec<V2TranslationChange>,
+    }
+
+    impl V2TranslationReport {
+        pub fn new(source: V2Version, target: V2Version) -> Self {
+            Self {
+                source,
+                target,
+                changes: Vec::new(),
+            }
+        }
+
+        pub fn push(&mut self, location: V2String, kind: V2TranslationKind, lossy: bool, description: V2String) {
+            self.changes.push(V2TranslationChange {
+                location,
+                kind,
+                lossy,
+                description,
+            });
+        }
+
+        pub fn source_version(&self) -> V2Version {
+            self.source
+        }
+
+        pub fn target_version(&self) -> V2Version {
+            self.target
+        }
+
+        pub fn changes(&self) -> &Vec<V2TranslationChange> {
+            &self.changes
+        }
+
+        ///
+        /// Changes that discarded data.
+        ///
+        pub fn lossy(&self) -> Vec<&V2TranslationChange> {
+            self.changes.iter().filter(|c| c.lossy).collect()
+        }
+
+        pub fn is_lossless(&self) -> bool {
+            !self.changes.iter().any(|c| c.lossy)
+        }
+
+        ///
+        /// Changes made at `location` or any location nested under it.
+        ///
+        pub fn at(&self, location: &str) -> Vec<&V2TranslationChange> {
+            self.changes
+                .iter()
+                .filter(|c| {
+                    c.location == location
+                        || c.location.starts_with(&rumtk_format!("{}.", location))
+                        || c.location.starts_with(&rumtk_format!("{}[", location))
+                })
+                .collect()
+        }
+
+        pub fn len(&self) -> usize {
+            self.changes.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.changes.is_empty()
+        }
+    }
+
+    ///
+    /// Field superseded by another location in the standard. Moves with an `id_type` are undone
+    /// when translating to a release older than `since`.
+    ///
+    #[derive(Debug)]
+    pub struct V2FieldMove {
+        pub segment: &'static str,
+        pub from: u16,
+        pub to: u16,
+        /// Component of `to` receiving the value. 0 appends the value as a new repetition of `to`.
+        pub component: u16,
+        /// Identifier type code (CX.5) of the value once moved into an identifier list. Only the
+        /// first component of the original value is kept when set.
+        pub id_type: &'static str,
+        /// First release where `from` is only retained for backward compatibility.
+        pub since: V2Version,
+    }
+
+    pub static V2_FIELD_MOVES: &[V2FieldMove] = &[
+        V2FieldMove { segment: "PID", from: 2, to: 3, component: 0, id_type: "", since: V2Version::V2_3_1 },
+        V2FieldMove { segment: "PID", from: 4, to: 3, component: 0, id_type: "", since: V2Version::V2_3_1 },
+        V2FieldMove { segment: "PID", from: 12, to: 11, component: 9, id_type: "", since: V2Version::V2_5 },
+        V2FieldMove { segment: "PID", from: 19, to: 3, component: 0, id_type: "SS", since: V2Version::V2_5 },
+        V2FieldMove { segment: "PID", from: 20, to: 3, component: 0, id_type: "DL", since: V2Version::V2_5 },
+        V2FieldMove { segment: "PID", from: 9, to: 5, component: 0, id_type: "", since: V2Version::V2_7 },
+    ];
+
+    /// Field repetition rewritten during translation.
+    struct V2FieldUpdate {
+        segment_name: V2String,
+        instance: usize,
+        seq: u16,
+        repetition: usize,
+        components: Vec<V2String>,
+    }
+
+    /****************************** API *****************************************/
+
+    ///
+    /// Translate `message` from its release (see [V2Message::version]) to `target`. The original
+    /// message is left untouched. Returns the translated message and the report of every change
+    /// made.
+    ///
+    pub fn translate_message(message: &V2Message, target: V2Version) -> V2Result<(V2Message, V2TranslationReport)> {
+        let source = message.version();
+        let mut report = V2TranslationReport::new(source, target);
+        let mut translated = V2Message::try_from_text(&message.to_string())?;
+        translated.set_version_override(message.version_override());
+
+        for update in translate_fields(&translated, &mut report) {
+            let field_group = translated
+                .get_named_mut(&update.segment_name, update.instance)?
+                .get_mut(segment_field_index(&update.segment_name, update.seq))?;
+            field_group[update.repetition] = build_field(&update.components);
+        }
+        move_fields(&mut translated, source, target, &mut report)?;
+        set_version_id(&mut translated, message.declared_version(), target, &mut report)?;
+        if translated.version_override().is_some() {
+            translated.set_version_override(Some(target));
+        }
+
+        Ok((translated, report))
+    }
+
+    fn translate_fields(message: &V2Message, report: &mut V2TranslationReport) -> Vec<V2FieldUpdate> {
+        let parser_chars = message.parser_chars();
+        let mut updates = Vec::new();
+
+        for segment_id in 1..=V2_TOTAL_VALID_SEGMENTS {
+            let group = match message.get_group(segment_id) {
+                Ok(group) => group,
+                Err(_) => continue,
+            };
+            for (i, segment) in group.iter().enumerate() {
+                let segment_name = segment.name(segment_id);
+                // Custom segments share a group so their instances are counted by name.
+                let instance = match segment_id {
+                    V2_CUSTOM_SEGMENT_ID => group[..i]
+                        .iter()
+                        .filter(|s| s.custom_name() == Some(segment_name))
+                        .count() + 1,
+                    _ => i + 1,
+                };
+                let view = match V2SegmentView::new(segment_name, segment, parser_chars) {
+                    Ok(view) => view,
+                    Err(_) => continue,
+                };
+                for descriptor in view.descriptors().iter() {
+                    // MSH-1 and MSH-2 hold the separators.
+                    if view.name() == V2_MSHEADER_PATTERN_STR && descriptor.seq < 3 {
+                        continue;
+                    }
+                    for (repetition, field) in view.get_raw(descriptor.seq).into_iter().enumerate() {
+                        if field.is_empty() {
+                            continue;
+                        }
+                        let location = rumtk_format!(
+                            "{}-{}[{}]",
+                            segment_location(view.name(), instance),
+                            descriptor.seq,
+                            repetition + 1
+                        );
+                        let components: Vec<V2String> = field.to_component_list().iter().map(|c| V2String::from(*c)).collect();
+                        let translated = translate_field(&components, descriptor, &location, parser_chars, report);
+                        if translated != components {
+                            updates.push(V2FieldUpdate {
+                                segment_name: V2String::from(view.name()),
+                                instance,
+                                seq: descriptor.seq,
+                                repetition,
+                                components: translated,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        updates
+    }
+
+    fn translate_field(
+        components: &[V2String],
+        descriptor: &V2ComponentTypeDescriptor,
+        location: &str,
+        parser_chars: &V2ParserCharacters,
+        report: &mut V2TranslationReport,
+    ) -> Vec<V2String> {
+        let (source, target) = (report.source_version(), report.target_version());
+        match &descriptor.data_type {
+            V2ComponentType::Primitive(V2PrimitiveType::DateTime) if crosses_dtm(source, target) && components.len() > 1 => {
+                vec![fold_time_stamp(components, location, report)]
+            }
+            V2ComponentType::Complex(complex_type) => {
+                let type_name = complex_type_to_str(complex_type);
+                let mut source_type_name = type_name;
+                if let V2ComplexType::CE = complex_type {
+                    if crosses_dtm(source, target) {
+                        report.push(
+                            V2String::from(location),
+                            V2TranslationKind::Retyped,
+                            false,
+                            V2String::from("CE was withdrawn in v2.7. The value is sent as CWE."),
+                        );
+                    }
+                    // Going down across v2.7.
+                    if crosses_dtm(target, source) {
+                        source_type_name = complex_type_to_str(&V2ComplexType::CWE);
+                        report.push(
+                            V2String::from(location),
+                            V2TranslationKind::Retyped,
+                            false,
+                            V2String::from("CWE replaced CE in v2.7. The value is sent as CE."),
+                        );
+                    }
+                }
+                translate_components(components, source_type_name, type_name, location, parser_chars, 0, report)
+            }
+            _ => components.to_vec(),
+        }
+    }
+
+    ///
+    /// Rewrite the components of a value of type `source_type_name` in the source release into
+    /// `type_name` in the target release. Both are the same unless the type was renamed. `depth` is
+    /// 0 for the components of a field and 1 for the subcomponents of a component.
+    ///
+    fn translate_components(
+        components: &[V2String],
+        source_type_name: &str,
+        type_name: &str,
+        location: &str,
+        parser_chars: &V2ParserCharacters,
+        depth: u8,
+        report: &mut V2TranslationReport,
+    ) -> Vec<V2String> {
+        let (source, target) = (report.source_version(), report.target_version());
+        let (source_descriptors, target_descriptors) = match (
+            get_field_descriptor(source_type_name, source),
+            get_field_descriptor(type_name, target),
+        ) {
+            (Some(source_descriptors), Some(target_descriptors)) => (source_descriptors, target_descriptors),
+            _ => return components.to_vec(),
+        };
+        let subcomponent_separator = parser_chars.subcomponent_separator as char;
+        let mut dropped = Vec::new();
+        let mut translated = Vec::with_capacity(components.len().min(target_descriptors.len()));
+
+        for (i, component) in components.iter().enumerate() {
+            let component_location = rumtk_format!("{}.{}", location, i + 1);
+            let target_descriptor = match target_descriptors.get(i) {
+                Some(target_descriptor) => target_descriptor,
+                None => {
+                    if !component.is_empty() {
+                        dropped.push(rumtk_format!("{}.{}", source_type_name, i + 1));
+                    }
+                    continue;
+                }
+            };
+            let source_type = source_descriptors.get(i).map(|d| &d.data_type);
+            let value = match (source_type, &target_descriptor.data_type) {
+                (
+                    Some(V2ComponentType::Complex(V2ComplexType::TS)),
+                    V2ComponentType::Primitive(V2PrimitiveType::DateTime),
+                ) if depth == 0 => {
+                    let subcomponents = split_value(component, subcomponent_separator);
+                    match subcomponents.len() {
+                        1 => component.clone(),
+                        _ => fold_time_stamp(&subcomponents, &component_location, report),
+                    }
+                }
+                (Some(V2ComponentType::Complex(nested)), V2ComponentType::Complex(_)) if depth == 0 => {
+                    let subcomponents = split_value(component, subcomponent_separator);
+                    translate_components(
+                        &subcomponents,
+                        complex_type_to_str(nested),
+                        complex_type_to_str(nested),
+                        &component_location,
+                        parser_chars,
+                        depth + 1,
+                        report,
+                    )
+                    .join(&subcomponent_separator.to_string())
+                }
+                _ => component.clone(),
+            };
+            translated.push(value);
+        }
+
+        if !dropped.is_empty() {
+            report.push(
+                V2String::from(location),
+                V2TranslationKind::Truncated,
+                true,
+                rumtk_format!(
+                    "{} has {} components in v{}. Dropped {}.",
+                    type_name,
+                    target_descriptors.len(),
+                    target.as_str(),
+                    dropped.join(", ")
+                ),
+            );
+        }
+
+        translated
+    }
+
+    ///
+    /// Fold a TS (time and degree of precision) into a DTM. The time stamp is cut down to the
+    /// precision given. Unknown precision codes cannot be expressed so they are reported as lossy.
+    ///
+    fn fold_time_stamp(parts: &[V2String], location: &str, report: &mut V2TranslationReport) -> V2String {
+        let time = parts.first().cloned().unwrap_or_default();
+        let precision = parts.get(1).map(|p| p.as_str()).unwrap_or_default();
+        if precision.is_empty() {
+            return time;
+        }
+        let digits = match precision {
+            "Y" => 4,
+            "L" => 6,
+            "D" => 8,
+            "H" => 10,
+            "M" => 12,
+            "S" => 14,
+            _ => {
+                report.push(
+                    V2String::from(location),
+                    V2TranslationKind::Precision,
+                    true,
+                    rumtk_format!("Degree of precision {} has no DTM equivalent and was dropped.", precision),
+                );
+                return time;
+            }
+        };
+        let (stamp, offset) = match time.char_indices().skip(1).find(|(_, c)| *c == '+' || *c == '-') {
+            Some((i, _)) => time.split_at(i),
+            None => (time.as_str(), ""),
+        };
+        let stamp = stamp.split('.').next().unwrap_or_default();
+        let folded = rumtk_format!("{}{}", &stamp[..stamp.len().min(digits)], offset);
+        report.push(
+            V2String::from(location),
+            V2TranslationKind::Precision,
+            false,
+            rumtk_format!("Degree of precision {} folded into the time stamp {}.", precision, folded),
+        );
+        folded
+    }
+
+    fn move_fields(
+        message: &mut V2Message,
+        source: V2Version,
+        target: V2Version,
+        report: &mut V2TranslationReport,
+    ) -> V2Result<()> {
+        let parser_chars = message.parser_chars().clone();
+        let component_separator = parser_chars.component_separator as char;
+
+        for rule in V2_FIELD_MOVES.iter().filter(|rule| source < rule.since && target >= rule.since) {
+            for instance in 1..=message.named_segment_count(rule.segment) {
+                let location = rumtk_format!("{}-{}", segment_location(rule.segment, instance), rule.from);
+                let destination = rumtk_format!("{}-{}", segment_location(rule.segment, instance), rule.to);
+                let segment = message.get_named_mut(rule.segment, instance)?;
+                let from_index = segment_field_index(rule.segment, rule.from);
+                if from_index < 1 || from_index as usize > segment.len() {
+                    continue;
+                }
+                let values: Vec<Vec<V2String>> = segment
+                    .get(from_index)?
+                    .iter()
+                    .filter(|field| !field.is_empty())
+                    .map(|field| field.to_component_list().iter().map(|c| V2String::from(*c)).collect())
+                    .collect();
+                if values.is_empty() {
+                    continue;
+                }
+                segment.clear(from_index as usize);
+
+                let to_group = segment.get_or_insert_mut(segment_field_index(rule.segment, rule.to) as usize);
+                if to_group.iter().all(|field| field.is_empty()) {
+                    to_group.clear();
+                }
+                for value in values {
+                    let (moved, lossy) = match rule.id_type {
+                        "" => (value, false),
+                        id_type => (
+                            vec![
+                                value.first().cloned().unwrap_or_default(),
+                                V2String::new(),
+                                V2String::new(),
+                                V2String::new(),
+                                V2String::from(id_type),
+                            ],
+                            value.iter().skip(1).any(|c| !c.is_empty()),
+                        ),
+                    };
+                    let text = moved.join(&component_separator.to_string());
+
+                    match rule.component {
+                        0 => {
+                            let duplicate = to_group.iter().any(|field| field.to_string(&parser_chars) == text);
+                            if !duplicate {
+                                to_group.push(build_field(&moved));
+                            }
+                            report.push(
+                                location.clone(),
+                                V2TranslationKind::Moved,
+                                lossy,
+                                rumtk_format!("{} moved to {} as {}.", location, destination, text),
+                            );
+                        }
+                        component => {
+                            if to_group.is_empty() {
+                                to_group.push(V2Field::new());
+                            }
+                            let target_component = to_group[0].get_or_insert_mut(component as usize);
+                            let conflict = !target_component.is_empty();
+                            if !conflict {
+                                target_component.set(&text);
+                            }
+                            report.push(
+                                location.clone(),
+                                V2TranslationKind::Moved,
+                                lossy || conflict,
+                                match conflict {
+                                    true => rumtk_format!("{}.{} is already valued so {} was dropped.", destination, component, location),
+                                    false => rumtk_format!("{} moved to {}.{}.", location, destination, component),
+                                },
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        for rule in V2_FIELD_MOVES.iter().filter(|rule| !rule.id_type.is_empty() && source >= rule.since && target < rule.since) {
+            for instance in 1..=message.named_segment_count(rule.segment) {
+                let segment = message.get_named_mut(rule.segment, instance)?;
+                let (from_index, to_index) = (segment_field_index(rule.segment, rule.from), segment_field_index(rule.segment, rule.to));
+                if to_index < 1 || to_index as usize > segment.len() {
+                    continue;
+                }
+                let from_valued = from_index as usize <= segment.len() && segment.get(from_index)?.iter().any(|field| !field.is_empty());
+                let to_group = segment.get_mut(to_index)?;
+                let repetition = match to_group.iter().position(|field| field.to_component_list().get(4) == Some(&rule.id_type)) {
+                    Some(repetition) => repetition,
+                    None => continue,
+                };
+                let location = rumtk_format!("{}-{}[{}]", segment_location(rule.segment, instance), rule.to, repetition + 1);
+                let destination = rumtk_format!("{}-{}", segment_location(rule.segment, instance), rule.from);
+                if from_valued {
+                    report.push(
+                        location.clone(),
+                        V2TranslationKind::Moved,
+                        false,
+                        rumtk_format!("{} is already valued so {} was kept.", destination, location),
+                    );
+                    continue;
+                }
+                let field = to_group.remove(repetition);
+                let components = field.to_component_list();
+                let lossy = components.iter().enumerate().any(|(i, c)| i != 0 && i != 4 && !c.is_empty());
+                let value = V2String::from(components.first().copied().unwrap_or_default());
+
+                let from_group = segment.get_or_insert_mut(from_index as usize);
+                from_group.clear();
+                from_group.push(build_field(std::slice::from_ref(&value)));
+                report.push(
+                    location.clone(),
+                    V2TranslationKind::Moved,
+                    lossy,
+                    rumtk_format!("{} moved back to {} as {}.", location, destination, value),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn set_version_id(
+        message: &mut V2Message,
+        declared: Option<V2Version>,
+        target: V2Version,
+        report: &mut V2TranslationReport,
+    ) -> V2Result<()> {
+        if declared == Some(target) {
+            return Ok(());
+        }
+        let previous = declared.map(|v| v.as_str()).unwrap_or("an unknown release");
+        let msh = message.get_mut(V2_MSHEADER_ID, 1)?;
+        let field_group = msh.get_or_insert_mut(segment_field_index(V2_MSHEADER_PATTERN_STR, 12) as usize);
+        if field_group.is_empty() {
+            field_group.push(V2Field::new());
+        }
+        field_group[0].get_or_insert_mut(1).set(target.as_str());
+        report.push(
+            V2String::from("MSH-12"),
+            V2TranslationKind::VersionId,
+            false,
+            rumtk_format!("Version changed from {} to {}.", previous, target.as_str()),
+        );
+        Ok(())
+    }
+
+    fn crosses_dtm(source: V2Version, target: V2Version) -> bool {
+        source < V2Version::V2_7 && target >= V2Version::V2_7
+    }
+
+    fn segment_location(segment_name: &str, instance: usize) -> V2String {
+        match instance {
+            1 => V2String::from(segment_name),
+            _ => rumtk_format!("{}({})", segment_name, instance),
+        }
+    }
+
+    fn split_value(value: &str, separator: char) -> Vec<V2String> {
+        value.split(separator).map(V2String::from).collect()
+    }
+
+    fn build_field(components: &[V2String]) -> V2Field {
+        let mut field = V2Field::new();
+        for (i, component) in components.iter().enumerate() {
+            field.get_or_insert_mut(i + 1).set(component);
+        }
+        field
+    }
+}
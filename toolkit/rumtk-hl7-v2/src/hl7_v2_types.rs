@@ -17,3 +17,1270 @@
  * You should have received a copy of the GNU General Public License
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
+
+///
+/// Typed segment layer. Binds the segments parsed by [V2Message] to their schema in
+/// [V2_SEGMENT_DESCRIPTORS] so fields can be retrieved already cast into their data type instead of
+/// doing index arithmetic on [V2Segment::get].
+///
+/// ## Example
+///
+/// ```
+/// use rumtk_hl7_v2::hl7_v2_parser::v2_parser::V2Message;
+/// use rumtk_hl7_v2::hl7_v2_types::v2_types::PID;
+///
+/// let raw = "MSH|^~\\&|ADT1|GOOD HEALTH HOSPITAL|GHH LAB, INC.|GOOD HEALTH HOSPITAL|198808181126|SECURITY|ADT^A01^ADT_A01|MSG00001|P|2.8||\rPID|1||PATID1234^5^M11^ADT1^MR^GOOD HEALTH HOSPITAL~123456789^^^USSSA^SS||EVERYMAN^ADAM^A^III||19610615|M||C|2222 HOME STREET^^GREENSBORO^NC^27401-1020";
+/// let message = V2Message::try_from(raw).unwrap();
+/// let pid = PID::from_message(&message, 1).unwrap();
+/// let name = pid.patient_name().unwrap().unwrap();
+///
+/// assert_eq!(name.given_name().unwrap().as_str(), Some("ADAM"), "Wrong given name!");
+/// assert!(pid.mothers_maiden_name().unwrap().is_none(), "PID-6 was not sent!");
+/// ```
+///
+pub mod v2_types {
+    use crate::hl7_v2_base_types::v2_primitives::{V2ParserCharacters, V2PrimitiveType, V2Result, V2ST};
+    use crate::hl7_v2_complex_types::hl7_v2_complex_types::{cast_component_as, V2ComplexValue, V2FieldToString, V2Type};
+    use crate::hl7_v2_parser::v2_parser::{V2Field, V2Message, V2Segment, V2String};
+    use crate::hl7_v2_segment_descriptors::v2_segment_descriptor::*;
+    use rumtk_core::strings::rumtk_format;
+
+    ///
+    /// Generic view over a parsed segment and its schema. The typed segments below wrap this view and
+    /// only add named accessors.
+    ///
+    pub struct V2SegmentView<'a> {
+        name: &'static str,
+        segment: &'a V2Segment,
+        descriptors: &'static V2ComponentDescriptor,
+        parser_chars: &'a V2ParserCharacters,
+        version: V2Version,
+    }
+
+    impl<'a> V2SegmentView<'a> {
+        pub fn new(
+            segment_name: &str,
+            segment: &'a V2Segment,
+            parser_chars: &'a V2ParserCharacters,
+        ) -> V2Result<Self> {
+            match get_segment_descriptor_entry(segment_name) {
+                Some((name, descriptors)) => Ok(Self {
+                    name,
+                    segment,
+                    descriptors,
+                    parser_chars,
+                    version: V2Version::default(),
+                }),
+                None => Err(rumtk_format!(
+                    "No schema available for segment {}!",
+                    segment_name
+                )),
+            }
+        }
+
+        ///
+        /// Grab the `sub_segment` instance (1-indexed) of the named segment from the message. Custom
+        /// segments are counted by name. See [V2Message::get_named]. Fields are cast with the
+        /// data types of [V2Message::version].
+        ///
+        pub fn from_message(
+            segment_name: &str,
+            message: &'a V2Message,
+            sub_segment: usize,
+        ) -> V2Result<Self> {
+            let segment = message.get_named(segment_name, sub_segment)?;
+            Ok(Self::new(segment_name, segment, message.parser_chars())?.with_version(message.version()))
+        }
+
+        ///
+        /// Cast fields with the data types of `version` instead of the latest release. This changes
+        /// the components of each data type and, for fields that were only defined in later
+        /// releases, the field itself. See [get_segment_descriptor_as].
+        ///
+        pub fn with_version(mut self, version: V2Version) -> Self {
+            if let Some(descriptors) = get_segment_descriptor_as(self.name, version) {
+                self.descriptors = descriptors;
+            }
+            self.version = version;
+            self
+        }
+
+        pub fn version(&self) -> V2Version {
+            self.version
+        }
+
+        pub fn name(&self) -> &str {
+            self.name
+        }
+
+        pub fn segment(&self) -> &V2Segment {
+            self.segment
+        }
+
+        pub fn descriptors(&self) -> &'static V2ComponentDescriptor {
+            self.descriptors
+        }
+
+        pub fn descriptor(&self, seq: u16) -> V2Result<&'static V2ComponentTypeDescriptor> {
+            match self.descriptors.get((seq as usize).wrapping_sub(1)) {
+                Some(descriptor) => Ok(*descriptor),
+                None => Err(rumtk_format!(
+                    "Field {}-{} is not defined in the schema!",
+                    self.name,
+                    seq
+                )),
+            }
+        }
+
+        ///
+        /// Get the raw repetitions of field `seq`. Fields past the end of the segment were simply
+        /// not sent so we return an empty list for them.
+        ///
+        pub fn get_raw(&self, seq: u16) -> Vec<&'a V2Field> {
+            let indx = segment_field_index(self.name, seq);
+            if indx < 1 || indx as usize > self.segment.len() {
+                return Vec::new();
+            }
+            match self.segment.get(indx) {
+                Ok(field_group) => field_group.iter().collect(),
+                Err(_) => Vec::new(),
+            }
+        }
+
+        ///
+        /// Get the first repetition of field `seq` cast into the data type defined in the schema.
+        ///
+        pub fn get(&self, seq: u16) -> V2Type {
+            match self.get_repetitions(seq).into_iter().next() {
+                Some(value) => value,
+                None => V2Type::Err(rumtk_format!(
+                    "Field {}-{} is not defined in the schema!",
+                    self.name,
+                    seq
+                )),
+            }
+        }
+
+        ///
+        /// Get all repetitions of field `seq` cast into the data type defined in the schema. An
+        /// unpopulated field yields a single [V2Type::Empty], or [V2Type::Err] if the field is
+        /// required.
+        ///
+        pub fn get_repetitions(&self, seq: u16) -> Vec<V2Type> {
+            let descriptor = match self.descriptor(seq) {
+                Ok(descriptor) => descriptor,
+                Err(e) => return vec![V2Type::Err(e)],
+            };
+
+            if self.is_field_separator(seq) {
+                let separator = (self.parser_chars.field_separator as char).to_string();
+                return vec![V2Type::V2ST(Ok(V2ST::from(separator)))];
+            }
+
+            let fields: Vec<&V2Field> = self
+                .get_raw(seq)
+                .into_iter()
+                .filter(|f| !is_empty_field(f))
+                .collect();
+
+            if fields.is_empty() {
+                return match descriptor.optionality.is_required() {
+                    true => vec![V2Type::Err(rumtk_format!(
+                        "Required field {}-{} ({}) is missing!",
+                        self.name,
+                        seq,
+                        descriptor.description
+                    ))],
+                    false => vec![V2Type::Empty],
+                };
+            }
+
+            fields
+                .into_iter()
+                .map(|field| cast_field_as(field, descriptor, self.parser_chars, self.version))
+                .collect()
+        }
+
+        ///
+        /// Same as [V2SegmentView::get] but converted into a typed composite such as [XPN]. Fields
+        /// that were not valued yield `None`.
+        ///
+        pub fn get_as<T: TryFrom<V2Type, Error = V2String>>(&self, seq: u16) -> V2Result<Option<T>> {
+            match self.get(seq) {
+                V2Type::Empty => Ok(None),
+                value => T::try_from(value).map(Some),
+            }
+        }
+
+        ///
+        /// Same as [V2SegmentView::get_repetitions] but converted into a typed composite. Fields
+        /// that were not valued yield an empty list.
+        ///
+        pub fn get_repetitions_as<T: TryFrom<V2Type, Error = V2String>>(&self, seq: u16) -> V2Result<Vec<T>> {
+            self.get_repetitions(seq)
+                .into_iter()
+                .filter(|value| !value.is_empty())
+                .map(T::try_from)
+                .collect()
+        }
+
+        ///
+        /// Same as [V2SegmentView::get] but looks the field up by its schema name.
+        ///
+        pub fn get_by_name(&self, name: &str) -> V2Type {
+            match self.descriptors.iter().find(|d| d.name == name) {
+                Some(descriptor) => self.get(descriptor.seq),
+                None => V2Type::Err(rumtk_format!(
+                    "Field {} is not defined for segment {}!",
+                    name,
+                    self.name
+                )),
+            }
+        }
+
+        fn is_field_separator(&self, seq: u16) -> bool {
+            seq == 1 && segment_field_index(self.name, seq) == 0
+        }
+    }
+
+    ///
+    /// Cast a single field (one repetition) using a field descriptor.
+    ///
+    pub fn cast_field(
+        field: &V2Field,
+        descriptor: &V2ComponentTypeDescriptor,
+        parser_chars: &V2ParserCharacters,
+    ) -> V2Type {
+        cast_field_as(field, descriptor, parser_chars, V2Version::default())
+    }
+
+    ///
+    /// Same as [cast_field] but using the data types of `version`.
+    ///
+    pub fn cast_field_as(
+        field: &V2Field,
+        descriptor: &V2ComponentTypeDescriptor,
+        parser_chars: &V2ParserCharacters,
+        version: V2Version,
+    ) -> V2Type {
+        match &descriptor.data_type {
+            V2ComponentType::Complex(complex_type) => {
+                field.to_complex_as(complex_type, parser_chars, version)
+            }
+            V2ComponentType::Primitive(_) => {
+                cast_component_as(field.to_component_list(), descriptor, parser_chars, version)
+            }
+        }
+    }
+
+    fn is_empty_field(field: &V2Field) -> bool {
+        field.to_component_list().iter().all(|c| c.is_empty())
+    }
+
+    ///
+    /// Generates a typed wrapper over a cast composite with one accessor per component. Components
+    /// are looked up by descriptor name so releases that lack a component simply yield `None`.
+    ///
+    macro_rules! v2_typed_complex {
+        (
+            $(#[$doc:meta])*
+            $complex:ident,
+            { $( $accessor:ident ),* $(,)? }
+        ) => {
+            $(#[$doc])*
+            #[derive(Debug)]
+            pub struct $complex {
+                value: V2ComplexValue,
+            }
+
+            impl $complex {
+                pub const NAME: &'static str = stringify!($complex);
+
+                pub fn value(&self) -> &V2ComplexValue {
+                    &self.value
+                }
+
+                $(
+                    pub fn $accessor(&self) -> Option<&V2Type> {
+                        self.value.get(stringify!($accessor))
+                    }
+                )*
+            }
+
+            impl TryFrom<V2Type> for $complex {
+                type Error = V2String;
+
+                fn try_from(value: V2Type) -> V2Result<Self> {
+                    Ok(Self {
+                        value: into_complex_value(value, Self::NAME)?,
+                    })
+                }
+            }
+        };
+    }
+
+    fn into_complex_value(value: V2Type, type_name: &str) -> V2Result<V2ComplexValue> {
+        match value {
+            V2Type::V2Complex(complex) if complex.type_name() == type_name => Ok(complex),
+            V2Type::V2Complex(complex) => Err(rumtk_format!(
+                "Expected a {} but got a {}!",
+                type_name,
+                complex.type_name()
+            )),
+            V2Type::Err(e) => Err(e),
+            V2Type::Empty => Err(rumtk_format!("Expected a {} but the value is empty!", type_name)),
+            other => Err(rumtk_format!("Expected a {} but got {:?}!", type_name, other)),
+        }
+    }
+
+    ///
+    /// Generates a typed segment wrapping [V2SegmentView] with one accessor per field. Fields
+    /// annotated with a composite (e.g. `patient_name: XPN => 5`) are returned as that type. The
+    /// rest are returned as the [V2Type] of their primitive.
+    ///
+    macro_rules! v2_typed_segment {
+        (@accessor $accessor:ident: $complex:ident => $seq:expr) => {
+            pub fn $accessor(&self) -> V2Result<Option<$complex>> {
+                self.view.get_as($seq)
+            }
+        };
+        (@accessor $accessor:ident => $seq:expr) => {
+            pub fn $accessor(&self) -> V2Type {
+                self.view.get($seq)
+            }
+        };
+        (
+            $(#[$doc:meta])*
+            $segment:ident,
+            $segment_name:expr,
+            { $( $accessor:ident $(: $complex:ident)? => $seq:expr ),* $(,)? }
+        ) => {
+            $(#[$doc])*
+            pub struct $segment<'a> {
+                view: V2SegmentView<'a>,
+            }
+
+            impl<'a> $segment<'a> {
+                pub const NAME: &'static str = $segment_name;
+
+                pub fn from_segment(
+                    segment: &'a V2Segment,
+                    parser_chars: &'a V2ParserCharacters,
+                ) -> V2Result<Self> {
+                    Ok(Self {
+                        view: V2SegmentView::new(Self::NAME, segment, parser_chars)?,
+                    })
+                }
+
+                ///
+                /// Grab the `sub_segment` instance (1-indexed) of this segment from the message.
+                ///
+                pub fn from_message(message: &'a V2Message, sub_segment: usize) -> V2Result<Self> {
+                    Ok(Self {
+                        view: V2SegmentView::from_message(Self::NAME, message, sub_segment)?,
+                    })
+                }
+
+                pub fn view(&self) -> &V2SegmentView<'a> {
+                    &self.view
+                }
+
+                $(
+                    v2_typed_segment!(@accessor $accessor $(: $complex)? => $seq);
+                )*
+            }
+        };
+    }
+
+    v2_typed_complex!(
+        ///
+        /// Address.
+        ///
+        AD, {
+            street_address, second_address, city, state, zip, country, address_type, county,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Authorization information.
+        ///
+        AUI, {
+            auth_number, date, source,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Charge code and date.
+        ///
+        CCD, {
+            event, date,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Channel calibration parameters.
+        ///
+        CCP, {
+            cc_factor, cc_baseline, cc_time_skew,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Channel definition.
+        ///
+        CD, {
+            channel_id, waveform_source, channel_sensitivity_units, channel_calibration_parameters,
+            channel_sampling_frequency, min_max_values,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Coded entry. Withdrawn in v2.7 in favor of CWE.
+        ///
+        CE, {
+            id, text, coding_system, alt_id, alt_text, alt_coding_system,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Coded element with formatted values.
+        ///
+        CF, {
+            id, formatted_text, coding_system, alt_id, alt_formatted_text, alt_coding_system,
+            version_id, alt_version_id, original_text, second_alt_id, second_alt_formatted_text,
+            second_alt_coding_system, second_alt_version_id, coding_system_oid, valueset_oid,
+            valueset_version_id, alt_coding_system_oid, alt_valueset_oid, alt_valueset_version_id,
+            second_alt_coding_system_oid, second_alt_valueset_oid, second_alt_valueset_version_id,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Coded with no exceptions.
+        ///
+        CNE, {
+            id, text, coding_system, alt_id, alt_text, alt_coding_system, version_id,
+            alt_version_id, original_text, second_alt_id, second_alt_text, second_alt_coding_system,
+            second_alt_version_id, coding_system_oid, valueset_oid, valueset_version_id,
+            alt_coding_system_oid, alt_valueset_oid, alt_valueset_version_id,
+            second_alt_coding_system_oid, second_alt_valueset_oid, second_alt_valueset_version_id,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Composite ID number and name simplified.
+        ///
+        CNN, {
+            id, family_name, given_name, second_given_name, suffix, prefix, degree, source_table,
+            aa_namespace_id, aa_universal_id, aa_universal_id_type,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Composite price.
+        ///
+        CP, {
+            price, price_type, from_value, to_value, range_units, range_type,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Composite quantity with units.
+        ///
+        CQ, {
+            quantity, units,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Channel sensitivity and units.
+        ///
+        CSU, {
+            quantity, units,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Coded with exceptions.
+        ///
+        CWE, {
+            id, text, coding_system, alt_id, alt_text, alt_coding_system, version_id,
+            alt_version_id, original_text, second_alt_id, second_alt_text, second_alt_coding_system,
+            second_alt_version_id, coding_system_oid, valueset_oid, valueset_version_id,
+            alt_coding_system_oid, alt_valueset_oid, alt_valueset_version_id,
+            second_alt_coding_system_oid, second_alt_valueset_oid, second_alt_valueset_version_id,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Extended composite ID with check digit.
+        ///
+        CX, {
+            id, check_digit, check_digit_scheme, assigning_authority, id_type, assigning_facility,
+            effective_date, expiration_date, assigning_jurisdiction, assigning_agency,
+            security_check, security_check_scheme,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Discharge to location and date.
+        ///
+        DLD, {
+            discharge_location, effective_date,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Driver's license number.
+        ///
+        DLN, {
+            license_number, issuing_state, expiration_date,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Date/time range.
+        ///
+        DR, {
+            range_start, range_end,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Entity identifier.
+        ///
+        EI, {
+            entity_id, namespace_id, universal_id, universal_id_type,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Entity identifier pair.
+        ///
+        EIP, {
+            placer_assigned_id, filler_assigned_id,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Error location and description.
+        ///
+        ELD, {
+            segment_id, segment_sequence, field_position, error_code,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Error location.
+        ///
+        ERL, {
+            segment_id, segment_sequence, field_position, field_repetition, component_number,
+            sub_component_number,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Financial class.
+        ///
+        FC, {
+            financial_class_code, effective_date,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Family name.
+        ///
+        FN, {
+            surname, own_surname_prefix, own_surname, partner_surname_prefix, partner_surname,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Hierarchic designator.
+        ///
+        HD, {
+            namespace_id, universal_id, universal_id_type,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Job code/class.
+        ///
+        JCC, {
+            job_code, job_class, job_description,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Money.
+        ///
+        MO, {
+            quantity, denomination,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Money and code.
+        ///
+        MOC, {
+            monetary_amount, charge_code,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Message type.
+        ///
+        MSG, {
+            message_code, trigger_event, message_structure,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Name with date and location.
+        ///
+        NDL, {
+            name, start_datetime, end_datetime, point_of_care, room, bed, facility, location_status,
+            patient_location_type, building, floor,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Numeric range.
+        ///
+        NR, {
+            low_value, high_value,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Person location.
+        ///
+        PL, {
+            point_of_care, room, bed, facility, location_status, person_location_type, building,
+            floor, location_description, comprehensive_location_id, location_assigning_authority,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Parent result link.
+        ///
+        PRL, {
+            parent_observation_id, parent_observation_sub_id, parent_observation_value,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Processing type.
+        ///
+        PT, {
+            processing_id, processing_mode,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Street address.
+        ///
+        SAD, {
+            street_address, street_name, dwelling_number,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Time stamp.
+        ///
+        TS, {
+            time, degree_of_precision,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Version identifier.
+        ///
+        VID, {
+            version_id, internationalization_code, international_version_id,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Channel identifier.
+        ///
+        WVI, {
+            channel_number, channel_name,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Waveform source.
+        ///
+        WVS, {
+            source_one_name, source_two_name,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Extended address.
+        ///
+        XAD, {
+            street_address, other_designation, city, state, zip, country, address_type,
+            other_geographic_designation, county_code, census_tract, address_representation_code,
+            address_validity_range, effective_date, expiration_date, expiration_reason,
+            temporary_indicator, bad_address_indicator, address_usage, addressee, comment,
+            preference_order, protection_code, address_identifier,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Extended composite ID number and name for persons.
+        ///
+        XCN, {
+            person_id, family_name, given_name, second_given_name, suffix, prefix, degree,
+            source_table, assigning_authority, name_type, check_digit, check_digit_scheme, id_type,
+            assigning_facility, name_representation_code, name_context, name_validity_range,
+            name_assembly_order, effective_date, expiration_date, professional_suffix,
+            assigning_jurisdiction, assigning_agency, security_check, security_check_scheme,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Extended composite name and identification number for organizations.
+        ///
+        XON, {
+            organization_name, organization_name_type, id_number, check_digit, check_digit_scheme,
+            assigning_authority, id_type, assigning_facility, name_representation_code,
+            organization_id,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Extended person name.
+        ///
+        XPN, {
+            family_name, given_name, second_given_name, suffix, prefix, degree, name_type,
+            name_representation_code, name_context, name_validity_range, name_assembly_order,
+            effective_date, expiration_date, professional_suffix, called_by,
+        }
+    );
+
+    v2_typed_complex!(
+        ///
+        /// Extended telecommunication number.
+        ///
+        XTN, {
+            telephone_number, use_code, equipment_type, communication_address, country_code,
+            area_code, local_number, extension, any_text, extension_prefix, speed_dial_code,
+            unformatted_telephone_number, effective_start_date, expiration_date, expiration_reason,
+            protection_code, shared_telecommunication_id, preference_order,
+        }
+    );
+
+    v2_typed_segment!(
+        ///
+        /// Message Header segment.
+        ///
+        MSH, "MSH", {
+            field_separator => 1,
+            encoding_characters => 2,
+            sending_application: HD => 3,
+            sending_facility: HD => 4,
+            receiving_application: HD => 5,
+            receiving_facility: HD => 6,
+            message_datetime => 7,
+            security => 8,
+            message_type: MSG => 9,
+            message_control_id => 10,
+            processing_id: PT => 11,
+            version_id: VID => 12,
+            sequence_number => 13,
+            continuation_pointer => 14,
+            accept_ack_type => 15,
+            application_ack_type => 16,
+            country_code => 17,
+            character_set => 18,
+            principal_language: CE => 19,
+            alt_character_set_handling => 20,
+            message_profile_id: EI => 21,
+        }
+    );
+
+    v2_typed_segment!(
+        ///
+        /// Patient Identification segment.
+        ///
+        PID, "PID", {
+            set_id => 1,
+            patient_id: CX => 2,
+            patient_identifier_list: CX => 3,
+            alternate_patient_id: CX => 4,
+            patient_name: XPN => 5,
+            mothers_maiden_name: XPN => 6,
+            datetime_of_birth => 7,
+            administrative_sex => 8,
+            patient_alias: XPN => 9,
+            race: CE => 10,
+            patient_address: XAD => 11,
+            county_code => 12,
+            phone_number_home: XTN => 13,
+            phone_number_business: XTN => 14,
+            primary_language: CE => 15,
+            marital_status: CE => 16,
+            religion: CE => 17,
+            patient_account_number: CX => 18,
+            ssn_number => 19,
+            drivers_license_number: DLN => 20,
+            mothers_identifier: CX => 21,
+            ethnic_group: CE => 22,
+            birth_place => 23,
+            multiple_birth_indicator => 24,
+            birth_order => 25,
+            citizenship: CE => 26,
+            veterans_military_status: CE => 27,
+            nationality: CE => 28,
+            patient_death_datetime => 29,
+            patient_death_indicator => 30,
+            identity_unknown_indicator => 31,
+            identity_reliability_code => 32,
+            last_update_datetime => 33,
+            last_update_facility: HD => 34,
+            species_code: CE => 35,
+            breed_code: CE => 36,
+            strain => 37,
+            production_class_code: CE => 38,
+            tribal_citizenship: CWE => 39,
+        }
+    );
+
+    v2_typed_segment!(
+        ///
+        /// Patient Visit segment.
+        ///
+        PV1, "PV1", {
+            set_id => 1,
+            patient_class => 2,
+            assigned_patient_location: PL => 3,
+            admission_type => 4,
+            preadmit_number: CX => 5,
+            prior_patient_location: PL => 6,
+            attending_doctor: XCN => 7,
+            referring_doctor: XCN => 8,
+            consulting_doctor: XCN => 9,
+            hospital_service => 10,
+            temporary_location: PL => 11,
+            preadmit_test_indicator => 12,
+            readmission_indicator => 13,
+            admit_source => 14,
+            ambulatory_status => 15,
+            vip_indicator => 16,
+            admitting_doctor: XCN => 17,
+            patient_type => 18,
+            visit_number: CX => 19,
+            financial_class: FC => 20,
+            charge_price_indicator => 21,
+            courtesy_code => 22,
+            credit_rating => 23,
+            contract_code => 24,
+            contract_effective_date => 25,
+            contract_amount => 26,
+            contract_period => 27,
+            interest_code => 28,
+            transfer_to_bad_debt_code => 29,
+            transfer_to_bad_debt_date => 30,
+            bad_debt_agency_code => 31,
+            bad_debt_transfer_amount => 32,
+            bad_debt_recovery_amount => 33,
+            delete_account_indicator => 34,
+            delete_account_date => 35,
+            discharge_disposition => 36,
+            discharged_to_location: DLD => 37,
+            diet_type: CE => 38,
+            servicing_facility => 39,
+            bed_status => 40,
+            account_status => 41,
+            pending_location: PL => 42,
+            prior_temporary_location: PL => 43,
+            admit_datetime => 44,
+            discharge_datetime => 45,
+            current_patient_balance => 46,
+            total_charges => 47,
+            total_adjustments => 48,
+            total_payments => 49,
+            alternate_visit_id: CX => 50,
+            visit_indicator => 51,
+            other_healthcare_provider: XCN => 52,
+        }
+    );
+
+    v2_typed_segment!(
+        ///
+        /// Common Order segment.
+        ///
+        ORC, "ORC", {
+            order_control => 1,
+            placer_order_number: EI => 2,
+            filler_order_number: EI => 3,
+            placer_group_number: EI => 4,
+            order_status => 5,
+            response_flag => 6,
+            quantity_timing => 7,
+            parent_order: EIP => 8,
+            datetime_of_transaction => 9,
+            entered_by: XCN => 10,
+            verified_by: XCN => 11,
+            ordering_provider: XCN => 12,
+            enterers_location: PL => 13,
+            call_back_phone_number: XTN => 14,
+            order_effective_datetime => 15,
+            order_control_code_reason: CE => 16,
+            entering_organization: CE => 17,
+            entering_device: CE => 18,
+            action_by: XCN => 19,
+            advanced_beneficiary_notice_code: CE => 20,
+            ordering_facility_name: XON => 21,
+            ordering_facility_address: XAD => 22,
+            ordering_facility_phone_number: XTN => 23,
+            ordering_provider_address: XAD => 24,
+            order_status_modifier: CWE => 25,
+            abn_override_reason: CWE => 26,
+            fillers_expected_availability_datetime => 27,
+            confidentiality_code: CWE => 28,
+            order_type: CWE => 29,
+            enterer_authorization_mode: CNE => 30,
+            parent_universal_service_id: CWE => 31,
+        }
+    );
+
+    v2_typed_segment!(
+        ///
+        /// Observation Request segment.
+        ///
+        OBR, "OBR", {
+            set_id => 1,
+            placer_order_number: EI => 2,
+            filler_order_number: EI => 3,
+            universal_service_id: CE => 4,
+            priority => 5,
+            requested_datetime => 6,
+            observation_datetime => 7,
+            observation_end_datetime => 8,
+            collection_volume: CQ => 9,
+            collector_id: XCN => 10,
+            specimen_action_code => 11,
+            danger_code: CE => 12,
+            relevant_clinical_information => 13,
+            specimen_received_datetime => 14,
+            specimen_source => 15,
+            ordering_provider: XCN => 16,
+            order_callback_phone_number: XTN => 17,
+            placer_field_1 => 18,
+            placer_field_2 => 19,
+            filler_field_1 => 20,
+            filler_field_2 => 21,
+            results_status_change_datetime => 22,
+            charge_to_practice: MOC => 23,
+            diagnostic_service_section_id => 24,
+            result_status => 25,
+            parent_result: PRL => 26,
+            quantity_timing => 27,
+            result_copies_to: XCN => 28,
+            parent: EIP => 29,
+            transportation_mode => 30,
+            reason_for_study: CE => 31,
+            principal_result_interpreter: NDL => 32,
+            assistant_result_interpreter: NDL => 33,
+            technician: NDL => 34,
+            transcriptionist: NDL => 35,
+            scheduled_datetime => 36,
+            number_of_sample_containers => 37,
+            transport_logistics: CE => 38,
+            collectors_comment: CE => 39,
+            transport_arrangement_responsibility: CE => 40,
+            transport_arranged => 41,
+            escort_required => 42,
+            planned_patient_transport_comment: CE => 43,
+            procedure_code: CE => 44,
+            procedure_code_modifier: CE => 45,
+            placer_supplemental_service_information: CE => 46,
+            filler_supplemental_service_information: CE => 47,
+            duplicate_procedure_reason: CWE => 48,
+            result_handling => 49,
+            parent_universal_service_id: CWE => 50,
+        }
+    );
+
+    v2_typed_segment!(
+        ///
+        /// Observation/Result segment.
+        ///
+        /// OBX-5 is typed by the value in OBX-2 so its accessor is implemented separately. See
+        /// [OBX::observation_value]. OBX-20 to OBX-22 were reserved until v2.6, so their accessors
+        /// error out on valued fields in messages from older releases.
+        ///
+        OBX, "OBX", {
+            set_id => 1,
+            value_type => 2,
+            observation_id: CE => 3,
+            observation_sub_id => 4,
+            units: CE => 6,
+            references_range => 7,
+            abnormal_flags => 8,
+            probability => 9,
+            nature_of_abnormal_test => 10,
+            observation_result_status => 11,
+            reference_range_effective_date => 12,
+            user_defined_access_checks => 13,
+            observation_datetime => 14,
+            producers_id: CE => 15,
+            responsible_observer: XCN => 16,
+            observation_method: CE => 17,
+            equipment_instance_id: EI => 18,
+            analysis_datetime => 19,
+            observation_site: CWE => 20,
+            observation_instance_id: EI => 21,
+            mood_code: CNE => 22,
+            performing_organization_name: XON => 23,
+            performing_organization_address: XAD => 24,
+            performing_organization_medical_director: XCN => 25,
+        }
+    );
+
+    v2_typed_segment!(
+        ///
+        /// Next of Kin / Associated Parties segment.
+        ///
+        NK1, "NK1", {
+            set_id => 1,
+            name: XPN => 2,
+            relationship: CE => 3,
+            address: XAD => 4,
+            phone_number: XTN => 5,
+            business_phone_number: XTN => 6,
+            contact_role: CE => 7,
+            start_date => 8,
+            end_date => 9,
+            job_title => 10,
+            job_code_class: JCC => 11,
+            employee_number: CX => 12,
+            organization_name: XON => 13,
+            marital_status: CE => 14,
+            administrative_sex => 15,
+            datetime_of_birth => 16,
+            living_dependency => 17,
+            ambulatory_status => 18,
+            citizenship: CE => 19,
+            primary_language: CE => 20,
+            living_arrangement => 21,
+            publicity_code: CE => 22,
+            protection_indicator => 23,
+            student_indicator => 24,
+            religion: CE => 25,
+            mothers_maiden_name: XPN => 26,
+            nationality: CE => 27,
+            ethnic_group: CE => 28,
+            contact_reason: CE => 29,
+            contact_persons_name: XPN => 30,
+            contact_persons_phone_number: XTN => 31,
+            contact_persons_address: XAD => 32,
+            next_of_kin_identifiers: CX => 33,
+            job_status => 34,
+            race: CE => 35,
+            handicap => 36,
+            contact_person_ssn => 37,
+            birth_place => 38,
+            vip_indicator => 39,
+        }
+    );
+
+    v2_typed_segment!(
+        ///
+        /// Insurance segment.
+        ///
+        IN1, "IN1", {
+            set_id => 1,
+            insurance_plan_id: CE => 2,
+            insurance_company_id: CX => 3,
+            insurance_company_name: XON => 4,
+            insurance_company_address: XAD => 5,
+            insurance_company_contact_person: XPN => 6,
+            insurance_company_phone_number: XTN => 7,
+            group_number => 8,
+            group_name: XON => 9,
+            insureds_group_employer_id: CX => 10,
+            insureds_group_employer_name: XON => 11,
+            plan_effective_date => 12,
+            plan_expiration_date => 13,
+            authorization_information: AUI => 14,
+            plan_type => 15,
+            name_of_insured: XPN => 16,
+            insureds_relationship_to_patient: CE => 17,
+            insureds_date_of_birth => 18,
+            insureds_address: XAD => 19,
+            assignment_of_benefits => 20,
+            coordination_of_benefits => 21,
+            coordination_of_benefits_priority => 22,
+            notice_of_admission_flag => 23,
+            notice_of_admission_date => 24,
+            report_of_eligibility_flag => 25,
+            report_of_eligibility_date => 26,
+            release_information_code => 27,
+            pre_admit_cert => 28,
+            verification_datetime => 29,
+            verification_by: XCN => 30,
+            type_of_agreement_code => 31,
+            billing_status => 32,
+            lifetime_reserve_days => 33,
+            delay_before_lr_day => 34,
+            company_plan_code => 35,
+            policy_number => 36,
+            policy_deductible: CP => 37,
+            policy_limit_amount: CP => 38,
+            policy_limit_days => 39,
+            room_rate_semi_private: CP => 40,
+            room_rate_private: CP => 41,
+            insureds_employment_status: CE => 42,
+            insureds_administrative_sex => 43,
+            insureds_employers_address: XAD => 44,
+            verification_status => 45,
+            prior_insurance_plan_id => 46,
+            coverage_type => 47,
+            handicap => 48,
+            insureds_id_number: CX => 49,
+            signature_code => 50,
+            signature_code_date => 51,
+            insureds_birth_place => 52,
+            vip_indicator => 53,
+        }
+    );
+
+    v2_typed_segment!(
+        ///
+        /// Message Acknowledgment segment.
+        ///
+        MSA, "MSA", {
+            ack_code => 1,
+            message_control_id => 2,
+            text_message => 3,
+            expected_sequence_number => 4,
+            delayed_ack_type => 5,
+            error_condition: CE => 6,
+        }
+    );
+
+    v2_typed_segment!(
+        ///
+        /// Error segment.
+        ///
+        ERR, "ERR", {
+            error_code_and_location: ELD => 1,
+            error_location: ERL => 2,
+            hl7_error_code: CWE => 3,
+            severity => 4,
+            application_error_code: CWE => 5,
+            application_error_parameter => 6,
+            diagnostic_information => 7,
+            user_message => 8,
+            inform_person_indicator => 9,
+            override_type: CWE => 10,
+            override_reason_code: CWE => 11,
+            help_desk_contact_point: XTN => 12,
+        }
+    );
+
+    impl<'a> OBX<'a> {
+        ///
+        /// Get OBX-5 cast into the data type named in OBX-2. If OBX-2 is missing or names a type we
+        /// do not know about, the value is returned as a plain string.
+        ///
+        pub fn observation_value(&self) -> V2Type {
+            self.observation_values().into_iter().next().unwrap_or(V2Type::Empty)
+        }
+
+        ///
+        /// Same as [OBX::observation_value] but returns every repetition of OBX-5.
+        ///
+        pub fn observation_values(&self) -> Vec<V2Type> {
+            let value_type = match self.view.get_raw(2).first() {
+                Some(field) => field.to_component_list().first().map(|t| t.to_string()),
+                None => None,
+            }
+            .unwrap_or_default();
+            let data_type = match value_type.as_str() {
+                "ST" => V2ComponentType::Primitive(V2PrimitiveType::ST),
+                "TX" => V2ComponentType::Primitive(V2PrimitiveType::Text),
+                "FT" => V2ComponentType::Primitive(V2PrimitiveType::FT),
+                "NM" => V2ComponentType::Primitive(V2PrimitiveType::NM),
+                "SI" => V2ComponentType::Primitive(V2PrimitiveType::SI),
+                "ID" => V2ComponentType::Primitive(V2PrimitiveType::ID),
+                "IS" => V2ComponentType::Primitive(V2PrimitiveType::IS),
+                "DT" => V2ComponentType::Primitive(V2PrimitiveType::Date),
+                "TM" => V2ComponentType::Primitive(V2PrimitiveType::Time),
+                "DTM" | "TS" => V2ComponentType::Primitive(V2PrimitiveType::DateTime),
+                other => match str_to_complex_type(other) {
+                    Some(complex_type) => V2ComponentType::Complex(complex_type),
+                    None => V2ComponentType::Primitive(V2PrimitiveType::String),
+                },
+            };
+            let descriptor = V2ComponentTypeDescriptor::new(
+                "observation_value",
+                "Observation Value",
+                data_type,
+                0,
+                5,
+                0,
+                Optionality::O,
+                false,
+            );
+            let fields: Vec<&V2Field> = self
+                .view
+                .get_raw(5)
+                .into_iter()
+                .filter(|f| !is_empty_field(f))
+                .collect();
+            match fields.is_empty() {
+                true => vec![V2Type::Empty],
+                false => fields
+                    .into_iter()
+                    .map(|field| cast_field_as(field, &descriptor, self.view.parser_chars, self.view.version))
+                    .collect(),
+            }
+        }
+    }
+}
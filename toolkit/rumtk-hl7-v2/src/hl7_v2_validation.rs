@@ -0,0 +1,604 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2026  Luis M. Santos, M.D. <lsantos@medicalmasses.com>
+ * Copyright (C) 2026  MedicalMasses L.L.C. <contact@medicalmasses.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+///
+/// Conformance validation. Walks every segment we have a schema for and reports every violation
+/// found instead of stopping at the first one. This lets consumers reject or quarantine a feed with
+/// a full report of what is wrong with it.
+///
+/// Locations use the same notation as the search syntax, with fields numbered per the standard
+/// (MSH-9 is the message type). The segment instance is only included when it is not the first
+/// one, e.g. `PID-5[2].1` or `OBX(3)-5[1].2`. Subcomponents are appended as an extra `.n`.
+///
+pub mod v2_validation {
+    use crate::hl7_v2_base_types::v2_primitives::{V2ParserCharacters, V2PrimitiveType, V2String};
+    use crate::hl7_v2_complex_types::hl7_v2_complex_types::{
+        build_condition_list, cast_component_as, condition_list_size, V2FieldToString,
+    };
+    use crate::hl7_v2_constants::{V2_CUSTOM_SEGMENT_ID, V2_TOTAL_VALID_SEGMENTS};
+    use crate::hl7_v2_message_structures::v2_message_structure::{
+        message_structure_id, parse_message_structure_as, segment_occurrence_limits, V2Cardinality,
+        V2StructureElement, V2_MESSAGE_STRUCTURES,
+    };
+    use crate::hl7_v2_parser::v2_parser::V2Message;
+    use crate::hl7_v2_segment_descriptors::v2_segment_descriptor::*;
+    use crate::hl7_v2_tables::v2_tables::validate_table_value;
+    use crate::hl7_v2_types::v2_types::V2SegmentView;
+    use rumtk_core::strings::rumtk_format;
+    use std::fmt::{Display, Formatter};
+
+    /// Used when the message structure is unknown. Every message still needs exactly one MSH.
+    const V2_HEADER_ONLY_STRUCTURE: &[V2StructureElement] = &[V2StructureElement::Segment("MSH", V2Cardinality::One)];
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum V2Severity {
+        Info,
+        Warning,
+        Error,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum V2ValidationRule {
+        /// A required field is not valued.
+        RequiredField,
+        /// A required component of a complex type is not valued.
+        RequiredComponent,
+        /// A conditionally required component is not valued while its condition holds.
+        ConditionalComponent,
+        /// Value is longer than the maximum length in the descriptor.
+        MaxLength,
+        /// Field repeats more often than allowed.
+        Repetition,
+        /// Segment occurs more or less often than allowed.
+        SegmentCardinality,
+        /// Value does not cast into the data type in the descriptor.
+        DataType,
+        /// Field or component flagged as not supported is valued.
+        NotSupported,
+        /// Value has more components than its data type defines.
+        ExtraComponents,
+        /// Coded value is not in the HL7 table referenced by the descriptor.
+        TableValue,
+        /// Segment is out of place for the message structure.
+        MessageStructure,
+    }
+
+    impl V2ValidationRule {
+        pub fn id(&self) -> &'static str {
+            match self {
+                V2ValidationRule::RequiredField => "REQUIRED_FIELD",
+                V2ValidationRule::RequiredComponent => "REQUIRED_COMPONENT",
+                V2ValidationRule::ConditionalComponent => "CONDITIONAL_COMPONENT",
+                V2ValidationRule::MaxLength => "MAX_LENGTH",
+                V2ValidationRule::Repetition => "REPETITION",
+                V2ValidationRule::SegmentCardinality => "SEGMENT_CARDINALITY",
+                V2ValidationRule::DataType => "DATA_TYPE",
+                V2ValidationRule::NotSupported => "NOT_SUPPORTED",
+                V2ValidationRule::ExtraComponents => "EXTRA_COMPONENTS",
+                V2ValidationRule::TableValue => "TABLE_VALUE",
+                V2ValidationRule::MessageStructure => "MESSAGE_STRUCTURE",
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct V2ValidationIssue {
+        pub location: V2String,
+        pub severity: V2Severity,
+        pub rule: V2ValidationRule,
+        pub message: V2String,
+    }
+
+    impl Display for V2ValidationIssue {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(
+                f,
+                "{:?} [{}] at {}: {}",
+                self.severity,
+                self.rule.id(),
+                self.location,
+                self.message
+            )
+        }
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq)]
+    pub struct V2ValidationReport {
+        issues: Vec<V2ValidationIssue>,
+    }
+
+    impl V2ValidationReport {
+        pub fn new() -> Self {
+            Self { issues: Vec::new() }
+        }
+
+        pub fn push(
+            &mut self,
+            location: V2String,
+            severity: V2Severity,
+            rule: V2ValidationRule,
+            message: V2String,
+        ) {
+            self.issues.push(V2ValidationIssue {
+                location,
+                severity,
+                rule,
+                message,
+            });
+        }
+
+        ///
+        /// True if the report contains no issue of [V2Severity::Error] severity.
+        ///
+        pub fn is_valid(&self) -> bool {
+            !self.issues.iter().any(|i| i.severity == V2Severity::Error)
+        }
+
+        pub fn issues(&self) -> &Vec<V2ValidationIssue> {
+            &self.issues
+        }
+
+        pub fn errors(&self) -> Vec<&V2ValidationIssue> {
+            self.with_severity(V2Severity::Error)
+        }
+
+        pub fn warnings(&self) -> Vec<&V2ValidationIssue> {
+            self.with_severity(V2Severity::Warning)
+        }
+
+        pub fn with_severity(&self, severity: V2Severity) -> Vec<&V2ValidationIssue> {
+            self.issues.iter().filter(|i| i.severity == severity).collect()
+        }
+
+        ///
+        /// Issues reported at `location` or any location nested under it.
+        ///
+        pub fn at(&self, location: &str) -> Vec<&V2ValidationIssue> {
+            self.issues
+                .iter()
+                .filter(|i| {
+                    i.location == location
+                        || i.location.starts_with(&rumtk_format!("{}.", location))
+                        || i.location.starts_with(&rumtk_format!("{}[", location))
+                })
+                .collect()
+        }
+
+        ///
+        /// Move the issues of `other` into this report.
+        ///
+        pub fn merge(&mut self, other: V2ValidationReport) {
+            self.issues.extend(other.issues);
+        }
+
+        pub fn len(&self) -> usize {
+            self.issues.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.issues.is_empty()
+        }
+    }
+
+    ///
+    /// Validate a whole message. Segment order and cardinality are checked against the message
+    /// structure named in MSH-9 when we have it. Segment contents are then visited in segment id
+    /// order using the data types of [V2Message::version].
+    ///
+    pub fn validate_message(message: &V2Message) -> V2ValidationReport {
+        let mut report = V2ValidationReport::new();
+        validate_segment_cardinality(message, &mut report);
+        validate_structure(message, &mut report);
+
+        for segment_id in 1..=V2_TOTAL_VALID_SEGMENTS {
+            let group = match message.get_group(segment_id) {
+                Ok(group) => group,
+                Err(_) => continue,
+            };
+            for (i, segment) in group.iter().enumerate() {
+                let segment_name = segment.name(segment_id);
+                // Custom segments share a group so their instances are counted by name.
+                let instance = match segment_id {
+                    V2_CUSTOM_SEGMENT_ID => group[..i]
+                        .iter()
+                        .filter(|s| s.custom_name() == Some(segment_name))
+                        .count() + 1,
+                    _ => i + 1,
+                };
+                match V2SegmentView::new(segment_name, segment, message.parser_chars()) {
+                    Ok(view) => validate_segment(
+                        &view.with_version(message.version()),
+                        instance,
+                        message.parser_chars(),
+                        &mut report,
+                    ),
+                    // Segments without a schema are only checked for cardinality and order.
+                    Err(_) => continue,
+                }
+            }
+        }
+
+        report
+    }
+
+    ///
+    /// Check how often each segment of the message structure appears. Without a known structure
+    /// only the MSH can be checked.
+    ///
+    fn validate_segment_cardinality(message: &V2Message, report: &mut V2ValidationReport) {
+        let structure = match message_structure_id(message).ok().and_then(|id| V2_MESSAGE_STRUCTURES.get(id)) {
+            Some(structure) => *structure,
+            None => V2_HEADER_ONLY_STRUCTURE,
+        };
+        for (segment_name, min, max) in segment_occurrence_limits(structure) {
+            let count = message.named_segment_count(segment_name);
+            if count < min {
+                let message = match count {
+                    0 => rumtk_format!("Message has no {} segment!", segment_name),
+                    _ => rumtk_format!(
+                        "Message has {} {} segments but at least {} are required!",
+                        count,
+                        segment_name,
+                        min
+                    ),
+                };
+                report.push(V2String::from(segment_name), V2Severity::Error, V2ValidationRule::SegmentCardinality, message);
+            }
+            match max {
+                Some(max) if count > max => report.push(
+                    V2String::from(segment_name),
+                    V2Severity::Error,
+                    V2ValidationRule::SegmentCardinality,
+                    rumtk_format!(
+                        "Message has {} {} segments but only {} allowed!",
+                        count,
+                        segment_name,
+                        match max {
+                            1 => V2String::from("one is"),
+                            _ => rumtk_format!("{} are", max),
+                        }
+                    ),
+                ),
+                _ => (),
+            }
+        }
+    }
+
+    fn validate_structure(message: &V2Message, report: &mut V2ValidationReport) {
+        let structure_id = match message_structure_id(message) {
+            Ok(structure_id) => structure_id,
+            Err(e) => {
+                report.push(
+                    V2String::from("MSH-9"),
+                    V2Severity::Info,
+                    V2ValidationRule::MessageStructure,
+                    rumtk_format!("Segment order was not validated. {}", e),
+                );
+                return;
+            }
+        };
+        match parse_message_structure_as(message, structure_id) {
+            Ok(tree) => report.merge(tree.into_issues()),
+            Err(e) => report.push(
+                V2String::from("MSH-9"),
+                V2Severity::Info,
+                V2ValidationRule::MessageStructure,
+                e,
+            ),
+        }
+    }
+
+    ///
+    /// Validate one segment instance against its schema. `instance` is 1-indexed. Complex types are
+    /// checked against their definition in [V2SegmentView::version].
+    ///
+    pub fn validate_segment(
+        view: &V2SegmentView,
+        instance: usize,
+        parser_chars: &V2ParserCharacters,
+        report: &mut V2ValidationReport,
+    ) {
+        let segment_location = match instance {
+            1 => V2String::from(view.name()),
+            _ => rumtk_format!("{}({})", view.name(), instance),
+        };
+
+        for descriptor in view.descriptors().iter() {
+            let seq = descriptor.seq;
+            let field_location = rumtk_format!("{}-{}", segment_location, seq);
+
+            // MSH-1 is the field separator which we already used to parse the message.
+            if segment_field_index(view.name(), seq) < 1 {
+                continue;
+            }
+
+            let repetitions: Vec<(usize, Vec<&str>)> = view
+                .get_raw(seq)
+                .into_iter()
+                .map(|field| field.to_component_list())
+                .enumerate()
+                .filter(|(_, components)| !components.iter().all(|c| c.is_empty()))
+                .collect();
+
+            if repetitions.is_empty() {
+                if descriptor.optionality.is_required() {
+                    report.push(
+                        field_location,
+                        V2Severity::Error,
+                        V2ValidationRule::RequiredField,
+                        rumtk_format!("Required field {} is missing!", descriptor.description),
+                    );
+                }
+                continue;
+            }
+
+            if matches!(descriptor.optionality, Optionality::X) {
+                report.push(
+                    field_location.clone(),
+                    V2Severity::Warning,
+                    V2ValidationRule::NotSupported,
+                    rumtk_format!("Field {} is not supported but was valued!", descriptor.description),
+                );
+            }
+
+            match field_repetition_limit(view.name(), seq) {
+                Some(limit) if repetitions.len() > limit as usize => report.push(
+                    field_location.clone(),
+                    V2Severity::Error,
+                    V2ValidationRule::Repetition,
+                    rumtk_format!(
+                        "Field {} repeats {} times but only {} allowed!",
+                        descriptor.description,
+                        repetitions.len(),
+                        limit
+                    ),
+                ),
+                _ => (),
+            }
+
+            for (rep, components) in repetitions.iter() {
+                let location = rumtk_format!("{}[{}]", field_location, rep + 1);
+                validate_value(components, descriptor, &location, parser_chars, view.version(), 0, report);
+            }
+        }
+    }
+
+    ///
+    /// Validate the components of one value against its descriptor. `depth` is 0 for a field, 1 for
+    /// a component holding a complex type split into subcomponents.
+    ///
+    fn validate_value(
+        components: &Vec<&str>,
+        descriptor: &V2ComponentTypeDescriptor,
+        location: &str,
+        parser_chars: &V2ParserCharacters,
+        version: V2Version,
+        depth: u8,
+        report: &mut V2ValidationReport,
+    ) {
+        match &descriptor.data_type {
+            V2ComponentType::Primitive(_) => {
+                if components.len() > 1 {
+                    report.push(
+                        V2String::from(location),
+                        V2Severity::Warning,
+                        V2ValidationRule::ExtraComponents,
+                        rumtk_format!(
+                            "{} is a primitive type but has {} components. Only the first one is used!",
+                            descriptor.description,
+                            components.len()
+                        ),
+                    );
+                }
+                let value = components.first().copied().unwrap_or_default();
+                validate_primitive(value, descriptor, location, parser_chars, version, report);
+            }
+            V2ComponentType::Complex(complex_type) => {
+                let type_name = complex_type_to_str(complex_type);
+                let descriptors = match get_field_descriptor(type_name, version) {
+                    Some(descriptors) if !descriptors.is_empty() => descriptors,
+                    _ => return,
+                };
+                let conditions = build_condition_list(components, condition_list_size(type_name, descriptors.len()), parser_chars);
+                validate_coded_value(components, descriptor, complex_type, location, report);
+
+                if components.len() > descriptors.len() {
+                    report.push(
+                        V2String::from(location),
+                        V2Severity::Warning,
+                        V2ValidationRule::ExtraComponents,
+                        rumtk_format!(
+                            "{} defines {} components but {} were received!",
+                            type_name,
+                            descriptors.len(),
+                            components.len()
+                        ),
+                    );
+                }
+
+                for (i, component_descriptor) in descriptors.iter().enumerate() {
+                    let raw = components.get(i).copied().unwrap_or_default();
+                    let component_location = rumtk_format!("{}.{}", location, i + 1);
+
+                    if raw.is_empty() {
+                        if component_descriptor.optionality.is_required() {
+                            report.push(
+                                component_location,
+                                V2Severity::Error,
+                                V2ValidationRule::RequiredComponent,
+                                rumtk_format!(
+                                    "Required component {}.{} ({}) is missing!",
+                                    type_name,
+                                    component_descriptor.seq,
+                                    component_descriptor.description
+                                ),
+                            );
+                        } else if component_descriptor
+                            .optionality
+                            .is_conditionally_required(&conditions)
+                        {
+                            report.push(
+                                component_location,
+                                V2Severity::Error,
+                                V2ValidationRule::ConditionalComponent,
+                                rumtk_format!(
+                                    "Component {}.{} ({}) is required by its condition but is missing!",
+                                    type_name,
+                                    component_descriptor.seq,
+                                    component_descriptor.description
+                                ),
+                            );
+                        }
+                        continue;
+                    }
+
+                    match &component_descriptor.data_type {
+                        V2ComponentType::Primitive(_) => validate_primitive(
+                            raw,
+                            component_descriptor,
+                            &component_location,
+                            parser_chars,
+                            version,
+                            report,
+                        ),
+                        V2ComponentType::Complex(_) => {
+                            let subcomponents = match depth {
+                                0 => raw
+                                    .split(parser_chars.subcomponent_separator as char)
+                                    .collect(),
+                                _ => vec![raw],
+                            };
+                            validate_value(
+                                &subcomponents,
+                                component_descriptor,
+                                &component_location,
+                                parser_chars,
+                                version,
+                                depth + 1,
+                                report,
+                            )
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn validate_primitive(
+        value: &str,
+        descriptor: &V2ComponentTypeDescriptor,
+        location: &str,
+        parser_chars: &V2ParserCharacters,
+        version: V2Version,
+        report: &mut V2ValidationReport,
+    ) {
+        if value.is_empty() {
+            return;
+        }
+
+        let max_len = descriptor.max_input_len as usize;
+        let value_len = value.chars().count();
+        if max_len > 0 && value_len > max_len {
+            let severity = match descriptor.truncate {
+                true => V2Severity::Warning,
+                false => V2Severity::Error,
+            };
+            report.push(
+                V2String::from(location),
+                severity,
+                V2ValidationRule::MaxLength,
+                rumtk_format!(
+                    "{} is {} characters long but the maximum is {}!",
+                    descriptor.description,
+                    value_len,
+                    max_len
+                ),
+            );
+        }
+
+        match descriptor.data_type {
+            V2ComponentType::Primitive(V2PrimitiveType::ID)
+            | V2ComponentType::Primitive(V2PrimitiveType::IS)
+                if descriptor.valid_table > 0 =>
+            {
+                validate_table(value, descriptor.valid_table, location, report)
+            }
+            _ => (),
+        }
+
+        let cast = cast_component_as(vec![value], descriptor, parser_chars, version);
+        if cast.is_err() {
+            report.push(
+                V2String::from(location),
+                V2Severity::Error,
+                V2ValidationRule::DataType,
+                rumtk_format!(
+                    "{} could not be cast into {:?} => {:?}",
+                    descriptor.description,
+                    descriptor.data_type,
+                    cast
+                ),
+            );
+        }
+    }
+
+    ///
+    /// Coded elements (CE, CWE, CNE) carry the table of the field in their descriptor. The identifier
+    /// is only checked against it when the coding system is not specified or is the HL7 table itself.
+    ///
+    fn validate_coded_value(
+        components: &Vec<&str>,
+        descriptor: &V2ComponentTypeDescriptor,
+        complex_type: &V2ComplexType,
+        location: &str,
+        report: &mut V2ValidationReport,
+    ) {
+        if descriptor.valid_table == 0 {
+            return;
+        }
+        match complex_type {
+            V2ComplexType::CE | V2ComplexType::CWE | V2ComplexType::CNE => (),
+            _ => return,
+        }
+        let identifier = components.first().copied().unwrap_or_default();
+        let coding_system = components.get(2).copied().unwrap_or_default();
+        if identifier.is_empty() || !(coding_system.is_empty() || coding_system.starts_with("HL7")) {
+            return;
+        }
+        validate_table(
+            identifier,
+            descriptor.valid_table,
+            &rumtk_format!("{}.1", location),
+            report,
+        )
+    }
+
+    fn validate_table(value: &str, table: u16, location: &str, report: &mut V2ValidationReport) {
+        match validate_table_value(table, value) {
+            Ok(_) => (),
+            Err(e) => report.push(
+                V2String::from(location),
+                V2Severity::Error,
+                V2ValidationRule::TableValue,
+                e,
+            ),
+        }
+    }
+}
@@ -23,51 +23,93 @@
 #![feature(allocator_api)]
 
 extern crate rumtk_core;
+pub mod hl7_v2_ack;
 pub mod hl7_v2_base_types;
+pub mod hl7_v2_batch;
+pub mod hl7_v2_builder;
+pub mod hl7_v2_charset;
 pub mod hl7_v2_complex_types;
 pub mod hl7_v2_constants;
 pub mod hl7_v2_datasets;
+pub mod hl7_v2_escape;
 pub mod hl7_v2_field_descriptors;
 pub mod hl7_v2_interpreter;
+pub mod hl7_v2_lazy;
+pub mod hl7_v2_message_structures;
 pub mod hl7_v2_mllp;
+pub mod hl7_v2_mllp_journal;
+pub mod hl7_v2_mllp_queue;
 pub mod hl7_v2_optionality_rules;
 pub mod hl7_v2_parser;
+pub mod hl7_v2_repair;
 pub mod hl7_v2_scripting;
 pub mod hl7_v2_search;
+pub mod hl7_v2_segment_descriptors;
+pub mod hl7_v2_tables;
+pub mod hl7_v2_translation;
 pub mod hl7_v2_types;
+pub mod hl7_v2_validation;
 pub mod hl7_v2_python_types;
 /*****************************************Tests****************************************/
 #[cfg(test)]
 mod tests {
+    use crate::hl7_v2_ack::v2_ack::{
+        ack_code, generate_ack, is_ack_message, V2AckCode, V2AckOutcome, V2AckResponse,
+    };
     use crate::hl7_v2_base_types::v2_base_types::{
         V2DateTime, V2ParserCharacters, V2SearchIndex, V2String,
     };
     use crate::hl7_v2_base_types::v2_primitives::{
         V2PrimitiveCasting, V2PrimitiveType, TRUNCATE_FT,
     };
-    use crate::hl7_v2_complex_types::hl7_v2_complex_types::{cast_component, V2Type};
-    use crate::hl7_v2_constants::{V2_SEGMENT_IDS, V2_SEGMENT_IDS_USIZE};
+    use crate::hl7_v2_batch::v2_batch::{V2BatchReader, V2BatchSegment, V2BatchWriter};
+    use crate::hl7_v2_builder::v2_builder::V2MessageBuilder;
+    use crate::hl7_v2_charset::v2_charset::{v2_declared_charset, v2_decode_message, V2EncodingSource};
+    use crate::hl7_v2_complex_types::hl7_v2_complex_types::{cast_complex_type, cast_complex_type_as, cast_component, V2Type};
+    use crate::hl7_v2_constants::{V2_CHARSET_ENCODINGS, V2_CUSTOM_SEGMENT_ID, V2_MSHEADER_ID, V2_SEGMENT_IDS, V2_SEGMENT_IDS_USIZE};
+    use crate::hl7_v2_escape::v2_escape::{
+        v2_unescape, V2EscapeHandling, V2EscapeOptions, V2EscapeSequence, V2EscapedText, V2FormattingCommand,
+    };
     use crate::hl7_v2_field_descriptors::v2_field_descriptor::{
-        V2ComponentType, V2ComponentTypeDescriptor,
+        get_field_descriptor, V2ComplexType, V2ComponentType, V2ComponentTypeDescriptor, V2Version,
     };
+    use crate::hl7_v2_lazy::v2_lazy::V2LazyMessage;
+    use crate::hl7_v2_message_structures::v2_message_structure::{message_structure_id, V2StructureNode};
     use crate::hl7_v2_mllp::mllp_v2::{
-        mllp_decode, mllp_encode, MLLPClientMessages, CR, EB, MLLP_FILTER_POLICY, SB,
+        mllp_decode, mllp_encode, MLLPClientMessages, MLLPConfig, MLLPRetryPolicy, RUMTLSConfig,
+        CR, EB, LOCALHOST, MLLP_FILTER_POLICY, SB, TIMEOUT_SOURCE,
+    };
+    use crate::hl7_v2_mllp::mllp_v2_helpers::{
+        create_async_mllp_with_config, mllp_close_idle, mllp_consume, mllp_get_retry_policy,
+        mllp_open_journal, mllp_open_queue, mllp_open_queued_channels, mllp_receive,
+        mllp_receive_journaled, mllp_send, mllp_send_ack, mllp_set_auto_ack, mllp_set_config,
     };
+    use crate::hl7_v2_mllp_journal::mllp_v2_journal::MLLPJournal;
+    use crate::hl7_v2_mllp_queue::mllp_v2_queue::{MLLPQueue, MLLPQueuePolicy, MLLPQueueState, QUEUE_COMPACT_RECORDS};
     use crate::hl7_v2_optionality_rules::Optionality;
     use crate::hl7_v2_parser::v2_parser::{V2Field, V2Message};
-    use crate::hl7_v2_search::REGEX_V2_SEARCH_DEFAULT;
+    use crate::hl7_v2_repair::v2_repair::{v2_repair, V2ParseFixKind, V2ParseOptions};
+    use crate::hl7_v2_search::{V2SearchExpression, V2SearchSelector, REGEX_V2_SEARCH_DEFAULT};
+    use crate::hl7_v2_segment_descriptors::v2_segment_descriptor::{register_custom_segment, V2CustomSegment};
+    use crate::hl7_v2_tables::v2_tables::{get_table, load_table_csv, load_table_json, validate_table_value, V2Table};
+    use crate::hl7_v2_translation::v2_translation::V2TranslationKind;
+    use crate::hl7_v2_types::v2_types::{V2SegmentView, CX, ERR, FN, MSA, MSH, OBX, PID};
+    use crate::hl7_v2_validation::v2_validation::{V2Severity, V2ValidationRule};
     use crate::{
-        rumtk_v2_find_component, rumtk_v2_generate_message, rumtk_v2_mllp_connect,
+        v2_component_descriptor, rumtk_v2_find_component, rumtk_v2_generate_message, rumtk_v2_mllp_connect,
         rumtk_v2_mllp_get_client_ids, rumtk_v2_mllp_get_ip_port, rumtk_v2_mllp_iter_channels,
-        rumtk_v2_mllp_listen, rumtk_v2_mllp_receive, rumtk_v2_mllp_send, rumtk_v2_parse_message,
+        rumtk_v2_mllp_listen, rumtk_v2_mllp_receive, rumtk_v2_mllp_send, rumtk_v2_mllp_send_with_ack,
+        rumtk_v2_parse_message,
     };
     use rumtk_core::base::{RUMResult, RUMVec};
     use rumtk_core::buffers::*;
     use rumtk_core::buffers::{buffer_find, buffer_find_instances, buffer_replace, buffer_replace_in_place, buffer_to_str, RUMBufferIteratorExt};
     use rumtk_core::cpu::{cpu_collect, CPUTokenIndexCollection, CPU_SEARCH_WINDOW_256_SIZE};
+    use rumtk_core::net::tls::cert_file_fingerprint;
     use rumtk_core::search::rumtk_search::{string_search_named_captures, SearchGroups};
     use rumtk_core::serde::{from_json, to_json, RUMDeJson, RUMSerJson};
     use rumtk_core::strings::{basic_escape, rumtk_format, AsStr, RUMArrayConversions, RUMString, StringUtils};
+    use rumtk_core::threading::threading_functions::block_on_task;
     use rumtk_core::{rumtk_benchmark_snippet, rumtk_create_task, rumtk_deserialize, rumtk_exec_task, rumtk_resolve_task, rumtk_serialize, rumtk_sleep};
     use std::thread::spawn;
     use std::time::Instant;
@@ -552,8 +594,8 @@ mod tests {
 
     #[test]
     fn test_v2_search_index() {
-        let expr = "MSH(1)-1[5].4";
-        let v2_search_index = V2SearchIndex::from(expr);
+        let expr = "MSH(1)--1[5].4";
+        let v2_search_index = V2SearchIndex::from(expr).unwrap();
         let expected = V2SearchIndex::new("MSH", 1, -1, 5, 4);
         println!(
             "Input: {:?} Expected: {:?} Got: {:?}",
@@ -565,6 +607,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_v2_search_index_errors() {
+        assert_eq!(V2SearchIndex::try_from("ZPD-3.1").unwrap(), V2SearchIndex::new("ZPD", 1, 3, 1, 1), "Dash after the segment name is HL7 notation!");
+        assert_eq!(V2SearchIndex::try_from("ZPD3.1").unwrap(), V2SearchIndex::try_from("ZPD-3.1").unwrap(), "The notation dash is optional!");
+        assert_eq!(V2SearchIndex::try_from("ZPD(1)-3.1").unwrap(), V2SearchIndex::try_from("ZPD-3.1").unwrap(), "A dash means the same with or without a segment group!");
+        assert_eq!(V2SearchIndex::try_from("ZPD--3.1").unwrap(), V2SearchIndex::new("ZPD", 1, -3, 1, 1), "A second dash is a sign!");
+        assert!(V2SearchIndex::try_from("ZPD-").is_err(), "The notation dash needs a field!");
+        assert!(V2SearchIndex::from("PID(1-5").is_err(), "Malformed expressions should not panic!");
+        assert_eq!(V2SearchIndex::try_from("OBX(2)").unwrap(), V2SearchIndex::new("OBX", 2, 1, 1, 1), "Omitted indices default to 1!");
+
+        let err = V2SearchIndex::try_from("PI").unwrap_err();
+        assert!(err.contains("position 0"), "Error should point at the segment name! Got {}", err);
+        let err = V2SearchIndex::try_from("PID(1-5").unwrap_err();
+        assert!(err.contains("Expected ')'") && err.contains("position 5"), "Error should point at the missing parenthesis! Got {}", err);
+        let err = V2SearchIndex::try_from("PID5.x").unwrap_err();
+        assert!(err.contains("position 5"), "Error should point at the component! Got {}", err);
+        let err = V2SearchIndex::try_from("PID5[300]").unwrap_err();
+        assert!(err.contains("out of range"), "Repetitions must fit in a byte! Got {}", err);
+        assert!(V2SearchIndex::try_from("PID5.1 junk").is_err(), "Trailing characters are not allowed!");
+
+        let message = V2Message::try_from(DEFAULT_HL7_V2_MESSAGE).unwrap();
+        assert!(rumtk_v2_find_component!(message, "PID(").is_err(), "find_component should propagate the parse error!");
+    }
+
     #[test]
     fn test_load_hl7_v2_message_macro() {
         let message = rumtk_v2_parse_message!(DEFAULT_HL7_V2_MESSAGE).unwrap();
@@ -1032,6 +1098,966 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cast_complex_component_xpn() {
+        let field_str = RUMBuffer::from("Smith&St^John^J^III^DR^PHD^L".as_bytes());
+        let encode_chars = V2ParserCharacters::new();
+        let field = V2Field::from(field_str.freeze(), &encode_chars);
+        let val = field.to_complex(&V2ComplexType::XPN, &encode_chars);
+        println!("{:#?}", val);
+        let name = val.as_complex().unwrap();
+        assert_eq!(name.type_name(), "XPN", "Wrong complex type returned!");
+        assert!(!val.is_err(), "Casting a valid XPN yielded errors => {:?}", name.errors());
+
+        let family_name = name.get("family_name").unwrap().as_complex().unwrap();
+        assert_eq!(
+            family_name.get("surname").unwrap().as_str(),
+            Some("Smith"),
+            "Wrong surname in nested FN!"
+        );
+        assert_eq!(
+            family_name.get_seq(2).unwrap().as_str(),
+            Some("St"),
+            "Wrong own surname prefix in nested FN!"
+        );
+        assert_eq!(
+            name.get("given_name").unwrap().as_str(),
+            Some("John"),
+            "Wrong given name!"
+        );
+        assert_eq!(
+            name.get("name_type").unwrap().as_str(),
+            Some("L"),
+            "Wrong name type code!"
+        );
+        assert!(
+            name.get("called_by").unwrap().is_empty(),
+            "Unpopulated component should be empty!"
+        );
+    }
+
+    #[test]
+    fn test_cast_complex_component_cx() {
+        let encode_chars = V2ParserCharacters::new();
+        let input = vec!["PATID1234", "5", "M11", "ADT1&2.16.840.1&ISO", "MR", "GOOD HEALTH HOSPITAL"];
+        let val = cast_complex_type(input, &V2ComplexType::CX, &encode_chars);
+        println!("{:#?}", val);
+        let id = val.as_complex().unwrap();
+        assert!(!val.is_err(), "Casting a valid CX yielded errors => {:?}", id.errors());
+        assert_eq!(id.get("id").unwrap().as_str(), Some("PATID1234"), "Wrong ID Number!");
+
+        let authority = id.get("assigning_authority").unwrap().as_complex().unwrap();
+        assert_eq!(authority.type_name(), "HD", "Assigning authority should be an HD!");
+        assert_eq!(
+            authority.get("universal_id_type").unwrap().as_str(),
+            Some("ISO"),
+            "Wrong universal ID type in assigning authority!"
+        );
+
+        let missing_id = cast_complex_type(vec!["", "", "", "ADT1"], &V2ComplexType::CX, &encode_chars);
+        let errors = missing_id.as_complex().unwrap().errors();
+        println!("{:?}", errors);
+        assert!(missing_id.is_err(), "CX without CX.1 and CX.5 should fail validation!");
+        assert_eq!(errors.len(), 2, "Expected errors for CX.1 and CX.5 only!");
+    }
+
+    #[test]
+    fn test_typed_segment_accessors() {
+        let message = rumtk_v2_parse_message!(V2_TEST_SMALL_MESSAGE).unwrap();
+
+        let msh = MSH::from_message(&message, 1).unwrap();
+        assert_eq!(msh.field_separator().as_str(), Some("|"), "Wrong field separator in MSH-1!");
+        assert_eq!(msh.encoding_characters().as_str(), Some("^~\\&#"), "Wrong encoding characters in MSH-2!");
+        assert_eq!(
+            msh.message_control_id().as_str(),
+            Some(rumtk_v2_find_component!(message, "MSH9").unwrap().as_str()),
+            "Typed MSH-10 disagrees with the parser's numbering!"
+        );
+        let message_type = msh.message_type().unwrap().unwrap();
+        println!("{:#?}", message_type);
+        assert_eq!(message_type.message_structure().unwrap().as_str(), Some("OML_O21"), "Wrong message structure in MSH-9!");
+        let version = msh.version_id().unwrap().unwrap();
+        assert_eq!(version.version_id().unwrap().as_str(), Some("2.5.1"), "Wrong version in MSH-12!");
+
+        let pid = PID::from_message(&message, 1).unwrap();
+        let name = pid.patient_name().unwrap().unwrap();
+        println!("{:#?}", name);
+        let family_name = FN::try_from(pid.view().get(5)).err();
+        assert!(family_name.is_some(), "PID-5 is an XPN, not an FN!");
+        let family_name = name.family_name().unwrap().as_complex().unwrap();
+        assert_eq!(family_name.get("surname").unwrap().as_str(), Some("Hernandez"), "Wrong surname in PID-5!");
+        assert_eq!(name.given_name().unwrap().as_str(), Some("Maria"), "Wrong given name in PID-5!");
+        assert!(pid.mothers_maiden_name().unwrap().is_none(), "PID-6 should be empty!");
+        let identifiers = pid.view().get_repetitions_as::<CX>(3).unwrap();
+        assert_eq!(identifiers[0].id().unwrap().as_str(), Some("PATID14567"), "Wrong identifier in PID-3!");
+
+        let obx = OBX::from_message(&message, 2).unwrap();
+        match obx.observation_value() {
+            V2Type::V2NM(value) => assert_eq!(value.unwrap(), 1250.0, "Wrong value in OBX-5!"),
+            other => panic!("OBX-5 should have been cast to NM per OBX-2! Got {:?}", other),
+        }
+
+        let raw = "MSH|^~\\&|LAB|FAC|||20250101120000||ORU^R01^ORU_R01|MSG0001|P|2.7\rOBX|1|NM|GLU^Glucose^L||95||||||F|||||||||LA^Left Arm^HL70163|OBS1^LAB|EVN^Event^HL70725\r";
+        let message = V2Message::try_from(raw).unwrap();
+        let obx = OBX::from_message(&message, 1).unwrap();
+        assert_eq!(obx.observation_site().unwrap().unwrap().id().unwrap().as_str(), Some("LA"), "Wrong site in OBX-20!");
+        assert_eq!(obx.observation_instance_id().unwrap().unwrap().entity_id().unwrap().as_str(), Some("OBS1"), "Wrong id in OBX-21!");
+        assert_eq!(obx.mood_code().unwrap().unwrap().id().unwrap().as_str(), Some("EVN"), "Wrong mood in OBX-22!");
+        assert_eq!(obx.view().descriptor(20).unwrap().name, "observation_site", "Accessor and schema names disagree!");
+
+        let older = V2Message::try_from(raw.replace("|P|2.7\r", "|P|2.5.1\r").as_str()).unwrap();
+        let obx = OBX::from_message(&older, 1).unwrap();
+        assert_eq!(obx.view().descriptor(20).unwrap().name, "reserved_20", "OBX-20 was reserved in v2.5.1!");
+        assert!(obx.mood_code().is_err(), "OBX-22 does not exist in v2.5.1!");
+    }
+
+    #[test]
+    fn test_validate_message() {
+        let message = rumtk_v2_parse_message!(V2_TEST_SMALL_MESSAGE).unwrap();
+        let report = message.validate();
+        for issue in report.issues() {
+            println!("{}", issue);
+        }
+        assert!(report.at("PID-5").is_empty(), "PID-5 is valued and should not report issues!");
+        assert!(report.at("MSH-9").is_empty(), "MSH-9 is valid and should not report issues!");
+        assert!(
+            report.with_severity(V2Severity::Error).iter().all(|i| i.rule != V2ValidationRule::RequiredField),
+            "No required fields are missing in this message!"
+        );
+    }
+
+    #[test]
+    fn test_validate_segment_cardinality() {
+        let raw = "MSH|^~\\&|APP|FAC|||20250101120000||ADT^A01^ADT_A01|MSG0001|P|2.5.1\rPID|1||12345^^^HOSP^MR||DOE^JANE\rPID|2||67890^^^HOSP^MR||DOE^JOHN\r";
+        let message = rumtk_v2_parse_message!(raw).unwrap();
+        let report = message.validate();
+        for issue in report.issues() {
+            println!("{}", issue);
+        }
+
+        let extra = report.at("PID");
+        assert_eq!(extra.len(), 1, "ADT_A01 allows a single PID!");
+        assert_eq!(extra[0].rule, V2ValidationRule::SegmentCardinality, "Wrong rule for the extra PID!");
+        for segment_name in ["EVN", "PV1"] {
+            let missing = report.at(segment_name);
+            assert_eq!(missing.len(), 1, "Expected the missing {} to be reported!", segment_name);
+            assert_eq!(missing[0].rule, V2ValidationRule::SegmentCardinality, "Wrong rule for the missing {}!", segment_name);
+        }
+        assert!(report.at("NK1").is_empty(), "NK1 is optional in ADT_A01!");
+        assert!(report.at("MSH").is_empty(), "MSH is present once!");
+    }
+
+    #[test]
+    fn test_validate_message_reports_located_issues() {
+        let raw = "MSH|^~\\&|APP|FAC|||20250101120000||ADT^A01^ADT_A01|MSG0001|P|2.5.1\rPID|1~2||12345^^^HOSP||&Jr^Maria~Hernandez^Maria\r";
+        let message = rumtk_v2_parse_message!(raw).unwrap();
+        let report = message.validate();
+        for issue in report.issues() {
+            println!("{}", issue);
+        }
+        assert!(!report.is_valid(), "Message should not be valid!");
+
+        let repetition = report.at("PID-1");
+        assert_eq!(repetition.len(), 1, "Expected one issue for PID-1!");
+        assert_eq!(repetition[0].rule, V2ValidationRule::Repetition, "PID-1 does not repeat!");
+        assert_eq!(repetition[0].rule.id(), "REPETITION", "Wrong rule id!");
+
+        let missing_type = report.at("PID-3[1].5");
+        assert_eq!(missing_type.len(), 1, "Expected CX.5 to be flagged as missing!");
+        assert_eq!(missing_type[0].severity, V2Severity::Error, "Missing CX.5 is an error!");
+        assert_eq!(missing_type[0].rule, V2ValidationRule::RequiredComponent, "CX.5 is required!");
+
+        let name = report.at("PID-5");
+        assert_eq!(name.len(), 1, "Only the first PID-5 repetition is missing the surname!");
+        assert_eq!(name[0].location, "PID-5[1].1.1", "Wrong location for the missing surname!");
+    }
+
+    #[test]
+    fn test_hl7_tables() {
+        let address_type = get_table(190).unwrap();
+        assert_eq!(address_type.description("H"), Some("Home"), "Wrong description for address type H!");
+        let err = validate_table_value(190, "XYZ").unwrap_err();
+        println!("{}", err);
+        assert!(err.contains("0190") && err.contains("XYZ"), "Error should mention the table and the value!");
+
+        // Registered tables are global so use ids no other test relies on.
+        load_table_csv(
+            9101,
+            "Local Sex",
+            "# Local codes\ncode,description\nF,Female\nM,Male\n\"U\",\"Unknown, or not stated\"\nX,\"Other \"\"X\"\"\"\n",
+        )
+        .unwrap();
+        let sex = get_table(9101).unwrap();
+        assert_eq!(sex.len(), 4, "Expected four values loaded from CSV!");
+        assert!(!sex.contains("code"), "The header after a comment should have been skipped!");
+        assert_eq!(sex.description("U"), Some("Unknown, or not stated"), "Quoted commas should not split CSV values!");
+        assert_eq!(sex.description("X"), Some("Other \"X\""), "Escaped quotes should be unescaped!");
+        assert!(V2Table::from_csv(9102, "Broken", "A,\"Unterminated\n").is_err(), "Unterminated quotes should fail!");
+
+        let id = load_table_json(r#"{"id": 9103, "name": "Local Codes", "values": {"A1": "First", "B2": "Second"}}"#).unwrap();
+        assert_eq!(id, 9103, "Wrong table id loaded from JSON!");
+        assert!(validate_table_value(9103, "B2").is_ok(), "B2 is in the loaded table!");
+        assert!(validate_table_value(9103, "C3").is_err(), "C3 is not in the loaded table!");
+
+        let raw = "MSH|^~\\&|APP|FAC|||20250101120000||ADT^A01^ADT_A01|MSG0001|P|2.5.1|||XX\rPID|1||12345^^^HOSP^MR||Hernandez^Maria||19880906|X\r";
+        let message = rumtk_v2_parse_message!(raw).unwrap();
+        let report = message.validate();
+        assert!(report.at("PID-8").is_empty(), "Table 0001 is site defined and ships empty!");
+        let issues = report.at("MSH-15");
+        assert_eq!(issues.len(), 1, "Expected MSH-15 to be flagged!");
+        assert_eq!(issues[0].rule, V2ValidationRule::TableValue, "MSH-15 should fail the table check!");
+        assert_eq!(issues[0].location, "MSH-15[1]", "Wrong location for the table issue!");
+    }
+
+    #[test]
+    fn test_message_structure_tree() {
+        let raw = "MSH|^~\\&|LAB|FAC|||20250101120000||ORU^R01|MSG0001|P|2.5.1\rPID|1||12345^^^HOSP^MR||DOE^JANE\rORC|RE|ORD1\rOBR|1|ORD1||CBC^Complete Blood Count^L\rOBX|1|NM|WBC^White Cells^L||7.2\rNTE|1||Checked twice\rORC|RE|ORD2\rOBR|2|ORD2||BMP^Basic Metabolic Panel^L\rOBX|2|NM|NA^Sodium^L||140\rOBX|3|NM|K^Potassium^L||4.1\r";
+        let message = rumtk_v2_parse_message!(raw).unwrap();
+        assert_eq!(message_structure_id(&message).unwrap(), "ORU_R01", "ORU^R01 should map to ORU_R01!");
+
+        let order: Vec<u8> = message.segment_order().iter().map(|(id, _)| *id).collect();
+        let expected: Vec<u8> = ["MSH", "PID", "ORC", "OBR", "OBX", "NTE", "ORC", "OBR", "OBX", "OBX"]
+            .iter()
+            .map(|name| V2_SEGMENT_IDS(name.as_bytes()))
+            .collect();
+        assert_eq!(order, expected, "Segment order was not preserved!");
+
+        let tree = message.structure().unwrap();
+        println!("{:#?}", tree.issues());
+        assert!(tree.is_valid(), "Message should match the ORU_R01 structure!");
+
+        let patient_result = tree.group("PATIENT_RESULT").unwrap();
+        let pid = patient_result.group("PATIENT").unwrap().segment("PID").unwrap();
+        assert_eq!(pid.location(), "PID", "Wrong PID location!");
+
+        let orders = patient_result.groups("ORDER_OBSERVATION");
+        assert_eq!(orders.len(), 2, "Expected two ORDER_OBSERVATION groups!");
+        let first_observations = orders[0].groups("OBSERVATION");
+        assert_eq!(first_observations.len(), 1, "First order has one observation!");
+        assert!(first_observations[0].segment("NTE").is_some(), "NTE should belong to the first observation!");
+
+        let second_results: Vec<usize> = orders[1]
+            .groups("OBSERVATION")
+            .iter()
+            .map(|observation| observation.segment("OBX").unwrap().sub_segment())
+            .collect();
+        assert_eq!(second_results, vec![2, 3], "OBX(2) and OBX(3) belong to the second order!");
+
+        let segment_count = tree.all_segments().len();
+        assert_eq!(segment_count, 10, "Tree lost segments!");
+        match &tree.children()[0] {
+            V2StructureNode::Segment(msh) => assert_eq!(msh.name(), "MSH", "First node should be MSH!"),
+            V2StructureNode::Group(group) => panic!("First node should be MSH! Got group {}", group.name()),
+        }
+    }
+
+    #[test]
+    fn test_message_structure_issues() {
+        let raw = "MSH|^~\\&|LAB|FAC|||20250101120000||ORU^R01^ORU_R01|MSG0001|P|2.5.1\rPID|1||12345^^^HOSP^MR||DOE^JANE\rOBX|1|NM|WBC^White Cells^L||7.2\r";
+        let message = rumtk_v2_parse_message!(raw).unwrap();
+        let tree = message.structure().unwrap();
+        for issue in tree.issues().issues() {
+            println!("{}", issue);
+        }
+        assert!(!tree.is_valid(), "ORU_R01 without OBR should not be valid!");
+
+        let missing = tree.issues().at("ORU_R01/PATIENT_RESULT[1]/ORDER_OBSERVATION");
+        assert_eq!(missing.len(), 1, "Expected the missing ORDER_OBSERVATION group to be reported!");
+        assert_eq!(missing[0].rule, V2ValidationRule::SegmentCardinality, "Wrong rule for missing group!");
+
+        let unexpected = tree.issues().at("OBX");
+        assert_eq!(unexpected.len(), 1, "Expected the orphan OBX to be reported!");
+        assert_eq!(unexpected[0].rule, V2ValidationRule::MessageStructure, "Wrong rule for orphan OBX!");
+        assert_eq!(tree.all_segments().len(), 3, "Orphan segments should still be in the tree!");
+
+        let report = message.validate();
+        assert_eq!(report.at("OBX").len(), 1, "Structure issues should be part of message validation!");
+    }
+
+    #[test]
+    fn test_message_round_trip_is_byte_identical() {
+        let raw = "MSH|^~\\&#|LAB|FAC|||20250101120000||ORU^R01^ORU_R01|MSG0001|P|2.5.1\rPID|1||12345^^^HOSP^MR||DOE^JANE\rZPI|1|internal^id\rOBR|1||ORD1|CBC^Complete Blood Count^L\rOBX|1|NM|WBC^White Cells^L||7.2\rNTE|1||Fasting sample\rOBR|2||ORD2|BMP^Basic Metabolic Panel^L\rZLB|A~B|\rOBX|1|NM|NA^Sodium^L||140\rNTE|1||Repeated\rOBX|2|NM|K^Potassium^L||4.1\r";
+        let message = rumtk_v2_parse_message!(raw).unwrap();
+        assert_eq!(message.to_string(), raw, "Round trip did not preserve the original message!");
+
+        let zlb = message.get(V2_CUSTOM_SEGMENT_ID, 2).unwrap();
+        assert_eq!(zlb.custom_name(), Some("ZLB"), "Z segment name was lost!");
+
+        let tree = message.structure().unwrap();
+        let orders = tree.group("PATIENT_RESULT").unwrap().groups("ORDER_OBSERVATION");
+        assert!(orders[1].segment("ZLB").is_some(), "ZLB should stay with the second order!");
+
+        let unterminated = &raw[..raw.len() - 1];
+        let message = rumtk_v2_parse_message!(unterminated).unwrap();
+        assert_eq!(message.to_string(), unterminated, "Round trip added a trailing terminator!");
+
+        let reparsed = rumtk_v2_parse_message!(&message.to_string()).unwrap();
+        assert_eq!(reparsed, message, "Reparsed message differs from the original!");
+
+        for terminator in ["\n", "\r\n"] {
+            let raw = raw.replace('\r', terminator);
+            let message = rumtk_v2_parse_message!(&raw).unwrap();
+            assert_eq!(message.to_string(), raw, "Round trip did not preserve the {:?} terminators!", terminator);
+        }
+    }
+
+    #[test]
+    fn test_custom_segments() {
+        static ZPV: V2CustomSegment = V2CustomSegment::new(
+            "ZPV",
+            "Patient Visit Extension",
+            &[
+                v2_component_descriptor!("set_id", "Set ID - ZPV", V2ComponentType::Primitive(V2PrimitiveType::SI), 4, 1, 0, Optionality::R, false),
+                v2_component_descriptor!("visit_reason", "Visit Reason", V2ComponentType::Complex(V2ComplexType::CWE), 0, 2, 0, Optionality::O, false),
+                v2_component_descriptor!("referring_site", "Referring Site", V2ComponentType::Complex(V2ComplexType::HD), 0, 3, 0, Optionality::O, false),
+            ],
+            &[(2, 0)],
+        );
+        register_custom_segment(&ZPV).unwrap();
+        assert!(register_custom_segment(&V2CustomSegment::new("PID", "", &[], &[])).is_err(), "Standard segments cannot be overridden!");
+        assert!(register_custom_segment(&V2CustomSegment::new("zp1", "", &[], &[])).is_err(), "Malformed name was accepted!");
+
+        let raw = "MSH|^~\\&|ADT1|FAC|||20250101120000||ADT^A01^ADT_A01|MSG0001|P|2.5.1\rEVN|A01|20250101120000\rPID|1||12345^^^HOSP^MR||DOE^JANE\rZPV|1|CHK^Checkup^L~FLU^Flu Shot^L|NORTH\rZXX|unregistered\rPV1|1|O\rZPV||INJ^Injury^L|SOUTH\r";
+        let message = rumtk_v2_parse_message!(raw).unwrap();
+        assert_eq!(message.to_string(), raw, "Custom segments did not survive a round trip!");
+        assert_eq!(message.named_segment_count("ZPV"), 2, "Wrong ZPV count!");
+
+        let site = rumtk_v2_find_component!(message, "ZPV-3.1").unwrap();
+        assert_eq!(site.as_str(), "NORTH", "Wrong component found in ZPV-3.1!");
+        let site = rumtk_v2_find_component!(message, "ZPV(2)3.1").unwrap();
+        assert_eq!(site.as_str(), "SOUTH", "Wrong component found in ZPV(2)3.1!");
+        let unregistered = rumtk_v2_find_component!(message, "ZXX1.1").unwrap();
+        assert_eq!(unregistered.as_str(), "unregistered", "Unregistered Z segments should still be searchable!");
+
+        let zpv = V2SegmentView::from_message("ZPV", &message, 1).unwrap();
+        let reasons = zpv.get_repetitions(2);
+        assert_eq!(reasons.len(), 2, "Expected two visit reasons!");
+        let second_reason = reasons[1].as_complex().unwrap().get("text").unwrap();
+        assert_eq!(second_reason.as_str(), Some("Flu Shot"), "Wrong visit reason!");
+
+        let report = message.validate();
+        assert!(report.at("ZPV-1").is_empty(), "First ZPV is valid!");
+        let missing = report.at("ZPV(2)-1");
+        assert_eq!(missing.len(), 1, "Expected the missing ZPV(2)-1 to be reported!");
+        assert_eq!(missing[0].rule, V2ValidationRule::RequiredField, "Wrong rule for missing set id!");
+        assert!(report.at("ZXX").is_empty(), "Unregistered Z segments are not validated!");
+    }
+
+    #[test]
+    fn test_ack_generation() {
+        let raw = "MSH|^~\\&|ADT1|HOSP|LAB|LABFAC|20250101120000||ADT^A01^ADT_A01|MSG0001|P|2.5.1\rEVN|A01|20250101120000\rPID|1||12345^^^HOSP^MR||DOE^JANE\rPV1|1|I\r";
+        let message = V2Message::try_from(raw).unwrap();
+        let ack = message.ack().unwrap();
+        let msh = MSH::from_message(&ack, 1).unwrap();
+        let msa = MSA::from_message(&ack, 1).unwrap();
+        let msh_field = |seq: isize| ack.get(V2_MSHEADER_ID, 1).unwrap().get(seq).unwrap()[0].to_string(ack.parser_chars());
+        assert_eq!(msh_field(2), "LAB", "MSH-3 should be the inbound receiving application!");
+        assert_eq!(msh_field(3), "LABFAC", "MSH-4 should be the inbound receiving facility!");
+        assert_eq!(msh_field(4), "ADT1", "MSH-5 should be the inbound sending application!");
+        assert_eq!(msh_field(5), "HOSP", "MSH-6 should be the inbound sending facility!");
+        assert_eq!(msh_field(8), "ACK^A01^ACK", "Wrong ACK message type!");
+        assert_ne!(msh_field(9), "MSG0001", "ACK needs its own control id!");
+        assert!(!msh.message_datetime().is_err(), "Invalid ACK timestamp!");
+        assert_eq!(msa.ack_code().as_str(), Some("AA"), "Valid message should be accepted!");
+        assert_eq!(msa.message_control_id().as_str(), Some("MSG0001"), "MSA-2 does not echo MSH-10!");
+        assert_eq!(ack.named_segment_count("ERR"), 0, "Valid message should not carry errors!");
+        assert!(is_ack_message(&ack), "Generated message is not an ACK!");
+
+        let raw = "MSH|^~\\&|ADT1|HOSP|LAB|LABFAC|20250101120000||ADT^A01^ADT_A01|MSG0002|P|2.5.1|||AL|NE\rEVN|A01|20250101120000\rPID|1||12345^^^HOSP^MR\rPV1|1|I\r";
+        let message = V2Message::try_from(raw).unwrap();
+        let ack = message.ack().unwrap();
+        assert_eq!(ack_code(&ack), Some(V2AckCode::CE), "Enhanced mode requested so we expected a commit error!");
+        let generated = ack.to_string();
+        assert!(
+            generated.contains("\rERR||PID^1^5|101^Required field missing^HL70357|E|||"),
+            "Missing ERR for PID-5 => {}",
+            generated
+        );
+        let err = ERR::from_message(&ack, 1).unwrap();
+        assert_eq!(err.severity().as_str(), Some("E"), "Wrong ERR-4 severity!");
+
+        let raw = "MSH|^~\\&|ADT1|HOSP|LAB|LABFAC|20250101120000||ADT^A01|MSG0003|P|2.3\rEVN|A01|20250101120000\rPID|1||12345^^^HOSP^MR\rPV1|1|I\r";
+        let message = V2Message::try_from(raw).unwrap();
+        let ack = generate_ack(&message, V2AckCode::AR, Some(&message.validate())).unwrap();
+        let generated = ack.to_string();
+        assert!(generated.contains("MSA|AR|MSG0003"), "Wrong MSA => {}", generated);
+        assert!(
+            generated.contains("\rERR|PID^1^5^101&Required field missing&HL70357"),
+            "Versions before 2.5 report errors in ERR-1 => {}",
+            generated
+        );
+    }
+
+    #[test]
+    fn test_ack_response() {
+        let raw = "MSH|^~\\&|ADT1|HOSP|LAB|LABFAC|20250101120000||ADT^A01^ADT_A01|MSG0002|P|2.5.1\rEVN|A01|20250101120000\rPID|1||12345^^^HOSP^MR\rPV1|1|I\r";
+        let message = V2Message::try_from(raw).unwrap();
+        let ack = message.ack().unwrap();
+        let response = V2AckResponse::from_message(&ack).unwrap();
+        assert_eq!(response.code, V2AckCode::AE, "Missing PID-5 should be an application error!");
+        assert!(response.acknowledges("MSG0002"), "MSA-2 should match the inbound MSH-10!");
+        assert!(!response.acknowledges("MSG0001"), "MSA-2 should not match other control ids!");
+        let error = response.errors.iter().find(|e| e.location == "PID-5").unwrap();
+        assert_eq!(error.code, "101", "Wrong ERR-3 code!");
+        assert_eq!(error.description, "Required field missing", "Wrong ERR-3 text!");
+        assert_eq!(error.severity, V2Severity::Error, "Wrong ERR-4 severity!");
+        match response.outcome() {
+            V2AckOutcome::Error(response) => assert!(!response.errors.is_empty(), "Lost the ERR details!"),
+            outcome => panic!("Expected an error outcome! Got {:?}", outcome),
+        }
+
+        let raw = "MSH|^~\\&|ADT1|HOSP|LAB|LABFAC|20250101120000||ADT^A01|MSG0003|P|2.3\rEVN|A01|20250101120000\rPID|1||12345^^^HOSP^MR\rPV1|1|I\r";
+        let message = V2Message::try_from(raw).unwrap();
+        let ack = generate_ack(&message, V2AckCode::CR, Some(&message.validate())).unwrap();
+        let outcome = V2AckResponse::from_message(&ack).unwrap().outcome();
+        assert!(matches!(outcome, V2AckOutcome::Rejected(_)), "CR should be a rejection!");
+        let error = outcome.response().errors.iter().find(|e| e.location == "PID-5").unwrap();
+        assert_eq!(error.code, "101", "Wrong ERR-1 code!");
+        assert_eq!(error.severity, V2Severity::Error, "ERR-1 errors carry no severity!");
+        assert!(V2AckResponse::from_message(&message).is_err(), "An ADT is not an acknowledgement!");
+    }
+
+    #[test]
+    fn test_message_builder() {
+        let mut builder = V2MessageBuilder::new();
+        builder
+            .set("MSH-9.1", "ORU").unwrap()
+            .set("MSH-9.2", "R01").unwrap()
+            .set("MSH-10", "MSG0001").unwrap()
+            .set("MSH-12", "2.5.1").unwrap()
+            .set("PID-3[1].1", "12345").unwrap()
+            .set("PID-3[1].4.1", "HOSP").unwrap()
+            .set("PID-3[1].5", "MR").unwrap()
+            .push_repetition("PID-3", "999-99-9999").unwrap()
+            .set("PID-5.1", "DOE|SMITH").unwrap()
+            .set("OBX-5", "7.2").unwrap()
+            .set("OBX(2)-5", "140").unwrap();
+        assert!(builder.set("MSH-2", "^~").is_err(), "MSH-2 comes from the parser characters!");
+        assert!(builder.set("OBX(4)-5", "1").is_err(), "OBX(3) does not exist yet!");
+        assert!(builder.set("PID-0", "1").is_err(), "Fields are 1-indexed!");
+        assert!(builder.set("pid-1", "1").is_err(), "Segment names are uppercase!");
+
+        let message = builder.build().unwrap();
+        let pid = message.get_named("PID", 1).unwrap();
+        let generated = message.to_string();
+        assert_eq!(
+            pid.to_string(message.parser_chars()),
+            "||12345^^^HOSP^MR~999-99-9999||DOE\\F\\SMITH",
+            "Wrong PID generated!"
+        );
+        assert!(generated.contains("|ORU^R01|MSG0001||2.5.1\r"), "Wrong MSH => {}", generated);
+        assert!(generated.ends_with("\rOBX|||||7.2\rOBX|||||140\r"), "Wrong OBX => {}", generated);
+        let msh = MSH::from_message(&message, 1).unwrap();
+        assert!(!msh.message_datetime().is_err(), "Builder should timestamp MSH-7!");
+        assert_eq!(rumtk_v2_find_component!(message, "PID3[2].1").unwrap().as_str(), "999-99-9999", "Wrong repetition!");
+
+        let mut parser_chars = V2ParserCharacters::new();
+        parser_chars.field_separator = b'!';
+        let mut builder = V2MessageBuilder::from_parser_chars(parser_chars);
+        builder.set_raw("MSH-9", "ADT^A01^ADT_A01").unwrap();
+        let message = builder.build().unwrap();
+        assert_eq!(message.parser_chars().field_separator, b'!', "Custom field separator was not used!");
+        assert!(message.to_string().contains("!ADT^A01^ADT_A01!"), "Raw value should not be escaped!");
+    }
+
+    #[test]
+    fn test_message_editing() {
+        let raw = "MSH|^~\\&|LAB|FAC|||20250101120000||ORU^R01^ORU_R01|MSG0001|P|2.5.1\rPID|1||12345^^^HOSP^MR||DOE^JANE\rOBR|1||ORD1|CBC^Complete Blood Count^L\rOBX|1|NM|WBC^White Cells^L||7.2\rZPD|1|custom\rOBX|2|NM|RBC^Red Cells^L||4.5\r";
+        let mut message = V2Message::try_from(raw).unwrap();
+
+        message.set("OBX(2)5", "4.6").unwrap();
+        message.set("PID5.1", "DOE^SMITH").unwrap();
+        assert_eq!(rumtk_v2_find_component!(message, "PID5.1").unwrap().as_str(), "DOE\\S\\SMITH", "Value was not escaped!");
+        message.set("OBX(3)5", "140").unwrap();
+        assert_eq!(message.segment_group_count(V2_SEGMENT_IDS("OBX".as_bytes())), 3, "OBX(3) should have been appended!");
+        assert!(message.set("OBX(5)5", "1").is_err(), "OBX(4) does not exist!");
+        assert!(message.set("MSH1", "^~").is_err(), "Encoding characters cannot be edited!");
+        assert!(message.set("PID0", "1").is_err(), "Fields are 1-indexed!");
+
+        let instance = message.insert_segment_after("OBX", "NTE").unwrap();
+        assert_eq!(instance, 1, "First NTE in the message!");
+        message.set("NTE1", "1").unwrap();
+        let instance = message.insert_segment_after("ZPD", "ZPD").unwrap();
+        assert_eq!(instance, 2, "Second ZPD in the message!");
+        message.set("ZPD(2)2", "more").unwrap();
+
+        message.clear_field("OBR4").unwrap();
+        message.clear_field("PID30").unwrap();
+        let removed = message.remove_segment("OBX(2)").unwrap();
+        assert_eq!(removed.to_string(message.parser_chars()), "2|NM|RBC^Red Cells^L||4.6", "Wrong segment removed!");
+        assert!(message.remove_segment("MSH").is_err(), "MSH cannot be removed!");
+        assert!(message.remove_segment("NTE(2)").is_err(), "There is a single NTE!");
+
+        assert_eq!(
+            message.to_string(),
+            "MSH|^~\\&|LAB|FAC|||20250101120000||ORU^R01^ORU_R01|MSG0001|P|2.5.1\rPID|1||12345^^^HOSP^MR||DOE\\S\\SMITH^JANE\rOBR|1||ORD1|\rOBX|1|NM|WBC^White Cells^L||7.2\rNTE|1\rZPD|1|custom\rZPD||more\rOBX|||||140\r",
+            "Edited message is wrong!"
+        );
+        assert_eq!(
+            rumtk_v2_find_component!(message, "OBX(2)5").unwrap().as_str(),
+            "140",
+            "Appended OBX should now be the second one!"
+        );
+
+        let mut message = V2Message::try_from(raw).unwrap();
+        message.insert_repetition("PID3[1]", "999-99-9999").unwrap();
+        message.insert_repetition("PID6", "MAIDEN").unwrap();
+        assert!(message.insert_repetition("PID3[5]", "X").is_err(), "Repetition 4 does not exist!");
+        let pid = message.get_named("PID", 1).unwrap();
+        assert_eq!(
+            pid.to_string(message.parser_chars()),
+            "1||999-99-9999~12345^^^HOSP^MR||DOE^JANE|MAIDEN",
+            "Wrong repetitions!"
+        );
+    }
+
+    #[test]
+    fn test_extended_search() {
+        let raw = "MSH|^~\\&|LAB|FAC|||20250101120000||ORU^R01^ORU_R01|MSG0001|P|2.5.1\rPID|1||12345^^^HOSP&1.2.3&ISO^MR~999^^^SSA&2.16.840&ISO^SS\rOBR|1||ORD1|CHEM^Chemistry^L\rOBX|1|NM|GLU^Glucose^L||95\rOBX|2|NM|NA^Sodium^L||140\rOBX|3|NM|GLU^Glucose^L||101\rOBX|4|ST|NOTE^Comment^L\r";
+        let message = V2Message::try_from(raw).unwrap();
+
+        let expr = V2SearchExpression::parse("OBX[OBX-3.1='GLU']-5").unwrap();
+        assert_eq!(expr.segment_group, V2SearchSelector::All, "Predicates should select every instance!");
+        assert_eq!(expr.predicates.len(), 1, "Expected a single predicate!");
+
+        let values: RUMVec<&str> = message.find_all("OBX(*)-5").unwrap().iter().map(|found| found.value).collect();
+        assert_eq!(values, vec!["95", "140", "101"], "OBX(4) has no OBX-5!");
+
+        let glucose = message.find_all("OBX[OBX-3.1='GLU']-5").unwrap();
+        let locations: RUMVec<&str> = glucose.iter().map(|found| found.location.as_str()).collect();
+        assert_eq!(locations, vec!["OBX(1)-5[1].1", "OBX(3)-5[1].1"], "Wrong glucose locations!");
+
+        let others = message.find_all("OBX[3.1!=\"GLU\"]-3.2").unwrap();
+        let values: RUMVec<&str> = others.iter().map(|found| found.value).collect();
+        assert_eq!(values, vec!["Sodium", "Comment"], "Wrong non glucose results!");
+
+        assert_eq!(message.find_value("PID-3.4.2").unwrap(), "1.2.3", "Wrong subcomponent!");
+        let authorities: RUMVec<&str> = message.find_all("PID-3[*].4.1").unwrap().iter().map(|found| found.value).collect();
+        assert_eq!(authorities, vec!["HOSP", "SSA"], "Expected both assigning authorities!");
+        assert!(message.find_component("PID-3.4.2").is_err(), "Subcomponents are not components!");
+
+        assert_eq!(
+            message.find_component("OBX[OBX-3.1='NA']-5").unwrap().as_str(),
+            "140",
+            "Extended expressions should work with find_component!"
+        );
+        assert!(message.find_all("OBX[OBX-3.1='K']-5").unwrap().is_empty(), "Nothing should match!");
+        assert!(message.find_value("OBX[OBX-3.1='K']-5").is_err(), "Nothing should match!");
+        assert!(message.find_all("OBX[OBX-3.1='GLU'-5").is_err(), "Unterminated predicate!");
+        assert!(message.find_all("OBX(0)-5").is_err(), "Instances are 1-indexed!");
+        assert!(message.find_all("OBX(1)-0").is_err(), "Fields are 1-indexed!");
+
+        assert_eq!(V2SearchExpression::parse("OBX(2)-5.1.1").unwrap().path.field, 5, "Dash after the segment is HL7 notation!");
+        assert_eq!(V2SearchExpression::parse("OBX(2)--5.1.1").unwrap().path.field, -5, "A second dash is a sign!");
+        let last = message.find_all("OBX(*)--1").unwrap();
+        let values: RUMVec<&str> = last.iter().map(|found| found.value).collect();
+        assert_eq!(values, vec!["95", "140", "101", "NOTE"], "Negative fields should count from the end!");
+        assert_eq!(last[3].location, "OBX(4)-3[1].1", "Locations should name the resolved field!");
+        assert!(message.find_all("OBX(4)--4").unwrap().is_empty(), "OBX(4) has only 3 fields!");
+    }
+
+    #[test]
+    fn test_subcomponents() {
+        let raw = "MSH|^~\\&|LAB|FAC|||20250101120000||ORU^R01^ORU_R01|MSG0001|P|2.5.1\rPID|1||12345^^^HOSP&1.2.3&ISO^MR||DOE^JANE\rPV1|1|I|||||1234^SMITH^JOHN^^^^^^NPI&2.16.840.1.113883.4.6&ISO\r";
+        let mut message = V2Message::try_from(raw).unwrap();
+        let sep = message.parser_chars().clone();
+
+        let authority = rumtk_v2_find_component!(message, "PID3.4").unwrap();
+        assert_eq!(authority.subcomponents(&sep), vec!["HOSP", "1.2.3", "ISO"], "Wrong CX-4 subcomponents!");
+        assert_eq!(authority.subcomponent_count(&sep), 3, "Wrong subcomponent count!");
+        assert_eq!(authority.get_subcomponent(2, &sep).unwrap(), "1.2.3", "Wrong universal id!");
+        assert_eq!(authority.get_subcomponent(-1, &sep).unwrap(), "ISO", "Negative indices count from the end!");
+        assert!(authority.get_subcomponent(4, &sep).is_err(), "There are only 3 subcomponents!");
+        assert!(authority.get_subcomponent(0, &sep).is_err(), "Subcomponents are 1-indexed!");
+        let name = rumtk_v2_find_component!(message, "PID5.1").unwrap();
+        assert_eq!(name.subcomponents(&sep), vec!["DOE"], "A plain component is its own subcomponent!");
+
+        assert_eq!(message.find_value("PV1-7.9.2").unwrap(), "2.16.840.1.113883.4.6", "Wrong XCN-9 universal id!");
+
+        let component = message.find_component_mut("PID3.4").unwrap();
+        component.set_subcomponent(3, "L", &sep).unwrap();
+        component.set_subcomponent(5, "X", &sep).unwrap();
+        assert_eq!(component.as_str(), "HOSP&1.2.3&L&&X", "Wrong padded subcomponents!");
+        assert!(message.find_component_mut("PID3.4.1").is_err(), "Subcomponents are not components!");
+
+        message.set("PID3.4.2", "2.16.840").unwrap();
+        message.set("PID3[2].4.3", "A&B").unwrap();
+        assert_eq!(
+            message.get_named("PID", 1).unwrap().to_string(&sep),
+            "1||12345^^^HOSP&2.16.840&L&&X^MR~^^^&&A\\T\\B||DOE^JANE",
+            "Wrong subcomponent edits!"
+        );
+        assert!(message.set("PID3.4.0", "1").is_err(), "Subcomponents are 1-indexed!");
+    }
+
+    #[test]
+    fn test_escape_codec() {
+        let sep = V2ParserCharacters::new();
+        let raw = "\\H\\BOLD\\N\\ 5\\S\\6\\.sp2\\\\.in+4\\x\\.sk3\\y\\X48c3a9\\\\C2842\\\\M2442\\\\Zfoo\\ \\Q\\ trailing\\";
+        let text = V2EscapedText::parse(raw, &sep);
+        assert_eq!(text.encode(), raw, "Escaped text must round trip byte for byte!");
+        assert_eq!(
+            text.tokens(),
+            &[
+                V2EscapeSequence::Formatting(V2FormattingCommand::Highlight),
+                V2EscapeSequence::Text(RUMString::from("BOLD")),
+                V2EscapeSequence::Formatting(V2FormattingCommand::Normal),
+                V2EscapeSequence::Text(RUMString::from(" 5")),
+                V2EscapeSequence::Delimiter('^'),
+                V2EscapeSequence::Text(RUMString::from("6")),
+                V2EscapeSequence::Formatting(V2FormattingCommand::Space(Some(2))),
+                V2EscapeSequence::Formatting(V2FormattingCommand::Indent(4)),
+                V2EscapeSequence::Text(RUMString::from("x")),
+                V2EscapeSequence::Formatting(V2FormattingCommand::Skip(Some(3))),
+                V2EscapeSequence::Text(RUMString::from("y")),
+                V2EscapeSequence::Hex(vec![0x48, 0xc3, 0xa9]),
+                V2EscapeSequence::CharacterSet(vec![0x28, 0x42]),
+                V2EscapeSequence::CharacterSet(vec![0x24, 0x42]),
+                V2EscapeSequence::Local(RUMString::from("foo")),
+                V2EscapeSequence::Text(RUMString::from(" ")),
+                V2EscapeSequence::Raw(RUMString::from("\\Q\\")),
+                V2EscapeSequence::Text(RUMString::from(" trailing")),
+                V2EscapeSequence::Raw(RUMString::from("\\")),
+            ],
+            "Wrong tokens!"
+        );
+
+        let options = V2EscapeOptions {
+            hex_encoding: Some(RUMString::from("UTF-8")),
+            ..Default::default()
+        };
+        assert_eq!(
+            text.render(&options),
+            "BOLD 5^6\n\nx   yH\u{e9}\\C2842\\\\M2442\\\\Zfoo\\ \\Q\\ trailing\\",
+            "Wrong default rendering!"
+        );
+
+        fn upper(payload: &str) -> RUMString {
+            payload.to_uppercase()
+        }
+        let options = V2EscapeOptions {
+            delimiters: V2EscapeHandling::Preserve,
+            formatting: V2EscapeHandling::Strip,
+            hex: V2EscapeHandling::Strip,
+            character_set: V2EscapeHandling::Decode,
+            local: V2EscapeHandling::Decode,
+            hex_encoding: None,
+            local_decoder: Some(upper),
+        };
+        assert_eq!(
+            text.render(&options),
+            "BOLD 5\\S\\6xy\u{1b}(B\u{1b}$BFOO \\Q\\ trailing\\",
+            "Wrong custom rendering!"
+        );
+
+        let plain = "a|b^c&d~e\\f";
+        let escaped = V2EscapedText::from_text(plain, &sep);
+        assert_eq!(escaped.encode(), "a\\F\\b\\S\\c\\T\\d\\R\\e\\E\\f", "Wrong escaping!");
+        assert_eq!(escaped.encode(), sep.escape(plain), "Both escaping paths should agree!");
+        assert_eq!(v2_unescape(&escaped.encode(), &sep), plain, "Unescaping should undo escaping!");
+
+        let raw = "MSH|^~\\&|LAB|FAC|||20250101120000||ORU^R01^ORU_R01|MSG0001|P|2.5.1\rOBX|1|FT|NOTE^Note^L||Line one\\.br\\Line \\T\\ two\\Zx\\\r";
+        let mut message = V2Message::try_from(raw).unwrap();
+        let note = rumtk_v2_find_component!(message, "OBX5").unwrap();
+        assert_eq!(note.text(message.parser_chars()), "Line one\nLine & two\\Zx\\", "Wrong component text!");
+        assert_eq!(message.to_string(), raw, "Untouched escapes must survive serialization!");
+
+        let sep = message.parser_chars().clone();
+        let note = message.find_component_mut("OBX5").unwrap();
+        let mut text = note.escaped_text(&sep);
+        text.push_token(V2EscapeSequence::Formatting(V2FormattingCommand::Break), &sep);
+        text.push_text("A|B", &sep);
+        note.set_escaped_text(&text);
+        assert_eq!(note.as_str(), "Line one\\.br\\Line \\T\\ two\\Zx\\\\.br\\A\\F\\B", "Wrong edited text!");
+        note.set_text("x^y", &sep);
+        assert_eq!(note.as_str(), "x\\S\\y", "Wrong plain text!");
+    }
+
+    #[test]
+    fn test_message_charset() {
+        let header = "MSH|^~\\&|APP|FAC|||20250101120000||ADT^A01^ADT_A01|MSG0001|P|2.5.1||||||";
+        let latin1 = [format!("{}8859/1~UNICODE UTF-8\r", header).as_bytes(), b"PID|1||12345||Mu\xf1oz^Jos\xe9\r"].concat();
+        assert_eq!(v2_declared_charset(&latin1), Some(RUMString::from("8859/1")), "Only the first repetition counts!");
+
+        let message = V2Message::try_from(&latin1[..]).unwrap();
+        assert_eq!(message.encoding().source, V2EncodingSource::Declared, "MSH-18 should have been honored!");
+        assert_eq!(message.encoding().encoding, "iso-8859-1", "Wrong encoding!");
+        assert_eq!(message.find_value("PID-5.1").unwrap(), "Muñoz", "Latin-1 was not decoded!");
+        assert_eq!(message.to_bytes().unwrap(), latin1, "Message should encode back to Latin-1!");
+
+        let mut edited = message.clone();
+        // MSH field numbers in search patterns are offset by one since MSH-1 is not stored.
+        edited.set("MSH17", "UNICODE UTF-8").unwrap();
+        assert_eq!(edited.find_value("MSH-17").unwrap(), "UNICODE UTF-8", "MSH-18 was not edited!");
+        assert_eq!(
+            edited.to_bytes().unwrap(),
+            edited.to_string().into_bytes(),
+            "Edited MSH-18 should switch the message to UTF-8!"
+        );
+
+        let utf8 = format!("{}UNICODE UTF-8\rPID|1||12345||Muñoz^José\r", header);
+        let message = V2Message::try_from(utf8.as_bytes()).unwrap();
+        assert_eq!(message.encoding().source, V2EncodingSource::Declared, "MSH-18 should have been honored!");
+        assert_eq!(message.find_value("PID-5.2").unwrap(), "José", "UTF-8 was not decoded!");
+
+        let (_, encoding) = v2_decode_message(DEFAULT_HL7_V2_MESSAGE.as_bytes()).unwrap();
+        assert_eq!(encoding.source, V2EncodingSource::Default, "UTF-8 without MSH-18 needs no guessing!");
+
+        let undeclared = [format!("{}\r", header).as_bytes(), b"PID|1||12345||Mu\xf1oz^Jos\xe9\r"].concat();
+        let message = V2Message::try_from(&undeclared[..]).unwrap();
+        assert_eq!(message.encoding().source, V2EncodingSource::Guessed, "Invalid UTF-8 without MSH-18 must be guessed!");
+        assert_eq!(message.to_bytes().unwrap(), undeclared, "Guessed encoding should round trip!");
+
+        let text = V2Message::try_from(format!("{}8859/1\rPID|1||12345||José\r", header).as_str()).unwrap();
+        assert_eq!(text.find_value("PID-5").unwrap(), "José", "Decoded text must not be decoded again!");
+        assert_eq!(text.encoding().encoding, "iso-8859-1", "MSH-18 should still be recorded!");
+
+        for charset in ["UNICODE UTF-16", "UNICODE UTF-32", "ISO IR14", "ISO IR13"] {
+            assert_eq!(V2_CHARSET_ENCODINGS(charset), None, "{} cannot be decoded faithfully!", charset);
+            let declared = [format!("{}{}\r", header, charset).as_bytes(), b"PID|1||12345||Mu\xf1oz^Jos\xe9\r"].concat();
+            let message = V2Message::try_from(&declared[..]).unwrap();
+            assert_eq!(message.encoding().source, V2EncodingSource::Guessed, "{} should not be honored!", charset);
+        }
+    }
+
+    #[test]
+    fn test_batch_roundtrip() {
+        let message = V2Message::try_from(DEFAULT_HL7_V2_MESSAGE).unwrap();
+        let mut writer = V2BatchWriter::new(Vec::new());
+        let mut file_header = V2BatchSegment::new_header("FHS", writer.parser_chars());
+        file_header.set_field(3, "LAB").unwrap();
+        file_header.set_field(11, "FILE0001").unwrap();
+        let mut batch_header = V2BatchSegment::new_header("BHS", writer.parser_chars());
+        batch_header.set_field(11, "BATCH0001").unwrap();
+        writer.begin_file(&file_header).unwrap();
+        writer.begin_batch(&batch_header).unwrap();
+        writer.write_message(&message).unwrap();
+        writer.write_message(&message).unwrap();
+        writer.end_batch().unwrap();
+        writer.begin_batch(&V2BatchSegment::new_header("BHS", writer.parser_chars())).unwrap();
+        writer.write_message(&message).unwrap();
+        writer.end_batch().unwrap();
+        writer.end_file().unwrap();
+        let raw = writer.into_inner();
+        assert!(raw.starts_with(b"FHS|^~\\&|LAB|"), "FHS was not written with its separators!");
+
+        let mut reader = V2BatchReader::new(raw.as_slice());
+        let messages: Vec<_> = reader.by_ref().map(|entry| entry.unwrap()).collect();
+        assert_eq!(messages.len(), 3, "Expected three messages across both batches!");
+        assert_eq!(messages.iter().map(|m| m.batch).collect::<Vec<_>>(), vec![1, 1, 2], "Messages landed in the wrong batch!");
+        assert_eq!(messages[0].message, message, "Message did not survive the round trip!");
+        assert_eq!(messages[1].batch_header.as_ref().unwrap().control_id(), "BATCH0001", "Batch header was not attached!");
+        assert_eq!(reader.file_header().unwrap().sending_application(), "LAB", "FHS-3 was not read!");
+        assert_eq!(reader.file_header().unwrap().control_id(), "FILE0001", "FHS-11 was not read!");
+        assert_eq!(reader.file_trailer().unwrap().message_count(), Some(2), "FTS-1 should count the batches!");
+        assert_eq!(reader.batch_count(), 2, "Expected two batches!");
+        assert_eq!(reader.message_count(), 3, "Expected three messages!");
+
+        let unix = RUMString::from_utf8(raw.clone()).unwrap().replace('\r', "\n");
+        let reader = V2BatchReader::from_buffer(RUMBuffer::from(unix.as_bytes()));
+        assert_eq!(reader.filter(|entry| entry.is_ok()).count(), 3, "Newline terminated batches should parse!");
+
+        let unbatched = [DEFAULT_HL7_V2_MESSAGE, DEFAULT_HL7_V2_MESSAGE, "FTS|1"].join("\r");
+        let reader = V2BatchReader::new(unbatched.as_bytes());
+        let entries: Vec<_> = reader.collect();
+        assert_eq!(entries.len(), 2, "Messages without BHS should still be read!");
+        assert!(entries.iter().all(|entry| entry.as_ref().unwrap().batch == 0), "Unbatched messages belong to batch 0!");
+
+        let trailing = RUMString::from_utf8(raw.clone()).unwrap().replacen("FTS|2", DEFAULT_HL7_V2_MESSAGE, 1);
+        let messages: Vec<_> = V2BatchReader::new(trailing.as_bytes()).map(|entry| entry.unwrap()).collect();
+        assert_eq!(messages.iter().map(|m| m.batch).collect::<Vec<_>>(), vec![1, 1, 2, 0], "A message after BTS is outside of any batch!");
+        assert!(messages[3].batch_header.is_none(), "A message after BTS has no batch header!");
+
+        let wrong_count = RUMString::from_utf8(raw).unwrap().replacen("BTS|2", "BTS|5", 1);
+        let results: Vec<_> = V2BatchReader::new(wrong_count.as_bytes()).collect();
+        assert_eq!(results.iter().filter(|entry| entry.is_ok()).count(), 3, "Count mismatch should not drop messages!");
+        let error = results.iter().find_map(|entry| entry.as_ref().err()).expect("BTS-1 mismatch was not reported!");
+        assert!(error.contains("BTS-1"), "Error should name the trailer! Got: {}", error);
+    }
+
+    #[test]
+    fn test_lazy_message() {
+        let eager = V2Message::try_from(DEFAULT_HL7_V2_MESSAGE).unwrap();
+        let lazy = V2LazyMessage::try_from(DEFAULT_HL7_V2_MESSAGE).unwrap();
+        assert_eq!(lazy.materialized(), 0, "Nothing should be parsed until it is touched!");
+        assert_eq!(lazy.segments().len(), 5, "Expected every segment to be indexed!");
+
+        for pattern in ["MSH1", "MSH2.1", "MSH8.3", "PID3[2].1", "PID5.2", "PID-1.1", "NK1(1)2.1", "PV1-3.1"] {
+            assert_eq!(
+                lazy.find_component(pattern).unwrap(),
+                eager.find_component(pattern).unwrap(),
+                "Lazy and eager parsers disagree on {}!",
+                pattern
+            );
+        }
+        assert_eq!(lazy.materialized(), 8, "Only the fields we looked at should have been parsed!");
+        assert_eq!(lazy.get_named("PID", 1).unwrap().materialized(), 3, "Expected PID-1, PID-3 and PID-5!");
+        assert_eq!(lazy.find_value("PID3.1").unwrap(), "PATID1234", "Wrong patient id!");
+        assert!(lazy.find_component("OBX5").is_err(), "There is no OBX in this message!");
+        assert!(lazy.find_component("PID(*)3").is_err(), "Extended expressions need the full message!");
+
+        let pid = lazy.get_named("PID", 1).unwrap();
+        assert_eq!(pid.to_segment().unwrap(), *eager.get_named("PID", 1).unwrap(), "Materialized segment differs!");
+        assert_eq!(
+            lazy.get_named("MSH", 1).unwrap().to_segment().unwrap(),
+            *eager.get_named("MSH", 1).unwrap(),
+            "MSH-2 must not be split into components!"
+        );
+        assert_eq!(lazy.into_message().unwrap(), eager, "Converting to a full message should match the eager parser!");
+
+        let raw = "MSH|^~\\&|LAB|FAC|||20250101120000||ORU^R01^ORU_R01|MSG0001|P|2.5.1\nPID|1||12345^^^HOSP&1.2.3&ISO^MR\nZPD|1|custom\nZPD|2|other\n";
+        let lazy = V2LazyMessage::try_from(raw.as_bytes()).unwrap();
+        assert_eq!(lazy.find_value("PID3.4.2").unwrap(), "1.2.3", "Subcomponents should be reachable!");
+        assert_eq!(lazy.find_value("ZPD(2)2").unwrap(), "other", "Custom segments should be indexed by name!");
+        assert_eq!(lazy.get_named("ZPD", 1).unwrap().name(), "ZPD", "Custom segment name was lost!");
+        assert!(V2LazyMessage::try_from("PID|1||12345\r").is_err(), "Messages must start with MSH!");
+    }
+
+    #[test]
+    fn test_lenient_parsing() {
+        let clean = "MSH|^~\\&|LAB|FAC|||20250101120000||ORU^R01^ORU_R01|MSG0001|P|2.5.1\rPID|1||12345^^^HOSP^MR||DOE^JANE\r";
+        let (message, fixes) = V2Message::try_from_bytes_with(clean.as_bytes(), &V2ParseOptions::strict()).unwrap();
+        assert!(fixes.is_empty(), "Clean messages need no fixes! Got {:?}", fixes);
+        assert_eq!(message.to_string(), clean, "Clean messages must not be altered!");
+
+        let dirty = b"\xef\xbb\xbf\x0bMSH|^~\\&|LAB|FAC|||20250101120000||ORU^R01^ORU_R01|MSG0001|P|2.5.1|||\r\n\r\nPID|1||123\x0045^^^HOSP^MR||DOE^JANE||\r\nX\r\nOBX|1|NM|GLU^Glucose^L||95\n\x1c\r";
+        let (message, fixes) = V2Message::try_from_bytes_with(dirty, &V2ParseOptions::lenient()).unwrap();
+        let kinds: Vec<V2ParseFixKind> = fixes.iter().map(|fix| fix.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                V2ParseFixKind::ByteOrderMark,
+                V2ParseFixKind::LeadingData,
+                V2ParseFixKind::SegmentTerminator,
+                V2ParseFixKind::TrailingSeparator,
+                V2ParseFixKind::EmptySegment,
+                V2ParseFixKind::ControlCharacter,
+                V2ParseFixKind::TrailingSeparator,
+                V2ParseFixKind::InvalidSegment,
+                V2ParseFixKind::ControlCharacter,
+            ],
+            "Unexpected fixes! Got {:?}",
+            fixes
+        );
+        assert_eq!(fixes[5].line, 3, "Control character was in the third line!");
+        assert_eq!(fixes[5].to_string(), "Line 3: Removed 1 control characters.", "Fix description is wrong!");
+        assert_eq!(message.find_value("PID3.1").unwrap(), "12345", "Control character was not removed!");
+        assert_eq!(message.find_value("OBX5").unwrap(), "95", "OBX should have survived the repairs!");
+        assert_eq!(
+            message.to_string(),
+            "MSH|^~\\&|LAB|FAC|||20250101120000||ORU^R01^ORU_R01|MSG0001|P|2.5.1\rPID|1||12345^^^HOSP^MR||DOE^JANE\rOBX|1|NM|GLU^Glucose^L||95\r",
+            "Repaired message is wrong!"
+        );
+
+        let error = V2Message::try_from_bytes_with(dirty, &V2ParseOptions::strict()).unwrap_err();
+        assert!(error.contains("byte order mark") && error.contains("Line 4"), "Strict mode should list every defect! Got {}", error);
+
+        let (repaired, fixes) = v2_repair(b"MSH|LAB|FAC|||20250101120000||ADT^A01|MSG0001|P|2.5.1\r", &V2ParseOptions::lenient()).unwrap();
+        assert_eq!(fixes[0].kind, V2ParseFixKind::EncodingCharacters, "Missing MSH-2 should be reported!");
+        assert!(repaired.starts_with(b"MSH|^~\\&|LAB|FAC|"), "Default encoding characters were not inserted!");
+        let (repaired, _) = v2_repair(b"MSH||LAB\r", &V2ParseOptions::lenient()).unwrap();
+        assert_eq!(repaired, b"MSH|^~\\&|LAB\r".to_vec(), "Empty MSH-2 should be filled in place!");
+        let (repaired, _) = v2_repair(b"MSH|^~|LAB\r", &V2ParseOptions::lenient()).unwrap();
+        assert_eq!(repaired, b"MSH|^~\\&|LAB\r".to_vec(), "Partial MSH-2 should be completed!");
+
+        assert!(v2_repair(b"PID|1||12345\r", &V2ParseOptions::lenient()).is_err(), "Messages without MSH cannot be repaired!");
+    }
+
+    #[test]
+    fn test_versioned_descriptors() {
+        assert_eq!(V2Version::from_version_id("2.5.1"), Some(V2Version::V2_5_1), "Wrong version parsed!");
+        assert_eq!(V2Version::from_version_id("2.2"), None, "Releases before 2.3 are not carried!");
+        assert_eq!(get_field_descriptor("CWE", V2Version::V2_4).unwrap().len(), 6, "CWE had 6 components in 2.4!");
+        assert_eq!(get_field_descriptor("CWE", V2Version::V2_6).unwrap().len(), 9, "CWE had 9 components in 2.6!");
+        assert_eq!(get_field_descriptor("CWE", V2Version::V2_8).unwrap().len(), 22, "CWE has 22 components since 2.7!");
+        assert_eq!(get_field_descriptor("XCN", V2Version::V2_3).unwrap().len(), 14, "XCN had 14 components in 2.3!");
+        assert_eq!(get_field_descriptor("XCN", V2Version::V2_4).unwrap().len(), 18, "XCN had 18 components in 2.4!");
+        assert_eq!(get_field_descriptor("XCN", V2Version::V2_5_1).unwrap().len(), 23, "XCN had 23 components in 2.5.1!");
+        assert!(get_field_descriptor("TS", V2Version::V2_7).is_none(), "TS was withdrawn in 2.7!");
+
+        let chars = V2ParserCharacters::new();
+        let range = cast_complex_type_as(vec!["20250101&D", "20250131"], &V2ComplexType::DR, &chars, V2Version::V2_5_1);
+        println!("{:?}", range);
+        assert!(!range.is_err(), "DR should carry TS components in 2.5.1!");
+        let start = range.as_complex().unwrap().get("range_start").unwrap().as_complex().unwrap();
+        assert_eq!(start.get("degree_of_precision").unwrap().as_str(), Some("D"), "Wrong degree of precision!");
+        let range = cast_complex_type_as(vec!["20250101", "20250131"], &V2ComplexType::DR, &chars, V2Version::V2_8);
+        let start = range.as_complex().unwrap().get("range_start").unwrap();
+        assert!(start.as_complex().is_none(), "DR carries plain DTM components since 2.7!");
+
+        let raw = "MSH|^~\\&|ADT1|HOSP|||20250101120000||ADT^A01|MSG0001|P|2.3\rPID|1||12345^^^HOSP^MR||DOE^JANE\rPV1|1|I|||||1234^SMITH^JOHN^^^^^^HOSP^^^^^^^^^G\r";
+        let mut message = rumtk_v2_parse_message!(raw).unwrap();
+        assert_eq!(message.version(), V2Version::V2_3, "MSH-12 was not honored!");
+        let doctor = V2SegmentView::from_message("PV1", &message, 1).unwrap().get(7);
+        assert_eq!(doctor.as_complex().unwrap().len(), 14, "PV1-7 should be cast with the 2.3 XCN!");
+        let report = message.validate();
+        let extra: Vec<_> = report.at("PV1-7").into_iter().filter(|i| i.rule == V2ValidationRule::ExtraComponents).collect();
+        assert_eq!(extra.len(), 1, "The 2.3 XCN does not define 18 components!");
+
+        message.set_version_override(Some(V2Version::V2_9));
+        assert_eq!(message.version(), V2Version::V2_9, "Override was not applied!");
+        assert_eq!(message.declared_version(), Some(V2Version::V2_3), "Override should not touch MSH-12!");
+        let doctor = V2SegmentView::from_message("PV1", &message, 1).unwrap().get(7);
+        assert_eq!(doctor.as_complex().unwrap().len(), 25, "PV1-7 should be cast with the 2.9 XCN!");
+        let report = message.validate();
+        assert!(
+            report.at("PV1-7").iter().all(|i| i.rule != V2ValidationRule::ExtraComponents),
+            "The 2.9 XCN defines 25 components!"
+        );
+    }
+
+    #[test]
+    fn test_version_translation() {
+        let raw = "MSH|^~\\&|LAB|FAC|EHR|HOSP|20250101120000||ORU^R01|MSG0001|P|2.3\rPID|1|EXT123^^^LAB|12345^^^HOSP^MR|ALT9^^^LAB|DOE^JANE||19610615^D|F|||1 MAIN ST^^TOWN^ST^12345|CNTY|||||||123-45-6789\rPV1|1|I|||||1234^SMITH^JOHN^^^^^^HOSP^^^^^^^^^G\r";
+        let message = rumtk_v2_parse_message!(raw).unwrap();
+        let (upgraded, report) = message.translate(V2Version::V2_5_1).unwrap();
+        for change in report.changes() {
+            println!("{}", change);
+        }
+        assert!(report.is_lossless(), "Going up to 2.5.1 should not lose data!");
+        assert_eq!(upgraded.version(), V2Version::V2_5_1, "MSH-12 was not updated!");
+        assert_eq!(upgraded.find_value("PID3[2].1").unwrap(), "EXT123", "PID-2 should have moved into PID-3!");
+        assert_eq!(upgraded.find_value("PID3[3].1").unwrap(), "ALT9", "PID-4 should have moved into PID-3!");
+        assert_eq!(upgraded.find_value("PID3[4].1").unwrap(), "123-45-6789", "PID-19 should have moved into PID-3!");
+        assert_eq!(upgraded.find_value("PID3[4].5").unwrap(), "SS", "Moved SSN should be typed as SS!");
+        assert_eq!(upgraded.find_value("PID11.9").unwrap(), "CNTY", "PID-12 should have moved into PID-11.9!");
+        assert_eq!(upgraded.find_value("PID7.2").unwrap(), "D", "TS is still valid in 2.5.1!");
+        assert_eq!(report.at("PID-2")[0].kind, V2TranslationKind::Moved, "PID-2 move was not reported!");
+        assert_eq!(message.find_value("PID2.1").unwrap(), "EXT123", "The original message should be untouched!");
+
+        let (latest, report) = upgraded.translate(V2Version::V2_9).unwrap();
+        let precision = report.at("PID-7");
+        assert_eq!(precision.len(), 1, "Expected the TS of PID-7 to be folded!");
+        assert_eq!(precision[0].kind, V2TranslationKind::Precision, "Wrong change kind for PID-7!");
+        assert_eq!(latest.find_value("PID7").unwrap(), "19610615", "PID-7 should be a plain DTM in 2.9!");
+
+        let (downgraded, report) = upgraded.translate(V2Version::V2_3).unwrap();
+        let lossy = report.lossy();
+        assert_eq!(lossy.len(), 1, "Only PV1-7 should lose data going down to 2.3!");
+        assert_eq!(lossy[0].location, "PV1-7[1]", "Wrong location for the truncated XCN!");
+        assert_eq!(lossy[0].kind, V2TranslationKind::Truncated, "Wrong change kind for PV1-7!");
+        let pv1 = downgraded.get_named("PV1", 1).unwrap();
+        assert_eq!(pv1.to_string(downgraded.parser_chars()), "1|I|||||1234^SMITH^JOHN^^^^^^HOSP^^^^^", "XCN was not cut down to 14 components!");
+        assert_eq!(downgraded.find_value("PID19").unwrap(), "123-45-6789", "The SSN should have moved back to PID-19!");
+        assert!(downgraded.find_value("PID3[4].1").is_err(), "The SSN should have left PID-3!");
+        assert_eq!(report.at("PID-3[4]")[0].kind, V2TranslationKind::Moved, "Moving the SSN back was not reported!");
+        assert_eq!(downgraded.find_value("PID3[2].1").unwrap(), "EXT123", "PID-3 is valid in 2.3 so PID-2 stays there!");
+
+        let raw = "MSH|^~\\&|LAB|FAC|EHR|HOSP|20250101120000||ORU^R01|MSG0001|P|2.9\rOBX|1|CWE|GLU^Glucose^LN^^^^2.68^^^^^^^^^^^^^^^\r";
+        let message = rumtk_v2_parse_message!(raw).unwrap();
+        let (downgraded, report) = message.translate(V2Version::V2_5_1).unwrap();
+        let obx = report.at("OBX-3[1]");
+        assert!(obx.iter().any(|c| c.kind == V2TranslationKind::Retyped), "CWE to CE was not reported!");
+        assert!(obx.iter().any(|c| c.kind == V2TranslationKind::Truncated && c.lossy), "CWE.7 does not fit in a CE!");
+        assert_eq!(downgraded.find_value("OBX3.3").unwrap(), "LN", "CE components should be kept!");
+
+        let mut message = rumtk_v2_parse_message!(raw).unwrap();
+        message.set_version_override(Some(V2Version::V2_8));
+        let (translated, report) = message.translate(V2Version::V2_9).unwrap();
+        assert_eq!(report.source_version(), V2Version::V2_8, "The override should be the source release!");
+        assert_eq!(translated.version_override(), Some(V2Version::V2_9), "The override should follow the translation!");
+    }
+
     // TODO: Add tests for sequenceid and telephonestring
     // TODO: Add fuzzing test for to_datetime().
 
@@ -1183,6 +2209,431 @@ mod tests {
         );
     }
 
+    fn tls_test_file(name: &str) -> RUMString {
+        rumtk_format!("{}/tests/tls/{}", env!("CARGO_MANIFEST_DIR"), name)
+    }
+
+    #[test]
+    fn test_mllp_tls_echo() {
+        // The sender waits for an acknowledgement, so send a valid message and let the listener ACK it.
+        static EXPECTED_MESSAGE: &str = "MSH|^~\\&|ADT1|HOSP|LAB|LABFAC|20250101120000||ADT^A01^ADT_A01|MSG0001|P|2.5.1\rEVN|A01|20250101120000\rPID|1||12345^^^HOSP^MR||DOE^JANE\rPV1|1|I\r";
+        let client_pin = cert_file_fingerprint(&tls_test_file("client.pem")).unwrap();
+        let server_pin = cert_file_fingerprint(&tls_test_file("server.pem")).unwrap();
+        let server_tls = RUMTLSConfig::new(&tls_test_file("server.pem"), &tls_test_file("server.key"))
+            .with_ca(&tls_test_file("ca.pem"))
+            .with_pin(&client_pin);
+        let client_tls = RUMTLSConfig::new(&tls_test_file("client.pem"), &tls_test_file("client.key"))
+            .with_ca(&tls_test_file("ca.pem"))
+            .with_pin(&server_pin);
+        assert!(server_tls.is_mutual(), "Expected the server to require client certificates!");
+
+        let safe_listener =
+            rumtk_v2_mllp_listen!(LOCALHOST, 0, MLLP_FILTER_POLICY::NONE, true, &server_tls).unwrap();
+        let (_, port) = rumtk_v2_mllp_get_ip_port!(&safe_listener).unwrap();
+        mllp_set_auto_ack(safe_listener.clone(), true);
+
+        let send_h = spawn(move || -> RUMResult<()> {
+            let message = RUMString::from(EXPECTED_MESSAGE);
+            let safe_client =
+                rumtk_v2_mllp_connect!(LOCALHOST, port, MLLP_FILTER_POLICY::NONE, &client_tls)?;
+            let (ip, port) = rumtk_v2_mllp_get_ip_port!(&safe_client)?;
+            let endpoint = rumtk_format!("{}:{}", ip, port);
+            rumtk_v2_mllp_send!(&safe_client, &endpoint, &message)
+        });
+
+        let mut client_ids = rumtk_v2_mllp_get_client_ids!(safe_listener).unwrap();
+        while client_ids.is_empty() {
+            rumtk_sleep!(1);
+            client_ids = rumtk_v2_mllp_get_client_ids!(safe_listener).unwrap();
+        }
+        let client_id = client_ids.first().unwrap().clone();
+
+        let results = rumtk_v2_mllp_receive!(&safe_listener, &client_id).unwrap();
+        let result = results.first().unwrap();
+        assert_eq!(
+            result, EXPECTED_MESSAGE,
+            "Message received over TLS does not match the expected message. Got {}",
+            result
+        );
+        send_h.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_mllp_tls_rejects_unpinned_server() {
+        let server_tls = RUMTLSConfig::new(&tls_test_file("server.pem"), &tls_test_file("server.key"));
+        let client_tls = RUMTLSConfig::trusting(&tls_test_file("ca.pem"))
+            .with_pin(&cert_file_fingerprint(&tls_test_file("client.pem")).unwrap());
+
+        let safe_listener =
+            rumtk_v2_mllp_listen!(LOCALHOST, 0, MLLP_FILTER_POLICY::NONE, true, &server_tls).unwrap();
+        let (_, port) = rumtk_v2_mllp_get_ip_port!(&safe_listener).unwrap();
+        mllp_set_auto_ack(safe_listener.clone(), true);
+
+        let result = rumtk_v2_mllp_connect!(LOCALHOST, port, MLLP_FILTER_POLICY::NONE, &client_tls);
+        assert!(result.is_err(), "Expected the connection to a server with an unpinned certificate to fail!");
+
+        let plain_tls = RUMTLSConfig::trusting(&tls_test_file("ca.pem"));
+        let result = rumtk_v2_mllp_connect!(LOCALHOST, port, MLLP_FILTER_POLICY::NONE, &plain_tls);
+        assert!(result.is_ok(), "Expected the connection without pinning to succeed!");
+    }
+
+    #[test]
+    fn test_mllp_send_with_ack() {
+        static MESSAGE: &str = "MSH|^~\\&|ADT1|HOSP|LAB|LABFAC|20250101120000||ADT^A01^ADT_A01|MSG0001|P|2.5.1\rEVN|A01|20250101120000\rPID|1||12345^^^HOSP^MR||DOE^JANE\rPV1|1|I\r";
+
+        let safe_listener = rumtk_v2_mllp_listen!(MLLP_FILTER_POLICY::NONE, true).unwrap();
+        let (_, port) = rumtk_v2_mllp_get_ip_port!(&safe_listener).unwrap();
+        let safe_client = rumtk_v2_mllp_connect!(port, MLLP_FILTER_POLICY::NONE).unwrap();
+        let policy = MLLPRetryPolicy {
+            attempts: 3,
+            ..MLLPRetryPolicy::default()
+        };
+
+        let instance_policy = mllp_get_retry_policy(safe_client.clone());
+
+        let sender = safe_client.clone();
+        let send_h = spawn(move || rumtk_v2_mllp_send_with_ack!(&sender, "", MESSAGE, policy));
+
+        let mut client_ids = rumtk_v2_mllp_get_client_ids!(safe_listener).unwrap();
+        while client_ids.is_empty() {
+            rumtk_sleep!(1);
+            client_ids = rumtk_v2_mllp_get_client_ids!(safe_listener).unwrap();
+        }
+        let client_id = client_ids.first().unwrap().clone();
+
+        // Answer the first attempt with an application error and the retry with an accept.
+        for code in [V2AckCode::AE, V2AckCode::AA] {
+            let mut received = rumtk_v2_mllp_receive!(&safe_listener, &client_id).unwrap();
+            while received.is_empty() {
+                rumtk_sleep!(0.001);
+                received = rumtk_v2_mllp_receive!(&safe_listener, &client_id).unwrap();
+            }
+            let inbound = V2Message::try_from(received[0].as_str()).unwrap();
+            if code == V2AckCode::AA {
+                // Slip in an acknowledgement for another message. The sender should keep it for us.
+                let other = V2Message::try_from(MESSAGE.replace("MSG0001", "MSG0002").as_str()).unwrap();
+                let unrelated = generate_ack(&other, V2AckCode::AA, None).unwrap();
+                mllp_send_ack(safe_listener.clone(), &client_id, &unrelated).unwrap();
+            }
+            let ack = generate_ack(&inbound, code, None).unwrap();
+            mllp_send_ack(safe_listener.clone(), &client_id, &ack).unwrap();
+        }
+
+        let outcome = send_h.join().unwrap().unwrap();
+        assert!(outcome.is_accepted(), "Expected the retry to be accepted! Got {:?}", outcome);
+        assert_eq!(outcome.response().control_id, "MSG0001", "Outcome belongs to another message!");
+        assert_eq!(
+            mllp_get_retry_policy(safe_client.clone()),
+            instance_policy,
+            "A per call policy should not replace the instance's retry policy!"
+        );
+
+        let kept = rumtk_v2_mllp_receive!(&safe_client, "").unwrap();
+        assert_eq!(kept.len(), 1, "Expected the unrelated acknowledgement to be kept! Got {:?}", kept);
+        assert!(kept[0].contains("MSA|AA|MSG0002"), "Kept the wrong message! Got {}", kept[0]);
+    }
+
+    fn queue_test_file(name: &str) -> RUMString {
+        let path = std::env::temp_dir().join(rumtk_format!("rumtk_{}_{}.log", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        RUMString::from(path.to_str().unwrap())
+    }
+
+    #[test]
+    fn test_mllp_queue() {
+        let path = queue_test_file("test_mllp_queue");
+        let policy = MLLPQueuePolicy {
+            max_attempts: 2,
+            backoff: 60.0,
+            max_backoff: 60.0,
+        };
+        let mut queue = MLLPQueue::open(&path, policy).unwrap();
+        let first = queue.enqueue("lab", "MSG0001").unwrap();
+        let second = queue.enqueue("lab", "MSG0002").unwrap();
+        let other = queue.enqueue("pharmacy", "MSG0003").unwrap();
+        assert_eq!(queue.due(), vec![first, other], "Only the head of each endpoint is due!");
+
+        queue.mark_failed(first, "Connection refused").unwrap();
+        assert_eq!(queue.due(), vec![other], "The failed head should be backing off and hold its endpoint!");
+        queue.mark_delivered(other).unwrap();
+
+        let mut queue = MLLPQueue::open(&path, policy).unwrap();
+        assert_eq!(queue.len(), 2, "Undelivered messages should survive a restart!");
+        let entry = queue.get(first).unwrap();
+        assert_eq!(entry.attempts, 1, "Lost the failed attempt!");
+        assert_eq!(entry.last_error, "Connection refused", "Lost the failure reason!");
+        assert!(queue.get(other).is_none(), "Delivered messages should be gone!");
+        assert!(queue.enqueue("lab", "MSG0004").unwrap() > other, "Ids should not be reused!");
+
+        queue.mark_failed(first, "Connection refused").unwrap();
+        assert_eq!(queue.get(first).unwrap().state, MLLPQueueState::DeadLetter, "Ran out of attempts!");
+        assert_eq!(queue.due(), vec![second], "The dead letter should not hold its endpoint!");
+        assert!(queue.mark_delivered(first).is_err(), "Dead letters cannot be delivered!");
+
+        queue.replay(first).unwrap();
+        assert_eq!(queue.due(), vec![first], "Replayed messages keep their position!");
+        assert_eq!(queue.pending_for("lab").len(), 3, "Wrong pending count for lab!");
+        queue.purge(first).unwrap();
+        assert_eq!(queue.due(), vec![second], "Purged messages should be gone!");
+        assert_eq!(queue.due_for("lab"), Some(second), "Wrong head for lab!");
+        assert_eq!(queue.due_for("pharmacy"), None, "Nothing is queued for pharmacy!");
+
+        // A crash halfway through an append leaves a partial line behind.
+        let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        std::io::Write::write_all(&mut file, b"{\"Delivered\":{\"id\"").unwrap();
+        let queue = MLLPQueue::open(&path, policy).unwrap();
+        assert_eq!(queue.len(), 2, "The partial record should be dropped!");
+
+        // The cut can also land in the middle of a multibyte character.
+        let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        std::io::Write::write_all(&mut file, b"{\"Enqueued\":{\"id\":9,\"endpoint\":\"\xE2\x82").unwrap();
+        let mut queue = MLLPQueue::open(&path, policy).unwrap();
+        assert_eq!(queue.len(), 2, "The partial record with broken UTF-8 should be dropped!");
+
+        // The log should not keep growing while messages keep flowing through a busy queue.
+        for _ in 0..QUEUE_COMPACT_RECORDS {
+            let id = queue.enqueue("pharmacy", "MSG0005").unwrap();
+            queue.mark_delivered(id).unwrap();
+        }
+        let lines = std::fs::read_to_string(&path).unwrap().lines().count();
+        assert!(lines < QUEUE_COMPACT_RECORDS, "The log was never compacted! It has {} lines", lines);
+        assert_eq!(queue.len(), 2, "Compacting should keep the pending messages!");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_mllp_queue_delivery() {
+        static MESSAGE: &str = "MSH|^~\\&|ADT1|HOSP|LAB|LABFAC|20250101120000||ADT^A01^ADT_A01|MSG0001|P|2.5.1\rEVN|A01|20250101120000\rPID|1||12345^^^HOSP^MR||DOE^JANE\rPV1|1|I\r";
+        let path = queue_test_file("test_mllp_queue_delivery");
+
+        let safe_listener = rumtk_v2_mllp_listen!(MLLP_FILTER_POLICY::NONE, true).unwrap();
+        let (_, port) = rumtk_v2_mllp_get_ip_port!(&safe_listener).unwrap();
+        let safe_client = rumtk_v2_mllp_connect!(port, MLLP_FILTER_POLICY::NONE).unwrap();
+        let queue = mllp_open_queue(&path, MLLPQueuePolicy::default()).unwrap();
+        // Messages for other peers should stay put when this channel delivers.
+        let pending = queue.clone();
+        let other = block_on_task(async move { pending.lock().await.enqueue("pharmacy", MESSAGE) }).unwrap();
+        let channels = mllp_open_queued_channels(safe_client, &queue).unwrap();
+        let channel = channels.first().unwrap().clone();
+
+        let send_h = spawn(move || channel.lock().unwrap().send_message(MESSAGE));
+
+        let mut client_ids = rumtk_v2_mllp_get_client_ids!(safe_listener).unwrap();
+        while client_ids.is_empty() {
+            rumtk_sleep!(1);
+            client_ids = rumtk_v2_mllp_get_client_ids!(safe_listener).unwrap();
+        }
+        let client_id = client_ids.first().unwrap().clone();
+        let mut received = rumtk_v2_mllp_receive!(&safe_listener, &client_id).unwrap();
+        while received.is_empty() {
+            rumtk_sleep!(0.001);
+            received = rumtk_v2_mllp_receive!(&safe_listener, &client_id).unwrap();
+        }
+        // The channel is waiting on our ACK, but the queue should not be held while it waits.
+        let shared = queue.clone();
+        let queued = block_on_task(async move { shared.lock().await.enqueue("pharmacy", MESSAGE) }).unwrap();
+        let inbound = V2Message::try_from(received[0].as_str()).unwrap();
+        mllp_send_ack(safe_listener.clone(), &client_id, &inbound.ack().unwrap()).unwrap();
+
+        send_h.join().unwrap().unwrap();
+        let queue = MLLPQueue::open(&path, MLLPQueuePolicy::default()).unwrap();
+        assert_eq!(queue.len(), 2, "Acknowledged message should have left the queue!");
+        assert!(queue.get(other).is_some(), "Delivered a message queued for another peer!");
+        assert!(queue.get(queued).is_some(), "Lost a message queued during delivery!");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_mllp_journal() {
+        static MESSAGE: &str = "MSH|^~\\&|ADT1|HOSP|LAB|LABFAC|20250101120000||ADT^A01^ADT_A01|MSG0001|P|2.5.1\rEVN|A01|20250101120000\rPID|1||12345^^^HOSP^MR||DOE^JANE\rPV1|1|I\r";
+        let path = queue_test_file("test_mllp_journal");
+
+        let safe_listener = rumtk_v2_mllp_listen!(MLLP_FILTER_POLICY::NONE, true).unwrap();
+        let (_, port) = rumtk_v2_mllp_get_ip_port!(&safe_listener).unwrap();
+        mllp_open_journal(safe_listener.clone(), &path).unwrap();
+        mllp_set_auto_ack(safe_listener.clone(), true);
+        let safe_client = rumtk_v2_mllp_connect!(port, MLLP_FILTER_POLICY::NONE).unwrap();
+        let channels = rumtk_v2_mllp_iter_channels!(safe_client).unwrap();
+        let channel = channels.first().unwrap().clone();
+
+        let sender = channel.clone();
+        let send_h = spawn(move || sender.lock().unwrap().send_message(MESSAGE));
+
+        let mut received = mllp_receive_journaled(safe_listener.clone()).unwrap();
+        while received.is_empty() {
+            rumtk_sleep!(0.001);
+            received = mllp_receive_journaled(safe_listener.clone()).unwrap();
+        }
+        send_h.join().unwrap().unwrap();
+        assert_eq!(received.len(), 1, "Expected exactly one journaled message!");
+        assert_eq!(received[0].message, MESSAGE, "Journaled message does not match!");
+        assert!(
+            mllp_receive_journaled(safe_listener.clone()).unwrap().is_empty(),
+            "Messages should only be handed out once per run!"
+        );
+
+        // Simulate the consumer crashing before consuming the message.
+        let mut journal = MLLPJournal::open(&path).unwrap();
+        let recovered = journal.take_undelivered();
+        assert_eq!(recovered, received, "Unconsumed message should be recovered!");
+
+        mllp_consume(safe_listener.clone(), received[0].id).unwrap();
+        let journal = MLLPJournal::open(&path).unwrap();
+        assert!(journal.is_empty(), "Consumed message should have left the journal!");
+
+        // Plain receive calls have no ids to consume with, so they consume what they hand out.
+        let send_h = spawn(move || channel.lock().unwrap().send_message(MESSAGE));
+        let mut received = mllp_receive(safe_listener.clone()).unwrap();
+        while received.values().all(|messages| messages.is_empty()) {
+            rumtk_sleep!(0.001);
+            received = mllp_receive(safe_listener.clone()).unwrap();
+        }
+        send_h.join().unwrap().unwrap();
+        assert!(
+            mllp_receive_journaled(safe_listener.clone()).unwrap().is_empty(),
+            "Messages handed out by a plain receive should not be handed out again!"
+        );
+        let journal = MLLPJournal::open(&path).unwrap();
+        assert!(journal.is_empty(), "Messages handed out by a plain receive should be consumed!");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_mllp_journal_two_peers() {
+        static MESSAGE: &str = "MSH|^~\\&|ADT1|HOSP|LAB|LABFAC|20250101120000||ADT^A01^ADT_A01|MSG0001|P|2.5.1\rEVN|A01|20250101120000\rPID|1||12345^^^HOSP^MR||DOE^JANE\rPV1|1|I\r";
+        static OTHER: &str = "MSH|^~\\&|ADT1|HOSP|LAB|LABFAC|20250101120000||ADT^A01^ADT_A01|MSG0002|P|2.5.1\rEVN|A01|20250101120000\rPID|1||67890^^^HOSP^MR||ROE^RICHARD\rPV1|1|I\r";
+        let path = queue_test_file("test_mllp_journal_two_peers");
+
+        let safe_listener = rumtk_v2_mllp_listen!(MLLP_FILTER_POLICY::NONE, true).unwrap();
+        let (_, port) = rumtk_v2_mllp_get_ip_port!(&safe_listener).unwrap();
+        mllp_open_journal(safe_listener.clone(), &path).unwrap();
+        mllp_set_auto_ack(safe_listener.clone(), true);
+        let config = MLLPConfig {
+            retries: 1,
+            ack_timeout: 0.5,
+            ..MLLPConfig::default()
+        };
+        mllp_set_config(safe_listener.clone(), config);
+        let first = rumtk_v2_mllp_connect!(port, MLLP_FILTER_POLICY::NONE).unwrap();
+        let second =
+            create_async_mllp_with_config(LOCALHOST, port, MLLP_FILTER_POLICY::NONE, false, config, None)
+                .unwrap();
+        let second_id = rumtk_v2_mllp_get_client_ids!(second).unwrap().first().unwrap().clone();
+        while rumtk_v2_mllp_get_client_ids!(safe_listener).unwrap().len() < 2 {
+            rumtk_sleep!(0.01);
+        }
+
+        // The second peer's message arrives while we wait on it to acknowledge ours, so it is
+        // kept for the plain receive calls.
+        let sender = second.clone();
+        let send_h = spawn(move || mllp_send(sender, "", OTHER));
+        assert!(mllp_send(safe_listener.clone(), &second_id, "Hello World").is_err(), "Nobody acknowledges our message!");
+        let _ = send_h.join().unwrap();
+
+        let sender = first.clone();
+        let send_h = spawn(move || mllp_send(sender, "", MESSAGE));
+        let mut received = mllp_receive_journaled(safe_listener.clone()).unwrap();
+        while received.is_empty() {
+            rumtk_sleep!(0.001);
+            received = mllp_receive_journaled(safe_listener.clone()).unwrap();
+        }
+        send_h.join().unwrap().unwrap();
+        let messages: RUMVec<&str> = received.iter().map(|entry| entry.message.as_str()).collect();
+        assert_eq!(messages, vec![MESSAGE], "Only the first peer's message should come from the journal!");
+        mllp_consume(safe_listener.clone(), received[0].id).unwrap();
+
+        let kept = rumtk_v2_mllp_receive!(&safe_listener, &second_id).unwrap();
+        assert_eq!(kept, vec![RUMString::from(OTHER)], "The second peer's message was lost!");
+        let journal = MLLPJournal::open(&path).unwrap();
+        assert!(journal.is_empty(), "The kept message should be consumed once it is handed out!");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_mllp_config() {
+        let config = MLLPConfig {
+            backoff: 1.0,
+            max_backoff: 3.0,
+            jitter: 0.0,
+            ..MLLPConfig::default()
+        };
+        assert_eq!(config.delay(0), 0.0, "The first attempt should go out right away!");
+        assert_eq!(config.delay(1), 1.0, "Wrong initial backoff!");
+        assert_eq!(config.delay(2), 2.0, "Backoff should double!");
+        assert_eq!(config.delay(5), 3.0, "Backoff should be capped!");
+
+        let jittered = MLLPConfig { jitter: 0.5, ..config };
+        let delay = jittered.delay(2);
+        assert!(delay > 1.0 && delay <= 2.0, "Jitter out of range! Got {}", delay);
+        assert_eq!(MLLPConfig::default().ack_steps(), TIMEOUT_SOURCE, "Defaults should match the spec timeouts!");
+    }
+
+    #[test]
+    fn test_mllp_config_connection() {
+        let safe_listener = rumtk_v2_mllp_listen!(MLLP_FILTER_POLICY::NONE, true).unwrap();
+        let (_, port) = rumtk_v2_mllp_get_ip_port!(&safe_listener).unwrap();
+        let config = MLLPConfig {
+            retries: 2,
+            ack_timeout: 0.2,
+            ..MLLPConfig::default()
+        };
+        let safe_client =
+            create_async_mllp_with_config(LOCALHOST, port, MLLP_FILTER_POLICY::NONE, false, config, None)
+                .unwrap();
+
+        // Nobody acknowledges, so we should give up after the configured attempts.
+        let err = mllp_send(safe_client.clone(), "", "Hello World").unwrap_err();
+        assert!(err.contains("2 times"), "Wrong number of attempts! Got {}", err);
+
+        // A client would have nothing left to send with if it closed its only connection.
+        mllp_set_config(
+            safe_client.clone(),
+            MLLPConfig {
+                idle_close: Some(0.0),
+                ..MLLPConfig::default()
+            },
+        );
+        assert!(mllp_close_idle(safe_client.clone()).unwrap().is_empty(), "The client closed its connection!");
+
+        mllp_set_config(
+            safe_listener.clone(),
+            MLLPConfig {
+                idle_close: Some(0.2),
+                ..MLLPConfig::default()
+            },
+        );
+        rumtk_v2_mllp_receive!(&safe_listener).unwrap();
+        let client_ids = rumtk_v2_mllp_get_client_ids!(safe_listener).unwrap();
+        assert_eq!(client_ids.len(), 1, "Expected the client connection!");
+        rumtk_sleep!(0.3);
+        let closed = mllp_close_idle(safe_listener.clone()).unwrap();
+        assert_eq!(closed, client_ids, "The idle connection should have been closed!");
+        assert!(
+            rumtk_v2_mllp_get_client_ids!(safe_listener).unwrap().is_empty(),
+            "Closed connection is still listed!"
+        );
+    }
+
+    #[test]
+    fn test_mllp_config_no_retries() {
+        let safe_listener = rumtk_v2_mllp_listen!(MLLP_FILTER_POLICY::NONE, true).unwrap();
+        let (_, port) = rumtk_v2_mllp_get_ip_port!(&safe_listener).unwrap();
+        let config = MLLPConfig {
+            retries: 0,
+            ack_timeout: 0.2,
+            ..MLLPConfig::default()
+        };
+        let safe_client =
+            create_async_mllp_with_config(LOCALHOST, port, MLLP_FILTER_POLICY::NONE, false, config, None)
+                .unwrap();
+
+        // Without retries the message should still go out once.
+        let err = mllp_send(safe_client.clone(), "", "Hello World").unwrap_err();
+        assert!(err.contains("1 times"), "Wrong number of attempts! Got {}", err);
+        let received: RUMVec<RUMString> = rumtk_v2_mllp_receive!(&safe_listener).unwrap().into_values().flatten().collect();
+        assert_eq!(received, vec![RUMString::from("Hello World")], "The message was never sent!");
+    }
+
     #[test]
     fn test_mllp_connect() {
         let mllp_layer = match rumtk_v2_mllp_listen!(0, MLLP_FILTER_POLICY::NONE, true) {
@@ -0,0 +1,18 @@
+#!/usr/bin/env sh
+# Regenerates the self-signed certificates used by the MLLPS (MLLP over TLS) tests.
+set -e
+cd "$(dirname "$0")"
+
+openssl req -x509 -newkey rsa:2048 -nodes -days 36500 -subj "/CN=rumtk test ca" \
+    -keyout ca.key -out ca.pem
+
+for usage in server:serverAuth client:clientAuth; do
+    name="${usage%%:*}"
+    openssl req -newkey rsa:2048 -nodes -subj "/CN=rumtk test $name" -keyout "$name.key" -out "$name.csr"
+    printf "subjectAltName=DNS:localhost,IP:127.0.0.1\nbasicConstraints=CA:FALSE\nextendedKeyUsage=%s\n" "${usage#*:}" > "$name.ext"
+    openssl x509 -req -in "$name.csr" -CA ca.pem -CAkey ca.key -CAcreateserial -days 36500 \
+        -extfile "$name.ext" -out "$name.pem"
+    rm "$name.csr" "$name.ext"
+done
+
+rm -f ca.srl ca.key
@@ -20,14 +20,20 @@
 
 use rumtk_core::base::RUMResult;
 use rumtk_core::dependencies::clap;
-use rumtk_core::net::tcp::LOCALHOST;
-use rumtk_core::strings::{RUMArrayConversions, RUMString};
+use rumtk_core::net::tcp::{ANYHOST, LOCALHOST};
+use rumtk_core::strings::{rumtk_format, RUMArrayConversions, RUMString};
 use rumtk_core::types::RUMCLIParser;
 use rumtk_core::{rumtk_read_stdin, rumtk_write_stdout};
-use rumtk_hl7_v2::hl7_v2_mllp::mllp_v2::{SafeAsyncMLLP, SafeMLLPChannel, MLLP_FILTER_POLICY};
+use rumtk_hl7_v2::hl7_v2_mllp::mllp_v2::{
+    MLLPConfig, RUMTLSConfig, SafeAsyncMLLP, SafeMLLPChannel, MLLP_FILTER_POLICY,
+};
+use rumtk_hl7_v2::hl7_v2_mllp::mllp_v2_helpers::{
+    create_async_mllp_with_config, mllp_consume, mllp_open_journal, mllp_receive_journaled,
+    mllp_set_config,
+};
 use rumtk_hl7_v2::{
-    rumtk_v2_mllp_connect, rumtk_v2_mllp_get_ip_port, rumtk_v2_mllp_iter_channels,
-    rumtk_v2_mllp_listen, rumtk_v2_mllp_receive,
+    rumtk_v2_mllp_get_ip_port, rumtk_v2_mllp_iter_channels, rumtk_v2_mllp_listen,
+    rumtk_v2_mllp_receive,
 };
 use std::process::exit;
 
@@ -95,6 +101,127 @@ pub struct RUMTKInterfaceArgs {
     ///
     #[arg(short, long)]
     daemon: bool,
+    ///
+    /// Path to the PEM certificate chain this interface presents. Enables TLS (MLLPS).
+    ///
+    /// Required in inbound mode when using TLS. In outbound mode, it is the client certificate
+    /// used for mutual TLS.
+    ///
+    #[arg(long)]
+    tls_cert: Option<RUMString>,
+    ///
+    /// Path to the PEM private key matching `--tls-cert`.
+    ///
+    #[arg(long)]
+    tls_key: Option<RUMString>,
+    ///
+    /// Path to the PEM bundle of certificate authorities to trust. Enables TLS (MLLPS).
+    ///
+    /// Required in outbound mode when using TLS. In inbound mode, it makes clients present a
+    /// certificate signed by one of these authorities (mutual TLS).
+    ///
+    #[arg(long)]
+    tls_ca: Option<RUMString>,
+    ///
+    /// SHA-256 fingerprint of a peer certificate to accept. Can be repeated. If omitted, any peer
+    /// certificate that passes verification is accepted.
+    ///
+    #[arg(long)]
+    tls_pin: Vec<RUMString>,
+    ///
+    /// For outbound mode only. Name to verify the server certificate against. Defaults to `--ip`.
+    ///
+    #[arg(long)]
+    tls_server_name: Option<RUMString>,
+    ///
+    /// For inbound mode only. Path to a journal file. Every message received is written to it
+    /// before being acknowledged and only removed once it was written to stdout. Messages left in
+    /// the journal by a previous run are written out first.
+    ///
+    #[arg(long)]
+    journal: Option<RUMString>,
+    ///
+    /// For outbound mode only. Seconds to wait for the connection, TLS handshake included.
+    ///
+    #[arg(long)]
+    connect_timeout: Option<f32>,
+    ///
+    /// Seconds to wait for the acknowledgement after each send attempt.
+    ///
+    #[arg(long)]
+    ack_timeout: Option<f32>,
+    ///
+    /// Times to send a message before giving up.
+    ///
+    #[arg(long)]
+    retries: Option<u8>,
+    ///
+    /// Seconds to wait before the second send attempt. Doubles with every attempt after that.
+    ///
+    #[arg(long)]
+    backoff: Option<f32>,
+    ///
+    /// Cap, in seconds, on the delay between send attempts.
+    ///
+    #[arg(long)]
+    max_backoff: Option<f32>,
+    ///
+    /// Fraction (0 to 1) of each delay between send attempts that is randomized.
+    ///
+    #[arg(long)]
+    jitter: Option<f32>,
+    ///
+    /// Enable TCP keep-alive, probing after this many idle seconds.
+    ///
+    #[arg(long)]
+    keep_alive: Option<f32>,
+    ///
+    /// Close connections that saw no traffic for this many seconds. Only applies when listening.
+    ///
+    #[arg(long)]
+    idle_close: Option<f32>,
+}
+
+impl RUMTKInterfaceArgs {
+    fn tls_config(&self) -> RUMResult<Option<RUMTLSConfig>> {
+        if self.tls_cert.is_none() && self.tls_ca.is_none() {
+            let stray = [
+                ("--tls-key", self.tls_key.is_some()),
+                ("--tls-pin", !self.tls_pin.is_empty()),
+                ("--tls-server-name", self.tls_server_name.is_some()),
+            ];
+            return match stray.iter().find(|(_, given)| *given) {
+                Some((flag, _)) => Err(rumtk_format!(
+                    "{} has no effect without --tls-cert or --tls-ca!",
+                    flag
+                )),
+                None => Ok(None),
+            };
+        }
+
+        Ok(Some(RUMTLSConfig {
+            cert: self.tls_cert.clone(),
+            key: self.tls_key.clone(),
+            ca: self.tls_ca.clone(),
+            pinned: self.tls_pin.clone(),
+            server_name: self.tls_server_name.clone(),
+        }))
+    }
+
+    fn mllp_config(&self) -> MLLPConfig {
+        let defaults = MLLPConfig::default();
+        MLLPConfig {
+            connect_timeout: self.connect_timeout.unwrap_or(defaults.connect_timeout),
+            ack_timeout: self.ack_timeout.unwrap_or(defaults.ack_timeout),
+            retries: self.retries.unwrap_or(defaults.retries),
+            backoff: self.backoff.unwrap_or(defaults.backoff),
+            max_backoff: self.max_backoff.unwrap_or(defaults.max_backoff),
+            jitter: self.jitter.unwrap_or(defaults.jitter),
+            keep_alive: self.keep_alive,
+            idle_close: self.idle_close,
+            ..defaults
+        }
+    }
 }
 
 fn outbound_send(channel: &SafeMLLPChannel) -> RUMResult<()> {
@@ -131,14 +258,28 @@ fn inbound_receive(channel: &SafeAsyncMLLP) -> RUMResult<()> {
     Ok(())
 }
 
-fn inbound_loop(listener: &SafeAsyncMLLP) {
+fn inbound_receive_journaled(channel: &SafeAsyncMLLP) -> RUMResult<()> {
+    for entry in mllp_receive_journaled(channel.clone())? {
+        eprintln!("MLLP Received {} bytes", entry.message.len());
+        rumtk_write_stdout!(entry.message)?;
+        mllp_consume(channel.clone(), entry.id)?;
+    }
+    Ok(())
+}
+
+fn inbound_loop(listener: &SafeAsyncMLLP, journaled: bool) {
     loop {
-        inbound_receive(listener);
+        match journaled {
+            true => inbound_receive_journaled(listener),
+            false => inbound_receive(listener),
+        };
     }
 }
 
 fn main() {
     let args = RUMTKInterfaceArgs::parse();
+    let tls = args.tls_config().expect("Invalid TLS options");
+    let config = args.mllp_config();
 
     let mllp_filter_policy = match args.filter_policy.as_str() {
         "escape" => MLLP_FILTER_POLICY::ESCAPE_INPUT,
@@ -153,8 +294,15 @@ fn main() {
             false => args.ip.expect("Must provide an IP address"),
         };
         let port = args.port.expect("Must provide a port number");
-        let client =
-            rumtk_v2_mllp_connect!(&ip, port, mllp_filter_policy).expect("MLLP connection failed");
+        let client = create_async_mllp_with_config(
+            &ip,
+            port,
+            mllp_filter_policy,
+            false,
+            config,
+            tls.as_ref(),
+        )
+        .expect("MLLP connection failed");
         let channel_option = rumtk_v2_mllp_iter_channels!(client)
             .expect("Issue getting list of outbound connections.");
         let channel = channel_option.get(0).expect("No MLLP Connections");
@@ -174,7 +322,20 @@ fn main() {
     } else {
         // Build listener
         let mut listener: RUMResult<SafeAsyncMLLP> = Err(RUMString::from(""));
-        if args.ip.is_none() && args.port.is_none() {
+        if let Some(tls) = &tls {
+            let ip = match (&args.ip, args.local) {
+                (Some(ip), _) => ip.clone(),
+                (None, true) => RUMString::from(LOCALHOST),
+                (None, false) => RUMString::from(ANYHOST),
+            };
+            listener = rumtk_v2_mllp_listen!(
+                &ip,
+                args.port.unwrap_or_default(),
+                mllp_filter_policy,
+                args.local,
+                tls
+            );
+        } else if args.ip.is_none() && args.port.is_none() {
             listener = rumtk_v2_mllp_listen!(mllp_filter_policy, args.local);
         } else if args.ip.is_none() && !args.port.is_none() {
             listener = rumtk_v2_mllp_listen!(args.port.unwrap(), mllp_filter_policy, args.local);
@@ -196,6 +357,13 @@ fn main() {
         let connection_info = rumtk_v2_mllp_get_ip_port!(listener_handle).unwrap();
         eprintln!("Listening on {}:{}", connection_info.0, connection_info.1);
 
-        inbound_loop(&listener_handle);
+        mllp_set_config(listener_handle.clone(), config);
+
+        if let Some(journal) = &args.journal {
+            mllp_open_journal(listener_handle.clone(), journal)
+                .expect("Failed to open the inbound journal!");
+        }
+
+        inbound_loop(&listener_handle, args.journal.is_some());
     }
 }
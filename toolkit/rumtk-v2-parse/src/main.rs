@@ -18,10 +18,12 @@
  */
 #![feature(str_as_str)]
 
-use rumtk_core::base::RUMResult;
+use rumtk_core::base::{RUMResult, RUMVec};
+use rumtk_core::buffers::RUMBuffer;
 use rumtk_core::strings::{RUMArrayConversions, RUMString};
 use rumtk_core::types::RUMCLIParser;
 use rumtk_core::{rumtk_deserialize, rumtk_read_stdin, rumtk_serialize, rumtk_write_stdout};
+use rumtk_hl7_v2::hl7_v2_lazy::v2_lazy::V2LazyMessage;
 use rumtk_hl7_v2::hl7_v2_parser::v2_parser::{rumtk_format, V2Message};
 use rumtk_hl7_v2::{rumtk_v2_generate_message, rumtk_v2_parse_message};
 
@@ -54,11 +56,47 @@ pub struct RUMTKInterfaceArgs {
     ///
     #[arg(short, long)]
     daemon: bool,
+    ///
+    /// Print the value at each search pattern (e.g. `-s MSH8.1 -s PID3.1`), one per line, instead
+    /// of the whole message. This is what message routing looks like, so use it to benchmark
+    /// routing workloads.
+    ///
+    #[arg(short, long)]
+    select: Vec<RUMString>,
+    ///
+    /// Only parse the fields named by --select. Has no effect without --select.
+    ///
+    #[arg(short, long)]
+    lazy: bool,
+}
+
+fn select_fields(args: &RUMTKInterfaceArgs, stdin_msg: RUMVec<u8>) -> RUMResult<RUMString> {
+    let mut values = Vec::with_capacity(args.select.len());
+    if args.lazy {
+        let msg = V2LazyMessage::try_from_buffer(RUMBuffer::from(stdin_msg))?;
+        for pattern in args.select.iter() {
+            values.push(msg.find_value(pattern)?.to_string());
+        }
+    } else {
+        let msg = rumtk_v2_parse_message!(stdin_msg)?;
+        for pattern in args.select.iter() {
+            values.push(msg.find_value(pattern)?.to_string());
+        }
+    }
+    Ok(values.join("\n"))
 }
 
 fn process_message(args: &RUMTKInterfaceArgs) -> RUMResult<()> {
     let stdin_msg = rumtk_read_stdin!()?;
     if !stdin_msg.is_empty() {
+        if !args.select.is_empty() {
+            let out_data = select_fields(args, stdin_msg)?;
+            if !args.quiet {
+                rumtk_write_stdout!(&out_data);
+            }
+            return Ok(());
+        }
+
         let out_data = match rumtk_deserialize!(&stdin_msg.as_slice().to_string()?) {
             Ok(msg) => {
                 let parsed_msg: V2Message = msg;